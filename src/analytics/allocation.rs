@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use super::portfolio::{align, diffs};
+use super::{TimeseriesData, TimeseriesPoint};
+use crate::utils::stats::{mean, std_dev};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AllocationMethod {
+    EqualWeight,
+    
+    RiskParity,
+    
+    
+    MeanVariance { risk_aversion: f64 },
+}
+
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Allocation {
+    pub method: AllocationMethod,
+    pub labels: Vec<String>,
+    pub weights: Vec<f64>,
+    pub capital: Vec<f64>,
+    pub expected_pnl_per_step: f64,
+    pub volatility_per_step: f64,
+    pub equity_curve: Vec<TimeseriesPoint>,
+}
+
+impl Allocation {
+    pub fn optimize(parts: &[(&str, &TimeseriesData)], method: AllocationMethod, starting_capital: f64) -> Self {
+        let mut timestamps: Vec<u64> = parts
+            .iter()
+            .flat_map(|(_, series)| series.pnl_curve.iter().map(|p| p.timestamp_us))
+            .collect();
+        timestamps.sort_unstable();
+        timestamps.dedup();
+        let aligned: Vec<Vec<f64>> = parts.iter().map(|(_, series)| align(&series.pnl_curve, &timestamps)).collect();
+        let returns: Vec<Vec<f64>> = aligned.iter().map(|curve| diffs(curve)).collect();
+        let covariance = covariance(&returns);
+
+        let weights = match method {
+            AllocationMethod::EqualWeight => equal_weights(parts.len()),
+            AllocationMethod::RiskParity => risk_parity(&returns),
+            AllocationMethod::MeanVariance { risk_aversion } => {
+                let means: Vec<f64> = returns.iter().map(|r| mean(r)).collect();
+                mean_variance(&covariance, &means, risk_aversion).unwrap_or_else(|| risk_parity(&returns))
+            }
+        };
+
+        let combined: Vec<f64> = (0..timestamps.len())
+            .map(|i| aligned.iter().zip(&weights).map(|(curve, w)| w * curve[i]).sum())
+            .collect();
+        let combined_returns = diffs(&combined);
+        let equity_curve = timestamps
+            .iter()
+            .zip(&combined)
+            .enumerate()
+            .map(|(snapshot, (&timestamp_us, pnl))| TimeseriesPoint {
+                snapshot,
+                timestamp_us,
+                value: starting_capital + pnl,
+            })
+            .collect();
+
+        Self {
+            method,
+            labels: parts.iter().map(|(label, _)| label.to_string()).collect(),
+            capital: weights.iter().map(|w| w * starting_capital).collect(),
+            weights,
+            expected_pnl_per_step: mean(&combined_returns),
+            volatility_per_step: std_dev(&combined_returns),
+            equity_curve,
+        }
+    }
+
+    pub fn print(&self) {
+        println!("\n⚖️  Capital Allocation ({:?})", self.method);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        for ((label, weight), capital) in self.labels.iter().zip(&self.weights).zip(&self.capital) {
+            println!("   {:<20} {:>6.1}%  ${:>12.2}", label, weight * 100.0, capital);
+        }
+        println!("   Expected PnL/Step:  {:.4}", self.expected_pnl_per_step);
+        println!("   Volatility/Step:    {:.4}", self.volatility_per_step);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+fn equal_weights(n: usize) -> Vec<f64> {
+    vec![1.0 / n.max(1) as f64; n]
+}
+
+fn normalize(weights: Vec<f64>) -> Option<Vec<f64>> {
+    let total: f64 = weights.iter().sum();
+    (total > 0.0).then(|| weights.into_iter().map(|w| w / total).collect())
+}
+
+fn risk_parity(returns: &[Vec<f64>]) -> Vec<f64> {
+    let inverse_vol = returns
+        .iter()
+        .map(|r| std_dev(r))
+        .map(|vol| if vol > 0.0 { 1.0 / vol } else { 0.0 })
+        .collect();
+    normalize(inverse_vol).unwrap_or_else(|| equal_weights(returns.len()))
+}
+
+fn covariance(returns: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let means: Vec<f64> = returns.iter().map(|r| mean(r)).collect();
+    let steps = returns.first().map_or(0, Vec::len).max(1) as f64;
+    (0..returns.len())
+        .map(|i| {
+            (0..returns.len())
+                .map(|j| {
+                    returns[i].iter().zip(&returns[j]).map(|(a, b)| (a - means[i]) * (b - means[j])).sum::<f64>() / steps
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn mean_variance(covariance: &[Vec<f64>], means: &[f64], risk_aversion: f64) -> Option<Vec<f64>> {
+    let n = means.len();
+    let trace: f64 = (0..n).map(|i| covariance[i][i]).sum();
+    let ridge = 1e-9 * trace.max(1e-12);
+    let mut a: Vec<Vec<f64>> = covariance.to_vec();
+    for (i, row) in a.iter_mut().enumerate() {
+        row[i] += ridge;
+    }
+    let raw = solve(a, means.iter().map(|m| m / risk_aversion.max(1e-12)).collect())?;
+    normalize(raw.into_iter().map(|w| w.max(0.0)).collect())
+}
+
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&r, &s| a[r][col].abs().total_cmp(&a[s][col].abs()))?;
+        if a[pivot][col].abs() < 1e-15 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = a[row][col] / a[col][col];
+            let pivot_row = a[col].clone();
+            for (cell, pivot_cell) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                *cell -= factor * pivot_cell;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[f64]) -> TimeseriesData {
+        TimeseriesData {
+            pnl_curve: values
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| TimeseriesPoint { snapshot: i, timestamp_us: i as u64, value })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_allocation_methods() {
+        let steady = series(&[0.0, 1.0, 2.0, 2.5, 3.5, 4.0, 5.0]);
+        let volatile = series(&[0.0, 4.0, 0.0, 5.0, 1.0, 6.0, 2.0]);
+        let parts = [("steady", &steady), ("volatile", &volatile)];
+
+        let equal = Allocation::optimize(&parts, AllocationMethod::EqualWeight, 1_000.0);
+        assert_eq!(equal.weights, vec![0.5, 0.5]);
+        assert_eq!(equal.equity_curve.last().unwrap().value, 1_003.5);
+
+        let parity = Allocation::optimize(&parts, AllocationMethod::RiskParity, 1_000.0);
+        assert!(parity.weights[0] > 0.8);
+        assert!((parity.weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(parity.volatility_per_step < equal.volatility_per_step);
+
+        let mv = Allocation::optimize(&parts, AllocationMethod::MeanVariance { risk_aversion: 1.0 }, 1_000.0);
+        assert!(mv.weights[0] > parity.weights[0]);
+        assert!((mv.capital.iter().sum::<f64>() - 1_000.0).abs() < 1e-9);
+    }
+}
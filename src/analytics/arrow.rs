@@ -0,0 +1,104 @@
+use anyhow::Result;
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use crate::types::{Side, Trade};
+use super::export::{TimeseriesData, TimeseriesPoint};
+use std::sync::Arc;
+
+
+
+
+pub fn timeseries_to_arrow(timeseries: &TimeseriesData) -> Result<RecordBatch> {
+    let mut curves: Vec<(String, &Vec<TimeseriesPoint>)> = vec![
+        ("pnl".to_string(), &timeseries.pnl_curve),
+        ("position".to_string(), &timeseries.position_curve),
+        ("volume".to_string(), &timeseries.volume_curve),
+        ("drawdown".to_string(), &timeseries.drawdown_curve),
+    ];
+    if !timeseries.spread_curve.is_empty() || !timeseries.mid_curve.is_empty() {
+        curves.push(("spread".to_string(), &timeseries.spread_curve));
+        curves.push(("mid".to_string(), &timeseries.mid_curve));
+    }
+    for (prefix, bands) in [("spread", &timeseries.spread_bands), ("imbalance", &timeseries.imbalance_bands)] {
+        if !bands.is_empty() {
+            curves.push((format!("{}_p10", prefix), &bands.p10));
+            curves.push((format!("{}_p50", prefix), &bands.p50));
+            curves.push((format!("{}_p90", prefix), &bands.p90));
+        }
+    }
+    let rows = timeseries.pnl_curve.len();
+    for (name, curve) in &curves {
+        if curve.len() != rows {
+            anyhow::bail!("{} curve has {} points, expected {}", name, curve.len(), rows);
+        }
+    }
+
+    let mut fields = vec![
+        Field::new("snapshot", DataType::UInt64, false),
+        Field::new("timestamp_us", DataType::UInt64, false),
+    ];
+    let mut arrays: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(timeseries.pnl_curve.iter().map(|p| p.snapshot as u64))),
+        Arc::new(UInt64Array::from_iter_values(timeseries.pnl_curve.iter().map(|p| p.timestamp_us))),
+    ];
+    for (name, curve) in curves {
+        fields.push(Field::new(name, DataType::Float64, false));
+        arrays.push(Arc::new(Float64Array::from_iter_values(curve.iter().map(|p| p.value))));
+    }
+
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+pub fn trades_to_arrow(trades: &[Trade]) -> Result<RecordBatch> {
+    let schema = Schema::new(vec![
+        Field::new("timestamp_us", DataType::UInt64, false),
+        Field::new("side", DataType::Utf8, false),
+        Field::new("price", DataType::Float64, false),
+        Field::new("quantity", DataType::Float64, false),
+    ]);
+    let arrays: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(trades.iter().map(|t| t.timestamp_us))),
+        Arc::new(StringArray::from_iter_values(trades.iter().map(|t| match t.side {
+            Side::Bid => "buy",
+            Side::Ask => "sell",
+        }))),
+        Arc::new(Float64Array::from_iter_values(trades.iter().map(|t| t.price))),
+        Arc::new(Float64Array::from_iter_values(trades.iter().map(|t| t.quantity))),
+    ];
+
+    Ok(RecordBatch::try_new(Arc::new(schema), arrays)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trade_log_batch() {
+        let trades = vec![
+            Trade::new(Side::Bid, 100.0, 1.0, 10),
+            Trade::new(Side::Ask, 101.0, 0.5, 20),
+        ];
+        let batch = trades_to_arrow(&trades).unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        let sides = batch.column_by_name("side").unwrap().as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(sides.value(1), "sell");
+    }
+
+    #[test]
+    fn test_timeseries_requires_aligned_curves() {
+        let point = |snapshot: usize| TimeseriesPoint { snapshot, timestamp_us: snapshot as u64, value: 1.0 };
+        let mut timeseries = TimeseriesData {
+            pnl_curve: vec![point(0), point(100)],
+            position_curve: vec![point(0), point(100)],
+            volume_curve: vec![point(0), point(100)],
+            drawdown_curve: vec![point(0), point(100)],
+            ..Default::default()
+        };
+        assert_eq!(timeseries_to_arrow(&timeseries).unwrap().num_columns(), 6);
+
+        timeseries.drawdown_curve.pop();
+        assert!(timeseries_to_arrow(&timeseries).is_err());
+    }
+}
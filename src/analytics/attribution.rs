@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use crate::execution::Position;
+use crate::types::Trade;
+use crate::utils::time::Session;
+
+pub use crate::utils::time::hour_of_day;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributionBucket {
+    pub label: String,
+    pub realized_pnl: f64,
+    pub trade_count: usize,
+    pub volume: f64,
+    pub notional: f64,
+}
+
+impl AttributionBucket {
+    fn new(label: String) -> Self {
+        Self {
+            label,
+            realized_pnl: 0.0,
+            trade_count: 0,
+            volume: 0.0,
+            notional: 0.0,
+        }
+    }
+
+    fn add(&mut self, trade: &Trade, realized_pnl: f64) {
+        self.realized_pnl += realized_pnl;
+        self.trade_count += 1;
+        self.volume += trade.quantity;
+        self.notional += trade.notional();
+    }
+
+    fn merge(&mut self, other: &AttributionBucket) {
+        self.realized_pnl += other.realized_pnl;
+        self.trade_count += other.trade_count;
+        self.volume += other.volume;
+        self.notional += other.notional;
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlAttribution {
+    pub hourly: Vec<AttributionBucket>,
+    pub sessions: Vec<AttributionBucket>,
+}
+
+impl PnlAttribution {
+
+    pub fn from_trades(trades: &[Trade]) -> Self {
+        let mut hourly: Vec<AttributionBucket> = (0..24)
+            .map(|hour| AttributionBucket::new(format!("{:02}:00", hour)))
+            .collect();
+
+        let realized = realized_pnl_per_trade(trades);
+        for (trade, pnl) in trades.iter().zip(realized) {
+            hourly[hour_of_day(trade.timestamp_us) as usize].add(trade, pnl);
+        }
+
+        let sessions = Session::ALL
+            .iter()
+            .map(|session| {
+                let mut bucket = AttributionBucket::new(session.label().to_string());
+                for hour in session.hours() {
+                    bucket.merge(&hourly[hour as usize]);
+                }
+                bucket
+            })
+            .collect();
+
+        Self { hourly, sessions }
+    }
+
+
+    pub fn best_hour(&self) -> Option<&AttributionBucket> {
+        self.hourly
+            .iter()
+            .filter(|b| b.trade_count > 0)
+            .max_by(|a, b| a.realized_pnl.total_cmp(&b.realized_pnl))
+    }
+
+
+    pub fn worst_hour(&self) -> Option<&AttributionBucket> {
+        self.hourly
+            .iter()
+            .filter(|b| b.trade_count > 0)
+            .min_by(|a, b| a.realized_pnl.total_cmp(&b.realized_pnl))
+    }
+
+    pub fn print(&self) {
+        println!("\n🕒 PnL Attribution by Hour (UTC)");
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   {:<8} {:>12} {:>8} {:>12}", "Hour", "Realized", "Trades", "Volume");
+        for bucket in self.hourly.iter().filter(|b| b.trade_count > 0) {
+            println!(
+                "   {:<8} {:>12.2} {:>8} {:>12.4}",
+                bucket.label, bucket.realized_pnl, bucket.trade_count, bucket.volume
+            );
+        }
+        println!("   ───────────────────────────────────────");
+        for bucket in &self.sessions {
+            println!(
+                "   {:<8} {:>12.2} {:>8} {:>12.4}",
+                bucket.label, bucket.realized_pnl, bucket.trade_count, bucket.volume
+            );
+        }
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+
+
+pub fn realized_pnl_per_trade(trades: &[Trade]) -> Vec<f64> {
+    let mut position = Position::new();
+    let mut realized = Vec::with_capacity(trades.len());
+
+    for trade in trades {
+        let before = position.realized_pnl;
+        position.execute_trade(trade.clone());
+        realized.push(position.realized_pnl - before);
+    }
+
+    realized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Side;
+    use crate::utils::time::US_PER_HOUR;
+
+    #[test]
+    fn test_hour_of_day() {
+        assert_eq!(hour_of_day(0), 0);
+        assert_eq!(hour_of_day(US_PER_HOUR * 5 + 1), 5);
+        assert_eq!(hour_of_day(US_PER_HOUR * 25), 1);
+    }
+
+    #[test]
+    fn test_realized_pnl_lands_in_closing_hour() {
+        let trades = vec![
+            Trade::new(Side::Bid, 100.0, 1.0, US_PER_HOUR * 3),
+            Trade::new(Side::Ask, 105.0, 1.0, US_PER_HOUR * 9),
+        ];
+
+        let attribution = PnlAttribution::from_trades(&trades);
+
+        assert_eq!(attribution.hourly.len(), 24);
+        assert_eq!(attribution.hourly[3].trade_count, 1);
+        assert_eq!(attribution.hourly[3].realized_pnl, 0.0);
+        assert_eq!(attribution.hourly[9].realized_pnl, 5.0);
+        assert_eq!(attribution.best_hour().unwrap().label, "09:00");
+
+        assert_eq!(attribution.sessions[0].trade_count, 1);
+        assert_eq!(attribution.sessions[1].realized_pnl, 5.0);
+        assert_eq!(attribution.sessions[2].trade_count, 0);
+    }
+
+    #[test]
+    fn test_best_and_worst_hour_tolerate_nan() {
+        let trades = vec![
+            Trade::new(Side::Bid, 100.0, 1.0, US_PER_HOUR * 3),
+            Trade::new(Side::Ask, 105.0, 1.0, US_PER_HOUR * 9),
+        ];
+        let mut attribution = PnlAttribution::from_trades(&trades);
+        attribution.hourly[3].realized_pnl = f64::NAN;
+
+        assert!(attribution.best_hour().is_some());
+        assert!(attribution.worst_hour().is_some());
+    }
+}
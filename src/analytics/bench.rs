@@ -0,0 +1,167 @@
+use super::export::ComparisonApproach;
+use super::BacktestResult;
+use crate::types::L2Snapshot;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    
+    pub warmup: usize,
+    
+    pub repetitions: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 1,
+            repetitions: 5,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunStatistics {
+    pub warmup: usize,
+    pub repetitions: usize,
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub stddev_ns: f64,
+    pub min_ns: f64,
+    pub max_ns: f64,
+}
+
+impl RunStatistics {
+    pub fn from_samples(warmup: usize, samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self { warmup, ..Self::default() };
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+        let variance = if n > 1 {
+            sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Self {
+            warmup,
+            repetitions: n,
+            mean_ns: mean,
+            median_ns: median,
+            stddev_ns: variance.sqrt(),
+            min_ns: sorted[0],
+            max_ns: sorted[n - 1],
+        }
+    }
+
+    
+    pub fn cv_pct(&self) -> f64 {
+        if self.mean_ns > 0.0 {
+            self.stddev_ns / self.mean_ns * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+
+
+
+pub struct BenchRunner {
+    config: BenchConfig,
+}
+
+impl BenchRunner {
+    pub fn new(config: BenchConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &BenchConfig {
+        &self.config
+    }
+
+    
+    
+    pub fn run(&self, approach: &ComparisonApproach, snapshots: &[L2Snapshot]) -> (BacktestResult, RunStatistics) {
+        self.measure(|| approach.run(snapshots))
+    }
+
+    
+    
+    pub fn measure<F: FnMut() -> BacktestResult>(&self, mut run: F) -> (BacktestResult, RunStatistics) {
+        for _ in 0..self.config.warmup {
+            run();
+        }
+
+        let mut runs: Vec<BacktestResult> = (0..self.config.repetitions.max(1)).map(|_| run()).collect();
+        let samples: Vec<f64> = runs
+            .iter()
+            .map(|r| r.timing.total_duration.as_nanos() as f64 / r.timing.snapshots_processed.max(1) as f64)
+            .collect();
+        let stats = RunStatistics::from_samples(self.config.warmup, &samples);
+
+        runs.sort_by_key(|r| r.timing.total_duration);
+        let median = runs.len() / 2;
+        (runs.swap_remove(median), stats)
+    }
+}
+
+impl Default for BenchRunner {
+    fn default() -> Self {
+        Self::new(BenchConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trivial_approach::{NaiveMarketMaker, NaiveMarketMakerConfig};
+    use crate::types::PriceLevel;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_statistics_from_samples() {
+        let stats = RunStatistics::from_samples(2, &[4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(stats.repetitions, 4);
+        assert_eq!(stats.warmup, 2);
+        assert_eq!(stats.mean_ns, 2.5);
+        assert_eq!(stats.median_ns, 2.5);
+        assert_eq!(stats.min_ns, 1.0);
+        assert_eq!(stats.max_ns, 4.0);
+        assert!((stats.stddev_ns - (5.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(RunStatistics::from_samples(0, &[7.0]).stddev_ns, 0.0);
+    }
+
+    #[test]
+    fn test_runner_warms_up_then_repeats() {
+        let snapshots: Vec<L2Snapshot> = (0..20u64)
+            .map(|i| {
+                let bid = 100.0 + (i % 4) as f64 * 0.1;
+                L2Snapshot::from_levels(0, i * 1_000, String::new(), &[PriceLevel::new(bid, 1.0)], &[PriceLevel::new(bid + 0.1, 1.0)])
+            })
+            .collect();
+        let built = Rc::new(Cell::new(0));
+        let counter = built.clone();
+        let approach = ComparisonApproach::new("Naive", move || {
+            counter.set(counter.get() + 1);
+            Box::new(NaiveMarketMaker::new(NaiveMarketMakerConfig::default()))
+        });
+
+        let runner = BenchRunner::new(BenchConfig { warmup: 2, repetitions: 3 });
+        let (result, stats) = runner.run(&approach, &snapshots);
+
+        assert_eq!(built.get(), 5);
+        assert_eq!(stats.repetitions, 3);
+        assert_eq!(result.timing.snapshots_processed, 20);
+        assert!(stats.min_ns <= stats.median_ns && stats.median_ns <= stats.max_ns);
+    }
+}
@@ -4,8 +4,27 @@
 
 
 use serde::{Deserialize, Serialize};
-use crate::types::{Side, Trade};
+use crate::types::{L2Snapshot, Side, Trade};
 use super::{BacktestResult, PerformanceMetrics, TimingMetrics};
+use super::attribution::PnlAttribution;
+use super::kelly::KellyReport;
+use super::round_trips::{longest_streaks, match_round_trips, RoundTrip};
+use super::markout::MarkoutCurve;
+use super::regime::RegimeBreakdown;
+use super::liquidity::LiquidityProfile;
+use super::slippage::SlippageReport;
+use super::heatmap::DepthHeatmap;
+use super::histogram::LatencySummary;
+use super::bench::{BenchRunner, RunStatistics};
+use crate::features::VolatilityPoint;
+use crate::strategy::{Strategy, StrategyStats};
+use crate::execution::Position;
+use crate::engine::run_fingerprint;
+use crate::experiments::DatasetRange;
+use crate::utils::provenance::{build_profile, crate_version, git_commit};
+use crate::utils::time::{format_timestamp, hour_index, session_bucket};
+use std::collections::BTreeMap;
+use std::time::Instant;
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +34,18 @@ pub struct BacktestExport {
     pub timeseries: TimeseriesData,
     pub trades: TradeHistory,
     pub risk: RiskMetrics,
+    pub attribution: PnlAttribution,
+    #[serde(default)]
+    pub sizing: KellyReport,
+    pub markouts: Option<MarkoutCurve>,
+    pub regimes: Option<RegimeBreakdown>,
+    pub liquidity: Option<LiquidityProfile>,
+    #[serde(default)]
+    pub slippage: Option<SlippageReport>,
+    #[serde(default)]
+    pub heatmap: Option<DepthHeatmap>,
+    pub volatility: Option<Vec<VolatilityPoint>>,
+    pub strategy_metrics: Option<BTreeMap<String, f64>>,
 }
 
 
@@ -28,6 +59,41 @@ pub struct ExportMetadata {
     pub starting_capital: f64,
     pub final_capital: f64,
     pub return_pct: f64,
+    
+    
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    #[serde(default)]
+    pub strategy_config: Option<serde_json::Value>,
+    #[serde(default)]
+    pub dataset: Option<DatasetRange>,
+    #[serde(default)]
+    pub crate_version: String,
+    #[serde(default)]
+    pub build_profile: String,
+    #[serde(default)]
+    pub git_commit: Option<String>,
+}
+
+impl ExportMetadata {
+    fn new(strategy_name: String, timing: &TimingMetrics, starting_capital: f64, total_pnl: f64) -> Self {
+        Self {
+            strategy_name,
+            dataset_size: timing.snapshots_processed,
+            timestamp: chrono::Local::now().to_rfc3339(),
+            duration_ms: timing.total_duration.as_secs_f64() * 1000.0,
+            throughput: timing.throughput,
+            starting_capital,
+            final_capital: starting_capital + total_pnl,
+            return_pct: (total_pnl / starting_capital) * 100.0,
+            fingerprint: None,
+            strategy_config: None,
+            dataset: None,
+            crate_version: crate_version(),
+            build_profile: build_profile(),
+            git_commit: git_commit(),
+        }
+    }
 }
 
 
@@ -42,6 +108,12 @@ pub struct SummaryMetrics {
     pub total_pnl: f64,
     pub realized_pnl: f64,
     pub unrealized_pnl: f64,
+    pub spread_capture_pnl: f64,
+    pub inventory_pnl: f64,
+    #[serde(default)]
+    pub liquidation_cost: f64,
+    #[serde(default)]
+    pub borrow_cost: f64,
 
     
     pub final_position: f64,
@@ -68,10 +140,20 @@ pub struct SummaryMetrics {
     
     pub time_per_snapshot_ns: f64,
     pub throughput_per_sec: f64,
+    #[serde(default)]
+    pub data_duration_secs: f64,
+    #[serde(default)]
+    pub data_rate_per_sec: f64,
+    #[serde(default)]
+    pub replay_speed: f64,
+    #[serde(default)]
+    pub strategy_latency: Option<LatencySummary>,
+    #[serde(default)]
+    pub fill_latency: Option<LatencySummary>,
 }
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TimeseriesData {
     
     pub pnl_curve: Vec<TimeseriesPoint>,
@@ -84,6 +166,71 @@ pub struct TimeseriesData {
 
     
     pub drawdown_curve: Vec<TimeseriesPoint>,
+
+    #[serde(default)]
+    pub spread_curve: Vec<TimeseriesPoint>,
+
+    #[serde(default)]
+    pub mid_curve: Vec<TimeseriesPoint>,
+
+    #[serde(default)]
+    pub peak_curve: Vec<TimeseriesPoint>,
+
+    
+    #[serde(default)]
+    pub underwater_curve: Vec<TimeseriesPoint>,
+
+    #[serde(default)]
+    pub spread_bands: PercentileBands,
+
+    #[serde(default)]
+    pub imbalance_bands: PercentileBands,
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PercentileBands {
+    pub p10: Vec<TimeseriesPoint>,
+    pub p50: Vec<TimeseriesPoint>,
+    pub p90: Vec<TimeseriesPoint>,
+}
+
+impl PercentileBands {
+    pub fn is_empty(&self) -> bool {
+        self.p50.is_empty()
+    }
+}
+
+impl TimeseriesData {
+    
+    
+    pub fn with_drawdown(mut self) -> Self {
+        let mut peak = 0.0;
+        let mut peak_us = self.pnl_curve.first().map(|p| p.timestamp_us).unwrap_or(0);
+        self.drawdown_curve.clear();
+        self.peak_curve.clear();
+        self.underwater_curve.clear();
+
+        for point in &self.pnl_curve {
+            if point.value >= peak {
+                peak = point.value;
+                peak_us = point.timestamp_us;
+            }
+            let at = |value: f64| TimeseriesPoint {
+                snapshot: point.snapshot,
+                timestamp_us: point.timestamp_us,
+                value,
+            };
+            self.drawdown_curve.push(at(peak - point.value));
+            self.peak_curve.push(at(peak));
+            self.underwater_curve.push(at(point.timestamp_us.saturating_sub(peak_us) as f64));
+        }
+        self
+    }
+
+    pub fn max_underwater_us(&self) -> u64 {
+        self.underwater_curve.iter().map(|p| p.value as u64).max().unwrap_or(0)
+    }
 }
 
 
@@ -108,6 +255,11 @@ pub struct TradeHistory {
 pub struct TradeExport {
     pub id: usize,
     pub timestamp_us: u64,
+    
+    #[serde(default)]
+    pub time: String,
+    #[serde(default)]
+    pub session: String,
     pub side: String,
     pub price: f64,
     pub size: f64,
@@ -125,6 +277,19 @@ pub struct RiskMetrics {
     pub avg_loss: f64,
     pub largest_win: f64,
     pub largest_loss: f64,
+    
+    #[serde(default)]
+    pub max_underwater_us: u64,
+    #[serde(default)]
+    pub longest_win_streak: usize,
+    #[serde(default)]
+    pub longest_loss_streak: usize,
+    
+    #[serde(default)]
+    pub profitable_hours_pct: f64,
+    
+    #[serde(default)]
+    pub hourly_pnl_std: f64,
 }
 
 
@@ -143,6 +308,58 @@ pub struct ApproachMetrics {
     pub throughput_per_sec: f64,
     pub total_duration_ms: f64,
     pub speedup_vs_optimized: f64,
+    
+    #[serde(default)]
+    pub handicap_iterations: usize,
+    
+    #[serde(default)]
+    pub timing_stats: Option<RunStatistics>,
+}
+
+
+
+
+
+pub struct ComparisonApproach {
+    pub name: String,
+    
+    pub handicap: usize,
+    constructor: Box<dyn Fn() -> Box<dyn Strategy>>,
+}
+
+impl ComparisonApproach {
+    pub fn new<F>(name: impl Into<String>, constructor: F) -> Self
+    where
+        F: Fn() -> Box<dyn Strategy> + 'static,
+    {
+        Self { name: name.into(), handicap: 0, constructor: Box::new(constructor) }
+    }
+
+    pub fn with_handicap(mut self, iterations: usize) -> Self {
+        self.handicap = iterations;
+        self
+    }
+
+    
+    
+    pub fn run(&self, snapshots: &[L2Snapshot]) -> BacktestResult {
+        let final_price = snapshots.last().map(|s| s.mid_price()).unwrap_or(0.0);
+        let mut strategy = (self.constructor)();
+        let mut position = Position::new();
+        let start = Instant::now();
+        for snapshot in snapshots {
+            for trade in strategy.on_market_data(snapshot, &position) {
+                position.execute_trade(trade);
+            }
+        }
+        let duration = start.elapsed();
+
+        let stats = strategy.stats();
+        let mut result = BacktestResult::new(self.name.clone());
+        result.calculate_from_position(&position, final_price, stats.updates_processed, stats.quotes_placed);
+        result.set_timing(duration, snapshots.len());
+        result
+    }
 }
 
 
@@ -152,6 +369,33 @@ pub struct SpeedupPoint {
     pub speedup: f64,
 }
 
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    
+    pub sample_every: usize,
+    pub include_all_trades: bool,
+    
+    pub summary_only: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            sample_every: 100,
+            include_all_trades: true,
+            summary_only: false,
+        }
+    }
+}
+
+impl ExportOptions {
+    pub fn samples(&self, index: usize) -> bool {
+        !self.summary_only && index.is_multiple_of(self.sample_every.max(1))
+    }
+}
+
 impl BacktestExport {
     
     pub fn from_backtest(
@@ -162,24 +406,16 @@ impl BacktestExport {
         final_price: f64,
         starting_capital: f64,
     ) -> Self {
-        let total_pnl = result.metrics.total_pnl;
-        let final_capital = starting_capital + total_pnl;
-        let return_pct = (total_pnl / starting_capital) * 100.0;
-
         let metadata = ExportMetadata {
-            strategy_name: result.name.clone(),
-            dataset_size: result.timing.snapshots_processed,
-            timestamp: chrono::Local::now().to_rfc3339(),
-            duration_ms: result.timing.total_duration.as_secs_f64() * 1000.0,
-            throughput: result.timing.throughput,
-            starting_capital,
-            final_capital,
-            return_pct,
+            fingerprint: Some(run_fingerprint(trades, &result.metrics)),
+            ..ExportMetadata::new(result.name.clone(), &result.timing, starting_capital, result.metrics.total_pnl)
         };
 
         let summary = SummaryMetrics::from_metrics(&result.metrics, &result.timing, starting_capital);
         let trade_history = TradeHistory::from_trades(trades, start_price);
-        let risk = RiskMetrics::calculate(trades, &timeseries.pnl_curve, start_price, final_price);
+        let risk = RiskMetrics::calculate(trades, &timeseries, start_price, final_price);
+        let attribution = PnlAttribution::from_trades(trades);
+        let sizing = KellyReport::from_trades(trades, starting_capital + result.metrics.total_pnl, final_price);
 
         Self {
             metadata,
@@ -187,7 +423,94 @@ impl BacktestExport {
             timeseries,
             trades: trade_history,
             risk,
+            attribution,
+            sizing,
+            markouts: None,
+            regimes: None,
+            liquidity: None,
+            slippage: None,
+            heatmap: None,
+            volatility: None,
+            strategy_metrics: None,
+        }
+    }
+
+    
+    pub fn with_markouts(mut self, markouts: MarkoutCurve) -> Self {
+        self.markouts = Some(markouts);
+        self
+    }
+
+    
+    pub fn with_regimes(mut self, regimes: RegimeBreakdown) -> Self {
+        self.regimes = Some(regimes);
+        self
+    }
+
+    
+    pub fn with_liquidity(mut self, liquidity: LiquidityProfile) -> Self {
+        self.liquidity = Some(liquidity);
+        self
+    }
+
+    pub fn with_slippage(mut self, slippage: SlippageReport) -> Self {
+        self.slippage = Some(slippage);
+        self
+    }
+
+    pub fn with_heatmap(mut self, heatmap: DepthHeatmap) -> Self {
+        self.heatmap = Some(heatmap);
+        self
+    }
+
+    
+    pub fn with_volatility(mut self, volatility: Vec<VolatilityPoint>) -> Self {
+        self.volatility = Some(volatility);
+        self
+    }
+
+    pub fn with_strategy_config(mut self, config: serde_json::Value) -> Self {
+        self.metadata.strategy_config = Some(config);
+        self
+    }
+
+    pub fn with_dataset(mut self, dataset: DatasetRange) -> Self {
+        self.metadata.dataset = Some(dataset);
+        self
+    }
+
+    
+    pub fn with_strategy_stats(mut self, stats: &StrategyStats) -> Self {
+        let mut metrics = BTreeMap::new();
+        metrics.insert("updates_processed".to_string(), stats.updates_processed as f64);
+        metrics.insert("trades_generated".to_string(), stats.trades_generated as f64);
+        metrics.insert("quotes_placed".to_string(), stats.quotes_placed as f64);
+        metrics.insert("quotes_throttled".to_string(), stats.quotes_throttled as f64);
+        metrics.insert("quotes_held".to_string(), stats.quotes_held as f64);
+        metrics.insert("backoff_triggers".to_string(), stats.backoff_triggers as f64);
+        for (name, value) in &stats.custom {
+            metrics.insert(name.clone(), *value);
+        }
+        self.strategy_metrics = Some(metrics);
+        self
+    }
+
+    
+    
+    pub fn with_options(mut self, options: &ExportOptions) -> Self {
+        if options.summary_only || !options.include_all_trades {
+            self.trades.all_trades.clear();
         }
+        if options.summary_only {
+            self.timeseries = TimeseriesData::default();
+            self.markouts = None;
+            self.regimes = None;
+            self.liquidity = None;
+            self.slippage = None;
+            self.heatmap = None;
+            self.volatility = None;
+        }
+        self
     }
 
     
@@ -204,7 +527,7 @@ impl BacktestExport {
 }
 
 impl SummaryMetrics {
-    fn from_metrics(metrics: &PerformanceMetrics, timing: &TimingMetrics, starting_capital: f64) -> Self {
+    pub(crate) fn from_metrics(metrics: &PerformanceMetrics, timing: &TimingMetrics, starting_capital: f64) -> Self {
         let final_capital = starting_capital + metrics.total_pnl;
         let return_pct = (metrics.total_pnl / starting_capital) * 100.0;
 
@@ -215,6 +538,10 @@ impl SummaryMetrics {
             total_pnl: metrics.total_pnl,
             realized_pnl: metrics.realized_pnl,
             unrealized_pnl: metrics.unrealized_pnl,
+            spread_capture_pnl: metrics.spread_capture_pnl,
+            inventory_pnl: metrics.inventory_pnl,
+            liquidation_cost: metrics.liquidation_cost,
+            borrow_cost: metrics.borrow_cost,
             final_position: metrics.final_position,
             max_position_long: metrics.max_position_long,
             max_position_short: metrics.max_position_short,
@@ -231,6 +558,11 @@ impl SummaryMetrics {
             quote_rate: metrics.quote_rate,
             time_per_snapshot_ns: timing.time_per_snapshot.as_nanos() as f64,
             throughput_per_sec: timing.throughput,
+            data_duration_secs: timing.data_duration_us as f64 / 1_000_000.0,
+            data_rate_per_sec: timing.data_rate,
+            replay_speed: timing.replay_speed,
+            strategy_latency: timing.strategy_latency,
+            fill_latency: timing.fill_latency,
         }
     }
 }
@@ -261,6 +593,8 @@ impl TradeHistory {
             all_trades.push(TradeExport {
                 id,
                 timestamp_us: trade.timestamp_us,
+                time: format_timestamp(trade.timestamp_us),
+                session: session_bucket(trade.timestamp_us).to_string(),
                 side: match trade.side {
                     Side::Bid => "buy".to_string(),
                     Side::Ask => "sell".to_string(),
@@ -274,11 +608,10 @@ impl TradeHistory {
             current_pos += signed_qty;
 
             
-            if current_pos == 0.0 {
-                avg_entry = trade.price;
-            } else if (current_pos > 0.0 && matches!(trade.side, Side::Bid)) ||
-                      (current_pos < 0.0 && matches!(trade.side, Side::Ask)) {
-                
+            if current_pos == 0.0
+                || (current_pos > 0.0 && matches!(trade.side, Side::Bid))
+                || (current_pos < 0.0 && matches!(trade.side, Side::Ask))
+            {
                 avg_entry = trade.price;
             }
         }
@@ -312,12 +645,23 @@ impl TradeHistory {
 }
 
 impl RiskMetrics {
-    fn calculate(
+    pub(crate) fn calculate(
         trades: &[Trade],
-        pnl_curve: &[TimeseriesPoint],
+        timeseries: &TimeseriesData,
         start_price: f64,
         _final_price: f64,
     ) -> Self {
+        let pnl_curve = &timeseries.pnl_curve;
+        let max_underwater_us = if timeseries.underwater_curve.len() == pnl_curve.len() {
+            timeseries.max_underwater_us()
+        } else {
+            TimeseriesData {
+                pnl_curve: pnl_curve.clone(),
+                ..Default::default()
+            }
+            .with_drawdown()
+            .max_underwater_us()
+        };
         
         let (max_dd, max_dd_pct) = Self::calculate_max_drawdown(pnl_curve);
 
@@ -328,6 +672,10 @@ impl RiskMetrics {
         let (profit_factor, avg_win, avg_loss, largest_win, largest_loss) =
             Self::calculate_profit_metrics(trades, start_price);
 
+        let round_trips = match_round_trips(trades);
+        let (longest_win_streak, longest_loss_streak) = longest_streaks(&round_trips);
+        let (profitable_hours_pct, hourly_pnl_std) = Self::calculate_hourly_consistency(&round_trips);
+
         Self {
             max_drawdown: max_dd,
             max_drawdown_pct: max_dd_pct,
@@ -337,7 +685,28 @@ impl RiskMetrics {
             avg_loss,
             largest_win,
             largest_loss,
+            max_underwater_us,
+            longest_win_streak,
+            longest_loss_streak,
+            profitable_hours_pct,
+            hourly_pnl_std,
+        }
+    }
+
+    fn calculate_hourly_consistency(round_trips: &[RoundTrip]) -> (f64, f64) {
+        let mut hourly: BTreeMap<u64, f64> = BTreeMap::new();
+        for trip in round_trips {
+            *hourly.entry(hour_index(trip.exit_us)).or_default() += trip.pnl;
+        }
+        if hourly.is_empty() {
+            return (0.0, 0.0);
         }
+
+        let count = hourly.len() as f64;
+        let profitable = hourly.values().filter(|pnl| **pnl > 0.0).count() as f64;
+        let mean = hourly.values().sum::<f64>() / count;
+        let variance = hourly.values().map(|pnl| (pnl - mean).powi(2)).sum::<f64>() / count;
+        (profitable / count * 100.0, variance.sqrt())
     }
 
     fn calculate_max_drawdown(pnl_curve: &[TimeseriesPoint]) -> (f64, f64) {
@@ -474,19 +843,9 @@ impl PerformanceComparison {
         results: &[BacktestResult],
     ) -> Self {
         const STARTING_CAPITAL: f64 = 10_000.0;
-        let total_pnl = results[0].metrics.total_pnl;
-        let final_capital = STARTING_CAPITAL + total_pnl;
-        let return_pct = (total_pnl / STARTING_CAPITAL) * 100.0;
-
         let metadata = ExportMetadata {
-            strategy_name,
             dataset_size,
-            timestamp: chrono::Local::now().to_rfc3339(),
-            duration_ms: results[0].timing.total_duration.as_secs_f64() * 1000.0,
-            throughput: results[0].timing.throughput,
-            starting_capital: STARTING_CAPITAL,
-            final_capital,
-            return_pct,
+            ..ExportMetadata::new(strategy_name, &results[0].timing, STARTING_CAPITAL, results[0].metrics.total_pnl)
         };
 
         let approaches: Vec<ApproachMetrics> = results.iter().map(|r| {
@@ -503,6 +862,8 @@ impl PerformanceComparison {
                 throughput_per_sec: r.timing.throughput,
                 total_duration_ms: r.timing.total_duration.as_secs_f64() * 1000.0,
                 speedup_vs_optimized: speedup,
+                handicap_iterations: 0,
+                timing_stats: None,
             }
         }).collect();
 
@@ -528,6 +889,44 @@ impl PerformanceComparison {
     }
 
     
+    
+    pub fn run(strategy_name: String, snapshots: &[L2Snapshot], approaches: &[ComparisonApproach]) -> (Self, Vec<BacktestResult>) {
+        assert!(!approaches.is_empty(), "performance comparison needs at least one approach");
+        let results: Vec<BacktestResult> = approaches.iter().map(|approach| approach.run(snapshots)).collect();
+        (Self::new(strategy_name, snapshots.len(), &results).with_handicaps(approaches), results)
+    }
+
+    
+    
+    pub fn benchmark(
+        strategy_name: String,
+        snapshots: &[L2Snapshot],
+        approaches: &[ComparisonApproach],
+        runner: &BenchRunner,
+    ) -> (Self, Vec<BacktestResult>) {
+        assert!(!approaches.is_empty(), "performance comparison needs at least one approach");
+        let (results, stats): (Vec<_>, Vec<_>) = approaches.iter().map(|approach| runner.run(approach, snapshots)).unzip();
+        let comparison = Self::new(strategy_name, snapshots.len(), &results)
+            .with_handicaps(approaches)
+            .with_statistics(stats);
+        (comparison, results)
+    }
+
+    pub fn with_statistics(mut self, stats: Vec<RunStatistics>) -> Self {
+        for (metrics, stats) in self.approaches.iter_mut().zip(stats) {
+            metrics.timing_stats = Some(stats);
+        }
+        self
+    }
+
+    pub fn with_handicaps(mut self, approaches: &[ComparisonApproach]) -> Self {
+        for (metrics, approach) in self.approaches.iter_mut().zip(approaches) {
+            metrics.handicap_iterations = approach.handicap;
+        }
+        self
+    }
+
+    
     pub fn to_json(&self) -> anyhow::Result<String> {
         Ok(serde_json::to_string_pretty(self)?)
     }
@@ -539,3 +938,119 @@ impl PerformanceComparison {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::time::US_PER_HOUR;
+
+    fn export() -> BacktestExport {
+        let trades = vec![Trade::new(Side::Bid, 100.0, 1.0, 0), Trade::new(Side::Ask, 101.0, 1.0, 1)];
+        let point = |value| TimeseriesPoint { snapshot: 0, timestamp_us: 0, value };
+        let timeseries = TimeseriesData {
+            pnl_curve: vec![point(0.0), point(1.0)],
+            position_curve: vec![point(1.0), point(0.0)],
+            volume_curve: vec![point(1.0), point(2.0)],
+            drawdown_curve: vec![point(0.0), point(0.0)],
+            ..Default::default()
+        };
+        BacktestExport::from_backtest(&BacktestResult::new("test".to_string()), &trades, timeseries, 100.0, 101.0, 10_000.0)
+            .with_volatility(Vec::new())
+    }
+
+    #[test]
+    fn test_with_drawdown() {
+        let point = |snapshot: usize, value: f64| TimeseriesPoint { snapshot, timestamp_us: snapshot as u64 * 100, value };
+        let timeseries = TimeseriesData {
+            pnl_curve: vec![point(0, 0.0), point(1, 2.0), point(2, 1.0), point(3, -1.0), point(4, 3.0), point(5, 2.5)],
+            ..Default::default()
+        }
+        .with_drawdown();
+
+        let values = |curve: &[TimeseriesPoint]| curve.iter().map(|p| p.value).collect::<Vec<_>>();
+        assert_eq!(values(&timeseries.drawdown_curve), vec![0.0, 0.0, 1.0, 3.0, 0.0, 0.5]);
+        assert_eq!(values(&timeseries.peak_curve), vec![0.0, 2.0, 2.0, 2.0, 3.0, 3.0]);
+        assert_eq!(values(&timeseries.underwater_curve), vec![0.0, 0.0, 100.0, 200.0, 0.0, 100.0]);
+        assert_eq!(timeseries.max_underwater_us(), 200);
+
+        let export = BacktestExport::from_backtest(&BacktestResult::new("test".to_string()), &[], timeseries, 100.0, 100.0, 10_000.0);
+        assert_eq!(export.risk.max_underwater_us, 200);
+    }
+
+    #[test]
+    fn test_streaks_and_hourly_consistency() {
+        let hour = US_PER_HOUR;
+        let trades = vec![
+            Trade::new(Side::Bid, 100.0, 1.0, 0),
+            Trade::new(Side::Ask, 102.0, 1.0, 10),
+            Trade::new(Side::Bid, 100.0, 1.0, 20),
+            Trade::new(Side::Ask, 101.0, 1.0, 30),
+            Trade::new(Side::Bid, 100.0, 1.0, hour),
+            Trade::new(Side::Ask, 99.0, 1.0, hour + 10),
+            Trade::new(Side::Ask, 100.0, 1.0, 2 * hour),
+            Trade::new(Side::Bid, 101.0, 1.0, 2 * hour + 10),
+        ];
+        let timeseries = TimeseriesData::default();
+        let risk = RiskMetrics::calculate(&trades, &timeseries, 100.0, 100.0);
+
+        assert_eq!((risk.longest_win_streak, risk.longest_loss_streak), (2, 2));
+        assert!((risk.profitable_hours_pct - 100.0 / 3.0).abs() < 1e-9);
+        assert!((risk.hourly_pnl_std - (32.0_f64 / 9.0).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_export_options() {
+        let options = ExportOptions { sample_every: 10, ..Default::default() };
+        assert!(options.samples(20) && !options.samples(25));
+
+        let full = export().with_options(&ExportOptions::default());
+        assert_eq!(full.trades.all_trades.len(), 2);
+
+        let no_trades = export().with_options(&ExportOptions { include_all_trades: false, ..Default::default() });
+        assert!(no_trades.trades.all_trades.is_empty());
+        assert_eq!(no_trades.trades.recent_trades.len(), 2);
+        assert_eq!(no_trades.timeseries.pnl_curve.len(), 2);
+
+        let summary = export().with_options(&ExportOptions { summary_only: true, ..Default::default() });
+        assert!(summary.timeseries.pnl_curve.is_empty() && summary.volatility.is_none());
+        assert!(!ExportOptions { summary_only: true, ..Default::default() }.samples(0));
+    }
+
+    #[test]
+    fn test_comparison_runs_each_approach() {
+        use crate::strategy::market_maker::{MarketMaker, MarketMakerConfig};
+        use crate::trivial_approach::{NaiveMarketMaker, NaiveMarketMakerConfig};
+        use crate::types::PriceLevel;
+
+        let snapshots: Vec<L2Snapshot> = (0..50u64)
+            .map(|i| {
+                let bid = 100.0 + (i % 6) as f64 * 0.1;
+                L2Snapshot::from_levels(0, i * 1_000, String::new(), &[PriceLevel::new(bid, 1.0)], &[PriceLevel::new(bid + 0.1, 1.0)])
+            })
+            .collect();
+        let approaches = vec![
+            ComparisonApproach::new("Optimized", || Box::new(MarketMaker::new(MarketMakerConfig::default()))),
+            ComparisonApproach::new("Naive", || {
+                Box::new(NaiveMarketMaker::new(NaiveMarketMakerConfig {
+                    spread_ticks: 1.0,
+                    quote_size: 0.1,
+                    max_position: 1.0,
+                    tick_size: 0.1,
+                }))
+            })
+            .with_handicap(50),
+        ];
+        let (comparison, results) = PerformanceComparison::run("Market Maker".to_string(), &snapshots, &approaches);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].timing.snapshots_processed, 50);
+        assert_eq!(comparison.metadata.dataset_size, 50);
+        assert_eq!(
+            comparison.approaches.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(),
+            vec!["Optimized", "Naive"]
+        );
+        assert_eq!(comparison.speedup_data[0].speedup, 1.0);
+        assert_eq!(comparison.approaches[0].handicap_iterations, 0);
+        assert_eq!(comparison.approaches[1].handicap_iterations, 50);
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use super::{TimeseriesData, TimeseriesPoint};
+
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FxRate {
+    Static(f64),
+    
+    Series(Vec<(u64, f64)>),
+}
+
+impl FxRate {
+    pub fn rate_at(&self, timestamp_us: u64) -> f64 {
+        match self {
+            FxRate::Static(rate) => *rate,
+            FxRate::Series(points) => {
+                let idx = points.partition_point(|(ts, _)| *ts <= timestamp_us);
+                points.get(idx.saturating_sub(1)).map(|(_, rate)| *rate).unwrap_or(0.0)
+            }
+        }
+    }
+}
+
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertedPnl {
+    pub currency: String,
+    pub series: TimeseriesData,
+    pub total_pnl: f64,
+    
+    pub trading_pnl: f64,
+    pub fx_pnl: f64,
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CurrencyConverter {
+    pub reporting: String,
+    pub rates: BTreeMap<String, FxRate>,
+}
+
+impl CurrencyConverter {
+    pub fn new(reporting: &str) -> Self {
+        Self {
+            reporting: reporting.to_string(),
+            rates: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_rate(mut self, currency: &str, rate: FxRate) -> Self {
+        self.rates.insert(currency.to_string(), rate);
+        self
+    }
+
+    pub fn rate_at(&self, currency: &str, timestamp_us: u64) -> Result<f64> {
+        if currency == self.reporting {
+            return Ok(1.0);
+        }
+        match self.rates.get(currency) {
+            Some(rate) => Ok(rate.rate_at(timestamp_us)),
+            None => bail!("No {} rate for {}", self.reporting, currency),
+        }
+    }
+
+    
+    
+    pub fn convert(&self, series: &TimeseriesData, currency: &str) -> Result<ConvertedPnl> {
+        let mut pnl_curve = Vec::with_capacity(series.pnl_curve.len());
+        let mut trading_pnl = 0.0;
+        let mut previous = 0.0;
+        for point in &series.pnl_curve {
+            let rate = self.rate_at(currency, point.timestamp_us)?;
+            trading_pnl += (point.value - previous) * rate;
+            previous = point.value;
+            pnl_curve.push(TimeseriesPoint {
+                value: point.value * rate,
+                ..point.clone()
+            });
+        }
+        let total_pnl = pnl_curve.last().map(|p| p.value).unwrap_or(0.0);
+        let converted = TimeseriesData {
+            pnl_curve,
+            position_curve: series.position_curve.clone(),
+            volume_curve: series.volume_curve.clone(),
+            ..Default::default()
+        }
+        .with_drawdown();
+
+        Ok(ConvertedPnl {
+            currency: currency.to_string(),
+            series: converted,
+            total_pnl,
+            trading_pnl,
+            fx_pnl: total_pnl - trading_pnl,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion_splits_fx_effect() {
+        let point = |timestamp_us, value| TimeseriesPoint { snapshot: 0, timestamp_us, value };
+        let series = TimeseriesData {
+            pnl_curve: vec![point(0, 0.0), point(10, 100.0), point(20, 100.0)],
+            ..Default::default()
+        };
+        let converter = CurrencyConverter::new("USD")
+            .with_rate("EUR", FxRate::Series(vec![(0, 1.0), (10, 1.1), (15, 1.2)]))
+            .with_rate("GBP", FxRate::Static(1.25));
+
+        let eur = converter.convert(&series, "EUR").unwrap();
+        assert!((eur.total_pnl - 120.0).abs() < 1e-9);
+        assert!((eur.trading_pnl - 110.0).abs() < 1e-9);
+        assert!((eur.fx_pnl - 10.0).abs() < 1e-9);
+
+        assert!((converter.convert(&series, "GBP").unwrap().total_pnl - 125.0).abs() < 1e-9);
+        assert_eq!(converter.convert(&series, "USD").unwrap().fx_pnl, 0.0);
+        assert!(converter.convert(&series, "JPY").is_err());
+    }
+}
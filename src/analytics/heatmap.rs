@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use crate::types::L2Snapshot;
+
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeatmapConfig {
+    pub time_buckets: usize,
+    pub price_buckets: usize,
+}
+
+impl Default for HeatmapConfig {
+    fn default() -> Self {
+        Self {
+            time_buckets: 200,
+            price_buckets: 50,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DepthHeatmap {
+    pub timestamps_us: Vec<u64>,
+    pub prices: Vec<f64>,
+    pub price_step: f64,
+    pub bids: Vec<Vec<f64>>,
+    pub asks: Vec<Vec<f64>>,
+}
+
+impl DepthHeatmap {
+    pub fn build(snapshots: &[L2Snapshot], config: HeatmapConfig) -> Self {
+        let price_buckets = config.price_buckets.max(1);
+        let (mut low, mut high) = (f64::INFINITY, f64::NEG_INFINITY);
+        for snapshot in snapshots {
+            for level in snapshot.bids().iter().chain(snapshot.asks().iter()).filter(|l| l.quantity > 0.0) {
+                low = low.min(level.price);
+                high = high.max(level.price);
+            }
+        }
+        if snapshots.is_empty() || !low.is_finite() {
+            return Self::default();
+        }
+
+        let price_step = ((high - low) / price_buckets as f64).max(f64::EPSILON);
+        let prices = (0..price_buckets).map(|i| low + price_step * (i as f64 + 0.5)).collect();
+        let bucket_of = |price: f64| (((price - low) / price_step) as usize).min(price_buckets - 1);
+
+        let chunk = snapshots.len().div_ceil(config.time_buckets.max(1));
+        let mut heatmap = Self {
+            prices,
+            price_step,
+            ..Default::default()
+        };
+        for rows in snapshots.chunks(chunk) {
+            let mut bids = vec![0.0; price_buckets];
+            let mut asks = vec![0.0; price_buckets];
+            for snapshot in rows {
+                for level in snapshot.bids().into_iter().filter(|l| l.quantity > 0.0) {
+                    bids[bucket_of(level.price)] += level.quantity;
+                }
+                for level in snapshot.asks().into_iter().filter(|l| l.quantity > 0.0) {
+                    asks[bucket_of(level.price)] += level.quantity;
+                }
+            }
+            let n = rows.len() as f64;
+            heatmap.timestamps_us.push(rows[0].timestamp_us);
+            heatmap.bids.push(bids.into_iter().map(|q| q / n).collect());
+            heatmap.asks.push(asks.into_iter().map(|q| q / n).collect());
+        }
+        heatmap
+    }
+
+    pub fn to_file(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    #[test]
+    fn test_heatmap_grid() {
+        let snapshots: Vec<L2Snapshot> = (0..10)
+            .map(|i| {
+                L2Snapshot::from_levels(
+                    0,
+                    i * 100,
+                    String::new(),
+                    &[PriceLevel::new(99.0, 2.0), PriceLevel::new(98.0, 4.0)],
+                    &[PriceLevel::new(101.0, 1.0), PriceLevel::new(102.0, 3.0)],
+                )
+            })
+            .collect();
+
+        let heatmap = DepthHeatmap::build(&snapshots, HeatmapConfig { time_buckets: 5, price_buckets: 4 });
+
+        assert_eq!(heatmap.timestamps_us, vec![0, 200, 400, 600, 800]);
+        assert_eq!(heatmap.prices, vec![98.5, 99.5, 100.5, 101.5]);
+        assert_eq!(heatmap.bids[0], vec![4.0, 2.0, 0.0, 0.0]);
+        assert_eq!(heatmap.asks[4], vec![0.0, 0.0, 0.0, 4.0]);
+        assert!(DepthHeatmap::build(&[], HeatmapConfig::default()).bids.is_empty());
+    }
+}
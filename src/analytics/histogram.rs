@@ -0,0 +1,98 @@
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const MAX_TRACKABLE_NS: u64 = 60_000_000_000;
+const SIGNIFICANT_FIGURES: u8 = 3;
+
+
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    histogram: Histogram<u64>,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            histogram: Histogram::new_with_bounds(1, MAX_TRACKABLE_NS, SIGNIFICANT_FIGURES)
+                .expect("valid histogram bounds"),
+        }
+    }
+
+    
+    pub fn record(&mut self, elapsed: Duration) {
+        let ns = (elapsed.as_nanos() as u64).clamp(1, MAX_TRACKABLE_NS);
+        self.histogram.saturating_record(ns);
+    }
+
+    pub fn len(&self) -> u64 {
+        self.histogram.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.histogram.is_empty()
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        if self.histogram.is_empty() {
+            return LatencySummary::default();
+        }
+        LatencySummary {
+            count: self.histogram.len(),
+            mean_ns: self.histogram.mean(),
+            p50_ns: self.histogram.value_at_quantile(0.50),
+            p99_ns: self.histogram.value_at_quantile(0.99),
+            p999_ns: self.histogram.value_at_quantile(0.999),
+            max_ns: self.histogram.max(),
+        }
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub mean_ns: f64,
+    pub p50_ns: u64,
+    pub p99_ns: u64,
+    pub p999_ns: u64,
+    pub max_ns: u64,
+}
+
+impl LatencySummary {
+    pub fn print_row(&self, label: &str) {
+        println!("   {:<20} p50 {} ns, p99 {} ns, p99.9 {} ns, max {} ns ({} samples)",
+                 label, self.p50_ns, self.p99_ns, self.p999_ns, self.max_ns, self.count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_percentiles() {
+        let mut histogram = LatencyHistogram::new();
+        assert_eq!(histogram.summary(), LatencySummary::default());
+        for _ in 0..990 {
+            histogram.record(Duration::from_nanos(100));
+        }
+        for _ in 0..9 {
+            histogram.record(Duration::from_nanos(10_000));
+        }
+        histogram.record(Duration::from_millis(2));
+
+        let summary = histogram.summary();
+        assert_eq!(summary.count, 1_000);
+        assert_eq!(summary.p50_ns, 100);
+        assert_eq!(summary.p99_ns, 100);
+        assert!(summary.p999_ns >= 9_990 && summary.p999_ns <= 10_010);
+        assert!(summary.max_ns >= 1_999_000 && summary.max_ns <= 2_001_000);
+    }
+}
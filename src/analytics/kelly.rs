@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use crate::execution::Sizer;
+use crate::types::Trade;
+use super::round_trips::{match_round_trips, RoundTrip};
+
+const OPTIMAL_F_STEPS: usize = 100;
+
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KellyReport {
+    pub round_trips: usize,
+    pub win_rate: f64,
+    
+    pub avg_win_return: f64,
+    pub avg_loss_return: f64,
+    pub payoff_ratio: f64,
+    
+    pub kelly_fraction: f64,
+    pub half_kelly_fraction: f64,
+    
+    pub optimal_f: f64,
+    pub suggested_notional: f64,
+    pub suggested_quantity: f64,
+}
+
+impl KellyReport {
+    pub fn from_trades(trades: &[Trade], equity: f64, price: f64) -> Self {
+        Self::from_round_trips(&match_round_trips(trades), equity, price)
+    }
+
+    pub fn from_round_trips(round_trips: &[RoundTrip], equity: f64, price: f64) -> Self {
+        let returns: Vec<f64> = round_trips
+            .iter()
+            .filter(|trip| trip.entry_price > 0.0 && trip.quantity > 0.0)
+            .map(|trip| trip.pnl / (trip.entry_price * trip.quantity))
+            .collect();
+        if returns.is_empty() {
+            return Self::default();
+        }
+
+        let wins: Vec<f64> = returns.iter().copied().filter(|r| *r > 0.0).collect();
+        let losses: Vec<f64> = returns.iter().copied().filter(|r| *r < 0.0).map(f64::abs).collect();
+        let mean = |values: &[f64]| if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 };
+
+        let win_rate = wins.len() as f64 / returns.len() as f64;
+        let avg_win_return = mean(&wins);
+        let avg_loss_return = mean(&losses);
+        let payoff_ratio = if avg_loss_return > 0.0 { avg_win_return / avg_loss_return } else { 0.0 };
+        let kelly_fraction = if losses.is_empty() {
+            if wins.is_empty() { 0.0 } else { 1.0 }
+        } else if payoff_ratio > 0.0 {
+            win_rate - (1.0 - win_rate) / payoff_ratio
+        } else {
+            -1.0
+        };
+        let half_kelly_fraction = (kelly_fraction / 2.0).max(0.0);
+        let suggested_notional = equity.max(0.0) * half_kelly_fraction;
+        let suggested_quantity = if price > 0.0 { suggested_notional / price } else { 0.0 };
+
+        let pnls: Vec<f64> = round_trips.iter().map(|trip| trip.pnl).collect();
+
+        Self {
+            round_trips: returns.len(),
+            win_rate,
+            avg_win_return,
+            avg_loss_return,
+            payoff_ratio,
+            kelly_fraction,
+            half_kelly_fraction,
+            optimal_f: optimal_f(&pnls),
+            suggested_notional,
+            suggested_quantity,
+        }
+    }
+
+    
+    pub fn to_sizer(&self) -> Sizer {
+        Sizer::FixedNotional { notional: self.suggested_notional }
+    }
+
+    pub fn print(&self) {
+        println!("\n🎯 Kelly Sizing Guidance");
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   Round Trips:       {}", self.round_trips);
+        println!("   Win Rate:          {:.2}%", self.win_rate * 100.0);
+        println!("   Payoff Ratio:      {:.3}", self.payoff_ratio);
+        println!("   Kelly Fraction:    {:.4}", self.kelly_fraction);
+        println!("   Half Kelly:        {:.4}", self.half_kelly_fraction);
+        println!("   Optimal f:         {:.2}", self.optimal_f);
+        println!("   Suggested Size:    {:.4} (${:.2})", self.suggested_quantity, self.suggested_notional);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+
+
+fn optimal_f(pnls: &[f64]) -> f64 {
+    let worst_loss = pnls.iter().copied().fold(0.0_f64, f64::min).abs();
+    if worst_loss <= 0.0 {
+        return if pnls.iter().any(|pnl| *pnl > 0.0) { 1.0 } else { 0.0 };
+    }
+
+    let mut best = (0.0, 0.0);
+    for step in 1..OPTIMAL_F_STEPS {
+        let f = step as f64 / OPTIMAL_F_STEPS as f64;
+        let log_twr: f64 = pnls.iter().map(|pnl| (1.0 + f * pnl / worst_loss).ln()).sum();
+        if log_twr > best.1 {
+            best = (f, log_twr);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Side;
+
+    #[test]
+    fn test_kelly_and_optimal_f() {
+        let trades = vec![
+            Trade::new(Side::Bid, 100.0, 1.0, 0),
+            Trade::new(Side::Ask, 102.0, 1.0, 1),
+            Trade::new(Side::Bid, 100.0, 1.0, 2),
+            Trade::new(Side::Ask, 102.0, 1.0, 3),
+            Trade::new(Side::Bid, 100.0, 1.0, 4),
+            Trade::new(Side::Ask, 99.0, 1.0, 5),
+        ];
+        let report = KellyReport::from_trades(&trades, 10_000.0, 100.0);
+
+        assert_eq!(report.round_trips, 3);
+        assert!((report.payoff_ratio - 2.0).abs() < 1e-9);
+        assert!((report.kelly_fraction - 0.5).abs() < 1e-9);
+        assert!((report.suggested_quantity - 25.0).abs() < 1e-9);
+        assert!((report.optimal_f - 0.5).abs() < 1e-9);
+        assert!(matches!(report.to_sizer(), Sizer::FixedNotional { notional } if (notional - 2_500.0).abs() < 1e-9));
+
+        assert_eq!(KellyReport::from_trades(&[], 10_000.0, 100.0).suggested_quantity, 0.0);
+    }
+}
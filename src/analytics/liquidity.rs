@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{L2Snapshot, PriceLevel};
+
+pub const DEFAULT_IMPACT_BPS: [f64; 4] = [1.0, 2.0, 5.0, 10.0];
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactPoint {
+    pub bps: f64,
+    pub avg_buy_notional: f64,
+    pub avg_sell_notional: f64,
+
+
+    pub buy_reachable_pct: f64,
+    pub sell_reachable_pct: f64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityProfile {
+    pub snapshots: usize,
+    pub avg_bid_depth: Vec<f64>,
+    pub avg_ask_depth: Vec<f64>,
+    pub cumulative_bid_depth: Vec<f64>,
+    pub cumulative_ask_depth: Vec<f64>,
+    pub impact: Vec<ImpactPoint>,
+}
+
+impl LiquidityProfile {
+    pub fn compute(snapshots: &[L2Snapshot], impact_bps: &[f64]) -> Self {
+        let mut bid_depth = [0.0; 10];
+        let mut ask_depth = [0.0; 10];
+        let mut buy_sums = vec![(0.0, 0usize); impact_bps.len()];
+        let mut sell_sums = vec![(0.0, 0usize); impact_bps.len()];
+
+        for snapshot in snapshots {
+            let bids = snapshot.bids();
+            let asks = snapshot.asks();
+            for (slot, level) in bid_depth.iter_mut().zip(&bids) {
+                *slot += level.quantity;
+            }
+            for (slot, level) in ask_depth.iter_mut().zip(&asks) {
+                *slot += level.quantity;
+            }
+
+            let mid = snapshot.mid_price();
+            for (i, &bps) in impact_bps.iter().enumerate() {
+                let move_by = mid * bps / 10_000.0;
+                let target_ask = 2.0 * (mid + move_by) - snapshot.best_bid();
+                if let Some(notional) = sweep_notional(&asks, |price| price < target_ask) {
+                    buy_sums[i].0 += notional;
+                    buy_sums[i].1 += 1;
+                }
+                let target_bid = 2.0 * (mid - move_by) - snapshot.best_ask();
+                if let Some(notional) = sweep_notional(&bids, |price| price > target_bid) {
+                    sell_sums[i].0 += notional;
+                    sell_sums[i].1 += 1;
+                }
+            }
+        }
+
+        let n = snapshots.len().max(1) as f64;
+        let avg_bid_depth: Vec<f64> = bid_depth.iter().map(|q| q / n).collect();
+        let avg_ask_depth: Vec<f64> = ask_depth.iter().map(|q| q / n).collect();
+
+        let average = |(sum, count): (f64, usize)| if count > 0 { sum / count as f64 } else { 0.0 };
+        let impact = impact_bps
+            .iter()
+            .enumerate()
+            .map(|(i, &bps)| ImpactPoint {
+                bps,
+                avg_buy_notional: average(buy_sums[i]),
+                avg_sell_notional: average(sell_sums[i]),
+                buy_reachable_pct: buy_sums[i].1 as f64 / n * 100.0,
+                sell_reachable_pct: sell_sums[i].1 as f64 / n * 100.0,
+            })
+            .collect();
+
+        Self {
+            snapshots: snapshots.len(),
+            cumulative_bid_depth: cumulative(&avg_bid_depth),
+            cumulative_ask_depth: cumulative(&avg_ask_depth),
+            avg_bid_depth,
+            avg_ask_depth,
+            impact,
+        }
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn print(&self) {
+        println!("\n💧 Liquidity Profile ({} snapshots)", self.snapshots);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   {:<6} {:>10} {:>10} {:>10} {:>10}", "Level", "Bid Qty", "Bid Cum", "Ask Qty", "Ask Cum");
+        for level in 0..self.avg_bid_depth.len() {
+            println!(
+                "   {:<6} {:>10.3} {:>10.3} {:>10.3} {:>10.3}",
+                level + 1,
+                self.avg_bid_depth[level],
+                self.cumulative_bid_depth[level],
+                self.avg_ask_depth[level],
+                self.cumulative_ask_depth[level]
+            );
+        }
+        println!("   ───────────────────────────────────────");
+        println!("   {:<6} {:>14} {:>14} {:>10}", "Move", "Buy Notional", "Sell Notional", "Reachable");
+        for point in &self.impact {
+            println!(
+                "   {:<6} {:>14.2} {:>14.2} {:>9.1}%",
+                format!("{}bp", point.bps),
+                point.avg_buy_notional,
+                point.avg_sell_notional,
+                point.buy_reachable_pct.min(point.sell_reachable_pct)
+            );
+        }
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+
+
+
+fn sweep_notional(levels: &[PriceLevel], consumed: impl Fn(f64) -> bool) -> Option<f64> {
+    let mut notional = 0.0;
+    for level in levels {
+        if !consumed(level.price) {
+            return Some(notional);
+        }
+        notional += level.notional();
+    }
+    None
+}
+
+fn cumulative(values: &[f64]) -> Vec<f64> {
+    values
+        .iter()
+        .scan(0.0, |acc, v| {
+            *acc += v;
+            Some(*acc)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_snapshot(bid: f64, ask: f64) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - 0.01 * i as f64, 1.0 + i as f64)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + 0.01 * i as f64, 1.0 + i as f64)).collect();
+        L2Snapshot::from_levels(0, 0, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_depth_curves_and_impact() {
+        let snapshots = vec![create_test_snapshot(99.99, 100.01); 4];
+        let profile = LiquidityProfile::compute(&snapshots, &[1.25, 100.0]);
+
+        assert_eq!(profile.avg_bid_depth[0], 1.0);
+        assert_eq!(profile.cumulative_ask_depth[2], 6.0);
+        assert_eq!(profile.cumulative_bid_depth[9], 55.0);
+
+        let near = &profile.impact[0];
+        let expected = 100.01 * 1.0 + 100.02 * 2.0 + 100.03 * 3.0;
+        assert!((near.avg_buy_notional - expected).abs() < 1e-9);
+        assert_eq!(near.buy_reachable_pct, 100.0);
+
+        let too_far = &profile.impact[1];
+        assert_eq!(too_far.buy_reachable_pct, 0.0);
+        assert_eq!(too_far.avg_sell_notional, 0.0);
+    }
+}
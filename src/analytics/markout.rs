@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{L2Snapshot, Side, Trade};
+use super::performance::mid_at;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillMarkout {
+    pub trade_index: usize,
+    pub timestamp_us: u64,
+    pub side: Side,
+    pub price: f64,
+    pub quantity: f64,
+    
+    pub markouts: Vec<Option<f64>>,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkoutPoint {
+    pub horizon_us: u64,
+    pub fills: usize,
+    pub avg_markout: f64,
+    pub avg_markout_bps: f64,
+    pub total_markout_pnl: f64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkoutCurve {
+    pub points: Vec<MarkoutPoint>,
+}
+
+
+
+pub fn tag_fills(trades: &[Trade], snapshots: &[L2Snapshot], horizons_us: &[u64]) -> Vec<FillMarkout> {
+    let last_ts = snapshots.last().map(|s| s.timestamp_us).unwrap_or(0);
+    tag_fills_with(trades, horizons_us, |trade_index, h| {
+        let target = trades[trade_index].timestamp_us + horizons_us[h];
+        if target > last_ts {
+            return None;
+        }
+        mid_at(snapshots, target)
+    })
+}
+
+
+pub fn tag_fills_with<F: FnMut(usize, usize) -> Option<f64>>(trades: &[Trade], horizons_us: &[u64], mut mid: F) -> Vec<FillMarkout> {
+    trades
+        .iter()
+        .enumerate()
+        .map(|(trade_index, trade)| {
+            let markouts = (0..horizons_us.len())
+                .map(|h| {
+                    let mid = mid(trade_index, h)?;
+                    Some(match trade.side {
+                        Side::Bid => mid - trade.price,
+                        Side::Ask => trade.price - mid,
+                    })
+                })
+                .collect();
+
+            FillMarkout {
+                trade_index,
+                timestamp_us: trade.timestamp_us,
+                side: trade.side,
+                price: trade.price,
+                quantity: trade.quantity,
+                markouts,
+            }
+        })
+        .collect()
+}
+
+impl MarkoutCurve {
+    pub fn from_fills(fills: &[FillMarkout], horizons_us: &[u64]) -> Self {
+        let points = horizons_us
+            .iter()
+            .enumerate()
+            .map(|(h, &horizon_us)| {
+                let mut count = 0;
+                let mut sum = 0.0;
+                let mut sum_bps = 0.0;
+                let mut total_pnl = 0.0;
+
+                for fill in fills {
+                    if let Some(markout) = fill.markouts.get(h).copied().flatten() {
+                        count += 1;
+                        sum += markout;
+                        sum_bps += markout / fill.price * 10_000.0;
+                        total_pnl += markout * fill.quantity;
+                    }
+                }
+
+                let n = count.max(1) as f64;
+                MarkoutPoint {
+                    horizon_us,
+                    fills: count,
+                    avg_markout: sum / n,
+                    avg_markout_bps: sum_bps / n,
+                    total_markout_pnl: total_pnl,
+                }
+            })
+            .collect();
+
+        Self { points }
+    }
+
+    pub fn print(&self) {
+        println!("\n⏱️  Fill Markouts");
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   {:>10} {:>8} {:>12} {:>10}", "Horizon", "Fills", "Avg ($)", "Avg (bps)");
+        for point in &self.points {
+            println!(
+                "   {:>8}ms {:>8} {:>12.4} {:>10.3}",
+                point.horizon_us / 1_000, point.fills, point.avg_markout, point.avg_markout_bps
+            );
+        }
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64, bid: f64, ask: f64) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_markouts_per_horizon() {
+        let snapshots = vec![
+            create_test_snapshot(0, 99.0, 101.0),
+            create_test_snapshot(1_000_000, 101.0, 103.0),
+            create_test_snapshot(5_000_000, 97.0, 99.0),
+        ];
+        let trades = vec![
+            Trade::new(Side::Bid, 100.0, 2.0, 0),
+            Trade::new(Side::Ask, 102.0, 1.0, 1_000_000),
+        ];
+        let horizons = [0, 1_000_000, 5_000_000];
+
+        let fills = tag_fills(&trades, &snapshots, &horizons);
+        assert_eq!(fills[0].markouts, vec![Some(0.0), Some(2.0), Some(-2.0)]);
+        assert_eq!(fills[1].markouts, vec![Some(0.0), Some(0.0), None]);
+
+        let curve = MarkoutCurve::from_fills(&fills, &horizons);
+        assert_eq!(curve.points[1].fills, 2);
+        assert!((curve.points[1].avg_markout - 1.0).abs() < 1e-9);
+        assert!((curve.points[1].total_markout_pnl - 4.0).abs() < 1e-9);
+        assert_eq!(curve.points[2].fills, 1);
+    }
+}
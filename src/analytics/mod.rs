@@ -1,9 +1,43 @@
 
 pub mod performance;
 pub mod export;
+pub mod attribution;
+pub mod quote_quality;
+pub mod markout;
+pub mod regime;
+pub mod liquidity;
+pub mod stream;
+pub mod round_trips;
+pub mod kelly;
+pub mod slippage;
+pub mod tca;
+pub mod heatmap;
+pub mod portfolio;
+pub mod allocation;
+pub mod fx;
+pub mod histogram;
+pub mod bench;
+#[cfg(feature = "arrow")]
+pub mod arrow;
 
-pub use performance::{BacktestResult, PerformanceMetrics, TimingMetrics, print_comparison};
+pub use performance::{AllocProfile, BacktestResult, DataClock, PerformanceMetrics, TimingMetrics, print_comparison};
 pub use export::{
-    BacktestExport, ExportMetadata, SummaryMetrics, TimeseriesData, TimeseriesPoint,
-    TradeHistory, TradeExport, RiskMetrics, PerformanceComparison,
+    BacktestExport, ExportMetadata, ExportOptions, PercentileBands, SummaryMetrics, TimeseriesData, TimeseriesPoint,
+    TradeHistory, TradeExport, RiskMetrics, PerformanceComparison, ComparisonApproach,
 };
+pub use attribution::{AttributionBucket, PnlAttribution};
+pub use quote_quality::{QuoteEnd, QuoteOutcome, QuoteQualityReport};
+pub use markout::{FillMarkout, MarkoutCurve, MarkoutPoint};
+pub use regime::{Regime, RegimeBreakdown, RegimeClassifier, RegimeConfig, RegimeStats};
+pub use liquidity::{ImpactPoint, LiquidityProfile};
+pub use stream::{read_stream, StreamRecord, StreamingExporter};
+pub use round_trips::{match_round_trips, RoundTrip};
+pub use histogram::{LatencyHistogram, LatencySummary};
+pub use bench::{BenchConfig, BenchRunner, RunStatistics};
+pub use kelly::KellyReport;
+pub use slippage::{SlippageBucket, SlippageReport};
+pub use tca::{TcaOrder, TcaReport, TcaResult};
+pub use heatmap::{DepthHeatmap, HeatmapConfig};
+pub use portfolio::{PortfolioComponent, PortfolioResult};
+pub use allocation::{Allocation, AllocationMethod};
+pub use fx::{ConvertedPnl, CurrencyConverter, FxRate};
@@ -1,8 +1,41 @@
 
 use crate::Position;
+use crate::types::{L2Snapshot, Side, Trade};
+use crate::utils::alloc::AllocStats;
+use super::histogram::LatencySummary;
 use std::time::Duration;
 
 
+pub const BURST_FACTOR: f64 = 3.0;
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct DataClock {
+    first_us: Option<u64>,
+    last_us: u64,
+    count: usize,
+    buckets: Vec<usize>,
+}
+
+impl DataClock {
+    pub fn observe(&mut self, timestamp_us: u64) {
+        let first_us = *self.first_us.get_or_insert(timestamp_us);
+        let bucket = (timestamp_us.saturating_sub(first_us) / 1_000_000) as usize;
+        if bucket >= self.buckets.len() {
+            self.buckets.resize(bucket + 1, 0);
+        }
+        self.buckets[bucket] += 1;
+        self.last_us = timestamp_us;
+        self.count += 1;
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+
 #[derive(Debug, Clone)]
 pub struct BacktestResult {
     pub name: String,
@@ -19,6 +52,14 @@ pub struct PerformanceMetrics {
     pub unrealized_pnl: f64,
 
     
+    pub spread_capture_pnl: f64,
+    pub inventory_pnl: f64,
+    
+    pub liquidation_cost: f64,
+    
+    pub borrow_cost: f64,
+
+    
     pub final_position: f64,
     pub max_position_long: f64,
     pub max_position_short: f64,
@@ -46,8 +87,38 @@ pub struct PerformanceMetrics {
 pub struct TimingMetrics {
     pub total_duration: Duration,
     pub snapshots_processed: usize,
+    
+    pub snapshots_skipped: usize,
     pub time_per_snapshot: Duration,
     pub throughput: f64,  
+
+    
+    pub data_duration_us: u64,
+    
+    pub data_rate: f64,
+    
+    pub peak_data_rate: f64,
+    
+    pub burst_seconds: usize,
+    
+    pub replay_speed: f64,
+
+    
+    
+    pub allocations: Option<AllocProfile>,
+
+    
+    pub strategy_latency: Option<LatencySummary>,
+    
+    pub fill_latency: Option<LatencySummary>,
+}
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocProfile {
+    pub run: AllocStats,
+    
+    pub analytics: AllocStats,
 }
 
 impl BacktestResult {
@@ -71,6 +142,7 @@ impl BacktestResult {
         self.metrics.realized_pnl = position.realized_pnl;
         self.metrics.unrealized_pnl = position.unrealized_pnl(final_price);
         self.metrics.total_pnl = self.metrics.realized_pnl + self.metrics.unrealized_pnl;
+        self.metrics.inventory_pnl = self.metrics.total_pnl - self.metrics.spread_capture_pnl;
 
         
         self.metrics.final_position = position.quantity;
@@ -159,6 +231,27 @@ impl BacktestResult {
     }
 
     
+    
+    pub fn apply_liquidation(&mut self, cost: f64, fee: f64) {
+        self.metrics.liquidation_cost = cost;
+        self.metrics.realized_pnl -= fee;
+        self.metrics.total_pnl -= fee;
+        self.metrics.inventory_pnl = self.metrics.total_pnl - self.metrics.spread_capture_pnl;
+    }
+
+    
+    
+    
+    pub fn decompose_pnl(&mut self, trades: &[Trade], snapshots: &[L2Snapshot]) {
+        self.apply_spread_capture(spread_capture_pnl(trades, snapshots));
+    }
+
+    pub fn apply_spread_capture(&mut self, spread_capture_pnl: f64) {
+        self.metrics.spread_capture_pnl = spread_capture_pnl;
+        self.metrics.inventory_pnl = self.metrics.total_pnl - spread_capture_pnl;
+    }
+
+    
     pub fn set_timing(&mut self, duration: Duration, snapshots: usize) {
         self.timing.total_duration = duration;
         self.timing.snapshots_processed = snapshots;
@@ -170,6 +263,40 @@ impl BacktestResult {
     }
 
     
+    
+    
+    pub fn set_data_timing(&mut self, snapshots: &[L2Snapshot]) {
+        let mut clock = DataClock::default();
+        for snapshot in snapshots {
+            clock.observe(snapshot.timestamp_us);
+        }
+        self.set_data_clock(&clock);
+    }
+
+    pub fn set_data_clock(&mut self, clock: &DataClock) {
+        let Some(first_us) = clock.first_us else {
+            return;
+        };
+        let duration_us = clock.last_us.saturating_sub(first_us);
+        let data_secs = duration_us as f64 / 1_000_000.0;
+        self.timing.data_duration_us = duration_us;
+        if data_secs <= 0.0 {
+            return;
+        }
+
+        self.timing.data_rate = clock.count as f64 / data_secs;
+
+        let threshold = self.timing.data_rate * BURST_FACTOR;
+        self.timing.peak_data_rate = clock.buckets.iter().copied().max().unwrap_or(0) as f64;
+        self.timing.burst_seconds = clock.buckets.iter().filter(|&&count| count as f64 > threshold).count();
+
+        let processing_secs = self.timing.total_duration.as_secs_f64();
+        if processing_secs > 0.0 {
+            self.timing.replay_speed = data_secs / processing_secs;
+        }
+    }
+
+    
     pub fn print_report(&self) {
         println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         println!("📊 BACKTEST RESULTS: {}", self.name);
@@ -179,6 +306,14 @@ impl BacktestResult {
         println!("   Total PnL:           ${:.2}", self.metrics.total_pnl);
         println!("   Realized PnL:        ${:.2}", self.metrics.realized_pnl);
         println!("   Unrealized PnL:      ${:.2}", self.metrics.unrealized_pnl);
+        println!("   Spread Capture PnL:  ${:.2}", self.metrics.spread_capture_pnl);
+        println!("   Inventory PnL:       ${:.2}", self.metrics.inventory_pnl);
+        if self.metrics.liquidation_cost > 0.0 {
+            println!("   Liquidation Cost:    ${:.2}", self.metrics.liquidation_cost);
+        }
+        if self.metrics.borrow_cost > 0.0 {
+            println!("   Borrow Cost:         ${:.2}", self.metrics.borrow_cost);
+        }
 
         println!("\n📈 Position Metrics:");
         println!("   Final Position:      {:.3} BTC", self.metrics.final_position);
@@ -205,8 +340,33 @@ impl BacktestResult {
         println!("\n⚡ Performance Metrics:");
         println!("   Total Duration:      {:?}", self.timing.total_duration);
         println!("   Snapshots Processed: {}", self.timing.snapshots_processed);
+        if self.timing.snapshots_skipped > 0 {
+            println!("   Snapshots Skipped:   {}", self.timing.snapshots_skipped);
+        }
         println!("   Time per Snapshot:   {:.2} ns", self.timing.time_per_snapshot.as_nanos());
         println!("   Throughput:          {:.0} snapshots/sec", self.timing.throughput);
+        if self.timing.data_duration_us > 0 {
+            println!("   Data Covered:        {:.1} s", self.timing.data_duration_us as f64 / 1_000_000.0);
+            println!("   Feed Rate:           {:.1} snapshots/data-sec (peak {:.0})", self.timing.data_rate, self.timing.peak_data_rate);
+            println!("   Burst Seconds:       {}", self.timing.burst_seconds);
+            println!("   Replay Speed:        {:.0}× real time", self.timing.replay_speed);
+        }
+        if let Some(summary) = &self.timing.strategy_latency {
+            summary.print_row("Strategy Latency:");
+        }
+        if let Some(summary) = &self.timing.fill_latency {
+            summary.print_row("Fill Latency:");
+        }
+        if let Some(profile) = &self.timing.allocations {
+            for (phase, stats) in [("Run", &profile.run), ("Analytics", &profile.analytics)] {
+                println!("   {:<20} {} allocs ({:.2}/snapshot), {} bytes, peak {} bytes",
+                         format!("{} Allocations:", phase),
+                         stats.allocations,
+                         stats.per_item(self.timing.snapshots_processed),
+                         stats.bytes_allocated,
+                         stats.peak_bytes);
+            }
+        }
 
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
     }
@@ -218,6 +378,10 @@ impl Default for PerformanceMetrics {
             total_pnl: 0.0,
             realized_pnl: 0.0,
             unrealized_pnl: 0.0,
+            spread_capture_pnl: 0.0,
+            inventory_pnl: 0.0,
+            liquidation_cost: 0.0,
+            borrow_cost: 0.0,
             final_position: 0.0,
             max_position_long: 0.0,
             max_position_short: 0.0,
@@ -241,13 +405,54 @@ impl Default for TimingMetrics {
         Self {
             total_duration: Duration::ZERO,
             snapshots_processed: 0,
+            snapshots_skipped: 0,
             time_per_snapshot: Duration::ZERO,
             throughput: 0.0,
+            data_duration_us: 0,
+            data_rate: 0.0,
+            peak_data_rate: 0.0,
+            burst_seconds: 0,
+            replay_speed: 0.0,
+            allocations: None,
+            strategy_latency: None,
+            fill_latency: None,
         }
     }
 }
 
 
+
+
+pub fn spread_capture_pnl(trades: &[Trade], snapshots: &[L2Snapshot]) -> f64 {
+    spread_capture_pnl_with(trades, |trade_index| mid_at(snapshots, trades[trade_index].timestamp_us))
+}
+
+
+pub fn spread_capture_pnl_with<F: FnMut(usize) -> Option<f64>>(trades: &[Trade], mut mid: F) -> f64 {
+    trades
+        .iter()
+        .enumerate()
+        .filter_map(|(trade_index, trade)| {
+            let mid = mid(trade_index)?;
+            let edge = match trade.side {
+                Side::Bid => mid - trade.price,
+                Side::Ask => trade.price - mid,
+            };
+            Some(edge * trade.quantity)
+        })
+        .sum()
+}
+
+
+pub(crate) fn mid_at(snapshots: &[L2Snapshot], timestamp_us: u64) -> Option<f64> {
+    let idx = snapshots.partition_point(|s| s.timestamp_us <= timestamp_us);
+    if idx == 0 {
+        return None;
+    }
+    Some(snapshots[idx - 1].mid_price())
+}
+
+
 pub fn print_comparison(results: &[BacktestResult]) {
     if results.is_empty() {
         return;
@@ -300,3 +505,53 @@ pub fn print_comparison(results: &[BacktestResult]) {
 
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64, bid: f64, ask: f64) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_pnl_decomposition() {
+        let snapshots = vec![
+            create_test_snapshot(0, 99.0, 101.0),
+            create_test_snapshot(10, 104.0, 106.0),
+        ];
+        let mut position = Position::new();
+        position.execute_trade(Trade::new(Side::Bid, 99.0, 1.0, 0));
+        position.execute_trade(Trade::new(Side::Ask, 106.0, 1.0, 10));
+
+        let mut result = BacktestResult::new("MM".to_string());
+        result.calculate_from_position(&position, 105.0, 2, 2);
+        result.decompose_pnl(position.trades(), &snapshots);
+
+        assert!((result.metrics.total_pnl - 7.0).abs() < 1e-9);
+        assert!((result.metrics.spread_capture_pnl - 2.0).abs() < 1e-9);
+        assert!((result.metrics.inventory_pnl - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_data_timing_detects_bursts() {
+        let mut snapshots: Vec<L2Snapshot> = (0..10)
+            .map(|i| create_test_snapshot(i * 1_000_000, 99.0, 101.0))
+            .collect();
+        snapshots.splice(3..3, (0..40).map(|i| create_test_snapshot(3_000_000 + i * 1_000, 99.0, 101.0)));
+        snapshots.remove(43);
+
+        let mut result = BacktestResult::new("MM".to_string());
+        result.set_timing(Duration::from_millis(9), snapshots.len());
+        result.set_data_timing(&snapshots);
+
+        assert_eq!(result.timing.data_duration_us, 9_000_000);
+        assert!((result.timing.data_rate - 49.0 / 9.0).abs() < 1e-9);
+        assert_eq!(result.timing.peak_data_rate, 40.0);
+        assert_eq!(result.timing.burst_seconds, 1);
+        assert!((result.timing.replay_speed - 1_000.0).abs() < 1e-6);
+    }
+}
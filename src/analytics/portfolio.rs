@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use super::{BacktestResult, TimeseriesData, TimeseriesPoint};
+use super::fx::CurrencyConverter;
+use crate::engine::JobResult;
+use crate::utils::stats::{mean, std_dev};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioComponent {
+    pub label: String,
+    pub strategy: String,
+    pub total_pnl: f64,
+    pub total_trades: usize,
+    pub pnl_std: f64,
+    pub max_drawdown: f64,
+    #[serde(default)]
+    pub fx_pnl: f64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioResult {
+    pub starting_capital: f64,
+    pub components: Vec<PortfolioComponent>,
+    
+    pub equity_curve: Vec<TimeseriesPoint>,
+    pub total_pnl: f64,
+    pub max_drawdown: f64,
+    #[serde(default)]
+    pub reporting_currency: Option<String>,
+    #[serde(default)]
+    pub fx_pnl: f64,
+    
+    pub correlation: Vec<Vec<f64>>,
+    pub avg_pairwise_correlation: f64,
+    
+    pub diversification_ratio: f64,
+    
+    pub drawdown_reduction: f64,
+}
+
+impl PortfolioResult {
+    pub fn from_jobs(jobs: &[JobResult], starting_capital: f64) -> Self {
+        let parts: Vec<(&str, &BacktestResult, &TimeseriesData)> = jobs
+            .iter()
+            .map(|job| (job.label.as_str(), &job.run.result, &job.run.timeseries))
+            .collect();
+        Self::aggregate(&parts, starting_capital)
+    }
+
+    pub fn aggregate(parts: &[(&str, &BacktestResult, &TimeseriesData)], starting_capital: f64) -> Self {
+        let mut timestamps: Vec<u64> = parts
+            .iter()
+            .flat_map(|(_, _, series)| series.pnl_curve.iter().map(|p| p.timestamp_us))
+            .collect();
+        timestamps.sort_unstable();
+        timestamps.dedup();
+
+        let aligned: Vec<Vec<f64>> = parts.iter().map(|(_, _, series)| align(&series.pnl_curve, &timestamps)).collect();
+        let increments: Vec<Vec<f64>> = aligned.iter().map(|curve| diffs(curve)).collect();
+
+        let combined: Vec<f64> = (0..timestamps.len()).map(|i| aligned.iter().map(|c| c[i]).sum()).collect();
+        let equity_curve = timestamps
+            .iter()
+            .zip(&combined)
+            .enumerate()
+            .map(|(snapshot, (&timestamp_us, pnl))| TimeseriesPoint {
+                snapshot,
+                timestamp_us,
+                value: starting_capital + pnl,
+            })
+            .collect();
+
+        let components: Vec<PortfolioComponent> = parts
+            .iter()
+            .zip(&aligned)
+            .zip(&increments)
+            .map(|(((label, result, _), curve), incr)| PortfolioComponent {
+                label: label.to_string(),
+                strategy: result.name.clone(),
+                total_pnl: result.metrics.total_pnl,
+                total_trades: result.metrics.total_trades,
+                pnl_std: std_dev(incr),
+                max_drawdown: max_drawdown(curve),
+                fx_pnl: 0.0,
+            })
+            .collect();
+
+        let n = parts.len();
+        let correlation: Vec<Vec<f64>> = (0..n)
+            .map(|i| (0..n).map(|j| if i == j { 1.0 } else { correlation(&increments[i], &increments[j]) }).collect())
+            .collect();
+        let pairs = n * n.saturating_sub(1) / 2;
+        let avg_pairwise_correlation = if pairs > 0 {
+            (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))).map(|(i, j)| correlation[i][j]).sum::<f64>() / pairs as f64
+        } else {
+            0.0
+        };
+
+        let portfolio_std = std_dev(&diffs(&combined));
+        let sum_std: f64 = components.iter().map(|c| c.pnl_std).sum();
+        let sum_drawdowns: f64 = components.iter().map(|c| c.max_drawdown).sum();
+        let max_dd = max_drawdown(&combined);
+
+        Self {
+            starting_capital,
+            total_pnl: components.iter().map(|c| c.total_pnl).sum(),
+            components,
+            equity_curve,
+            max_drawdown: max_dd,
+            reporting_currency: None,
+            fx_pnl: 0.0,
+            correlation,
+            avg_pairwise_correlation,
+            diversification_ratio: if portfolio_std > 0.0 { sum_std / portfolio_std } else { 1.0 },
+            drawdown_reduction: if sum_drawdowns > 0.0 { 1.0 - max_dd / sum_drawdowns } else { 0.0 },
+        }
+    }
+
+    
+    pub fn aggregate_in(
+        parts: &[(&str, &BacktestResult, &TimeseriesData, &str)],
+        converter: &CurrencyConverter,
+        starting_capital: f64,
+    ) -> anyhow::Result<Self> {
+        let converted = parts
+            .iter()
+            .map(|(_, _, series, currency)| converter.convert(series, currency))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let plain: Vec<(&str, &BacktestResult, &TimeseriesData)> = parts
+            .iter()
+            .zip(&converted)
+            .map(|((label, result, _, _), converted)| (*label, *result, &converted.series))
+            .collect();
+
+        let mut portfolio = Self::aggregate(&plain, starting_capital);
+        for (component, converted) in portfolio.components.iter_mut().zip(&converted) {
+            component.total_pnl = converted.total_pnl;
+            component.fx_pnl = converted.fx_pnl;
+        }
+        portfolio.total_pnl = converted.iter().map(|c| c.total_pnl).sum();
+        portfolio.fx_pnl = converted.iter().map(|c| c.fx_pnl).sum();
+        portfolio.reporting_currency = Some(converter.reporting.clone());
+        Ok(portfolio)
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_file(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn print(&self) {
+        println!("\n💼 Portfolio ({} components)", self.components.len());
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        for component in &self.components {
+            println!(
+                "   {:<20} PnL ${:>12.2}  Max DD ${:>10.2}",
+                component.label, component.total_pnl, component.max_drawdown
+            );
+        }
+        println!("   Combined PnL:       ${:.2}", self.total_pnl);
+        if let Some(currency) = &self.reporting_currency {
+            println!("   FX PnL ({}):       {:.2}", currency, self.fx_pnl);
+        }
+        println!("   Combined Max DD:    ${:.2}", self.max_drawdown);
+        println!("   Avg Correlation:    {:.3}", self.avg_pairwise_correlation);
+        println!("   Diversification:    {:.3}x", self.diversification_ratio);
+        println!("   Drawdown Reduction: {:.1}%", self.drawdown_reduction * 100.0);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+pub(crate) fn align(curve: &[TimeseriesPoint], timestamps: &[u64]) -> Vec<f64> {
+    let mut value = 0.0;
+    let mut next = 0;
+    timestamps
+        .iter()
+        .map(|&ts| {
+            while next < curve.len() && curve[next].timestamp_us <= ts {
+                value = curve[next].value;
+                next += 1;
+            }
+            value
+        })
+        .collect()
+}
+
+pub(crate) fn diffs(curve: &[f64]) -> Vec<f64> {
+    curve.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+pub(crate) fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let (sa, sb) = (std_dev(a), std_dev(b));
+    if sa == 0.0 || sb == 0.0 {
+        return 0.0;
+    }
+    let (ma, mb) = (mean(a), mean(b));
+    let cov = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum::<f64>() / a.len() as f64;
+    cov / (sa * sb)
+}
+
+fn max_drawdown(curve: &[f64]) -> f64 {
+    let mut peak = 0.0_f64;
+    curve.iter().fold(0.0, |worst, &value| {
+        peak = peak.max(value);
+        f64::max(worst, peak - value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::fx::FxRate;
+
+    fn series(values: &[f64]) -> TimeseriesData {
+        TimeseriesData {
+            pnl_curve: values
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| TimeseriesPoint { snapshot: i, timestamp_us: i as u64 * 10, value })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_aggregates_hedged_streams() {
+        let mut a = BacktestResult::new("a".to_string());
+        a.metrics.total_pnl = 2.0;
+        let mut b = BacktestResult::new("b".to_string());
+        b.metrics.total_pnl = 0.0;
+        let (sa, sb) = (series(&[0.0, 2.0, 0.0, 2.0]), series(&[0.0, -1.0, 1.0, 0.0]));
+
+        let portfolio = PortfolioResult::aggregate(&[("a", &a, &sa), ("b", &b, &sb)], 1_000.0);
+
+        assert_eq!(portfolio.total_pnl, 2.0);
+        let equity: Vec<f64> = portfolio.equity_curve.iter().map(|p| p.value).collect();
+        assert_eq!(equity, vec![1_000.0, 1_001.0, 1_001.0, 1_002.0]);
+        assert!((portfolio.correlation[0][1] + 1.0).abs() < 1e-9);
+        assert_eq!(portfolio.correlation[1][1], 1.0);
+        assert!(portfolio.diversification_ratio > 1.0);
+        assert_eq!((portfolio.components[0].max_drawdown, portfolio.components[1].max_drawdown), (2.0, 1.0));
+        assert_eq!((portfolio.max_drawdown, portfolio.drawdown_reduction), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_aggregates_in_reporting_currency() {
+        let (usd, eur) = (BacktestResult::new("usd".to_string()), BacktestResult::new("eur".to_string()));
+        let (su, se) = (series(&[0.0, 10.0]), series(&[0.0, 10.0]));
+        let converter = CurrencyConverter::new("USD").with_rate("EUR", FxRate::Series(vec![(0, 1.0), (10, 1.5)]));
+
+        let portfolio = PortfolioResult::aggregate_in(&[("u", &usd, &su, "USD"), ("e", &eur, &se, "EUR")], &converter, 100.0).unwrap();
+
+        assert_eq!(portfolio.reporting_currency.as_deref(), Some("USD"));
+        assert_eq!((portfolio.components[1].total_pnl, portfolio.components[1].fx_pnl), (15.0, 0.0));
+        assert_eq!(portfolio.total_pnl, 25.0);
+        assert_eq!(portfolio.equity_curve.last().unwrap().value, 125.0);
+    }
+
+    #[test]
+    fn test_forward_fills_misaligned_curves() {
+        let curve = |pts: &[(u64, f64)]| pts.iter().map(|&(timestamp_us, value)| TimeseriesPoint { snapshot: 0, timestamp_us, value }).collect::<Vec<_>>();
+        assert_eq!(align(&curve(&[(5, 1.0), (20, 3.0)]), &[0, 5, 10, 20, 30]), vec![0.0, 1.0, 1.0, 3.0, 3.0]);
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use crate::types::Side;
+use crate::utils::stats::{mean, percentile, sorted};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuoteEnd {
+    Filled,
+    Replaced,
+    Cancelled,
+    Open,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct QuoteOutcome {
+    pub side: Side,
+    pub price: f64,
+    pub quantity: f64,
+    pub placed_us: u64,
+    pub ended_us: u64,
+    pub end: QuoteEnd,
+
+
+    pub adverse_move: Option<f64>,
+    
+    pub time_at_best_us: u64,
+}
+
+impl QuoteOutcome {
+    pub fn lifetime_us(&self) -> u64 {
+        self.ended_us.saturating_sub(self.placed_us)
+    }
+
+    
+    pub fn time_at_best_pct(&self) -> f64 {
+        match self.lifetime_us() {
+            0 => 0.0,
+            lifetime => self.time_at_best_us as f64 / lifetime as f64 * 100.0,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteQualityReport {
+    pub quotes: usize,
+    pub filled: usize,
+    pub replaced: usize,
+    pub cancelled: usize,
+    pub fill_ratio: f64,
+    pub adverse_horizon: usize,
+    pub avg_adverse_selection: f64,
+    pub adverse_fill_pct: f64,
+    pub lifetime_mean_us: f64,
+    pub lifetime_p50_us: f64,
+    pub lifetime_p90_us: f64,
+    pub lifetime_p99_us: f64,
+    
+    pub time_at_best_mean_us: f64,
+    
+    pub time_at_best_pct: f64,
+}
+
+impl QuoteQualityReport {
+    pub fn from_outcomes(outcomes: &[QuoteOutcome], adverse_horizon: usize) -> Self {
+        let count = |end: QuoteEnd| outcomes.iter().filter(|o| o.end == end).count();
+        let filled = count(QuoteEnd::Filled);
+
+        let adverse: Vec<f64> = outcomes.iter().filter_map(|o| o.adverse_move).collect();
+        let adverse_fills = adverse.iter().filter(|&&m| m > 0.0).count();
+
+        let lifetimes: Vec<f64> = outcomes
+            .iter()
+            .filter(|o| o.end != QuoteEnd::Open)
+            .map(|o| o.lifetime_us() as f64)
+            .collect();
+        let sorted_lifetimes = sorted(&lifetimes);
+        let at_best: Vec<f64> = outcomes
+            .iter()
+            .filter(|o| o.end != QuoteEnd::Open)
+            .map(|o| o.time_at_best_us as f64)
+            .collect();
+        let total_lifetime: f64 = lifetimes.iter().sum();
+
+        Self {
+            quotes: outcomes.len(),
+            filled,
+            replaced: count(QuoteEnd::Replaced),
+            cancelled: count(QuoteEnd::Cancelled),
+            fill_ratio: if outcomes.is_empty() {
+                0.0
+            } else {
+                filled as f64 / outcomes.len() as f64
+            },
+            adverse_horizon,
+            avg_adverse_selection: mean(&adverse),
+            adverse_fill_pct: if adverse.is_empty() {
+                0.0
+            } else {
+                adverse_fills as f64 / adverse.len() as f64 * 100.0
+            },
+            lifetime_mean_us: mean(&lifetimes),
+            lifetime_p50_us: percentile(&sorted_lifetimes, 50.0),
+            lifetime_p90_us: percentile(&sorted_lifetimes, 90.0),
+            lifetime_p99_us: percentile(&sorted_lifetimes, 99.0),
+            time_at_best_mean_us: mean(&at_best),
+            time_at_best_pct: if total_lifetime > 0.0 {
+                at_best.iter().sum::<f64>() / total_lifetime * 100.0
+            } else {
+                0.0
+            },
+        }
+    }
+
+    pub fn print(&self) {
+        println!("\n🎯 Quote Quality");
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   Quotes:            {}", self.quotes);
+        println!("   Filled:            {}", self.filled);
+        println!("   Replaced:          {}", self.replaced);
+        println!("   Cancelled:         {}", self.cancelled);
+        println!("   Fill Ratio:        {:.2}%", self.fill_ratio * 100.0);
+        println!("   Adverse Selection: ${:.4} avg over {} snapshots", self.avg_adverse_selection, self.adverse_horizon);
+        println!("   Adverse Fills:     {:.1}%", self.adverse_fill_pct);
+        println!("   Lifetime (mean):   {:.0} us", self.lifetime_mean_us);
+        println!("   Lifetime (p50):    {:.0} us", self.lifetime_p50_us);
+        println!("   Lifetime (p90):    {:.0} us", self.lifetime_p90_us);
+        println!("   Lifetime (p99):    {:.0} us", self.lifetime_p99_us);
+        println!("   At Best (mean):    {:.0} us", self.time_at_best_mean_us);
+        println!("   At Best (share):   {:.1}% of lifetime", self.time_at_best_pct);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(end: QuoteEnd, placed_us: u64, ended_us: u64, adverse_move: Option<f64>) -> QuoteOutcome {
+        QuoteOutcome {
+            side: Side::Bid,
+            price: 100.0,
+            quantity: 1.0,
+            placed_us,
+            ended_us,
+            end,
+            adverse_move,
+            time_at_best_us: (ended_us - placed_us) / 2,
+        }
+    }
+
+    #[test]
+    fn test_report_from_outcomes() {
+        let outcomes = vec![
+            outcome(QuoteEnd::Filled, 0, 100, Some(0.5)),
+            outcome(QuoteEnd::Filled, 0, 300, Some(-0.1)),
+            outcome(QuoteEnd::Replaced, 100, 200, None),
+            outcome(QuoteEnd::Open, 200, 200, None),
+        ];
+
+        let report = QuoteQualityReport::from_outcomes(&outcomes, 10);
+
+        assert_eq!(report.quotes, 4);
+        assert_eq!(report.filled, 2);
+        assert_eq!(report.replaced, 1);
+        assert!((report.fill_ratio - 0.5).abs() < 1e-9);
+        assert!((report.avg_adverse_selection - 0.2).abs() < 1e-9);
+        assert!((report.adverse_fill_pct - 50.0).abs() < 1e-9);
+        assert_eq!(report.lifetime_p50_us, 100.0);
+        assert!((report.time_at_best_mean_us - 250.0 / 3.0).abs() < 1e-9);
+        assert!((report.time_at_best_pct - 50.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::execution::Position;
+use crate::types::{L2Snapshot, Trade};
+use super::attribution::realized_pnl_per_trade;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Regime {
+    Warmup,
+    Trending,
+    Choppy,
+    Volatile,
+}
+
+impl Regime {
+    pub const ALL: [Regime; 4] = [Regime::Warmup, Regime::Trending, Regime::Choppy, Regime::Volatile];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Regime::Warmup => "Warmup",
+            Regime::Trending => "Trending",
+            Regime::Choppy => "Choppy",
+            Regime::Volatile => "Volatile",
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct RegimeConfig {
+    pub window: usize,
+    
+    pub volatility_threshold_bps: f64,
+    
+    pub trend_threshold: f64,
+}
+
+impl Default for RegimeConfig {
+    fn default() -> Self {
+        Self {
+            window: 500,
+            volatility_threshold_bps: 0.5,
+            trend_threshold: 2.0,
+        }
+    }
+}
+
+
+pub struct RegimeClassifier {
+    config: RegimeConfig,
+    mids: VecDeque<f64>,
+    sum_sq_returns: f64,
+    returns: VecDeque<f64>,
+}
+
+impl RegimeClassifier {
+    pub fn new(config: RegimeConfig) -> Self {
+        let capacity = config.window + 1;
+        Self {
+            config,
+            mids: VecDeque::with_capacity(capacity),
+            sum_sq_returns: 0.0,
+            returns: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn update(&mut self, snapshot: &L2Snapshot) -> Regime {
+        let mid = snapshot.mid_price();
+
+        if let Some(&prev) = self.mids.back() {
+            let ret_bps = (mid - prev) / prev * 10_000.0;
+            self.returns.push_back(ret_bps);
+            self.sum_sq_returns += ret_bps * ret_bps;
+            if self.returns.len() > self.config.window {
+                let old = self.returns.pop_front().unwrap();
+                self.sum_sq_returns -= old * old;
+            }
+        }
+
+        self.mids.push_back(mid);
+        if self.mids.len() > self.config.window + 1 {
+            self.mids.pop_front();
+        }
+
+        self.current()
+    }
+
+    
+    pub fn current(&self) -> Regime {
+        if self.returns.len() < self.config.window {
+            return Regime::Warmup;
+        }
+
+        let n = self.returns.len() as f64;
+        let volatility_bps = (self.sum_sq_returns.max(0.0) / n).sqrt();
+        if volatility_bps > self.config.volatility_threshold_bps {
+            return Regime::Volatile;
+        }
+
+        let first = *self.mids.front().unwrap();
+        let last = *self.mids.back().unwrap();
+        let drift_bps = (last - first) / first * 10_000.0;
+        let trend_strength = if volatility_bps > 0.0 {
+            drift_bps.abs() / (volatility_bps * n.sqrt())
+        } else {
+            0.0
+        };
+
+        if trend_strength > self.config.trend_threshold {
+            Regime::Trending
+        } else {
+            Regime::Choppy
+        }
+    }
+
+    pub fn classify(config: RegimeConfig, snapshots: &[L2Snapshot]) -> Vec<Regime> {
+        let mut classifier = Self::new(config);
+        snapshots.iter().map(|s| classifier.update(s)).collect()
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeStats {
+    pub regime: Regime,
+    pub snapshots: usize,
+    pub trades: usize,
+    pub volume: f64,
+    pub realized_pnl: f64,
+    pub mark_to_market_pnl: f64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeBreakdown {
+    pub regimes: Vec<RegimeStats>,
+}
+
+impl RegimeBreakdown {
+    
+    
+    pub fn compute(snapshots: &[L2Snapshot], trades: &[Trade], config: RegimeConfig) -> Self {
+        let labels = RegimeClassifier::classify(config, snapshots);
+        let realized = realized_pnl_per_trade(trades);
+
+        let mut regimes: Vec<RegimeStats> = Regime::ALL
+            .iter()
+            .map(|&regime| RegimeStats {
+                regime,
+                snapshots: 0,
+                trades: 0,
+                volume: 0.0,
+                realized_pnl: 0.0,
+                mark_to_market_pnl: 0.0,
+            })
+            .collect();
+        let slot = |regime: Regime| Regime::ALL.iter().position(|&r| r == regime).unwrap();
+
+        let mut position = Position::new();
+        let mut next_trade = 0;
+        let mut prev_equity = 0.0;
+
+        for (snapshot, &regime) in snapshots.iter().zip(&labels) {
+            let stats = &mut regimes[slot(regime)];
+            stats.snapshots += 1;
+
+            while next_trade < trades.len() && trades[next_trade].timestamp_us <= snapshot.timestamp_us {
+                let trade = &trades[next_trade];
+                stats.trades += 1;
+                stats.volume += trade.quantity;
+                stats.realized_pnl += realized[next_trade];
+                position.execute_trade(trade.clone());
+                next_trade += 1;
+            }
+
+            let equity = position.total_pnl(snapshot.mid_price());
+            stats.mark_to_market_pnl += equity - prev_equity;
+            prev_equity = equity;
+        }
+
+        Self { regimes }
+    }
+
+    pub fn get(&self, regime: Regime) -> Option<&RegimeStats> {
+        self.regimes.iter().find(|r| r.regime == regime)
+    }
+
+    pub fn print(&self) {
+        println!("\n🌦️  Regime Breakdown");
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   {:<10} {:>10} {:>8} {:>12} {:>12}", "Regime", "Snapshots", "Trades", "Realized", "MtM PnL");
+        for stats in &self.regimes {
+            println!(
+                "   {:<10} {:>10} {:>8} {:>12.2} {:>12.2}",
+                stats.regime.name(), stats.snapshots, stats.trades, stats.realized_pnl, stats.mark_to_market_pnl
+            );
+        }
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+    use crate::types::Side;
+
+    fn create_test_snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        let bid = mid - 0.05;
+        let ask = mid + 0.05;
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    fn config() -> RegimeConfig {
+        RegimeConfig {
+            window: 20,
+            volatility_threshold_bps: 5.0,
+            trend_threshold: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_classifies_trend_chop_and_volatility() {
+        let trend: Vec<L2Snapshot> = (0..40).map(|i| create_test_snapshot(i, 1000.0 + i as f64 * 0.1)).collect();
+        assert_eq!(*RegimeClassifier::classify(config(), &trend).last().unwrap(), Regime::Trending);
+
+        let chop: Vec<L2Snapshot> = (0..40)
+            .map(|i| create_test_snapshot(i, 1000.0 + if i % 2 == 0 { 0.1 } else { -0.1 }))
+            .collect();
+        assert_eq!(*RegimeClassifier::classify(config(), &chop).last().unwrap(), Regime::Choppy);
+
+        let wild: Vec<L2Snapshot> = (0..40)
+            .map(|i| create_test_snapshot(i, 1000.0 + if i % 2 == 0 { 5.0 } else { -5.0 }))
+            .collect();
+        assert_eq!(*RegimeClassifier::classify(config(), &wild).last().unwrap(), Regime::Volatile);
+    }
+
+    #[test]
+    fn test_breakdown_attributes_pnl() {
+        let snapshots: Vec<L2Snapshot> = (0..40).map(|i| create_test_snapshot(i, 1000.0 + i as f64 * 0.1)).collect();
+        let trades = vec![Trade::new(Side::Bid, 1000.05, 1.0, 0)];
+
+        let breakdown = RegimeBreakdown::compute(&snapshots, &trades, config());
+        let total_snapshots: usize = breakdown.regimes.iter().map(|r| r.snapshots).sum();
+        let total_mtm: f64 = breakdown.regimes.iter().map(|r| r.mark_to_market_pnl).sum();
+
+        assert_eq!(total_snapshots, 40);
+        assert_eq!(breakdown.get(Regime::Warmup).unwrap().trades, 1);
+        assert_eq!(breakdown.get(Regime::Warmup).unwrap().snapshots, 20);
+        assert_eq!(breakdown.get(Regime::Choppy).unwrap().trades, 0);
+        assert!((total_mtm - (snapshots[39].mid_price() - 1000.05)).abs() < 1e-9);
+        assert!(breakdown.get(Regime::Trending).unwrap().mark_to_market_pnl > 0.0);
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use crate::types::{Side, Trade};
+
+const QTY_EPSILON: f64 = 1e-10;
+
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundTrip {
+    
+    pub side: Side,
+    pub quantity: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub entry_us: u64,
+    pub exit_us: u64,
+    pub pnl: f64,
+}
+
+impl RoundTrip {
+    pub fn is_win(&self) -> bool {
+        self.pnl > 0.0
+    }
+
+    pub fn holding_us(&self) -> u64 {
+        self.exit_us.saturating_sub(self.entry_us)
+    }
+}
+
+struct Lot {
+    side: Side,
+    quantity: f64,
+    price: f64,
+    timestamp_us: u64,
+}
+
+
+
+pub fn match_round_trips(trades: &[Trade]) -> Vec<RoundTrip> {
+    let mut lots: VecDeque<Lot> = VecDeque::new();
+    let mut round_trips = Vec::new();
+
+    for trade in trades {
+        let mut remaining = trade.quantity;
+        while remaining > QTY_EPSILON {
+            let Some(lot) = lots.front_mut().filter(|lot| lot.side != trade.side) else {
+                break;
+            };
+            let quantity = remaining.min(lot.quantity);
+            let pnl = match lot.side {
+                Side::Bid => (trade.price - lot.price) * quantity,
+                Side::Ask => (lot.price - trade.price) * quantity,
+            };
+            round_trips.push(RoundTrip {
+                side: lot.side,
+                quantity,
+                entry_price: lot.price,
+                exit_price: trade.price,
+                entry_us: lot.timestamp_us,
+                exit_us: trade.timestamp_us,
+                pnl,
+            });
+            lot.quantity -= quantity;
+            remaining -= quantity;
+            if lot.quantity <= QTY_EPSILON {
+                lots.pop_front();
+            }
+        }
+        if remaining > QTY_EPSILON {
+            lots.push_back(Lot {
+                side: trade.side,
+                quantity: remaining,
+                price: trade.price,
+                timestamp_us: trade.timestamp_us,
+            });
+        }
+    }
+
+    round_trips
+}
+
+
+pub fn longest_streaks(round_trips: &[RoundTrip]) -> (usize, usize) {
+    let (mut wins, mut losses, mut best_wins, mut best_losses) = (0, 0, 0, 0);
+    for trip in round_trips {
+        if trip.pnl > 0.0 {
+            wins += 1;
+            losses = 0;
+        } else if trip.pnl < 0.0 {
+            losses += 1;
+            wins = 0;
+        } else {
+            wins = 0;
+            losses = 0;
+        }
+        best_wins = usize::max(best_wins, wins);
+        best_losses = usize::max(best_losses, losses);
+    }
+    (best_wins, best_losses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fifo_matching_with_flip() {
+        let trades = vec![
+            Trade::new(Side::Bid, 100.0, 1.0, 0),
+            Trade::new(Side::Bid, 102.0, 1.0, 10),
+            Trade::new(Side::Ask, 103.0, 1.5, 20),
+            Trade::new(Side::Ask, 101.0, 1.0, 30),
+            Trade::new(Side::Bid, 99.0, 0.5, 40),
+        ];
+        let trips = match_round_trips(&trades);
+
+        assert_eq!(trips.len(), 4);
+        assert_eq!((trips[0].quantity, trips[0].pnl), (1.0, 3.0));
+        assert_eq!((trips[1].quantity, trips[1].pnl, trips[1].entry_us), (0.5, 0.5, 10));
+        assert_eq!((trips[2].quantity, trips[2].pnl), (0.5, -0.5));
+        assert_eq!((trips[3].side, trips[3].pnl, trips[3].holding_us()), (Side::Ask, 1.0, 10));
+        assert_eq!(longest_streaks(&trips), (2, 1));
+    }
+}
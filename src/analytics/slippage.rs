@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{L2Snapshot, PriceLevel, Side, Trade};
+
+pub const DEFAULT_SIZE_BUCKETS: [f64; 3] = [0.01, 0.1, 1.0];
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlippageBucket {
+    pub side: Side,
+    pub min_quantity: f64,
+    
+    pub max_quantity: Option<f64>,
+    pub fills: usize,
+    
+    pub avg_realized_bps: f64,
+    
+    pub avg_modeled_bps: f64,
+    pub modeled_fills: usize,
+    pub realized_cost: f64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlippageReport {
+    pub fills: usize,
+    
+    pub unmatched: usize,
+    pub avg_realized_bps: f64,
+    pub avg_modeled_bps: f64,
+    pub buckets: Vec<SlippageBucket>,
+}
+
+struct FillSlippage {
+    side: Side,
+    quantity: f64,
+    realized_bps: f64,
+    modeled_bps: Option<f64>,
+    cost: f64,
+}
+
+impl SlippageReport {
+    
+    
+    pub fn compute(trades: &[Trade], snapshots: &[L2Snapshot], size_buckets: &[f64]) -> Self {
+        let mut unmatched = 0;
+        let fills: Vec<FillSlippage> = trades
+            .iter()
+            .filter_map(|trade| {
+                let slippage = measure(trade, snapshots);
+                if slippage.is_none() {
+                    unmatched += 1;
+                }
+                slippage
+            })
+            .collect();
+
+        let mut bounds = vec![0.0];
+        bounds.extend(size_buckets.iter().copied().filter(|edge| *edge > 0.0));
+        let mut buckets = Vec::new();
+        for side in [Side::Bid, Side::Ask] {
+            for (i, &min_quantity) in bounds.iter().enumerate() {
+                let max_quantity = bounds.get(i + 1).copied();
+                let in_bucket: Vec<&FillSlippage> = fills
+                    .iter()
+                    .filter(|fill| {
+                        fill.side == side
+                            && fill.quantity >= min_quantity
+                            && max_quantity.is_none_or(|max| fill.quantity < max)
+                    })
+                    .collect();
+                let (avg_realized_bps, avg_modeled_bps, modeled_fills) = averages(&in_bucket);
+                buckets.push(SlippageBucket {
+                    side,
+                    min_quantity,
+                    max_quantity,
+                    fills: in_bucket.len(),
+                    avg_realized_bps,
+                    avg_modeled_bps,
+                    modeled_fills,
+                    realized_cost: in_bucket.iter().map(|fill| fill.cost).sum(),
+                });
+            }
+        }
+
+        let all: Vec<&FillSlippage> = fills.iter().collect();
+        let (avg_realized_bps, avg_modeled_bps, _) = averages(&all);
+        Self {
+            fills: fills.len(),
+            unmatched,
+            avg_realized_bps,
+            avg_modeled_bps,
+            buckets,
+        }
+    }
+
+    pub fn print(&self) {
+        println!("\n💸 Slippage: Realized vs Modeled");
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   Fills: {} (unmatched: {})", self.fills, self.unmatched);
+        println!("   Avg Realized: {:.3} bps | Avg Modeled: {:.3} bps", self.avg_realized_bps, self.avg_modeled_bps);
+        println!("   {:<5} {:>16} {:>7} {:>12} {:>12}", "Side", "Size", "Fills", "Realized", "Modeled");
+        for bucket in self.buckets.iter().filter(|b| b.fills > 0) {
+            let size = match bucket.max_quantity {
+                Some(max) => format!("{}-{}", bucket.min_quantity, max),
+                None => format!(">={}", bucket.min_quantity),
+            };
+            let side = match bucket.side {
+                Side::Bid => "buy",
+                Side::Ask => "sell",
+            };
+            println!(
+                "   {:<5} {:>16} {:>7} {:>12.3} {:>12.3}",
+                side, size, bucket.fills, bucket.avg_realized_bps, bucket.avg_modeled_bps
+            );
+        }
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+fn measure(trade: &Trade, snapshots: &[L2Snapshot]) -> Option<FillSlippage> {
+    let idx = snapshots.partition_point(|s| s.timestamp_us <= trade.timestamp_us);
+    let snapshot = snapshots.get(idx.checked_sub(1)?)?;
+    let (touch, levels) = match trade.side {
+        Side::Bid => (snapshot.best_ask(), snapshot.asks()),
+        Side::Ask => (snapshot.best_bid(), snapshot.bids()),
+    };
+    if touch <= 0.0 {
+        return None;
+    }
+
+    let adverse = |price: f64| match trade.side {
+        Side::Bid => price - touch,
+        Side::Ask => touch - price,
+    };
+    let modeled_bps = sweep_price(&levels, trade.quantity).map(|avg| adverse(avg) / touch * 10_000.0);
+
+    Some(FillSlippage {
+        side: trade.side,
+        quantity: trade.quantity,
+        realized_bps: adverse(trade.price) / touch * 10_000.0,
+        modeled_bps,
+        cost: adverse(trade.price) * trade.quantity,
+    })
+}
+
+fn sweep_price(levels: &[PriceLevel], quantity: f64) -> Option<f64> {
+    if quantity <= 0.0 {
+        return None;
+    }
+    let mut remaining = quantity;
+    let mut notional = 0.0;
+    for level in levels {
+        let take = remaining.min(level.quantity);
+        notional += take * level.price;
+        remaining -= take;
+        if remaining <= 0.0 {
+            return Some(notional / quantity);
+        }
+    }
+    None
+}
+
+fn averages(fills: &[&FillSlippage]) -> (f64, f64, usize) {
+    if fills.is_empty() {
+        return (0.0, 0.0, 0);
+    }
+    let realized = fills.iter().map(|fill| fill.realized_bps).sum::<f64>() / fills.len() as f64;
+    let modeled: Vec<f64> = fills.iter().filter_map(|fill| fill.modeled_bps).collect();
+    let avg_modeled = if modeled.is_empty() { 0.0 } else { modeled.iter().sum::<f64>() / modeled.len() as f64 };
+    (realized, avg_modeled, modeled.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_realized_vs_modeled_buckets() {
+        let snapshot = L2Snapshot::from_levels(
+            0,
+            100,
+            String::new(),
+            &[PriceLevel::new(99.0, 1.0), PriceLevel::new(98.0, 1.0)],
+            &[PriceLevel::new(100.0, 1.0), PriceLevel::new(101.0, 1.0)],
+        );
+        let trades = vec![
+            Trade::new(Side::Bid, 100.5, 2.0, 150),
+            Trade::new(Side::Ask, 99.0, 0.05, 200),
+            Trade::new(Side::Bid, 100.0, 1.0, 50),
+        ];
+        let report = SlippageReport::compute(&trades, &[snapshot], &DEFAULT_SIZE_BUCKETS);
+
+        assert_eq!((report.fills, report.unmatched), (2, 1));
+        assert_eq!(report.buckets.len(), 8);
+
+        let large_buys = report.buckets.iter().find(|b| b.side == Side::Bid && b.max_quantity.is_none()).unwrap();
+        assert_eq!(large_buys.fills, 1);
+        assert!((large_buys.avg_realized_bps - 50.0).abs() < 1e-9);
+        assert!((large_buys.avg_modeled_bps - 50.0).abs() < 1e-9);
+        assert!((large_buys.realized_cost - 1.0).abs() < 1e-9);
+
+        let small_sells = report.buckets.iter().find(|b| b.side == Side::Ask && b.min_quantity == 0.01).unwrap();
+        assert_eq!((small_sells.fills, small_sells.avg_realized_bps), (1, 0.0));
+    }
+}
@@ -0,0 +1,171 @@
+use super::export::SummaryMetrics;
+use super::BacktestResult;
+use crate::types::{Side, Trade};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamRecord {
+    Start {
+        strategy_name: String,
+        timestamp: String,
+    },
+    Point {
+        snapshot: usize,
+        timestamp_us: u64,
+        pnl: f64,
+        position: f64,
+        volume: f64,
+    },
+    Trade {
+        id: usize,
+        timestamp_us: u64,
+        side: String,
+        price: f64,
+        size: f64,
+    },
+    Summary {
+        summary: Box<SummaryMetrics>,
+    },
+}
+
+
+
+
+pub struct StreamingExporter {
+    writer: BufWriter<File>,
+    flush_every: usize,
+    pending_snapshots: usize,
+    trades_written: usize,
+    points_written: usize,
+}
+
+impl StreamingExporter {
+    pub fn create(path: &Path, strategy_name: &str, flush_every: usize) -> Result<Self> {
+        let file = File::create(path).context(format!("Failed to create stream file: {}", path.display()))?;
+        let mut exporter = Self {
+            writer: BufWriter::new(file),
+            flush_every: flush_every.max(1),
+            pending_snapshots: 0,
+            trades_written: 0,
+            points_written: 0,
+        };
+        exporter.write(&StreamRecord::Start {
+            strategy_name: strategy_name.to_string(),
+            timestamp: chrono::Local::now().to_rfc3339(),
+        })?;
+        exporter.flush()?;
+        Ok(exporter)
+    }
+
+    fn write(&mut self, record: &StreamRecord) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n").context("Failed to write stream record")?;
+        Ok(())
+    }
+
+    pub fn record_point(&mut self, snapshot: usize, timestamp_us: u64, pnl: f64, position: f64, volume: f64) -> Result<()> {
+        self.points_written += 1;
+        self.write(&StreamRecord::Point { snapshot, timestamp_us, pnl, position, volume })
+    }
+
+    pub fn record_trade(&mut self, trade: &Trade) -> Result<()> {
+        let id = self.trades_written;
+        self.trades_written += 1;
+        self.write(&StreamRecord::Trade {
+            id,
+            timestamp_us: trade.timestamp_us,
+            side: match trade.side {
+                Side::Bid => "buy".to_string(),
+                Side::Ask => "sell".to_string(),
+            },
+            price: trade.price,
+            size: trade.quantity,
+        })
+    }
+
+    
+    pub fn tick(&mut self) -> Result<()> {
+        self.pending_snapshots += 1;
+        if self.pending_snapshots >= self.flush_every {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.pending_snapshots = 0;
+        self.writer.flush().context("Failed to flush stream file")
+    }
+
+    pub fn trades_written(&self) -> usize {
+        self.trades_written
+    }
+
+    pub fn points_written(&self) -> usize {
+        self.points_written
+    }
+
+    pub fn finish(mut self, result: &BacktestResult, starting_capital: f64) -> Result<()> {
+        let summary = SummaryMetrics::from_metrics(&result.metrics, &result.timing, starting_capital);
+        self.write(&StreamRecord::Summary { summary: Box::new(summary) })?;
+        self.flush()
+    }
+}
+
+
+
+pub fn read_stream(path: &Path) -> Result<Vec<StreamRecord>> {
+    let file = File::open(path).context(format!("Failed to open stream file: {}", path.display()))?;
+    let lines: Vec<String> = BufReader::new(file)
+        .lines()
+        .collect::<std::io::Result<_>>()
+        .context("Failed to read stream file")?;
+
+    let mut records = Vec::with_capacity(lines.len());
+    for (line_no, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(record) => records.push(record),
+            Err(_) if line_no + 1 == lines.len() => break,
+            Err(e) => return Err(e).context(format!("Invalid stream record at line {}", line_no + 1)),
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_survives_truncation() {
+        let path = std::env::temp_dir().join(format!("zaphft_stream_{}.ndjson", std::process::id()));
+        let mut exporter = StreamingExporter::create(&path, "Momentum Strategy", 2).unwrap();
+        exporter.record_trade(&Trade::new(Side::Bid, 100.0, 1.0, 5)).unwrap();
+        exporter.tick().unwrap();
+        exporter.record_point(1, 10, 0.5, 1.0, 1.0).unwrap();
+        exporter.tick().unwrap();
+
+        let records = read_stream(&path).unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(matches!(&records[1], StreamRecord::Trade { side, price, .. } if side == "buy" && *price == 100.0));
+
+        exporter.finish(&BacktestResult::new("Momentum Strategy".to_string()), 10_000.0).unwrap();
+        let records = read_stream(&path).unwrap();
+        assert!(matches!(records.last(), Some(StreamRecord::Summary { .. })));
+
+        let mut text = std::fs::read_to_string(&path).unwrap();
+        text.push_str("{\"type\":\"po");
+        std::fs::write(&path, text).unwrap();
+        assert_eq!(read_stream(&path).unwrap().len(), 4);
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use crate::market_data::TradeTape;
+use crate::types::{L2Snapshot, Side, Trade};
+use super::performance::mid_at;
+
+
+
+#[derive(Debug, Clone)]
+pub struct TcaOrder {
+    pub side: Side,
+    pub arrival_us: u64,
+    
+    pub target_quantity: f64,
+    pub fills: Vec<Trade>,
+}
+
+impl TcaOrder {
+    pub fn new(side: Side, arrival_us: u64, target_quantity: f64, fills: Vec<Trade>) -> Self {
+        Self {
+            side,
+            arrival_us,
+            target_quantity,
+            fills,
+        }
+    }
+
+    
+    pub fn from_fills(trades: &[Trade]) -> Vec<TcaOrder> {
+        trades
+            .iter()
+            .map(|trade| TcaOrder::new(trade.side, trade.timestamp_us, trade.quantity, vec![trade.clone()]))
+            .collect()
+    }
+
+    fn filled_quantity(&self) -> f64 {
+        self.fills.iter().map(|f| f.quantity).sum()
+    }
+}
+
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TcaResult {
+    pub side: Side,
+    pub arrival_us: u64,
+    pub end_us: u64,
+    pub filled_quantity: f64,
+    pub avg_price: f64,
+    pub arrival_mid: f64,
+    pub interval_vwap: f64,
+    pub close_mid: f64,
+    pub shortfall_bps: f64,
+    pub vs_vwap_bps: f64,
+    pub vs_close_bps: f64,
+    
+    pub timing_bps: f64,
+    pub execution_bps: f64,
+    pub opportunity_bps: f64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TcaReport {
+    pub orders: Vec<TcaResult>,
+    pub notional: f64,
+    
+    pub shortfall_bps: f64,
+    pub vs_vwap_bps: f64,
+    pub vs_close_bps: f64,
+    pub timing_bps: f64,
+    pub execution_bps: f64,
+    pub opportunity_bps: f64,
+}
+
+impl TcaReport {
+    pub fn from_fills(trades: &[Trade], snapshots: &[L2Snapshot]) -> Self {
+        Self::from_orders(&TcaOrder::from_fills(trades), snapshots)
+    }
+
+    pub fn from_orders(orders: &[TcaOrder], snapshots: &[L2Snapshot]) -> Self {
+        let tape = TradeTape::from_snapshots(snapshots);
+        let close_mid = snapshots.last().map(|s| s.mid_price()).unwrap_or(0.0);
+        let results: Vec<TcaResult> = orders
+            .iter()
+            .filter_map(|order| analyze(order, snapshots, &tape, close_mid))
+            .collect();
+
+        let weight = |r: &TcaResult| r.avg_price * r.filled_quantity;
+        let notional: f64 = results.iter().map(weight).sum();
+        let weighted = |f: fn(&TcaResult) -> f64| {
+            if notional <= 0.0 {
+                return 0.0;
+            }
+            results.iter().map(|r| f(r) * weight(r)).sum::<f64>() / notional
+        };
+
+        Self {
+            notional,
+            shortfall_bps: weighted(|r| r.shortfall_bps),
+            vs_vwap_bps: weighted(|r| r.vs_vwap_bps),
+            vs_close_bps: weighted(|r| r.vs_close_bps),
+            timing_bps: weighted(|r| r.timing_bps),
+            execution_bps: weighted(|r| r.execution_bps),
+            opportunity_bps: weighted(|r| r.opportunity_bps),
+            orders: results,
+        }
+    }
+
+    pub fn print(&self) {
+        println!("\n🧾 Transaction Cost Analysis ({} orders)", self.orders.len());
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   Notional:          ${:.2}", self.notional);
+        println!("   Shortfall:         {:+.3} bps", self.shortfall_bps);
+        println!("     Timing:          {:+.3} bps", self.timing_bps);
+        println!("     Execution:       {:+.3} bps", self.execution_bps);
+        println!("     Opportunity:     {:+.3} bps", self.opportunity_bps);
+        println!("   vs Interval VWAP:  {:+.3} bps", self.vs_vwap_bps);
+        println!("   vs Close:          {:+.3} bps", self.vs_close_bps);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+fn analyze(order: &TcaOrder, snapshots: &[L2Snapshot], tape: &TradeTape, close_mid: f64) -> Option<TcaResult> {
+    let filled_quantity = order.filled_quantity();
+    if filled_quantity <= 0.0 {
+        return None;
+    }
+    let arrival_mid = mid_at(snapshots, order.arrival_us)?;
+    let avg_price = order.fills.iter().map(|f| f.price * f.quantity).sum::<f64>() / filled_quantity;
+    let end_us = order.fills.iter().map(|f| f.timestamp_us).max().unwrap_or(order.arrival_us);
+    let interval_vwap = tape
+        .vwap_between(order.arrival_us, end_us + 1)
+        .unwrap_or_else(|| interval_mid(snapshots, order.arrival_us, end_us).unwrap_or(arrival_mid));
+
+    let sign = match order.side {
+        Side::Bid => 1.0,
+        Side::Ask => -1.0,
+    };
+    let bps = |price: f64, benchmark: f64| sign * (price - benchmark) / benchmark * 10_000.0;
+    let unfilled = (order.target_quantity - filled_quantity).max(0.0) / order.target_quantity.max(filled_quantity);
+    let fill_share = 1.0 - unfilled;
+
+    let timing_bps = bps(interval_vwap, arrival_mid) * fill_share;
+    let execution_bps = bps(avg_price, interval_vwap) * fill_share;
+    let opportunity_bps = bps(close_mid, arrival_mid) * unfilled;
+
+    Some(TcaResult {
+        side: order.side,
+        arrival_us: order.arrival_us,
+        end_us,
+        filled_quantity,
+        avg_price,
+        arrival_mid,
+        interval_vwap,
+        close_mid,
+        shortfall_bps: timing_bps + execution_bps + opportunity_bps,
+        vs_vwap_bps: bps(avg_price, interval_vwap),
+        vs_close_bps: bps(avg_price, close_mid),
+        timing_bps,
+        execution_bps,
+        opportunity_bps,
+    })
+}
+
+fn interval_mid(snapshots: &[L2Snapshot], start_us: u64, end_us: u64) -> Option<f64> {
+    let start = snapshots.partition_point(|s| s.timestamp_us < start_us);
+    let end = snapshots.partition_point(|s| s.timestamp_us <= end_us);
+    let window = &snapshots[start..end.max(start)];
+    if window.is_empty() {
+        return None;
+    }
+    Some(window.iter().map(|s| s.mid_price()).sum::<f64>() / window.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(
+            0,
+            timestamp_us,
+            String::new(),
+            &[PriceLevel::new(mid - 0.5, 1.0)],
+            &[PriceLevel::new(mid + 0.5, 1.0)],
+        )
+    }
+
+    #[test]
+    fn test_shortfall_decomposition() {
+        let snapshots = vec![snapshot(0, 100.0), snapshot(10, 101.0), snapshot(20, 102.0), snapshot(30, 104.0)];
+        let order = TcaOrder::new(
+            Side::Bid,
+            0,
+            2.0,
+            vec![Trade::new(Side::Bid, 101.0, 0.5, 10), Trade::new(Side::Bid, 102.0, 0.5, 20)],
+        );
+
+        let report = TcaReport::from_orders(&[order], &snapshots);
+        let result = &report.orders[0];
+
+        assert_eq!((result.arrival_mid, result.close_mid), (100.0, 104.0));
+        assert!((result.avg_price - 101.5).abs() < 1e-9);
+        assert!((result.interval_vwap - 101.0).abs() < 1e-9);
+        assert!((result.timing_bps - 50.0).abs() < 1e-9);
+        assert!((result.execution_bps - 0.5 * 0.5 / 101.0 * 10_000.0).abs() < 1e-9);
+        assert!((result.opportunity_bps - 200.0).abs() < 1e-9);
+        assert!((result.shortfall_bps - (result.timing_bps + result.execution_bps + result.opportunity_bps)).abs() < 1e-12);
+
+        let sells = TcaReport::from_fills(&[Trade::new(Side::Ask, 99.0, 1.0, 0)], &snapshots);
+        assert!((sells.shortfall_bps - 100.0).abs() < 1e-9);
+        assert!((sells.vs_close_bps - 5.0 / 104.0 * 10_000.0).abs() < 1e-9);
+    }
+}
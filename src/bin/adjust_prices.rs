@@ -0,0 +1,28 @@
+use anyhow::Result;
+use rusthft::market_data::adjust::{adjust_prices_file, AdjustmentSchedule};
+use std::path::Path;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 4 {
+        println!("Usage: adjust_prices <input.csv> <schedule.csv> <output.csv>");
+        println!();
+        println!("Schedule columns: effective_us,kind,value");
+        println!("  kind = additive | multiplicative");
+        println!("  Prices before effective_us are back-adjusted onto the later series.");
+        return Ok(());
+    }
+
+    let input_path = Path::new(&args[1]);
+    let schedule = AdjustmentSchedule::from_csv(Path::new(&args[2]))?;
+    let output_path = Path::new(&args[3]);
+
+    println!("📐 Applying {} price adjustments", schedule.adjustments().len());
+    println!("   Input:  {}", input_path.display());
+    println!("   Output: {}", output_path.display());
+
+    let rows = adjust_prices_file(input_path, output_path, &schedule)?;
+    println!("   ✓ Adjusted {} rows", rows);
+
+    Ok(())
+}
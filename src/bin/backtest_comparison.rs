@@ -9,7 +9,7 @@ use rusthft::{
     SnapshotReader,
     Position, Strategy, MarketMaker, MarketMakerConfig,
     NaiveMarketMaker, NaiveMarketMakerConfig,
-    analytics::{BacktestResult, print_comparison},
+    analytics::{BacktestResult, BenchConfig, BenchRunner, RunStatistics, print_comparison},
 };
 use rusthft::trivial_approach::{NaivePosition, CachedNaivePosition};
 use std::path::Path;
@@ -23,11 +23,13 @@ fn main() -> anyhow::Result<()> {
     
     let num_snapshots = 200_000;
     let data_path = Path::new("data/L2_processed.csv");
+    let bench = parse_bench(&std::env::args().collect::<Vec<_>>())?;
 
     println!("Test Configuration:");
     println!("   Snapshots:  {}", num_snapshots);
     println!("   Data file:  {:?}", data_path);
     println!("   Approaches: 3 (Optimized HFT, Cached Naive, Pure Naive)");
+    println!("   Runs:       {} warmup + {} measured per approach", bench.warmup, bench.repetitions.max(1));
     println!();
 
     
@@ -44,6 +46,11 @@ fn main() -> anyhow::Result<()> {
 
     println!("Loaded {} snapshots\n", snapshots.len());
 
+    let final_price = {
+        let last = snapshots.last().unwrap();
+        (last.best_bid() + last.best_ask()) / 2.0
+    };
+    let runner = BenchRunner::new(bench);
     let mut results = Vec::new();
 
     
@@ -61,32 +68,27 @@ fn main() -> anyhow::Result<()> {
         ..MarketMakerConfig::default()
     };
 
-    let mut strategy = MarketMaker::new(config);
-    let mut position = Position::new();
-
-    println!("Running backtest...");
-    let start = Instant::now();
-
-    for snapshot in &snapshots {
-        let trades = strategy.on_market_data(snapshot, &position);
-        for trade in trades {
-            position.execute_trade(trade);
+    print_running(runner.config());
+    let (result, stats) = runner.measure(|| {
+        let mut strategy = MarketMaker::new(config.clone());
+        let mut position = Position::new();
+        let start = Instant::now();
+
+        for snapshot in &snapshots {
+            let trades = strategy.on_market_data(snapshot, &position);
+            for trade in trades {
+                position.execute_trade(trade);
+            }
         }
-    }
-
-    let duration = start.elapsed();
-    println!("Completed in {:?}\n", duration);
 
-    
-    let final_price = {
-        let last = snapshots.last().unwrap();
-        (last.best_bid() + last.best_ask()) / 2.0
-    };
-
-    let stats = strategy.stats();
-    let mut result = BacktestResult::new("HFT Optimized".to_string());
-    result.calculate_from_position(&position, final_price, stats.updates_processed, stats.quotes_placed);
-    result.set_timing(duration, snapshots.len());
+        let duration = start.elapsed();
+        let stats = strategy.stats();
+        let mut result = BacktestResult::new("HFT Optimized".to_string());
+        result.calculate_from_position(&position, final_price, stats.updates_processed, stats.quotes_placed);
+        result.set_timing(duration, snapshots.len());
+        result
+    });
+    print_statistics(&stats);
     result.print_report();
 
     results.push(result);
@@ -105,47 +107,39 @@ fn main() -> anyhow::Result<()> {
         tick_size: 0.1,
     };
 
-    let mut naive_strategy = NaiveMarketMaker::new(naive_config);
-    let mut naive_position = CachedNaivePosition::new();
-
-    println!("Running backtest...");
-    let start = Instant::now();
+    print_running(runner.config());
+    let (naive_result, naive_run_stats) = runner.measure(|| {
+        let mut naive_strategy = NaiveMarketMaker::new(naive_config.clone());
+        let mut naive_position = CachedNaivePosition::new();
+        let start = Instant::now();
+
+        for snapshot in &snapshots {
+            let trades = naive_strategy.on_market_data(snapshot, &naive_position);
+            for trade in trades {
+                naive_position.execute_trade(trade);
+            }
+        }
 
-    for snapshot in &snapshots {
-        
-        let mut position_adapter = Position::new();
+        let duration = start.elapsed();
+        let naive_stats = naive_strategy.stats();
+        let mut naive_result = BacktestResult::new("Cached Naive".to_string());
 
         
+        let mut position_for_analytics = Position::new();
         for trade in naive_position.trades() {
-            position_adapter.execute_trade(trade.clone());
+            position_for_analytics.execute_trade(trade.clone());
         }
 
-        let trades = naive_strategy.on_market_data(snapshot, &position_adapter);
-        for trade in trades {
-            naive_position.execute_trade(trade);
-        }
-    }
-
-    let duration = start.elapsed();
-    println!("Completed in {:?}\n", duration);
-
-    
-    let naive_stats = naive_strategy.stats();
-    let mut naive_result = BacktestResult::new("Cached Naive".to_string());
-
-    
-    let mut position_for_analytics = Position::new();
-    for trade in naive_position.trades() {
-        position_for_analytics.execute_trade(trade.clone());
-    }
-
-    naive_result.calculate_from_position(
-        &position_for_analytics,
-        final_price,
-        naive_stats.updates_processed,
-        naive_stats.quotes_placed
-    );
-    naive_result.set_timing(duration, snapshots.len());
+        naive_result.calculate_from_position(
+            &position_for_analytics,
+            final_price,
+            naive_stats.updates_processed,
+            naive_stats.quotes_placed
+        );
+        naive_result.set_timing(duration, snapshots.len());
+        naive_result
+    });
+    print_statistics(&naive_run_stats);
     naive_result.print_report();
 
     results.push(naive_result);
@@ -164,47 +158,39 @@ fn main() -> anyhow::Result<()> {
         tick_size: 0.1,
     };
 
-    let mut pure_naive_strategy = NaiveMarketMaker::new(pure_naive_config);
-    let mut pure_naive_position = NaivePosition::new();
-
-    println!("Running backtest...");
-    let start = Instant::now();
+    print_running(runner.config());
+    let (pure_naive_result, pure_naive_run_stats) = runner.measure(|| {
+        let mut pure_naive_strategy = NaiveMarketMaker::new(pure_naive_config.clone());
+        let mut pure_naive_position = NaivePosition::new();
+        let start = Instant::now();
+
+        for snapshot in &snapshots {
+            let trades = pure_naive_strategy.on_market_data(snapshot, &pure_naive_position);
+            for trade in trades {
+                pure_naive_position.execute_trade(trade);
+            }
+        }
 
-    for snapshot in &snapshots {
-        
-        let mut position_adapter = Position::new();
+        let duration = start.elapsed();
+        let pure_naive_stats = pure_naive_strategy.stats();
+        let mut pure_naive_result = BacktestResult::new("Pure Naive".to_string());
 
         
+        let mut pure_position_for_analytics = Position::new();
         for trade in pure_naive_position.trades() {
-            position_adapter.execute_trade(trade.clone());
+            pure_position_for_analytics.execute_trade(trade.clone());
         }
 
-        let trades = pure_naive_strategy.on_market_data(snapshot, &position_adapter);
-        for trade in trades {
-            pure_naive_position.execute_trade(trade);
-        }
-    }
-
-    let duration = start.elapsed();
-    println!("Completed in {:?}\n", duration);
-
-    
-    let pure_naive_stats = pure_naive_strategy.stats();
-    let mut pure_naive_result = BacktestResult::new("Pure Naive".to_string());
-
-    
-    let mut pure_position_for_analytics = Position::new();
-    for trade in pure_naive_position.trades() {
-        pure_position_for_analytics.execute_trade(trade.clone());
-    }
-
-    pure_naive_result.calculate_from_position(
-        &pure_position_for_analytics,
-        final_price,
-        pure_naive_stats.updates_processed,
-        pure_naive_stats.quotes_placed
-    );
-    pure_naive_result.set_timing(duration, snapshots.len());
+        pure_naive_result.calculate_from_position(
+            &pure_position_for_analytics,
+            final_price,
+            pure_naive_stats.updates_processed,
+            pure_naive_stats.quotes_placed
+        );
+        pure_naive_result.set_timing(duration, snapshots.len());
+        pure_naive_result
+    });
+    print_statistics(&pure_naive_run_stats);
     pure_naive_result.print_report();
 
     results.push(pure_naive_result);
@@ -279,3 +265,29 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn print_running(bench: &BenchConfig) {
+    println!("Running backtest ({} warmup + {} measured)...", bench.warmup, bench.repetitions.max(1));
+}
+
+fn print_statistics(stats: &RunStatistics) {
+    println!("Completed: {:.2} ns/snapshot median, {:.2} ± {:.2} mean ({:.1}% CV)\n",
+             stats.median_ns, stats.mean_ns, stats.stddev_ns, stats.cv_pct());
+}
+
+fn parse_bench(args: &[String]) -> anyhow::Result<BenchConfig> {
+    let mut bench = BenchConfig::default();
+    let mut i = 1;
+    while i < args.len() {
+        let Some(value) = args.get(i + 1) else {
+            anyhow::bail!("Missing value for {}", args[i]);
+        };
+        match args[i].as_str() {
+            "--warmup" => bench.warmup = value.parse()?,
+            "--repeat" => bench.repetitions = value.parse()?,
+            other => anyhow::bail!("Unknown argument: {} (expected --warmup <NUM> or --repeat <NUM>)", other),
+        }
+        i += 2;
+    }
+    Ok(bench)
+}
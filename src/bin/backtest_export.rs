@@ -4,7 +4,16 @@
 
 
 use rusthft::*;
-use rusthft::analytics::{BacktestResult, BacktestExport, TimeseriesData, TimeseriesPoint, PerformanceComparison};
+use rusthft::analytics::{BacktestResult, BacktestExport, BenchConfig, BenchRunner, ComparisonApproach, PerformanceComparison, StreamingExporter, ExportOptions};
+use rusthft::engine::MetricsRecorder;
+use rusthft::analytics::markout::{tag_fills, MarkoutCurve};
+use rusthft::analytics::regime::{RegimeBreakdown, RegimeConfig};
+use rusthft::analytics::liquidity::{LiquidityProfile, DEFAULT_IMPACT_BPS};
+use rusthft::analytics::slippage::{SlippageReport, DEFAULT_SIZE_BUCKETS};
+use rusthft::analytics::tca::TcaReport;
+use rusthft::analytics::heatmap::{DepthHeatmap, HeatmapConfig};
+use rusthft::features::{Volatility, VolatilityConfig};
+use rusthft::experiments::{DatasetRange, ExperimentRecord, ExperimentRegistry};
 use rusthft::strategy::momentum::{MomentumStrategy, MomentumConfig};
 use rusthft::trivial_approach::{NaiveMomentumStrategy, PureNaiveMomentumStrategy};
 use std::io::{self, Write};
@@ -57,10 +66,19 @@ fn main() -> anyhow::Result<()> {
     
     match config.strategy {
         StrategyType::Momentum => {
-            export_momentum(&snapshots, &config.output_path, start_price, final_price)?;
+            let (result, momentum_config) = export_momentum(&snapshots, data_path, &config, start_price, final_price)?;
+            if let Some(registry_path) = &config.registry {
+                let record = ExperimentRecord::new(
+                    &result,
+                    serde_json::to_value(&momentum_config)?,
+                    DatasetRange::from_snapshots(data_path, &snapshots),
+                );
+                ExperimentRegistry::open(registry_path).append(&record)?;
+                println!("Recorded run {} in {}", record.run_id, registry_path.display());
+            }
         }
         StrategyType::Performance => {
-            export_performance_comparison(&snapshots, &config.output_path)?;
+            export_performance_comparison(&snapshots, &config.approaches, config.handicap, &config.bench, &config.output_path)?;
         }
     }
 
@@ -85,10 +103,11 @@ fn print_progress(label: &str, processed: usize, total: usize) {
 
 fn export_momentum(
     snapshots: &[L2Snapshot],
-    output_path: &Path,
+    data_path: &Path,
+    export_config: &ExportConfig,
     start_price: f64,
     final_price: f64,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<(BacktestResult, MomentumConfig)> {
     const STARTING_CAPITAL: f64 = 10_000.0;  
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("EXPORTING MOMENTUM STRATEGY");
@@ -99,82 +118,60 @@ fn export_momentum(
         trade_size: 0.1,
         max_position: 1.0,
         lookback: 500,
+        ..MomentumConfig::default()
     };
 
-    let mut strategy = MomentumStrategy::new(config);
+    let mut strategy = MomentumStrategy::new(config.clone());
     let mut position = Position::new();
+    let options = &export_config.options;
+    let mut stream = match &export_config.stream {
+        Some(path) => Some(StreamingExporter::create(path, "Momentum Strategy", export_config.flush_every)?),
+        None => None,
+    };
 
-    
-    let mut pnl_curve = Vec::new();
-    let mut position_curve = Vec::new();
-    let mut volume_curve = Vec::new();
+    let mut recorder = MetricsRecorder::new(options.sample_every);
     let mut cumulative_volume = 0.0;
 
     let start = Instant::now();
 
-    for (idx, snapshot) in snapshots.iter().enumerate() {
-        let trades = strategy.on_market_data(snapshot, &position);
+    let cache = DerivedCache::build(snapshots);
+    for (idx, (snapshot, derived)) in snapshots.iter().zip(cache.values()).enumerate() {
+        let trades = strategy.on_market_data_derived(snapshot, derived, &position);
 
         for trade in trades {
             cumulative_volume += trade.quantity;
+            if let Some(stream) = &mut stream {
+                stream.record_trade(&trade)?;
+            }
             position.execute_trade(trade);
         }
 
         
-        if idx % 100 == 0 {
-            let mid_price = (snapshot.best_bid() + snapshot.best_ask()) / 2.0;
-            let total_pnl = position.total_pnl(mid_price);
-
-            pnl_curve.push(TimeseriesPoint {
-                snapshot: idx,
-                timestamp_us: snapshot.timestamp_us,
-                value: total_pnl,
-            });
-
-            position_curve.push(TimeseriesPoint {
-                snapshot: idx,
-                timestamp_us: snapshot.timestamp_us,
-                value: position.quantity,
-            });
-
-            volume_curve.push(TimeseriesPoint {
-                snapshot: idx,
-                timestamp_us: snapshot.timestamp_us,
-                value: cumulative_volume,
-            });
+        if options.samples(idx) {
+            recorder.record_derived(idx, snapshot.timestamp_us, derived, &position);
+            if let Some(stream) = &mut stream {
+                let total_pnl = position.total_pnl(derived.mid);
+                stream.record_point(idx, snapshot.timestamp_us, total_pnl, position.quantity, cumulative_volume)?;
+            }
         }
-    }
 
-    let duration = start.elapsed();
-
-    
-    let mut max_pnl = 0.0;
-    let mut drawdown_curve = Vec::new();
-    for point in &pnl_curve {
-        if point.value > max_pnl {
-            max_pnl = point.value;
+        if let Some(stream) = &mut stream {
+            stream.tick()?;
         }
-        let drawdown = max_pnl - point.value;
-        drawdown_curve.push(TimeseriesPoint {
-            snapshot: point.snapshot,
-            timestamp_us: point.timestamp_us,
-            value: drawdown,
-        });
     }
 
+    let duration = start.elapsed();
+
     
     let stats = strategy.stats();
     let mut result = BacktestResult::new("Momentum Strategy".to_string());
     result.calculate_from_position(&position, final_price, stats.updates_processed, stats.quotes_placed);
     result.set_timing(duration, snapshots.len());
+    if let Some(stream) = stream {
+        stream.finish(&result, STARTING_CAPITAL)?;
+    }
 
-    
-    let timeseries = TimeseriesData {
-        pnl_curve,
-        position_curve,
-        volume_curve,
-        drawdown_curve,
-    };
+    let timeseries = recorder.finish();
 
     let export = BacktestExport::from_backtest(
         &result,
@@ -185,8 +182,22 @@ fn export_momentum(
         STARTING_CAPITAL,
     );
 
+    let horizons_us = [0, 1_000_000, 5_000_000, 30_000_000];
+    let fill_markouts = tag_fills(position.trades(), snapshots, &horizons_us);
+    let export = export
+        .with_markouts(MarkoutCurve::from_fills(&fill_markouts, &horizons_us))
+        .with_regimes(RegimeBreakdown::compute(snapshots, position.trades(), RegimeConfig::default()))
+        .with_liquidity(LiquidityProfile::compute(snapshots, &DEFAULT_IMPACT_BPS))
+        .with_slippage(SlippageReport::compute(position.trades(), snapshots, &DEFAULT_SIZE_BUCKETS))
+        .with_heatmap(DepthHeatmap::build(snapshots, HeatmapConfig::default()))
+        .with_volatility(Volatility::series(snapshots, VolatilityConfig::default(), 100))
+        .with_strategy_stats(&stats)
+        .with_strategy_config(serde_json::to_value(&config)?)
+        .with_dataset(DatasetRange::from_snapshots(data_path, snapshots))
+        .with_options(options);
+
     
-    export.to_file(output_path)?;
+    export.to_file(&export_config.output_path)?;
 
     println!("Momentum strategy exported");
     println!("   Starting Capital: ${:.2}", STARTING_CAPITAL);
@@ -195,112 +206,137 @@ fn export_momentum(
     println!("   Return:           {:+.2}%", (result.metrics.total_pnl / STARTING_CAPITAL) * 100.0);
     println!("   Total Trades:     {}", result.metrics.total_trades);
     println!("   Duration:         {:?}", duration);
+    if let Some(fingerprint) = &export.metadata.fingerprint {
+        println!("   Fingerprint:      {}", fingerprint);
+    }
+    export.attribution.print();
+    export.sizing.print();
+    if let Some(markouts) = &export.markouts {
+        markouts.print();
+    }
+    if let Some(regimes) = &export.regimes {
+        regimes.print();
+    }
+    if let Some(slippage) = &export.slippage {
+        slippage.print();
+    }
+    TcaReport::from_fills(position.trades(), snapshots).print();
     println!();
 
-    Ok(())
+    Ok((result, config))
 }
 
-fn export_performance_comparison(
-    snapshots: &[L2Snapshot],
-    output_path: &Path,
-) -> anyhow::Result<()> {
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("EXPORTING MOMENTUM PERFORMANCE COMPARISON");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+const MOMENTUM_APPROACHES: [&str; 3] = ["optimized", "cached_naive", "pure_naive"];
+const MARKET_MAKER_APPROACHES: [&str; 5] = ["mm_optimized", "mm_runtime_lean", "mm_lean", "mm_naive", "mm_pure_naive"];
 
-    let final_price = {
-        let last = snapshots.last().unwrap();
-        (last.best_bid() + last.best_ask()) / 2.0
-    };
+fn lean_market_maker(naive: &NaiveMarketMakerConfig) -> MarketMakerConfig {
+    MarketMakerConfig {
+        spread_ticks: naive.spread_ticks,
+        quote_size: naive.quote_size,
+        max_position: naive.max_position,
+        tick_size: naive.tick_size,
+        ..LeanMarketMaker::default_config()
+    }
+}
 
-    let mut results = Vec::new();
-    let base_config = MomentumConfig {
+fn comparison_approach(name: &str, handicap: usize) -> anyhow::Result<ComparisonApproach> {
+    let momentum = MomentumConfig {
         trigger_threshold: 15.0,
         trade_size: 0.1,
         max_position: 1.0,
         lookback: 500,
+        ..MomentumConfig::default()
     };
-
-    
-    println!("Running HFT Optimized momentum...");
-    let mut strategy = MomentumStrategy::new(base_config.clone());
-    let mut position = Position::new();
-    let start = Instant::now();
-
-    for (idx, snapshot) in snapshots.iter().enumerate() {
-        let trades = strategy.on_market_data(snapshot, &position);
-        for trade in trades {
-            position.execute_trade(trade);
-        }
-        print_progress("HFT Optimized", idx + 1, snapshots.len());
-    }
-
-    let duration = start.elapsed();
-    let stats = strategy.stats();
-    let mut result = BacktestResult::new("HFT Optimized Momentum".to_string());
-    result.calculate_from_position(&position, final_price, stats.updates_processed, stats.quotes_placed);
-    result.set_timing(duration, snapshots.len());
-    results.push(result);
-
-    
-    println!("Running Cached Naive momentum...");
-    let mut strategy = NaiveMomentumStrategy::new(base_config.clone());
-    let mut position = Position::new();
-    let start = Instant::now();
-
-    for (idx, snapshot) in snapshots.iter().enumerate() {
-        let trades = strategy.on_market_data(snapshot, &position);
-        for trade in trades {
-            position.execute_trade(trade);
+    let market_maker = NaiveMarketMakerConfig::default();
+    Ok(match name {
+        "optimized" => ComparisonApproach::new("HFT Optimized Momentum", move || Box::new(MomentumStrategy::new(momentum.clone()))),
+        "cached_naive" => ComparisonApproach::new("Cached Naive Momentum", move || {
+            Box::new(NaiveMomentumStrategy::new(momentum.clone()).with_handicap(handicap))
+        })
+        .with_handicap(handicap),
+        "pure_naive" => ComparisonApproach::new("Pure Naive Momentum", move || {
+            Box::new(PureNaiveMomentumStrategy::new(momentum.clone()).with_handicap(handicap))
+        })
+        .with_handicap(handicap),
+        "mm_optimized" => ComparisonApproach::new("HFT Optimized Market Maker", move || {
+            Box::new(MarketMaker::new(MarketMakerConfig {
+                spread_ticks: market_maker.spread_ticks,
+                quote_size: market_maker.quote_size,
+                max_position: market_maker.max_position,
+                tick_size: market_maker.tick_size,
+                ..MarketMakerConfig::default()
+            }))
+        }),
+        "mm_runtime_lean" => ComparisonApproach::new("Runtime Lean Market Maker", move || {
+            Box::new(MarketMaker::new(lean_market_maker(&market_maker)))
+        }),
+        "mm_lean" => ComparisonApproach::new("Const-Generic Lean Market Maker", move || {
+            Box::new(LeanMarketMaker::new(lean_market_maker(&market_maker)))
+        }),
+        "mm_naive" => ComparisonApproach::new("Cached Naive Market Maker", move || Box::new(NaiveMarketMaker::new(market_maker.clone()))),
+        "mm_pure_naive" => ComparisonApproach::new("Pure Naive Market Maker", move || Box::new(PureNaiveMarketMaker::new(market_maker.clone()))),
+        other => {
+            let factory = StrategyFactory::with_builtins();
+            factory.create(other, &serde_json::Value::Null)?;
+            let label = other.to_string();
+            ComparisonApproach::new(other, move || {
+                factory.create(&label, &serde_json::Value::Null).expect("strategy was validated") as Box<dyn Strategy>
+            })
         }
-        print_progress("Cached Naive", idx + 1, snapshots.len());
-    }
+    })
+}
 
-    let duration = start.elapsed();
-    let stats = strategy.stats();
-    let mut result = BacktestResult::new("Cached Naive Momentum".to_string());
-    result.calculate_from_position(&position, final_price, stats.updates_processed, stats.quotes_placed);
-    result.set_timing(duration, snapshots.len());
-    results.push(result);
+fn export_performance_comparison(
+    snapshots: &[L2Snapshot],
+    approaches: &[String],
+    handicap: usize,
+    bench: &BenchConfig,
+    output_path: &Path,
+) -> anyhow::Result<()> {
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("EXPORTING PERFORMANCE COMPARISON");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    
-    println!("Running Pure Naive momentum...");
-    let mut strategy = PureNaiveMomentumStrategy::new(base_config);
-    let mut position = Position::new();
-    let start = Instant::now();
+    let approaches = approaches
+        .iter()
+        .map(|name| comparison_approach(name, handicap))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let title = if approaches.iter().all(|a| a.name.ends_with("Momentum")) {
+        "Momentum Strategy".to_string()
+    } else if approaches.iter().all(|a| a.name.ends_with("Market Maker")) {
+        "Market Maker Strategy".to_string()
+    } else {
+        approaches.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(" vs ")
+    };
 
-    for (idx, snapshot) in snapshots.iter().enumerate() {
-        let trades = strategy.on_market_data(snapshot, &position);
-        for trade in trades {
-            position.execute_trade(trade);
-        }
-        print_progress("Pure Naive", idx + 1, snapshots.len());
+    let runner = BenchRunner::new(bench.clone());
+    let mut results = Vec::with_capacity(approaches.len());
+    let mut stats = Vec::with_capacity(approaches.len());
+    for (idx, approach) in approaches.iter().enumerate() {
+        println!("Running {} ({} warmup + {} measured)...", approach.name, bench.warmup, bench.repetitions.max(1));
+        let (result, run_stats) = runner.run(approach, snapshots);
+        results.push(result);
+        stats.push(run_stats);
+        print_progress("Comparison", idx + 1, approaches.len());
     }
 
-    let duration = start.elapsed();
-    let stats = strategy.stats();
-    let mut result = BacktestResult::new("Pure Naive Momentum".to_string());
-    result.calculate_from_position(&position, final_price, stats.updates_processed, stats.quotes_placed);
-    result.set_timing(duration, snapshots.len());
-    results.push(result);
-
-    let comparison = PerformanceComparison::new(
-        "Momentum Strategy".to_string(),
-        snapshots.len(),
-        &results,
-    );
-
+    let comparison = PerformanceComparison::new(title, snapshots.len(), &results)
+        .with_handicaps(&approaches)
+        .with_statistics(stats.clone());
     comparison.to_file(output_path)?;
 
     println!("Performance comparison exported");
     println!();
-    println!("   HFT Optimized:  {:.2} ns/snapshot", results[0].timing.time_per_snapshot.as_nanos());
-    println!("   Cached Naive:   {:.2} ns/snapshot ({:.1}× slower)",
-             results[1].timing.time_per_snapshot.as_nanos(),
-             results[1].timing.total_duration.as_nanos() as f64 / results[0].timing.total_duration.as_nanos() as f64);
-    println!("   Pure Naive:     {:.2} ns/snapshot ({:.1}× slower)",
-             results[2].timing.time_per_snapshot.as_nanos(),
-             results[2].timing.total_duration.as_nanos() as f64 / results[0].timing.total_duration.as_nanos() as f64);
+    let baseline = &stats[0];
+    for ((result, approach), run_stats) in results.iter().zip(&approaches).zip(&stats) {
+        let ratio = run_stats.median_ns / baseline.median_ns.max(f64::EPSILON);
+        print!("   {:<28} {:>10.2} ns/snapshot median, {:.2} ± {:.2} mean ({:.1}× baseline)",
+               format!("{}:", result.name), run_stats.median_ns, run_stats.mean_ns, run_stats.stddev_ns, ratio);
+        if approach.handicap > 0 {
+            print!(" [handicap: {} iterations/update]", approach.handicap);
+        }
+        println!();
+    }
     println!();
 
     Ok(())
@@ -312,6 +348,13 @@ struct ExportConfig {
     strategy: StrategyType,
     num_snapshots: usize,
     output_path: PathBuf,
+    registry: Option<PathBuf>,
+    stream: Option<PathBuf>,
+    flush_every: usize,
+    options: ExportOptions,
+    approaches: Vec<String>,
+    handicap: usize,
+    bench: BenchConfig,
 }
 
 #[derive(Debug)]
@@ -329,6 +372,13 @@ fn parse_args(args: &[String]) -> anyhow::Result<ExportConfig> {
     let mut strategy = StrategyType::Momentum;
     let mut num_snapshots = 200_000;
     let mut output_path = PathBuf::from("results/");
+    let mut registry = None;
+    let mut stream = None;
+    let mut flush_every = 1_000;
+    let mut options = ExportOptions::default();
+    let mut approaches: Vec<String> = MOMENTUM_APPROACHES.iter().map(|s| s.to_string()).collect();
+    let mut handicap = 0;
+    let mut bench = BenchConfig::default();
 
     let mut i = 1;
     while i < args.len() {
@@ -358,6 +408,73 @@ fn parse_args(args: &[String]) -> anyhow::Result<ExportConfig> {
                 output_path = PathBuf::from(&args[i + 1]);
                 i += 2;
             },
+            "--registry" | "-r" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --registry");
+                }
+                registry = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            },
+            "--stream" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --stream");
+                }
+                stream = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            },
+            "--flush-every" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --flush-every");
+                }
+                flush_every = args[i + 1].parse()?;
+                i += 2;
+            },
+            "--sample-every" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --sample-every");
+                }
+                options.sample_every = args[i + 1].parse()?;
+                i += 2;
+            },
+            "--approaches" | "-a" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --approaches");
+                }
+                approaches = args[i + 1].split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+                if approaches.is_empty() {
+                    anyhow::bail!("--approaches needs at least one approach");
+                }
+                i += 2;
+            },
+            "--handicap" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --handicap");
+                }
+                handicap = args[i + 1].parse()?;
+                i += 2;
+            },
+            "--warmup" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --warmup");
+                }
+                bench.warmup = args[i + 1].parse()?;
+                i += 2;
+            },
+            "--repeat" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --repeat");
+                }
+                bench.repetitions = args[i + 1].parse()?;
+                i += 2;
+            },
+            "--no-trades" => {
+                options.include_all_trades = false;
+                i += 1;
+            },
+            "--summary-only" => {
+                options.summary_only = true;
+                i += 1;
+            },
             "--help" | "-h" => {
                 print_usage();
                 std::process::exit(0);
@@ -374,6 +491,13 @@ fn parse_args(args: &[String]) -> anyhow::Result<ExportConfig> {
         strategy,
         num_snapshots,
         output_path,
+        registry,
+        stream,
+        flush_every,
+        options,
+        approaches,
+        handicap,
+        bench,
     })
 }
 
@@ -387,9 +511,28 @@ fn print_usage() {
     println!("                             Default: 200000");
     println!("  --output, -o <PATH>        Output file or directory");
     println!("                             Default: results/");
+    println!("  --registry, -r <PATH>      Append the run to an experiment registry (JSONL)");
+    println!("  --stream <PATH>            Append points and trades to an NDJSON file during the run");
+    println!("  --flush-every <NUM>        Flush the stream every NUM snapshots");
+    println!("                             Default: 1000");
+    println!("  --sample-every <NUM>       Timeseries sampling interval in snapshots");
+    println!("                             Default: 100");
+    println!("  --approaches, -a <LIST>    Comma-separated approaches for the performance comparison");
+    println!("                             ({}, {}", MOMENTUM_APPROACHES.join(", "), MARKET_MAKER_APPROACHES.join(", "));
+    println!("                             or any built-in strategy name)");
+    println!("                             Default: {}", MOMENTUM_APPROACHES.join(","));
+    println!("  --handicap <ITERATIONS>    Extra busy-work per update for the naive momentum approaches");
+    println!("                             Default: 0 (reported in the comparison output)");
+    println!("  --warmup <NUM>             Discarded warmup runs per comparison approach");
+    println!("                             Default: 1");
+    println!("  --repeat <NUM>             Measured runs per approach (mean/median/stddev are reported)");
+    println!("                             Default: 5");
+    println!("  --no-trades                Omit the full trade list (best/worst/recent are kept)");
+    println!("  --summary-only             Export summary, risk and metadata only");
     println!("  --help, -h                 Show this help message");
     println!();
     println!("Examples:");
     println!("  backtest_export --strategy momentum --snapshots 7200 --output momentum_2hr.json");
     println!("  backtest_export --strategy performance --snapshots 200000 --output results/performance_200k.json");
+    println!("  backtest_export --strategy performance --approaches {} --output results/mm_performance.json", MARKET_MAKER_APPROACHES.join(","));
 }
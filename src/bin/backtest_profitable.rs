@@ -79,6 +79,7 @@ fn main() -> anyhow::Result<()> {
         trade_size: 0.1,
         max_position: 1.0,        
         lookback: 500,            
+        ..MomentumConfig::default()
     };
 
     let mut strategy = MomentumStrategy::new(config);
@@ -7,11 +7,10 @@
 
 use rusthft::{
     SnapshotReader,
-    Position, Strategy, MarketMaker, MarketMakerConfig,
-    analytics::BacktestResult,
+    MarketMaker, MarketMakerConfig,
+    BacktestEngine, EngineConfig,
 };
 use std::path::Path;
-use std::time::Instant;
 
 fn main() -> anyhow::Result<()> {
     println!("╔══════════════════════════════════════════════════════════════╗");
@@ -73,9 +72,12 @@ fn main() -> anyhow::Result<()> {
         tick_size: 0.1,
         ..MarketMakerConfig::default()
     };
+    if let Some(warning) = config.tick_size_warning(&snapshots) {
+        println!("⚠️  {}", warning);
+    }
 
     let mut strategy = MarketMaker::new(config);
-    let mut position = Position::new();
+    let engine = BacktestEngine::new(EngineConfig::default());
 
     println!("Running backtest...");
     println!("Strategy details:");
@@ -83,25 +85,18 @@ fn main() -> anyhow::Result<()> {
     println!("  • We quote ASK at: best_ask + $0.10");
     println!("  • Market must move TO us for fills");
     println!();
-    let start = Instant::now();
 
-    for snapshot in &snapshots {
-        let trades = strategy.on_market_data(snapshot, &position);
-        for trade in trades {
-            position.execute_trade(trade);
-        }
-    }
-
-    let duration = start.elapsed();
-    println!("Completed in {:?}\n", duration);
+    let mut run = engine.run(&mut strategy, &snapshots);
+    println!("Completed in {:?}\n", run.result.timing.total_duration);
 
     
-    let final_price = last_mid;
-    let stats = strategy.stats();
-    let mut result = BacktestResult::new("Market Maker (Passive)".to_string());
-    result.calculate_from_position(&position, final_price, stats.updates_processed, stats.quotes_placed);
-    result.set_timing(duration, snapshots.len());
+    run.result.name = "Market Maker (Passive)".to_string();
+    let result = &run.result;
     result.print_report();
+    if let Some(quality) = &run.quote_quality {
+        quality.print();
+    }
+    run.markout_curve.print();
 
     
     
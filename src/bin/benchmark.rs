@@ -8,14 +8,35 @@ use rusthft::{
     Strategy, MarketMaker, MarketMakerConfig,
     NaiveMarketMaker, NaiveMarketMakerConfig,
 };
+use rusthft::analytics::{BenchConfig, BenchRunner, ComparisonApproach};
+use rusthft::engine::{BacktestEngine, EngineConfig};
 use rusthft::trivial_approach::{NaiveSnapshot, NaiveOrderBook};
+use rusthft::utils::affinity::ThreadPinning;
+use rusthft::utils::alloc::{AllocProbe, AllocStats, CountingAllocator};
 use std::path::Path;
 use std::time::Instant;
 
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
 fn main() -> Result<()> {
     println!("🏁 HFT Performance Benchmark: Optimized vs Naive\n");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
 
+    let (pinning, bench) = parse_args(&std::env::args().collect::<Vec<_>>())?;
+    if pinning != ThreadPinning::default() {
+        let outcome = pinning.apply(0);
+        match outcome.core {
+            Some(core) => println!("Pinned benchmark thread to core {}", core),
+            None if !pinning.cores.is_empty() => println!("WARNING: could not pin to core {:?}", pinning.cores),
+            None => {}
+        }
+        if pinning.high_priority {
+            println!("Scheduler priority raised: {}", if outcome.priority_raised { "yes" } else { "no (needs privileges)" });
+        }
+        println!();
+    }
+
     let data_path = Path::new("data/L2_processed.csv");
 
     if !data_path.exists() {
@@ -41,29 +62,28 @@ fn main() -> Result<()> {
     let naive_snapshots: Vec<NaiveSnapshot> = optimized_snapshots
         .iter()
         .map(|s| {
-            let mut values = Vec::with_capacity(40);
-            
-            values.push(s.bid_price_1); values.push(s.bid_qty_1);
-            values.push(s.bid_price_2); values.push(s.bid_qty_2);
-            values.push(s.bid_price_3); values.push(s.bid_qty_3);
-            values.push(s.bid_price_4); values.push(s.bid_qty_4);
-            values.push(s.bid_price_5); values.push(s.bid_qty_5);
-            values.push(s.bid_price_6); values.push(s.bid_qty_6);
-            values.push(s.bid_price_7); values.push(s.bid_qty_7);
-            values.push(s.bid_price_8); values.push(s.bid_qty_8);
-            values.push(s.bid_price_9); values.push(s.bid_qty_9);
-            values.push(s.bid_price_10); values.push(s.bid_qty_10);
-            
-            values.push(s.ask_price_1); values.push(s.ask_qty_1);
-            values.push(s.ask_price_2); values.push(s.ask_qty_2);
-            values.push(s.ask_price_3); values.push(s.ask_qty_3);
-            values.push(s.ask_price_4); values.push(s.ask_qty_4);
-            values.push(s.ask_price_5); values.push(s.ask_qty_5);
-            values.push(s.ask_price_6); values.push(s.ask_qty_6);
-            values.push(s.ask_price_7); values.push(s.ask_qty_7);
-            values.push(s.ask_price_8); values.push(s.ask_qty_8);
-            values.push(s.ask_price_9); values.push(s.ask_qty_9);
-            values.push(s.ask_price_10); values.push(s.ask_qty_10);
+            let values = vec![
+                s.bid_price_1, s.bid_qty_1,
+                s.bid_price_2, s.bid_qty_2,
+                s.bid_price_3, s.bid_qty_3,
+                s.bid_price_4, s.bid_qty_4,
+                s.bid_price_5, s.bid_qty_5,
+                s.bid_price_6, s.bid_qty_6,
+                s.bid_price_7, s.bid_qty_7,
+                s.bid_price_8, s.bid_qty_8,
+                s.bid_price_9, s.bid_qty_9,
+                s.bid_price_10, s.bid_qty_10,
+                s.ask_price_1, s.ask_qty_1,
+                s.ask_price_2, s.ask_qty_2,
+                s.ask_price_3, s.ask_qty_3,
+                s.ask_price_4, s.ask_qty_4,
+                s.ask_price_5, s.ask_qty_5,
+                s.ask_price_6, s.ask_qty_6,
+                s.ask_price_7, s.ask_qty_7,
+                s.ask_price_8, s.ask_qty_8,
+                s.ask_price_9, s.ask_qty_9,
+                s.ask_price_10, s.ask_qty_10,
+            ];
 
             NaiveSnapshot::from_csv_data(
                 s.timestamp_us,
@@ -81,7 +101,7 @@ fn main() -> Result<()> {
     let start = Instant::now();
     let mut book = OrderBook::new();
     for snap in &optimized_snapshots {
-        book.update(snap.clone());
+        book.update_ref(snap);
         let _ = book.best_bid();
         let _ = book.best_ask();
         let _ = book.spread();
@@ -169,8 +189,6 @@ fn main() -> Result<()> {
         tick_size: 0.1,
         ..MarketMakerConfig::default()
     };
-    let mut opt_mm = MarketMaker::new(config);
-    let mut opt_position = Position::new();
 
     
     let naive_config = NaiveMarketMakerConfig {
@@ -179,47 +197,34 @@ fn main() -> Result<()> {
         max_position: 2.0,
         tick_size: 0.1,
     };
-    let mut naive_mm = NaiveMarketMaker::new(naive_config);
-    let mut naive_position = Position::new();
-
-    
-    let start = Instant::now();
-    for snap in &optimized_snapshots {
-        let trades = opt_mm.on_market_data(snap, &opt_position);
-        for trade in trades {
-            opt_position.execute_trade(trade);
-        }
-    }
-    let opt_strategy_time = start.elapsed();
 
-    
-    let start = Instant::now();
-    for snap in &optimized_snapshots {
-        let trades = naive_mm.on_market_data(snap, &naive_position);
-        for trade in trades {
-            naive_position.execute_trade(trade);
-        }
-    }
-    let naive_strategy_time = start.elapsed();
+    let optimized_approach = {
+        let config = config.clone();
+        ComparisonApproach::new("Optimized Market Maker", move || Box::new(MarketMaker::new(config.clone())))
+    };
+    let naive_approach = {
+        let naive_config = naive_config.clone();
+        ComparisonApproach::new("Naive Market Maker", move || Box::new(NaiveMarketMaker::new(naive_config.clone())))
+    };
 
-    let opt_strat_ns = opt_strategy_time.as_nanos() / optimized_snapshots.len() as u128;
-    let naive_strat_ns = naive_strategy_time.as_nanos() / optimized_snapshots.len() as u128;
-    let strategy_speedup = naive_strategy_time.as_nanos() as f64 / opt_strategy_time.as_nanos() as f64;
+    let runner = BenchRunner::new(bench);
+    let (opt_result, opt_stats) = runner.run(&optimized_approach, &optimized_snapshots);
+    let (naive_result, naive_stats) = runner.run(&naive_approach, &optimized_snapshots);
+    let strategy_speedup = naive_stats.median_ns / opt_stats.median_ns.max(f64::EPSILON);
 
-    println!("Results (10,000 iterations):\n");
+    println!("Results ({} updates, {} warmup + {} measured runs):\n",
+             optimized_snapshots.len(), opt_stats.warmup, opt_stats.repetitions);
     println!("   Optimized Market Maker:");
-    println!("      Total Time:  {:?}", opt_strategy_time);
-    println!("      Per Update:  {} ns", opt_strat_ns);
-    println!("      Trades:      {}", opt_mm.stats().trades_generated);
-    println!("      Quotes:      {}", opt_mm.stats().quotes_placed);
+    println!("      Per Update:  {:.2} ns median, {:.2} ± {:.2} ns mean", opt_stats.median_ns, opt_stats.mean_ns, opt_stats.stddev_ns);
+    println!("      Trades:      {}", opt_result.metrics.total_trades);
+    println!("      Quotes:      {}", opt_result.metrics.quotes_placed);
     println!();
     println!("   Naive Market Maker (HashMap):");
-    println!("      Total Time:  {:?}", naive_strategy_time);
-    println!("      Per Update:  {} ns", naive_strat_ns);
-    println!("      Trades:      {}", naive_mm.stats().trades_generated);
-    println!("      Quotes:      {}", naive_mm.stats().quotes_placed);
+    println!("      Per Update:  {:.2} ns median, {:.2} ± {:.2} ns mean", naive_stats.median_ns, naive_stats.mean_ns, naive_stats.stddev_ns);
+    println!("      Trades:      {}", naive_result.metrics.total_trades);
+    println!("      Quotes:      {}", naive_result.metrics.quotes_placed);
     println!();
-    println!("   Speedup:     {:.2}× faster", strategy_speedup);
+    println!("   Speedup:     {:.2}× faster (median)", strategy_speedup);
     println!();
     println!("   Why the difference?");
     println!("      • Optimized: Direct struct fields (config.spread_ticks)");
@@ -238,6 +243,122 @@ fn main() -> Result<()> {
     println!("   Difference:            ~{}× more memory",
              naive_snap_size as f64 / opt_snap_size as f64);
 
+    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("BENCHMARK 5: Datetime Column");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let parse = |reader: SnapshotReader| -> Result<(std::time::Duration, Vec<L2Snapshot>)> {
+        let mut reader = reader;
+        let mut snapshots = Vec::with_capacity(optimized_snapshots.len());
+        let start = Instant::now();
+        while snapshots.len() < optimized_snapshots.len() {
+            match reader.next_snapshot()? {
+                Some(snap) => snapshots.push(snap),
+                None => break,
+            }
+        }
+        Ok((start.elapsed(), snapshots))
+    };
+    let (with_parse, with_datetime) = parse(SnapshotReader::new(data_path)?)?;
+    let (lean_parse, lean) = parse(SnapshotReader::new(data_path)?.without_datetime()?)?;
+
+    let start = Instant::now();
+    let cloned = with_datetime.clone();
+    let with_clone = start.elapsed();
+    drop(cloned);
+    let start = Instant::now();
+    let cloned = lean.clone();
+    let lean_clone = start.elapsed();
+    drop(cloned);
+
+    let per_row = |d: std::time::Duration, n: usize| d.as_nanos() / n.max(1) as u128;
+    println!("   {:<22} {:>12} {:>12}", "", "Parse/row", "Clone/row");
+    println!("   {:<22} {:>9} ns {:>9} ns", "With datetime",
+             per_row(with_parse, with_datetime.len()), per_row(with_clone, with_datetime.len()));
+    println!("   {:<22} {:>9} ns {:>9} ns", "Without datetime",
+             per_row(lean_parse, lean.len()), per_row(lean_clone, lean.len()));
+    println!("   Parse speedup:  {:.2}×", with_parse.as_nanos() as f64 / lean_parse.as_nanos().max(1) as f64);
+    println!("   Clone speedup:  {:.2}×", with_clone.as_nanos() as f64 / lean_clone.as_nanos().max(1) as f64);
+    if let Some(first) = lean.first() {
+        println!("   Lazy datetime:  {}", first.display_time());
+    }
+
+    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("BENCHMARK 6: Allocations");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let probe = AllocProbe::start();
+    let mut book = OrderBook::new();
+    for snap in &optimized_snapshots {
+        book.update_ref(snap);
+    }
+    let opt_book_alloc = probe.finish();
+
+    let probe = AllocProbe::start();
+    let mut naive_book = NaiveOrderBook::new();
+    for snap in &naive_snapshots {
+        naive_book.update(snap.clone());
+    }
+    let naive_book_alloc = probe.finish();
+
+    let probe = AllocProbe::start();
+    let mut opt_mm = MarketMaker::new(config.clone());
+    let mut opt_position = Position::new();
+    let mut trades = Vec::new();
+    for snap in &optimized_snapshots {
+        trades.clear();
+        opt_mm.on_market_data_into(snap, &opt_position, &mut trades);
+        for trade in trades.drain(..) {
+            opt_position.execute_trade(trade);
+        }
+    }
+    let opt_mm_alloc = probe.finish();
+
+    let probe = AllocProbe::start();
+    let mut naive_mm = NaiveMarketMaker::new(naive_config);
+    let mut naive_position = Position::new();
+    for snap in &optimized_snapshots {
+        for trade in naive_mm.on_market_data(snap, &naive_position) {
+            naive_position.execute_trade(trade);
+        }
+    }
+    let naive_mm_alloc = probe.finish();
+
+    let updates = optimized_snapshots.len();
+    let row = |label: &str, stats: &AllocStats| {
+        println!("   {:<24} {:>10} {:>10.2} {:>14} {:>12}",
+                 label, stats.allocations, stats.per_item(updates), stats.bytes_allocated, stats.peak_bytes);
+    };
+    println!("   {:<24} {:>10} {:>10} {:>14} {:>12}", "", "Allocs", "Per Op", "Bytes", "Peak");
+    row("Optimized book", &opt_book_alloc);
+    row("Naive book", &naive_book_alloc);
+    row("Optimized market maker", &opt_mm_alloc);
+    row("Naive market maker", &naive_mm_alloc);
+
+    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("BENCHMARK 7: Streaming Backtest");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let engine = BacktestEngine::new(EngineConfig::default());
+    let limit = Some(updates);
+    let start = Instant::now();
+    let inline = engine.run_source(&mut MarketMaker::new(config.clone()), &mut SnapshotReader::new(data_path)?, limit)?;
+    let inline_time = start.elapsed();
+    let path = data_path.to_path_buf();
+    let start = Instant::now();
+    let prefetched = engine.run_prefetched(
+        &mut MarketMaker::new(config),
+        &path.display().to_string(),
+        move || SnapshotReader::new(&path),
+        limit,
+    )?;
+    let prefetched_time = start.elapsed();
+
+    println!("   {:<22} {:>12} {:>10}", "", "Time", "Trades");
+    println!("   {:<22} {:>9.2} ms {:>10}", "Parse inline", inline_time.as_secs_f64() * 1000.0, inline.result.metrics.total_trades);
+    println!("   {:<22} {:>9.2} ms {:>10}", "Prefetch thread", prefetched_time.as_secs_f64() * 1000.0, prefetched.result.metrics.total_trades);
+    println!("   Overlap speedup: {:.2}×", inline_time.as_secs_f64() / prefetched_time.as_secs_f64().max(1e-9));
+
     println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("Summary");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
@@ -253,3 +374,43 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+fn parse_args(args: &[String]) -> Result<(ThreadPinning, BenchConfig)> {
+    let mut pinning = ThreadPinning::default();
+    let mut bench = BenchConfig::default();
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--pin-core" => {
+                let Some(value) = args.get(i + 1) else {
+                    anyhow::bail!("Missing value for --pin-core");
+                };
+                pinning.cores = vec![value.parse()?];
+                i += 2;
+            },
+            "--high-priority" => {
+                pinning.high_priority = true;
+                i += 1;
+            },
+            "--warmup" => {
+                let Some(value) = args.get(i + 1) else {
+                    anyhow::bail!("Missing value for --warmup");
+                };
+                bench.warmup = value.parse()?;
+                i += 2;
+            },
+            "--repeat" => {
+                let Some(value) = args.get(i + 1) else {
+                    anyhow::bail!("Missing value for --repeat");
+                };
+                bench.repetitions = value.parse()?;
+                i += 2;
+            },
+            other => anyhow::bail!(
+                "Unknown argument: {} (expected --pin-core <CORE>, --high-priority, --warmup <NUM> or --repeat <NUM>)",
+                other
+            ),
+        }
+    }
+    Ok((pinning, bench))
+}
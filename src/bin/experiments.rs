@@ -0,0 +1,152 @@
+use rusthft::engine::{
+    capacity_sweep, latency_sweep, print_latency_sweep, EngineConfig, DEFAULT_LATENCIES_US, DEFAULT_SIZE_SCALES,
+};
+use rusthft::experiments::{print_comparison, print_runs, ExperimentRegistry};
+use rusthft::market_data::SnapshotReader;
+use rusthft::strategy::StrategyFactory;
+use std::path::{Path, PathBuf};
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+
+    let mut registry_path = PathBuf::from("results/experiments.jsonl");
+    let mut positional = Vec::new();
+    let mut latencies_us = DEFAULT_LATENCIES_US.to_vec();
+    let mut output_path = PathBuf::from("results/capacity.json");
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--registry" | "-r" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --registry");
+                }
+                registry_path = PathBuf::from(&args[i + 1]);
+                i += 2;
+            }
+            "--latencies" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --latencies");
+                }
+                latencies_us = args[i + 1]
+                    .split(',')
+                    .map(|value| value.trim().parse())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| anyhow::anyhow!("Invalid --latencies value: {}", args[i + 1]))?;
+                i += 2;
+            }
+            "--output" | "-o" => {
+                if i + 1 >= args.len() {
+                    anyhow::bail!("Missing value for --output");
+                }
+                output_path = PathBuf::from(&args[i + 1]);
+                i += 2;
+            }
+            "--help" | "-h" => {
+                print_usage();
+                return Ok(());
+            }
+            other => {
+                positional.push(other.to_string());
+                i += 1;
+            }
+        }
+    }
+
+    let registry = ExperimentRegistry::open(&registry_path);
+
+    match positional.first().map(String::as_str) {
+        Some("list") | None => {
+            let records = registry.load()?;
+            println!("{} runs in {}\n", records.len(), registry.path().display());
+            print_runs(&records);
+        }
+        Some("show") | Some("compare") => {
+            if positional.len() < 2 {
+                anyhow::bail!("Expected at least one run id or config hash");
+            }
+            let mut records = Vec::new();
+            for id in &positional[1..] {
+                let found = registry.find(id)?;
+                if found.is_empty() {
+                    anyhow::bail!("No run matches '{}'", id);
+                }
+                records.extend(found);
+            }
+            if positional[0] == "show" {
+                for record in &records {
+                    println!("{}", serde_json::to_string_pretty(record)?);
+                }
+            } else {
+                print_comparison(&records);
+            }
+        }
+        Some("latency") => {
+            if positional.len() < 3 {
+                anyhow::bail!("Expected a data file and a strategy name");
+            }
+            let params = positional.get(3).map(String::as_str).unwrap_or("{}");
+            run_latency_sweep(Path::new(&positional[1]), &positional[2], params, &latencies_us)?;
+        }
+        Some("capacity") => {
+            if positional.len() < 3 {
+                anyhow::bail!("Expected a data file and a strategy name");
+            }
+            let params = positional.get(3).map(String::as_str).unwrap_or("{}");
+            run_capacity_sweep(Path::new(&positional[1]), &positional[2], params, &output_path)?;
+        }
+        Some(other) => {
+            print_usage();
+            anyhow::bail!("Unknown command: {}", other);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_latency_sweep(data_path: &Path, strategy: &str, params: &str, latencies_us: &[u64]) -> anyhow::Result<()> {
+    let snapshots = SnapshotReader::read_all_parallel(data_path, 0)?;
+    let factory = StrategyFactory::with_builtins();
+    let job = factory.job(strategy, strategy, serde_json::from_str(params)?)?;
+
+    println!("Sweeping {} over {} snapshots from {}", strategy, snapshots.len(), data_path.display());
+    let points = latency_sweep(&EngineConfig::default(), &job, &snapshots, latencies_us);
+    print_latency_sweep(&points);
+    Ok(())
+}
+
+fn run_capacity_sweep(data_path: &Path, strategy: &str, params: &str, output_path: &Path) -> anyhow::Result<()> {
+    let snapshots = SnapshotReader::read_all_parallel(data_path, 0)?;
+    let factory = StrategyFactory::with_builtins();
+    let job = factory.job(strategy, strategy, serde_json::from_str(params)?)?;
+
+    let curve = capacity_sweep(&EngineConfig::default(), &job, &snapshots, &DEFAULT_SIZE_SCALES);
+    curve.print();
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    curve.to_file(output_path)?;
+    println!("Capacity curve written to {}", output_path.display());
+    Ok(())
+}
+
+fn print_usage() {
+    println!("Usage: experiments [--registry <PATH>] <COMMAND>");
+    println!();
+    println!("Commands:");
+    println!("  list                       List all recorded runs (default)");
+    println!("  show <ID>...               Print full records for runs or config hashes");
+    println!("  compare <ID> <ID>...       Compare metrics and parameters side by side");
+    println!("  latency <DATA> <STRATEGY> [PARAMS_JSON]");
+    println!("                             Rerun a strategy across simulated latencies");
+    println!("  capacity <DATA> <STRATEGY> [PARAMS_JSON]");
+    println!("                             Sweep trade size and write the capacity curve");
+    println!();
+    println!("Options:");
+    println!("  --registry, -r <PATH>      Registry file");
+    println!("                             Default: results/experiments.jsonl");
+    println!("  --latencies <US,US,...>    Latencies for the latency command");
+    println!("                             Default: 0,100,1000,10000");
+    println!("  --output, -o <PATH>        Output file for the capacity command");
+    println!("                             Default: results/capacity.json");
+}
@@ -3,6 +3,7 @@
 
 
 use rusthft::SnapshotReader;
+use rusthft::market_data::TradeTape;
 use std::path::Path;
 
 fn main() -> anyhow::Result<()> {
@@ -112,6 +113,9 @@ fn main() -> anyhow::Result<()> {
     println!("Moves > $0.10:      {} times", big_moves);
     println!();
 
+    TradeTape::from_snapshots(&snapshots).print();
+    println!();
+
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("ANALYSIS");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use rusthft::utils::csv_processor::{add_headers, remap_columns, ColumnMapping};
+use std::path::Path;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let (paths, mapping) = match args.iter().position(|a| a == "--mapping") {
+        Some(at) if at + 1 < args.len() => {
+            let mut paths = args[1..at].to_vec();
+            paths.extend_from_slice(&args[at + 2..]);
+            (paths, Some(ColumnMapping::from_file(Path::new(&args[at + 1]))?))
+        }
+        _ => (args[1..].to_vec(), None),
+    };
+    if paths.len() != 2 {
+        println!("Usage: preprocess <input.csv> <output.csv> [--mapping <mapping.json>]");
+        println!();
+        println!("Without --mapping the input is assumed to be the raw 43-column layout.");
+        println!("Mapping keys: columns, skip_rows, delimiter, timestamp, timestamp_unit");
+        println!("  (seconds | millis | micros | nanos), datetime, row_index, depth, level_base,");
+        println!("  bid_price, bid_qty, ask_price, ask_qty (patterns using {{level}})");
+        return Ok(());
+    }
+
+    let input_path = Path::new(&paths[0]);
+    let output_path = Path::new(&paths[1]);
+    match mapping {
+        Some(mapping) => remap_columns(input_path, output_path, &mapping)?,
+        None => add_headers(input_path, output_path)?,
+    };
+
+    Ok(())
+}
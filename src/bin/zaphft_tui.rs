@@ -0,0 +1,285 @@
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline};
+use ratatui::{DefaultTerminal, Frame};
+use rusthft::{
+    L2Snapshot, MarketMaker, MarketMakerConfig, OrderBook, Position, Side, SnapshotReader,
+    Strategy, Trade,
+};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+const PNL_HISTORY: usize = 512;
+const TAPE_LENGTH: usize = 50;
+const LADDER_DEPTH: usize = 10;
+
+struct App {
+    snapshots: Vec<L2Snapshot>,
+    index: usize,
+    strategy: MarketMaker,
+    position: Position,
+    book: OrderBook<'static>,
+    pnl_history: VecDeque<f64>,
+    tape: VecDeque<Trade>,
+    steps_per_frame: usize,
+    paused: bool,
+}
+
+impl App {
+    fn new(snapshots: Vec<L2Snapshot>, config: MarketMakerConfig) -> Self {
+        Self {
+            snapshots,
+            index: 0,
+            strategy: MarketMaker::new(config),
+            position: Position::new(),
+            book: OrderBook::new(),
+            pnl_history: VecDeque::with_capacity(PNL_HISTORY),
+            tape: VecDeque::with_capacity(TAPE_LENGTH),
+            steps_per_frame: 100,
+            paused: false,
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.index >= self.snapshots.len()
+    }
+
+    fn step(&mut self) {
+        let Some(snapshot) = self.snapshots.get(self.index) else {
+            return;
+        };
+
+        for trade in self.strategy.on_market_data(snapshot, &self.position) {
+            if self.tape.len() == TAPE_LENGTH {
+                self.tape.pop_back();
+            }
+            self.tape.push_front(trade.clone());
+            self.position.execute_trade(trade);
+        }
+
+        self.book.update(snapshot.clone());
+        self.index += 1;
+    }
+
+    fn tick(&mut self) {
+        if self.paused {
+            return;
+        }
+        for _ in 0..self.steps_per_frame {
+            if self.finished() {
+                break;
+            }
+            self.step();
+        }
+        self.record_pnl();
+    }
+
+    fn record_pnl(&mut self) {
+        let Some(mid) = self.book.mid_price() else {
+            return;
+        };
+        if self.pnl_history.len() == PNL_HISTORY {
+            self.pnl_history.pop_front();
+        }
+        self.pnl_history.push_back(self.position.total_pnl(mid));
+    }
+
+    fn current_pnl(&self) -> f64 {
+        self.pnl_history.back().copied().unwrap_or(0.0)
+    }
+
+    fn sparkline_data(&self) -> Vec<u64> {
+        let min = self.pnl_history.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = self.pnl_history.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = (max - min).max(1e-9);
+        self.pnl_history
+            .iter()
+            .map(|pnl| ((pnl - min) / range * 100.0) as u64)
+            .collect()
+    }
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let mut data_path = PathBuf::from("data/L2_processed.csv");
+    let mut num_snapshots = 100_000;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--data" => {
+                data_path = PathBuf::from(args.get(i + 1).ok_or_else(|| anyhow::anyhow!("--data needs a path"))?);
+                i += 1;
+            }
+            "--snapshots" => {
+                num_snapshots = args
+                    .get(i + 1)
+                    .ok_or_else(|| anyhow::anyhow!("--snapshots needs a count"))?
+                    .parse()?;
+                i += 1;
+            }
+            other => anyhow::bail!("unknown argument: {}", other),
+        }
+        i += 1;
+    }
+
+    if !data_path.exists() {
+        println!("❌ Processed data not found: {}", data_path.display());
+        return Ok(());
+    }
+
+    let mut reader = SnapshotReader::new(&data_path)?;
+    let mut snapshots = Vec::with_capacity(num_snapshots);
+    for _ in 0..num_snapshots {
+        match reader.next_snapshot()? {
+            Some(snapshot) => snapshots.push(snapshot),
+            None => break,
+        }
+    }
+
+    let config = MarketMakerConfig {
+        spread_ticks: 0.5,
+        tick_size: 0.1,
+        ..MarketMakerConfig::default()
+    };
+    if let Some(warning) = config.tick_size_warning(&snapshots) {
+        println!("⚠️  {}", warning);
+    }
+    let mut app = App::new(snapshots, config);
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app);
+    ratatui::restore();
+    result?;
+
+    let final_price = app.book.mid_price().unwrap_or(0.0);
+    app.position.stats(final_price).print();
+    app.strategy.stats().print();
+
+    Ok(())
+}
+
+fn run(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    loop {
+        app.tick();
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if event::poll(FRAME_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => app.paused = !app.paused,
+                    KeyCode::Char('n') if app.paused => {
+                        app.step();
+                        app.record_pnl();
+                    }
+                    KeyCode::Char('+') | KeyCode::Char('=') => {
+                        app.steps_per_frame = (app.steps_per_frame * 2).min(100_000);
+                    }
+                    KeyCode::Char('-') => {
+                        app.steps_per_frame = (app.steps_per_frame / 2).max(1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(16), Constraint::Length(8)])
+        .split(frame.area());
+
+    let status = if app.finished() {
+        "done"
+    } else if app.paused {
+        "paused"
+    } else {
+        "running"
+    };
+    let header = Paragraph::new(format!(
+        " Snapshot {}/{}  |  {}  |  {} snapshots/frame  |  q quit, space pause, n step, +/- speed",
+        app.index,
+        app.snapshots.len(),
+        status,
+        app.steps_per_frame
+    ))
+    .block(Block::default().borders(Borders::ALL).title(" zaphft "));
+    frame.render_widget(header, rows[0]);
+
+    let middle = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(rows[1]);
+
+    let ladder = Paragraph::new(app.book.render_ladder(LADDER_DEPTH))
+        .block(Block::default().borders(Borders::ALL).title(" Order Book "));
+    frame.render_widget(ladder, middle[0]);
+
+    let side_panels = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(8), Constraint::Length(7), Constraint::Min(3)])
+        .split(middle[1]);
+
+    let mid = app.book.mid_price().unwrap_or(0.0);
+    let position = Paragraph::new(vec![
+        Line::from(format!("Quantity:     {:.4}", app.position.quantity)),
+        Line::from(format!("Avg Entry:    ${:.2}", app.position.avg_entry_price)),
+        Line::from(format!("Realized:     ${:.2}", app.position.realized_pnl)),
+        Line::from(format!("Unrealized:   ${:.2}", app.position.unrealized_pnl(mid))),
+        Line::from(format!("Total PnL:    ${:.2}", app.current_pnl())),
+        Line::from(format!("Trades:       {}", app.position.trade_count)),
+    ])
+    .block(Block::default().borders(Borders::ALL).title(" Position "));
+    frame.render_widget(position, side_panels[0]);
+
+    let stats = app.strategy.stats();
+    let stats_panel = Paragraph::new(vec![
+        Line::from(stats.name.clone()),
+        Line::from(format!("Updates:      {}", stats.updates_processed)),
+        Line::from(format!("Trades:       {}", stats.trades_generated)),
+        Line::from(format!("Quotes:       {}", stats.quotes_placed)),
+    ])
+    .block(Block::default().borders(Borders::ALL).title(" Strategy "));
+    frame.render_widget(stats_panel, side_panels[1]);
+
+    let tape: Vec<ListItem> = app
+        .tape
+        .iter()
+        .map(|trade| {
+            let (label, color) = match trade.side {
+                Side::Bid => ("BUY ", Color::Green),
+                Side::Ask => ("SELL", Color::Red),
+            };
+            ListItem::new(format!(
+                "{} {:>10.2} x {:.4}  @ {}",
+                label, trade.price, trade.quantity, trade.timestamp_us
+            ))
+            .style(Style::default().fg(color))
+        })
+        .collect();
+    let tape = List::new(tape).block(Block::default().borders(Borders::ALL).title(" Trade Tape "));
+    frame.render_widget(tape, side_panels[2]);
+
+    let pnl_color = if app.current_pnl() >= 0.0 { Color::Green } else { Color::Red };
+    let data = app.sparkline_data();
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" PnL ${:.2} ", app.current_pnl())),
+        )
+        .data(&data)
+        .style(Style::default().fg(pnl_color));
+    frame.render_widget(sparkline, rows[2]);
+}
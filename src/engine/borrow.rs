@@ -0,0 +1,44 @@
+use crate::execution::Position;
+
+const US_PER_YEAR: f64 = 365.0 * 24.0 * 3_600.0 * 1_000_000.0;
+
+
+
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BorrowFee {
+    
+    pub annual_rate: f64,
+}
+
+impl BorrowFee {
+    pub fn new(annual_rate: f64) -> Self {
+        Self { annual_rate }
+    }
+
+    
+    pub fn accrue(&self, position: &Position, price: f64, elapsed_us: u64) -> f64 {
+        if position.quantity >= 0.0 || elapsed_us == 0 {
+            return 0.0;
+        }
+        -position.quantity * price * self.annual_rate * elapsed_us as f64 / US_PER_YEAR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Side, Trade};
+
+    #[test]
+    fn test_accrues_only_while_short() {
+        let fee = BorrowFee::new(0.0365);
+        let day_us = 24 * 3_600 * 1_000_000;
+        let mut position = Position::new();
+        position.execute_trade(Trade::new(Side::Bid, 100.0, 2.0, 0));
+        assert_eq!(fee.accrue(&position, 100.0, day_us), 0.0);
+
+        position.execute_trade(Trade::new(Side::Ask, 100.0, 4.0, 0));
+        assert!((fee.accrue(&position, 100.0, day_us) - 0.02).abs() < 1e-12);
+    }
+}
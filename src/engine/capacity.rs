@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use super::{BacktestEngine, EngineConfig, StrategyJob};
+use crate::execution::{AckEvent, PositionView};
+use crate::market_data::DerivedValues;
+use crate::strategy::{Strategy, StrategyStats};
+use crate::types::{L2Snapshot, QuoteEvent, Side, Trade};
+
+pub const DEFAULT_SIZE_SCALES: [f64; 7] = [0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0];
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityPoint {
+    pub scale: f64,
+    pub total_pnl: f64,
+    pub total_trades: usize,
+    pub volume: f64,
+    pub pnl_per_unit: f64,
+    
+    pub avg_slippage_bps: f64,
+    
+    pub fill_fraction: f64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityCurve {
+    pub strategy: String,
+    pub points: Vec<CapacityPoint>,
+    
+    pub capacity_scale: Option<f64>,
+}
+
+impl CapacityCurve {
+    pub fn to_file(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn print(&self) {
+        println!("\n📦 Capacity: {}", self.strategy);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   {:>7} {:>12} {:>10} {:>12} {:>10} {:>8}", "Scale", "PnL", "Volume", "PnL/Unit", "Slip bps", "Filled");
+        for point in &self.points {
+            println!(
+                "   {:>6}x {:>12.2} {:>10.4} {:>12.4} {:>10.3} {:>7.1}%",
+                point.scale,
+                point.total_pnl,
+                point.volume,
+                point.pnl_per_unit,
+                point.avg_slippage_bps,
+                point.fill_fraction * 100.0
+            );
+        }
+        match self.capacity_scale {
+            Some(scale) => println!("   Capacity:          {}x", scale),
+            None => println!("   Capacity:          not profitable at any scale"),
+        }
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+
+
+pub fn capacity_sweep(
+    config: &EngineConfig,
+    job: &StrategyJob,
+    snapshots: &[L2Snapshot],
+    scales: &[f64],
+) -> CapacityCurve {
+    let engine = BacktestEngine::new(config.clone());
+    let mut strategy_name = job.label.clone();
+    let points: Vec<CapacityPoint> = scales
+        .iter()
+        .map(|&scale| {
+            let mut scaled = ScaledStrategy::new(job.build(), scale);
+            let run = engine.run(&mut scaled, snapshots);
+            strategy_name = scaled.inner.name().to_string();
+
+            let volume: f64 = run.position.trades().iter().map(|t| t.quantity).sum();
+            CapacityPoint {
+                scale,
+                total_pnl: run.result.metrics.total_pnl,
+                total_trades: run.result.metrics.total_trades,
+                volume,
+                pnl_per_unit: if volume > 0.0 { run.result.metrics.total_pnl / volume } else { 0.0 },
+                avg_slippage_bps: if scaled.filled > 0.0 { scaled.slippage_bps_qty / scaled.filled } else { 0.0 },
+                fill_fraction: if scaled.requested > 0.0 { scaled.filled / scaled.requested } else { 0.0 },
+            }
+        })
+        .collect();
+
+    let capacity_scale = points
+        .iter()
+        .filter(|p| p.total_pnl > 0.0)
+        .map(|p| p.scale)
+        .fold(None, |best: Option<f64>, scale| Some(best.map_or(scale, |b| b.max(scale))));
+
+    CapacityCurve {
+        strategy: strategy_name,
+        points,
+        capacity_scale,
+    }
+}
+
+
+
+struct ScaledStrategy {
+    inner: Box<dyn Strategy + Send>,
+    scale: f64,
+    requested: f64,
+    filled: f64,
+    slippage_bps_qty: f64,
+}
+
+impl ScaledStrategy {
+    fn new(inner: Box<dyn Strategy + Send>, scale: f64) -> Self {
+        Self {
+            inner,
+            scale,
+            requested: 0.0,
+            filled: 0.0,
+            slippage_bps_qty: 0.0,
+        }
+    }
+
+    fn scale_trades(&mut self, snapshot: &L2Snapshot, trades: Vec<Trade>) -> Vec<Trade> {
+        trades
+            .into_iter()
+            .filter_map(|mut trade| {
+                let (touch, levels) = match trade.side {
+                    Side::Bid => (snapshot.best_ask(), snapshot.asks()),
+                    Side::Ask => (snapshot.best_bid(), snapshot.bids()),
+                };
+                let requested = trade.quantity * self.scale;
+                self.requested += requested;
+
+                let mut remaining = requested;
+                let mut notional = 0.0;
+                for level in &levels {
+                    let take = remaining.min(level.quantity);
+                    notional += take * level.price;
+                    remaining -= take;
+                    if remaining <= 0.0 {
+                        break;
+                    }
+                }
+                let quantity = requested - remaining.max(0.0);
+                if quantity <= 0.0 || touch <= 0.0 {
+                    return None;
+                }
+
+                let impact = match trade.side {
+                    Side::Bid => notional / quantity - touch,
+                    Side::Ask => touch - notional / quantity,
+                };
+                trade.price += match trade.side {
+                    Side::Bid => impact,
+                    Side::Ask => -impact,
+                };
+                trade.quantity = quantity;
+                self.filled += quantity;
+                self.slippage_bps_qty += impact / touch * 10_000.0 * quantity;
+                Some(trade)
+            })
+            .collect()
+    }
+}
+
+impl Strategy for ScaledStrategy {
+    fn on_market_data(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) -> Vec<Trade> {
+        let trades = self.inner.on_market_data(snapshot, position);
+        self.scale_trades(snapshot, trades)
+    }
+
+    fn on_market_data_derived(&mut self, snapshot: &L2Snapshot, derived: &DerivedValues, position: &dyn PositionView) -> Vec<Trade> {
+        let trades = self.inner.on_market_data_derived(snapshot, derived, position);
+        self.scale_trades(snapshot, trades)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn stats(&self) -> StrategyStats {
+        self.inner.stats()
+    }
+
+    fn set_quote_tracking(&mut self, enabled: bool) {
+        self.inner.set_quote_tracking(enabled);
+    }
+
+    fn drain_quote_events(&mut self, events: &mut Vec<QuoteEvent>) {
+        self.inner.drain_quote_events(events);
+    }
+
+    fn drain_ack_events(&mut self, events: &mut Vec<AckEvent>) {
+        self.inner.drain_ack_events(events);
+    }
+
+    fn on_halt(&mut self, halted: bool, timestamp_us: u64) {
+        self.inner.on_halt(halted, timestamp_us);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::momentum::{MomentumConfig, MomentumStrategy};
+    use crate::types::PriceLevel;
+
+    fn snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(
+            0,
+            timestamp_us,
+            String::new(),
+            &[PriceLevel::new(mid - 0.05, 1.0), PriceLevel::new(mid - 0.5, 1.0)],
+            &[PriceLevel::new(mid + 0.05, 1.0), PriceLevel::new(mid + 0.5, 1.0)],
+        )
+    }
+
+    #[test]
+    fn test_capacity_sweep_adds_depth_slippage() {
+        let snapshots: Vec<L2Snapshot> = (0..300)
+            .map(|i| snapshot(i * 1_000, 100.0 + (i as f64 * 0.1).sin() * 3.0))
+            .collect();
+        let job = StrategyJob::new("momentum", || {
+            Box::new(MomentumStrategy::new(MomentumConfig {
+                trigger_threshold: 0.2,
+                lookback: 5,
+                trade_size: 0.5,
+                max_position: 100.0,
+                ..Default::default()
+            }))
+        });
+
+        let curve = capacity_sweep(&EngineConfig::default(), &job, &snapshots, &[1.0, 3.0, 10.0]);
+
+        assert_eq!(curve.points.len(), 3);
+        let (base, large, huge) = (&curve.points[0], &curve.points[1], &curve.points[2]);
+        assert!(base.volume > 0.0);
+        assert_eq!((base.avg_slippage_bps, base.fill_fraction), (0.0, 1.0));
+        assert!(large.avg_slippage_bps > 0.0);
+        assert!(huge.fill_fraction < 1.0);
+        assert!(large.pnl_per_unit < base.pnl_per_unit);
+    }
+}
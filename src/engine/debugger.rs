@@ -0,0 +1,268 @@
+use super::{BacktestEngine, RunState};
+use crate::execution::Position;
+use crate::market_data::DerivedValues;
+use crate::strategy::Strategy;
+use crate::types::{L2Snapshot, Trade};
+use std::collections::BTreeSet;
+
+type Predicate = Box<dyn Fn(&Position, &L2Snapshot) -> bool>;
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopReason {
+    Breakpoint(usize),
+    Watch(String),
+    Finished,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct DebugFrame {
+    pub index: usize,
+    pub timestamp_us: u64,
+    pub best_bid: f64,
+    pub best_ask: f64,
+    pub mid_price: f64,
+    pub position_qty: f64,
+    pub realized_pnl: f64,
+    pub total_pnl: f64,
+    pub trades: Vec<Trade>,
+    pub strategy_state: String,
+}
+
+impl DebugFrame {
+    pub fn print(&self) {
+        println!("\n🔎 Snapshot #{} @ {}", self.index, self.timestamp_us);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   Bid / Ask:   ${:.2} / ${:.2}", self.best_bid, self.best_ask);
+        println!("   Mid Price:   ${:.2}", self.mid_price);
+        println!("   Position:    {:.4}", self.position_qty);
+        println!("   Realized:    ${:.2}", self.realized_pnl);
+        println!("   Total PnL:   ${:.2}", self.total_pnl);
+        for trade in &self.trades {
+            println!("   Fill:        {:?} {:.4} @ ${:.2}", trade.side, trade.quantity, trade.price);
+        }
+        if !self.strategy_state.is_empty() {
+            println!("   ───────────────────────────────────────");
+            for line in self.strategy_state.lines() {
+                println!("   {}", line);
+            }
+        }
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+
+
+pub struct Debugger<'a, S: Strategy + ?Sized> {
+    engine: BacktestEngine,
+    strategy: &'a mut S,
+    snapshots: &'a [L2Snapshot],
+    state: RunState,
+    index: usize,
+    last_trades: Vec<Trade>,
+    breakpoints: BTreeSet<usize>,
+    watches: Vec<(String, Predicate)>,
+}
+
+impl<'a, S: Strategy + ?Sized> Debugger<'a, S> {
+    pub fn new(strategy: &'a mut S, snapshots: &'a [L2Snapshot]) -> Self {
+        Self::for_engine(BacktestEngine::default(), strategy, snapshots)
+    }
+
+    pub(super) fn for_engine(engine: BacktestEngine, strategy: &'a mut S, snapshots: &'a [L2Snapshot]) -> Self {
+        strategy.set_quote_tracking(engine.config.track_quotes);
+        let state = RunState::new(&engine.config);
+        Self {
+            engine,
+            strategy,
+            snapshots,
+            state,
+            index: 0,
+            last_trades: Vec::new(),
+            breakpoints: BTreeSet::new(),
+            watches: Vec::new(),
+        }
+    }
+
+
+    pub fn break_at(&mut self, index: usize) -> &mut Self {
+        self.breakpoints.insert(index);
+        self
+    }
+
+
+    pub fn break_when<F>(&mut self, label: impl Into<String>, predicate: F) -> &mut Self
+    where
+        F: Fn(&Position, &L2Snapshot) -> bool + 'static,
+    {
+        self.watches.push((label.into(), Box::new(predicate)));
+        self
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+        self.watches.clear();
+    }
+
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.index >= self.snapshots.len()
+    }
+
+    pub fn position(&self) -> &Position {
+        &self.state.position
+    }
+
+    pub fn strategy(&self) -> &S {
+        self.strategy
+    }
+
+
+    pub fn step(&mut self) -> Option<DebugFrame> {
+        self.advance()?;
+        self.frame()
+    }
+
+
+
+    pub fn resume(&mut self) -> StopReason {
+        while let Some(processed) = self.advance() {
+            if self.breakpoints.contains(&processed) {
+                return StopReason::Breakpoint(processed);
+            }
+            let snapshot = &self.snapshots[processed];
+            if let Some((label, _)) = self
+                .watches
+                .iter()
+                .find(|(_, predicate)| predicate(&self.state.position, snapshot))
+            {
+                return StopReason::Watch(label.clone());
+            }
+        }
+        StopReason::Finished
+    }
+
+
+    pub fn frame(&self) -> Option<DebugFrame> {
+        let index = self.index.checked_sub(1)?;
+        let snapshot = &self.snapshots[index];
+        let mid_price = snapshot.mid_price();
+        Some(DebugFrame {
+            index,
+            timestamp_us: snapshot.timestamp_us,
+            best_bid: snapshot.best_bid(),
+            best_ask: snapshot.best_ask(),
+            mid_price,
+            position_qty: self.state.position.quantity,
+            realized_pnl: self.state.position.realized_pnl,
+            total_pnl: self.state.position.total_pnl(mid_price),
+            trades: self.last_trades.clone(),
+            strategy_state: self.strategy.debug_state(),
+        })
+    }
+
+    fn advance(&mut self) -> Option<usize> {
+        let snapshot = self.snapshots.get(self.index)?;
+        let previous = self.state.last_processed.map(|i| &self.snapshots[i]);
+        let derived = self.engine.config.use_derived_cache.then(|| DerivedValues::compute(snapshot));
+        let filled = self.state.position.trades().len();
+        self.engine
+            .step(&mut self.state, self.strategy, self.index, snapshot, previous, derived.as_ref());
+        self.last_trades = self.state.position.trades()[filled..].to_vec();
+        self.index += 1;
+        Some(self.index - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{BacktestEngine, DedupMode, EngineConfig};
+    use crate::strategy::market_maker::{MarketMaker, MarketMakerConfig};
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        let (bid, ask) = (mid - 0.05, mid + 0.05);
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - 0.1 * i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + 0.1 * i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    fn falling_market() -> Vec<L2Snapshot> {
+        (0..300)
+            .map(|i| create_test_snapshot(i * 1_000, 100.0 - i as f64 * 0.2))
+            .collect()
+    }
+
+    #[test]
+    fn test_step_and_breakpoint() {
+        let snapshots = falling_market();
+        let mut strategy = MarketMaker::new(MarketMakerConfig::default());
+        let mut debugger = BacktestEngine::default().debugger(&mut strategy, &snapshots);
+
+        assert!(debugger.frame().is_none());
+        let frame = debugger.step().unwrap();
+        assert_eq!(frame.index, 0);
+        assert!(frame.strategy_state.contains("resting bid"));
+
+        debugger.break_at(10);
+        assert_eq!(debugger.resume(), StopReason::Breakpoint(10));
+        assert_eq!(debugger.index(), 11);
+        assert_eq!(debugger.frame().unwrap().timestamp_us, 10_000);
+    }
+
+    #[test]
+    fn test_watch_predicate_and_finish() {
+        let snapshots = falling_market();
+        let mut strategy = MarketMaker::new(MarketMakerConfig {
+            tick_size: 0.1,
+            ..Default::default()
+        });
+        let mut debugger = Debugger::new(&mut strategy, &snapshots);
+        debugger.break_when("long", |position, _| position.quantity > 0.05);
+
+        assert_eq!(debugger.resume(), StopReason::Watch("long".to_string()));
+        assert!(debugger.position().quantity > 0.05);
+
+        debugger.clear_breakpoints();
+        assert_eq!(debugger.resume(), StopReason::Finished);
+        assert!(debugger.is_finished());
+        assert!(debugger.step().is_none());
+    }
+
+    #[test]
+    fn test_debugger_follows_engine_config() {
+        let mut snapshots = falling_market();
+        snapshots.insert(50, snapshots[49].clone());
+        let config = || EngineConfig {
+            latency_us: 2_000,
+            dedup: DedupMode::TopOfBook,
+            ..Default::default()
+        };
+        let strategy_config = MarketMakerConfig { tick_size: 0.1, ..Default::default() };
+
+        let mut strategy = MarketMaker::new(strategy_config.clone());
+        let run = BacktestEngine::new(config()).run(&mut strategy, &snapshots);
+
+        let mut strategy = MarketMaker::new(strategy_config);
+        let engine = BacktestEngine::new(config());
+        let mut debugger = engine.debugger(&mut strategy, &snapshots);
+        let mut fills = Vec::new();
+        while let Some(frame) = debugger.step() {
+            fills.extend(frame.trades);
+        }
+
+        assert!(!fills.is_empty());
+        assert_eq!(debugger.strategy().stats().updates_processed, run.strategy_stats.updates_processed);
+        assert_eq!(fills.len(), run.position.trades().len());
+        for (a, b) in fills.iter().zip(run.position.trades()) {
+            assert_eq!((a.side, a.price, a.quantity, a.timestamp_us), (b.side, b.price, b.quantity, b.timestamp_us));
+        }
+        assert_eq!(debugger.position().quantity, run.position.quantity);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::analytics::PerformanceMetrics;
+use crate::types::{Side, Trade};
+use crate::utils::hash::Fnv1a;
+
+
+
+pub fn run_fingerprint(trades: &[Trade], metrics: &PerformanceMetrics) -> String {
+    let mut hash = Fnv1a::new();
+    hash.write_u64(trades.len() as u64);
+    for trade in trades {
+        hash.write(&[match trade.side {
+            Side::Bid => 0,
+            Side::Ask => 1,
+        }]);
+        hash.write_f64(trade.price);
+        hash.write_f64(trade.quantity);
+        hash.write_u64(trade.timestamp_us);
+    }
+    for value in [
+        metrics.total_pnl,
+        metrics.realized_pnl,
+        metrics.unrealized_pnl,
+        metrics.final_position,
+        metrics.total_volume,
+    ] {
+        hash.write_f64(value);
+    }
+    hash.write_u64(metrics.total_trades as u64);
+    hash.hex()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::BacktestResult;
+    use crate::execution::Position;
+
+    fn fingerprint_for(trades: &[Trade]) -> String {
+        let mut position = Position::new();
+        for trade in trades {
+            position.execute_trade(trade.clone());
+        }
+        let mut result = BacktestResult::new("test".to_string());
+        result.calculate_from_position(&position, 101.0, 10, 0);
+        run_fingerprint(position.trades(), &result.metrics)
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_trading_decisions() {
+        let trades = vec![Trade::new(Side::Bid, 100.0, 1.0, 1), Trade::new(Side::Ask, 101.0, 1.0, 2)];
+        let base = fingerprint_for(&trades);
+        assert_eq!(base.len(), 16);
+        assert_eq!(base, fingerprint_for(&trades));
+
+        let mut moved = trades.clone();
+        moved[1].timestamp_us = 3;
+        assert_ne!(base, fingerprint_for(&moved));
+    }
+}
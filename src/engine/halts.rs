@@ -0,0 +1,79 @@
+use crate::types::L2Snapshot;
+use serde::{Deserialize, Serialize};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HaltWindow {
+    pub start_us: u64,
+    pub end_us: u64,
+}
+
+impl HaltWindow {
+    pub fn new(start_us: u64, end_us: u64) -> Self {
+        Self { start_us, end_us }
+    }
+
+    pub fn contains(&self, timestamp_us: u64) -> bool {
+        timestamp_us >= self.start_us && timestamp_us < self.end_us
+    }
+
+    
+    pub fn overlaps(&self, after_us: u64, until_us: u64) -> bool {
+        self.start_us <= until_us && self.end_us > after_us
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct Halts {
+    pub windows: Vec<HaltWindow>,
+    
+    pub detect_gap_us: Option<u64>,
+}
+
+impl Halts {
+    pub fn new(windows: Vec<HaltWindow>) -> Self {
+        Self { windows, detect_gap_us: None }
+    }
+
+    pub fn with_gap_detection(mut self, gap_us: u64) -> Self {
+        self.detect_gap_us = Some(gap_us);
+        self
+    }
+
+    
+    pub fn resolve(&self, snapshots: &[L2Snapshot]) -> Vec<HaltWindow> {
+        let mut windows = self.windows.clone();
+        if let Some(gap_us) = self.detect_gap_us {
+            windows.extend(snapshots.windows(2).filter_map(|pair| {
+                let (prev, next) = (pair[0].timestamp_us, pair[1].timestamp_us);
+                (next.saturating_sub(prev) > gap_us).then(|| HaltWindow::new(prev + 1, next))
+            }));
+        }
+        windows.sort_by_key(|w| w.start_us);
+        windows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    #[test]
+    fn test_resolve_merges_config_and_gaps() {
+        let snapshots: Vec<_> = [0, 1_000, 60_000_000, 60_001_000]
+            .iter()
+            .map(|&ts| L2Snapshot::from_levels(0, ts, String::new(), &[PriceLevel::new(99.0, 1.0)], &[PriceLevel::new(101.0, 1.0)]))
+            .collect();
+        let halts = Halts::new(vec![HaltWindow::new(500, 800)]).with_gap_detection(5_000_000);
+        let windows = halts.resolve(&snapshots);
+
+        assert_eq!(windows, vec![HaltWindow::new(500, 800), HaltWindow::new(1_001, 60_000_000)]);
+        assert!(!windows[1].contains(60_000_000));
+        assert!(windows[1].overlaps(1_000, 60_000_000));
+        assert!(!windows[0].overlaps(800, 1_000));
+    }
+}
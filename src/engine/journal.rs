@@ -0,0 +1,419 @@
+use crate::execution::{AckEvent, Order, Position, RejectReason};
+use crate::types::{QuoteEvent, QuoteEventKind, Side, TimeInForce, Trade};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"ZJNL";
+const VERSION: u8 = 1;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskEvent {
+    SessionFlatten,
+    Liquidation,
+}
+
+
+#[derive(Debug, Clone)]
+pub enum JournalEvent {
+    Snapshot { index: usize, timestamp_us: u64 },
+    
+    Order(Trade),
+    
+    Quote(QuoteEvent),
+    Fill(Trade),
+    
+    Missed(Trade),
+    Halt { halted: bool, timestamp_us: u64 },
+    Risk(RiskEvent, Trade),
+    
+    Ack(AckEvent),
+    
+    Cancel(Trade),
+}
+
+impl JournalEvent {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            JournalEvent::Snapshot { index, timestamp_us } => {
+                buf.push(0);
+                buf.extend_from_slice(&(*index as u64).to_le_bytes());
+                buf.extend_from_slice(&timestamp_us.to_le_bytes());
+            }
+            JournalEvent::Order(trade) => encode_trade(buf, 1, trade),
+            JournalEvent::Quote(event) => {
+                buf.push(2);
+                buf.push(match event.kind {
+                    QuoteEventKind::Placed => 0,
+                    QuoteEventKind::Replaced => 1,
+                    QuoteEventKind::Cancelled => 2,
+                    QuoteEventKind::Filled => 3,
+                });
+                write_trade(buf, &Trade::new(event.side, event.price, event.quantity, event.timestamp_us));
+            }
+            JournalEvent::Fill(trade) => encode_trade(buf, 3, trade),
+            JournalEvent::Missed(trade) => encode_trade(buf, 4, trade),
+            JournalEvent::Halt { halted, timestamp_us } => {
+                buf.push(5);
+                buf.push(*halted as u8);
+                buf.extend_from_slice(&timestamp_us.to_le_bytes());
+            }
+            JournalEvent::Risk(kind, trade) => {
+                buf.push(6);
+                buf.push(match kind {
+                    RiskEvent::SessionFlatten => 0,
+                    RiskEvent::Liquidation => 1,
+                });
+                write_trade(buf, trade);
+            }
+            JournalEvent::Ack(event) => {
+                buf.push(7);
+                let order = match event {
+                    AckEvent::Acked(order) => {
+                        buf.push(0);
+                        order
+                    }
+                    AckEvent::Rejected(order, reason) => {
+                        buf.push(match reason {
+                            RejectReason::WouldCross => 1,
+                            RejectReason::InsufficientLiquidity => 2,
+                            RejectReason::SelfTrade => 3,
+                            RejectReason::BelowLotSize => 4,
+                            RejectReason::BelowMinNotional => 5,
+                            RejectReason::Exchange => 6,
+                        });
+                        order
+                    }
+                };
+                buf.extend_from_slice(&order.id.to_le_bytes());
+                write_trade(buf, &Trade::new(order.side, order.price, order.quantity, order.placed_us));
+                buf.push(match order.tif {
+                    TimeInForce::Gtc => 0,
+                    TimeInForce::PostOnly => 1,
+                    TimeInForce::Ioc => 2,
+                    TimeInForce::Fok => 3,
+                });
+            }
+            JournalEvent::Cancel(trade) => encode_trade(buf, 8, trade),
+        }
+    }
+
+    fn decode(cursor: &mut Cursor) -> Result<Self> {
+        Ok(match cursor.u8()? {
+            0 => JournalEvent::Snapshot { index: cursor.u64()? as usize, timestamp_us: cursor.u64()? },
+            1 => JournalEvent::Order(cursor.trade()?),
+            2 => {
+                let kind = match cursor.u8()? {
+                    0 => QuoteEventKind::Placed,
+                    1 => QuoteEventKind::Replaced,
+                    2 => QuoteEventKind::Cancelled,
+                    3 => QuoteEventKind::Filled,
+                    other => anyhow::bail!("Unknown quote event kind {}", other),
+                };
+                let trade = cursor.trade()?;
+                JournalEvent::Quote(QuoteEvent::new(kind, trade.side, trade.price, trade.quantity, trade.timestamp_us))
+            }
+            3 => JournalEvent::Fill(cursor.trade()?),
+            4 => JournalEvent::Missed(cursor.trade()?),
+            5 => JournalEvent::Halt { halted: cursor.u8()? != 0, timestamp_us: cursor.u64()? },
+            6 => {
+                let kind = match cursor.u8()? {
+                    0 => RiskEvent::SessionFlatten,
+                    1 => RiskEvent::Liquidation,
+                    other => anyhow::bail!("Unknown risk event {}", other),
+                };
+                JournalEvent::Risk(kind, cursor.trade()?)
+            }
+            7 => {
+                let status = cursor.u8()?;
+                let id = cursor.u64()?;
+                let trade = cursor.trade()?;
+                let tif = match cursor.u8()? {
+                    0 => TimeInForce::Gtc,
+                    1 => TimeInForce::PostOnly,
+                    2 => TimeInForce::Ioc,
+                    3 => TimeInForce::Fok,
+                    other => anyhow::bail!("Unknown time in force {}", other),
+                };
+                let order = Order {
+                    id,
+                    side: trade.side,
+                    price: trade.price,
+                    quantity: trade.quantity,
+                    placed_us: trade.timestamp_us,
+                    tif,
+                };
+                JournalEvent::Ack(match status {
+                    0 => AckEvent::Acked(order),
+                    1 => AckEvent::Rejected(order, RejectReason::WouldCross),
+                    2 => AckEvent::Rejected(order, RejectReason::InsufficientLiquidity),
+                    3 => AckEvent::Rejected(order, RejectReason::SelfTrade),
+                    4 => AckEvent::Rejected(order, RejectReason::BelowLotSize),
+                    5 => AckEvent::Rejected(order, RejectReason::BelowMinNotional),
+                    6 => AckEvent::Rejected(order, RejectReason::Exchange),
+                    other => anyhow::bail!("Unknown ack status {}", other),
+                })
+            }
+            8 => JournalEvent::Cancel(cursor.trade()?),
+            other => anyhow::bail!("Unknown journal event tag {}", other),
+        })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode(&mut buf);
+        buf
+    }
+}
+
+fn side_byte(side: Side) -> u8 {
+    match side {
+        Side::Bid => 0,
+        Side::Ask => 1,
+    }
+}
+
+fn encode_trade(buf: &mut Vec<u8>, tag: u8, trade: &Trade) {
+    buf.push(tag);
+    write_trade(buf, trade);
+}
+
+fn write_trade(buf: &mut Vec<u8>, trade: &Trade) {
+    buf.push(side_byte(trade.side));
+    buf.extend_from_slice(&trade.price.to_le_bytes());
+    buf.extend_from_slice(&trade.quantity.to_le_bytes());
+    buf.extend_from_slice(&trade.timestamp_us.to_le_bytes());
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let slice = self.bytes.get(self.pos..self.pos + N).context("Truncated journal")?;
+        self.pos += N;
+        Ok(slice.try_into()?)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.take()?))
+    }
+
+    fn trade(&mut self) -> Result<Trade> {
+        let side = match self.u8()? {
+            0 => Side::Bid,
+            _ => Side::Ask,
+        };
+        Ok(Trade::new(side, self.f64()?, self.f64()?, self.u64()?))
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    enabled: bool,
+    events: Vec<JournalEvent>,
+}
+
+impl Journal {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, events: Vec::new() }
+    }
+
+    pub fn record(&mut self, event: JournalEvent) {
+        if self.enabled {
+            self.events.push(event);
+        }
+    }
+
+    pub fn events(&self) -> &[JournalEvent] {
+        &self.events
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(5 + self.events.len() * 26);
+        buf.extend_from_slice(MAGIC);
+        buf.push(VERSION);
+        for event in &self.events {
+            event.encode(&mut buf);
+        }
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 5 || &bytes[..4] != MAGIC {
+            anyhow::bail!("Not an event journal");
+        }
+        if bytes[4] != VERSION {
+            anyhow::bail!("Unsupported journal version {}", bytes[4]);
+        }
+        let mut cursor = Cursor { bytes, pos: 5 };
+        let mut events = Vec::new();
+        while cursor.pos < bytes.len() {
+            events.push(JournalEvent::decode(&mut cursor).context(format!("Failed to decode journal event {}", events.len()))?);
+        }
+        Ok(Self { enabled: true, events })
+    }
+
+    pub fn to_file(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_bytes()).context(format!("Failed to write journal {}", path.display()))
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path).context(format!("Failed to read journal {}", path.display()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    
+    pub fn first_divergence(&self, other: &Journal) -> Option<usize> {
+        let common = self.events.len().min(other.events.len());
+        (0..common)
+            .find(|&i| self.events[i].to_bytes() != other.events[i].to_bytes())
+            .or((self.events.len() != other.events.len()).then_some(common))
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct ReplayState {
+    
+    pub event_index: usize,
+    pub snapshot_index: Option<usize>,
+    pub timestamp_us: u64,
+    pub halted: bool,
+    pub position: Position,
+}
+
+
+
+
+pub struct JournalReplayer<'a> {
+    journal: &'a Journal,
+    state: ReplayState,
+}
+
+impl<'a> JournalReplayer<'a> {
+    pub fn new(journal: &'a Journal) -> Self {
+        Self {
+            journal,
+            state: ReplayState {
+                event_index: 0,
+                snapshot_index: None,
+                timestamp_us: 0,
+                halted: false,
+                position: Position::new(),
+            },
+        }
+    }
+
+    pub fn state(&self) -> &ReplayState {
+        &self.state
+    }
+
+    
+    pub fn step(&mut self) -> Option<&'a JournalEvent> {
+        let event = self.journal.events.get(self.state.event_index)?;
+        self.state.event_index += 1;
+        match event {
+            JournalEvent::Snapshot { index, timestamp_us } => {
+                self.state.snapshot_index = Some(*index);
+                self.state.timestamp_us = *timestamp_us;
+            }
+            JournalEvent::Fill(trade) | JournalEvent::Risk(_, trade) => {
+                self.state.position.execute_trade(trade.clone());
+            }
+            JournalEvent::Halt { halted, .. } => self.state.halted = *halted,
+            JournalEvent::Order(_)
+            | JournalEvent::Quote(_)
+            | JournalEvent::Missed(_)
+            | JournalEvent::Ack(_)
+            | JournalEvent::Cancel(_) => {}
+        }
+        Some(event)
+    }
+
+    
+    pub fn fast_forward(&mut self, event_index: usize) -> &ReplayState {
+        while self.state.event_index < event_index && self.step().is_some() {}
+        &self.state
+    }
+
+    
+    pub fn fast_forward_to_snapshot(&mut self, snapshot_index: usize) -> &ReplayState {
+        loop {
+            match self.journal.events.get(self.state.event_index) {
+                Some(JournalEvent::Snapshot { index, .. }) if *index > snapshot_index => break,
+                Some(_) => {
+                    self.step();
+                }
+                None => break,
+            }
+        }
+        &self.state
+    }
+
+    
+    pub fn finish(mut self) -> ReplayState {
+        while self.step().is_some() {}
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_replay() {
+        let mut journal = Journal::new(true);
+        journal.record(JournalEvent::Snapshot { index: 0, timestamp_us: 10 });
+        journal.record(JournalEvent::Order(Trade::new(Side::Bid, 100.0, 1.0, 10)));
+        journal.record(JournalEvent::Fill(Trade::new(Side::Bid, 100.0, 1.0, 10)));
+        journal.record(JournalEvent::Quote(QuoteEvent::new(QuoteEventKind::Placed, Side::Ask, 101.0, 1.0, 10)));
+        journal.record(JournalEvent::Snapshot { index: 1, timestamp_us: 20 });
+        journal.record(JournalEvent::Halt { halted: true, timestamp_us: 20 });
+        journal.record(JournalEvent::Risk(RiskEvent::Liquidation, Trade::new(Side::Ask, 99.0, 1.0, 20)));
+        let order = Order { id: 3, side: Side::Bid, price: 98.0, quantity: 1.0, placed_us: 20, tif: TimeInForce::PostOnly };
+        journal.record(JournalEvent::Ack(AckEvent::Acked(order)));
+        journal.record(JournalEvent::Ack(AckEvent::Rejected(order, RejectReason::Exchange)));
+        journal.record(JournalEvent::Cancel(Trade::new(Side::Bid, 98.0, 1.0, 20)));
+
+        let decoded = Journal::from_bytes(&journal.to_bytes()).unwrap();
+        assert_eq!(decoded.len(), 10);
+        assert!(matches!(decoded.events()[8], JournalEvent::Ack(AckEvent::Rejected(o, RejectReason::Exchange)) if o == order));
+        assert_eq!(decoded.first_divergence(&journal), None);
+
+        let mut replayer = JournalReplayer::new(&decoded);
+        let state = replayer.fast_forward_to_snapshot(0);
+        assert_eq!((state.event_index, state.position.quantity), (4, 1.0));
+        let state = replayer.finish();
+        assert!(state.halted);
+        assert!(state.position.is_flat());
+        assert!((state.position.realized_pnl + 1.0).abs() < 1e-12);
+
+        let mut other = decoded.clone();
+        other.events[2] = JournalEvent::Fill(Trade::new(Side::Bid, 100.5, 1.0, 10));
+        assert_eq!(journal.first_divergence(&other), Some(2));
+        assert!(Journal::from_bytes(b"nope").is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use super::{BacktestEngine, EngineConfig, StrategyJob};
+use crate::types::L2Snapshot;
+
+pub const DEFAULT_LATENCIES_US: [u64; 4] = [0, 100, 1_000, 10_000];
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyPoint {
+    pub latency_us: u64,
+    pub total_pnl: f64,
+    pub total_trades: usize,
+    pub orders_submitted: usize,
+    pub fill_ratio: f64,
+    
+    pub pnl_change: f64,
+    pub fill_ratio_change: f64,
+}
+
+
+
+pub fn latency_sweep(
+    config: &EngineConfig,
+    job: &StrategyJob,
+    snapshots: &[L2Snapshot],
+    latencies_us: &[u64],
+) -> Vec<LatencyPoint> {
+    let mut points: Vec<LatencyPoint> = latencies_us
+        .iter()
+        .map(|&latency_us| {
+            let engine = BacktestEngine::new(EngineConfig { latency_us, ..config.clone() });
+            let mut strategy = job.build();
+            let run = engine.run(strategy.as_mut(), snapshots);
+            LatencyPoint {
+                latency_us,
+                total_pnl: run.result.metrics.total_pnl,
+                total_trades: run.result.metrics.total_trades,
+                orders_submitted: run.orders_submitted,
+                fill_ratio: run.fill_ratio(),
+                pnl_change: 0.0,
+                fill_ratio_change: 0.0,
+            }
+        })
+        .collect();
+
+    if let Some((pnl, fill_ratio)) = points.first().map(|p| (p.total_pnl, p.fill_ratio)) {
+        for point in &mut points {
+            point.pnl_change = point.total_pnl - pnl;
+            point.fill_ratio_change = point.fill_ratio - fill_ratio;
+        }
+    }
+    points
+}
+
+pub fn print_latency_sweep(points: &[LatencyPoint]) {
+    println!("\n⏳ Latency Sensitivity");
+    println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("   {:>10} {:>12} {:>12} {:>8} {:>10} {:>10}", "Latency", "PnL", "ΔPnL", "Trades", "Fill %", "ΔFill %");
+    for point in points {
+        println!(
+            "   {:>8}µs {:>12.2} {:>+12.2} {:>8} {:>9.1}% {:>+9.1}%",
+            point.latency_us,
+            point.total_pnl,
+            point.pnl_change,
+            point.total_trades,
+            point.fill_ratio * 100.0,
+            point.fill_ratio_change * 100.0
+        );
+    }
+    println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::momentum::{MomentumConfig, MomentumStrategy};
+    use crate::types::PriceLevel;
+
+    fn snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(
+            0,
+            timestamp_us,
+            String::new(),
+            &[PriceLevel::new(mid - 0.05, 10.0)],
+            &[PriceLevel::new(mid + 0.05, 10.0)],
+        )
+    }
+
+    #[test]
+    fn test_latency_sweep_degrades_fills() {
+        let snapshots: Vec<L2Snapshot> = (0..400)
+            .map(|i| snapshot(i * 500, 100.0 + (i as f64 * 0.2).sin() * 2.0))
+            .collect();
+        let job = StrategyJob::new("momentum", || {
+            Box::new(MomentumStrategy::new(MomentumConfig {
+                trigger_threshold: 0.1,
+                lookback: 3,
+                ..Default::default()
+            }))
+        });
+
+        let points = latency_sweep(&EngineConfig::default(), &job, &snapshots, &DEFAULT_LATENCIES_US);
+
+        assert_eq!(points.len(), 4);
+        assert!(points[0].orders_submitted > 0);
+        assert_eq!((points[0].fill_ratio, points[0].pnl_change), (1.0, 0.0));
+        assert!(points[3].fill_ratio < points[0].fill_ratio);
+        assert!(points[3].fill_ratio_change < 0.0);
+    }
+}
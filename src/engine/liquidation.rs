@@ -0,0 +1,63 @@
+use crate::execution::Position;
+use crate::types::{L2Snapshot, Side, Trade};
+
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Liquidation {
+    
+    pub slippage_bps: f64,
+    pub fee_bps: f64,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct LiquidationReport {
+    pub trade: Trade,
+    pub fee: f64,
+    
+    pub cost: f64,
+}
+
+impl Liquidation {
+    
+    pub fn liquidate(&self, position: &mut Position, snapshot: &L2Snapshot) -> Option<LiquidationReport> {
+        if position.is_flat() {
+            return None;
+        }
+        let slip = self.slippage_bps / 10_000.0;
+        let trade = if position.is_long() {
+            Trade::new(Side::Ask, snapshot.best_bid() * (1.0 - slip), position.quantity, snapshot.timestamp_us)
+        } else {
+            Trade::new(Side::Bid, snapshot.best_ask() * (1.0 + slip), -position.quantity, snapshot.timestamp_us)
+        };
+        let fee = trade.notional() * self.fee_bps / 10_000.0;
+        let cost = (trade.price - snapshot.mid_price()).abs() * trade.quantity + fee;
+        position.execute_trade(trade.clone());
+        Some(LiquidationReport { trade, fee, cost })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    #[test]
+    fn test_liquidation_cost() {
+        let snapshot = L2Snapshot::from_levels(0, 10, String::new(), &[PriceLevel::new(99.0, 5.0)], &[PriceLevel::new(101.0, 5.0)]);
+        let mut position = Position::new();
+        position.execute_trade(Trade::new(Side::Bid, 100.0, 2.0, 0));
+
+        let liquidation = Liquidation {
+            slippage_bps: 100.0,
+            fee_bps: 10.0,
+        };
+        let report = liquidation.liquidate(&mut position, &snapshot).unwrap();
+
+        assert!(position.is_flat());
+        assert!((report.trade.price - 98.01).abs() < 1e-9);
+        assert!((report.fee - 98.01 * 2.0 * 0.001).abs() < 1e-9);
+        assert!((report.cost - (1.99 * 2.0 + report.fee)).abs() < 1e-9);
+        assert!(liquidation.liquidate(&mut position, &snapshot).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::analytics::DataClock;
+use crate::types::{L2Snapshot, Trade};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+
+
+
+
+#[derive(Debug, Default)]
+pub(crate) struct StreamMarks {
+    horizons_us: Vec<u64>,
+    quote_horizon: usize,
+    clock: DataClock,
+    index: usize,
+    last_us: u64,
+    mid: Option<f64>,
+    pending: BinaryHeap<Reverse<(u64, usize)>>,
+    marks: Vec<Option<f64>>,
+    trades_seen: usize,
+    fills_seen: usize,
+    last_fill: Option<usize>,
+    quote_pending: VecDeque<(usize, usize, f64)>,
+    quote_moves: Vec<(usize, f64)>,
+}
+
+impl StreamMarks {
+    pub(crate) fn new(horizons_us: &[u64], quote_horizon: usize) -> Self {
+        Self {
+            horizons_us: horizons_us.to_vec(),
+            quote_horizon,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.clock.count()
+    }
+
+    pub(crate) fn clock(&self) -> &DataClock {
+        &self.clock
+    }
+
+    fn stride(&self) -> usize {
+        self.horizons_us.len() + 1
+    }
+
+    
+    pub(crate) fn observe(&mut self, snapshot: &L2Snapshot) {
+        let ts = snapshot.timestamp_us;
+        while let Some(&Reverse((target, slot))) = self.pending.peek() {
+            if target >= ts {
+                break;
+            }
+            self.pending.pop();
+            self.marks[slot] = self.mid;
+        }
+        self.index = self.clock.count();
+        self.clock.observe(ts);
+        self.last_us = ts;
+        let mid = snapshot.mid_price();
+        self.mid = Some(mid);
+        while let Some(&(target, _, _)) = self.quote_pending.front() {
+            if target > self.index {
+                break;
+            }
+            let (_, snapshot_idx, mid_at_fill) = self.quote_pending.pop_front().unwrap();
+            self.quote_moves.push((snapshot_idx, mid - mid_at_fill));
+        }
+    }
+
+    pub(crate) fn track_trades(&mut self, trades: &[Trade]) {
+        let stride = self.stride();
+        for trade in &trades[self.trades_seen.min(trades.len())..] {
+            let base = self.marks.len();
+            self.marks.resize(base + stride, None);
+            self.pending.push(Reverse((trade.timestamp_us, base)));
+            for (h, horizon) in self.horizons_us.iter().enumerate() {
+                self.pending.push(Reverse((trade.timestamp_us + horizon, base + 1 + h)));
+            }
+        }
+        self.trades_seen = trades.len();
+    }
+
+    pub(crate) fn track_quote_fills(&mut self, fills: &[(usize, usize)]) {
+        let Some(mid) = self.mid else {
+            return;
+        };
+        for &(_, snapshot_idx) in &fills[self.fills_seen.min(fills.len())..] {
+            if self.last_fill == Some(snapshot_idx) {
+                continue;
+            }
+            self.last_fill = Some(snapshot_idx);
+            if self.quote_horizon == 0 {
+                self.quote_moves.push((snapshot_idx, 0.0));
+            } else {
+                self.quote_pending.push_back((snapshot_idx + self.quote_horizon, snapshot_idx, mid));
+            }
+        }
+        self.fills_seen = fills.len();
+    }
+
+    
+    
+    pub(crate) fn close(&mut self) {
+        let stride = self.stride();
+        while let Some(Reverse((target, slot))) = self.pending.pop() {
+            if target <= self.last_us || slot % stride == 0 {
+                self.marks[slot] = self.mid;
+            }
+        }
+        if let Some(mid) = self.mid {
+            for (_, snapshot_idx, mid_at_fill) in self.quote_pending.drain(..) {
+                self.quote_moves.push((snapshot_idx, mid - mid_at_fill));
+            }
+        }
+    }
+
+    pub(crate) fn entry_mid(&self, trade_index: usize) -> Option<f64> {
+        self.marks.get(trade_index * self.stride()).copied().flatten()
+    }
+
+    pub(crate) fn markout_mid(&self, trade_index: usize, horizon: usize) -> Option<f64> {
+        self.marks.get(trade_index * self.stride() + 1 + horizon).copied().flatten()
+    }
+
+    pub(crate) fn quote_move(&self, snapshot_idx: usize) -> Option<f64> {
+        let at = self.quote_moves.partition_point(|&(idx, _)| idx < snapshot_idx);
+        self.quote_moves.get(at).filter(|&&(idx, _)| idx == snapshot_idx).map(|&(_, m)| m)
+    }
+}
@@ -0,0 +1,1386 @@
+
+pub mod quotes;
+pub mod parallel;
+pub mod debugger;
+pub mod fingerprint;
+pub mod liquidation;
+pub mod borrow;
+pub mod schedule;
+pub mod halts;
+pub mod recorder;
+pub mod latency;
+pub mod capacity;
+pub mod scenario;
+pub mod robustness;
+pub mod journal;
+mod marks;
+
+pub use quotes::QuoteTracker;
+pub use parallel::{JobResult, StrategyJob};
+pub use debugger::{DebugFrame, Debugger, StopReason};
+pub use fingerprint::run_fingerprint;
+pub use liquidation::{Liquidation, LiquidationReport};
+pub use borrow::BorrowFee;
+pub use recorder::{MetricsRecorder, DEFAULT_BAND_WINDOW};
+pub use latency::{latency_sweep, print_latency_sweep, LatencyPoint, DEFAULT_LATENCIES_US};
+pub use capacity::{capacity_sweep, CapacityCurve, CapacityPoint, DEFAULT_SIZE_SCALES};
+pub use scenario::{print_scenarios, run_scenarios, Perturbation, Scenario, ScenarioResult};
+pub use robustness::{run_robustness, ResampleMethod, RobustnessConfig, RobustnessReport};
+pub use journal::{Journal, JournalEvent, JournalReplayer, ReplayState, RiskEvent};
+pub use schedule::{Schedule, SessionPhase};
+pub use halts::{HaltWindow, Halts};
+
+use crate::analytics::markout::{tag_fills, tag_fills_with, FillMarkout, MarkoutCurve};
+use crate::analytics::quote_quality::{QuoteOutcome, QuoteQualityReport};
+use crate::analytics::performance::spread_capture_pnl_with;
+use crate::analytics::{AllocProfile, BacktestResult, LatencyHistogram, TimeseriesData};
+use crate::execution::orders::crosses;
+use crate::execution::{net_trades, AckEvent, GridReport, Instrument, NettingMode, Position};
+use crate::market_data::source::{PrefetchSource, SnapshotSource, DEFAULT_PREFETCH_BATCH, DEFAULT_PREFETCH_DEPTH};
+use crate::market_data::{DerivedCache, DerivedValues};
+use crate::strategy::{Strategy, StrategyStats};
+use crate::types::{L2Snapshot, QuoteEvent, QuoteEventKind, Side, TimeInForce, Trade};
+use crate::utils::affinity::{PinScope, ThreadPinning};
+use crate::utils::alloc::{self, AllocProbe};
+use marks::StreamMarks;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupMode {
+    #[default]
+    Off,
+    
+    TopOfBook,
+    
+    AllLevels,
+}
+
+impl DedupMode {
+    
+    pub fn unchanged(&self, previous: &L2Snapshot, current: &L2Snapshot) -> bool {
+        match self {
+            DedupMode::Off => false,
+            DedupMode::TopOfBook => current.same_touch(previous),
+            DedupMode::AllLevels => current.same_book(previous),
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct EngineConfig {
+    pub track_quotes: bool,
+    
+    pub adverse_selection_horizon: usize,
+    
+    pub markout_horizons_us: Vec<u64>,
+    
+    
+    pub deterministic: bool,
+    
+    pub threads: usize,
+    
+    
+    pub use_derived_cache: bool,
+    
+    
+    pub dedup: DedupMode,
+    
+    
+    pub schedule: Option<Schedule>,
+    
+    pub halts: Option<Halts>,
+    
+    pub liquidation: Option<Liquidation>,
+    
+    pub borrow: Option<BorrowFee>,
+    
+    
+    pub netting: NettingMode,
+    
+    pub sample_every: usize,
+    
+    
+    pub latency_us: u64,
+    
+    
+    pub instrument: Option<Instrument>,
+    
+    pub journal: bool,
+    
+    pub latency_histograms: bool,
+    
+    
+    pub pinning: Option<ThreadPinning>,
+    
+    
+    
+    pub batch_size: usize,
+}
+
+impl EngineConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.batch_size > 1 && self.use_derived_cache {
+            anyhow::bail!("batch_size {} cannot be combined with use_derived_cache", self.batch_size);
+        }
+        if self.batch_size > 0 && self.latency_us > 0 {
+            anyhow::bail!("batch_size {} cannot be combined with latency_us {}", self.batch_size, self.latency_us);
+        }
+        Ok(())
+    }
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            track_quotes: true,
+            adverse_selection_horizon: 10,
+            markout_horizons_us: vec![0, 1_000_000, 5_000_000],
+            deterministic: true,
+            threads: 0,
+            use_derived_cache: false,
+            dedup: DedupMode::Off,
+            schedule: None,
+            halts: None,
+            liquidation: None,
+            borrow: None,
+            netting: NettingMode::Off,
+            sample_every: 100,
+            latency_us: 0,
+            instrument: None,
+            journal: false,
+            latency_histograms: false,
+            pinning: None,
+            batch_size: 0,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct EngineRun {
+    pub result: BacktestResult,
+    pub position: Position,
+    pub strategy_stats: StrategyStats,
+    pub quote_outcomes: Vec<QuoteOutcome>,
+    pub quote_quality: Option<QuoteQualityReport>,
+    pub fill_markouts: Vec<FillMarkout>,
+    pub markout_curve: MarkoutCurve,
+    
+    pub snapshots_out_of_session: usize,
+    pub session_flattens: usize,
+    pub halts: usize,
+    pub snapshots_halted: usize,
+    pub liquidation: Option<LiquidationReport>,
+    
+    pub raw_trades: Vec<Trade>,
+    pub timeseries: TimeseriesData,
+    pub orders_submitted: usize,
+    
+    pub orders_missed: usize,
+    pub grid: GridReport,
+    pub journal: Journal,
+}
+
+impl EngineRun {
+    pub fn fingerprint(&self) -> String {
+        run_fingerprint(self.position.trades(), &self.result.metrics)
+    }
+
+    pub fn fill_ratio(&self) -> f64 {
+        if self.orders_submitted == 0 {
+            return 0.0;
+        }
+        (self.orders_submitted - self.orders_missed) as f64 / self.orders_submitted as f64
+    }
+}
+
+
+pub struct BacktestEngine {
+    config: EngineConfig,
+}
+
+impl BacktestEngine {
+    
+    
+    pub fn new(config: EngineConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn try_new(config: EngineConfig) -> anyhow::Result<Self> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    pub fn config(&self) -> &EngineConfig {
+        &self.config
+    }
+
+    
+    pub fn run<S: Strategy + ?Sized>(&self, strategy: &mut S, snapshots: &[L2Snapshot]) -> EngineRun {
+        let _pinned = self.pin_caller();
+        if self.config.use_derived_cache {
+            let cache = DerivedCache::build(snapshots);
+            self.run_inner(strategy, snapshots, Some(&cache))
+        } else {
+            self.run_inner(strategy, snapshots, None)
+        }
+    }
+
+    
+    
+    pub fn run_source<S: Strategy + ?Sized, Src: SnapshotSource + ?Sized>(
+        &self,
+        strategy: &mut S,
+        source: &mut Src,
+        limit: Option<usize>,
+    ) -> anyhow::Result<EngineRun> {
+        let _pinned = self.pin_caller();
+        self.run_streaming(strategy, source, limit)
+    }
+
+    
+    
+    
+    pub fn run_prefetched<S, Src, F>(&self, strategy: &mut S, description: &str, open: F, limit: Option<usize>) -> anyhow::Result<EngineRun>
+    where
+        S: Strategy + ?Sized,
+        Src: SnapshotSource,
+        F: FnOnce() -> anyhow::Result<Src> + Send + 'static,
+    {
+        let mut source = PrefetchSource::spawn(description, open, DEFAULT_PREFETCH_BATCH, DEFAULT_PREFETCH_DEPTH);
+        self.run_source(strategy, &mut source, limit)
+    }
+
+    
+    pub fn debugger<'a, S: Strategy + ?Sized>(
+        &self,
+        strategy: &'a mut S,
+        snapshots: &'a [L2Snapshot],
+    ) -> Debugger<'a, S> {
+        Debugger::for_engine(BacktestEngine { config: self.config.clone() }, strategy, snapshots)
+    }
+
+    
+    
+    pub fn run_with_cache<S: Strategy + ?Sized>(
+        &self,
+        strategy: &mut S,
+        snapshots: &[L2Snapshot],
+        cache: &DerivedCache,
+    ) -> anyhow::Result<EngineRun> {
+        if self.config.batch_size > 1 {
+            anyhow::bail!("batch_size {} cannot be combined with a derived cache", self.config.batch_size);
+        }
+        if cache.len() != snapshots.len() {
+            anyhow::bail!("Derived cache has {} entries but {} snapshots were given", cache.len(), snapshots.len());
+        }
+        let _pinned = self.pin_caller();
+        Ok(self.run_inner(strategy, snapshots, Some(cache)))
+    }
+
+    
+    
+    pub(crate) fn pin_worker(&self, worker: usize) {
+        if let Some(pinning) = &self.config.pinning {
+            pinning.apply(worker);
+        }
+    }
+
+    
+    
+    fn pin_caller(&self) -> Option<PinScope> {
+        self.config.pinning.as_ref().map(|pinning| pinning.apply_scoped(0))
+    }
+
+    pub(crate) fn run_inner<S: Strategy + ?Sized>(
+        &self,
+        strategy: &mut S,
+        snapshots: &[L2Snapshot],
+        cache: Option<&DerivedCache>,
+    ) -> EngineRun {
+        let mut state = RunState::new(&self.config);
+        strategy.set_quote_tracking(self.config.track_quotes);
+
+        let start = Instant::now();
+        let batched = self.config.batch_size > 1 && self.config.latency_us == 0 && cache.is_none();
+        if batched && strategy.supports_batches() {
+            self.run_batched(&mut state, strategy, snapshots);
+        } else {
+            for (idx, snapshot) in snapshots.iter().enumerate() {
+                let previous = state.last_processed.map(|i| &snapshots[i]);
+                let derived = cache.map(|cache| &cache.values()[idx]);
+                self.step(&mut state, strategy, idx, snapshot, previous, derived);
+            }
+        }
+        let final_price = match cache {
+            Some(cache) => cache.values().last().map(|d| d.mid).unwrap_or(0.0),
+            None => snapshots.last().map(|s| s.mid_price()).unwrap_or(0.0),
+        };
+        self.finish(state, strategy, History::Full(snapshots), start, final_price)
+    }
+
+    
+    
+    
+    fn run_streaming<S: Strategy + ?Sized, Src: SnapshotSource + ?Sized>(
+        &self,
+        strategy: &mut S,
+        source: &mut Src,
+        limit: Option<usize>,
+    ) -> anyhow::Result<EngineRun> {
+        let mut state = RunState::new(&self.config);
+        strategy.set_quote_tracking(self.config.track_quotes);
+
+        let mut marks = StreamMarks::new(&self.config.markout_horizons_us, self.config.adverse_selection_horizon);
+        let mut previous: Option<L2Snapshot> = None;
+        let mut trailing: Option<L2Snapshot> = None;
+        let start = Instant::now();
+        while limit.is_none_or(|max| marks.len() < max) {
+            let Some(snapshot) = source.next_snapshot()? else {
+                break;
+            };
+            let idx = marks.len();
+            marks.observe(&snapshot);
+            self.step(&mut state, strategy, idx, &snapshot, previous.as_ref(), None);
+            marks.track_trades(state.position.trades());
+            marks.track_quote_fills(state.tracker.fills());
+            if state.last_processed == Some(idx) {
+                previous = Some(snapshot);
+                trailing = None;
+            } else {
+                trailing = Some(snapshot);
+            }
+        }
+        let last = trailing.as_ref().or(previous.as_ref());
+        let final_price = last.map(|s| s.mid_price()).unwrap_or(0.0);
+        Ok(self.finish(state, strategy, History::Streamed { marks: Box::new(marks), last }, start, final_price))
+    }
+
+    
+    
+    
+    fn run_batched<S: Strategy + ?Sized>(&self, state: &mut RunState, strategy: &mut S, snapshots: &[L2Snapshot]) {
+        let mut admitted = Vec::with_capacity(self.config.batch_size);
+        let mut batch = Vec::new();
+        let mut quotes = Vec::new();
+        for (chunk_idx, chunk) in snapshots.chunks(self.config.batch_size).enumerate() {
+            let base = chunk_idx * self.config.batch_size;
+            admitted.clear();
+            for (offset, snapshot) in chunk.iter().enumerate() {
+                let previous = state.last_processed.map(|i| &snapshots[i]);
+                if self.admit(state, strategy, base + offset, snapshot, previous) {
+                    admitted.push(base + offset);
+                }
+            }
+
+            let mut remaining = admitted.as_slice();
+            while let Some(&first) = remaining.first() {
+                let len = remaining.iter().enumerate().take_while(|&(k, &idx)| idx == first + k).count();
+                self.submit_batch(state, strategy, first, &snapshots[first..first + len], &mut batch, &mut quotes);
+                remaining = &remaining[len..];
+            }
+        }
+    }
+
+    
+    
+    fn submit_batch<S: Strategy + ?Sized>(
+        &self,
+        state: &mut RunState,
+        strategy: &mut S,
+        first: usize,
+        run: &[L2Snapshot],
+        batch: &mut Vec<(usize, Trade)>,
+        quotes: &mut Vec<QuoteEvent>,
+    ) {
+        let started = state.strategy_latency.is_some().then(Instant::now);
+        strategy.on_market_data_batch(run, &state.position, batch);
+        record_latency(&mut state.strategy_latency, started);
+        batch.sort_by_key(|(offset, _)| *offset);
+        if self.config.track_quotes {
+            strategy.drain_quote_events(quotes);
+        }
+
+        let mut pending = batch.drain(..).peekable();
+        let mut quotes = quotes.drain(..).peekable();
+        for (offset, snapshot) in run.iter().enumerate() {
+            let last = offset + 1 == run.len();
+            while let Some(event) = quotes.next_if(|event| last || event.timestamp_us <= snapshot.timestamp_us) {
+                state.events.push(event);
+            }
+            let mut trades = std::mem::take(&mut state.orders);
+            while let Some((_, trade)) = pending.next_if(|(at, _)| *at == offset) {
+                trades.push(trade);
+            }
+            self.submit(state, strategy, first + offset, snapshot, None, trades);
+        }
+    }
+
+    fn step<S: Strategy + ?Sized>(
+        &self,
+        state: &mut RunState,
+        strategy: &mut S,
+        idx: usize,
+        snapshot: &L2Snapshot,
+        previous: Option<&L2Snapshot>,
+        derived: Option<&DerivedValues>,
+    ) {
+        if !self.admit(state, strategy, idx, snapshot, previous) {
+            return;
+        }
+        let mut trades = std::mem::take(&mut state.orders);
+        let started = state.strategy_latency.is_some().then(Instant::now);
+        match derived {
+            Some(derived) => strategy.on_market_data_derived_into(snapshot, derived, &state.position, &mut trades),
+            None => strategy.on_market_data_into(snapshot, &state.position, &mut trades),
+        }
+        record_latency(&mut state.strategy_latency, started);
+        self.submit(state, strategy, idx, snapshot, derived, trades);
+    }
+
+    
+    
+    fn admit<S: Strategy + ?Sized>(
+        &self,
+        state: &mut RunState,
+        strategy: &mut S,
+        idx: usize,
+        snapshot: &L2Snapshot,
+        previous: Option<&L2Snapshot>,
+    ) -> bool {
+        let ts = snapshot.timestamp_us;
+        if let (Some(borrow), Some(last)) = (&self.config.borrow, state.last_timestamp_us) {
+            let fee = borrow.accrue(&state.position, snapshot.mid_price(), ts.saturating_sub(last));
+            state.position.realized_pnl -= fee;
+            state.borrow_cost += fee;
+        }
+        state.journal.record(JournalEvent::Snapshot { index: idx, timestamp_us: ts });
+        let (inside_halt, crossed_halt) = match &self.config.halts {
+            Some(halts) => (
+                halts.windows.iter().any(|w| w.contains(ts)),
+                state.last_timestamp_us.is_some_and(|last| {
+                    halts.windows.iter().any(|w| w.overlaps(last, ts))
+                        || halts.detect_gap_us.is_some_and(|gap_us| ts.saturating_sub(last) > gap_us)
+                }),
+            ),
+            None => (false, false),
+        };
+        state.last_timestamp_us = Some(ts);
+        if (inside_halt || crossed_halt) && !state.halted {
+            state.halted = true;
+            state.halts += 1;
+            state.orders_missed += state.in_flight.len();
+            for (_, trade) in state.in_flight.drain(..) {
+                state.journal.record(JournalEvent::Missed(trade));
+            }
+            state.journal.record(JournalEvent::Halt { halted: true, timestamp_us: ts });
+            strategy.on_halt(true, ts);
+        }
+        if inside_halt {
+            state.snapshots_halted += 1;
+            return false;
+        }
+        if state.halted {
+            state.halted = false;
+            state.journal.record(JournalEvent::Halt { halted: false, timestamp_us: ts });
+            strategy.on_halt(false, ts);
+        }
+        if !state.in_flight.is_empty() {
+            let started = state.fill_latency.is_some().then(Instant::now);
+            let mut arrived = Vec::new();
+            while let Some(&(due_us, _)) = state.in_flight.front() {
+                if due_us > ts {
+                    break;
+                }
+                let (_, trade) = state.in_flight.pop_front().unwrap();
+                if !fill_with_tif(trade.clone(), snapshot, &mut arrived) {
+                    state.orders_missed += 1;
+                    state.journal.record(JournalEvent::Missed(trade));
+                }
+            }
+            self.conform(&mut arrived, &mut state.grid);
+            self.execute(state, &mut arrived);
+            record_latency(&mut state.fill_latency, started);
+        }
+        if let Some(schedule) = &self.config.schedule {
+            if schedule.phase(ts) != SessionPhase::Trading {
+                state.out_of_session += 1;
+                if schedule.flatten {
+                    if let Some(trade) = schedule::flatten_trade(&state.position, snapshot) {
+                        state.journal.record(JournalEvent::Risk(RiskEvent::SessionFlatten, trade.clone()));
+                        state.position.execute_trade(trade);
+                        state.session_flattens += 1;
+                    }
+                }
+                return false;
+            }
+        }
+        if let Some(previous) = previous {
+            if self.config.dedup.unchanged(previous, snapshot) {
+                state.skipped += 1;
+                return false;
+            }
+        }
+        state.last_processed = Some(idx);
+        true
+    }
+
+    fn submit<S: Strategy + ?Sized>(
+        &self,
+        state: &mut RunState,
+        strategy: &mut S,
+        idx: usize,
+        snapshot: &L2Snapshot,
+        derived: Option<&DerivedValues>,
+        mut trades: Vec<Trade>,
+    ) {
+        let ts = snapshot.timestamp_us;
+        state.orders_submitted += trades.len();
+        for trade in &trades {
+            state.journal.record(JournalEvent::Order(trade.clone()));
+        }
+        self.conform(&mut trades, &mut state.grid);
+        if self.config.latency_us == 0 {
+            let started = state.fill_latency.is_some().then(Instant::now);
+            if trades.iter().any(|trade| trade.tif != TimeInForce::Gtc) {
+                fill_immediate(state, snapshot, &mut trades);
+            }
+            self.execute(state, &mut trades);
+            record_latency(&mut state.fill_latency, started);
+        } else {
+            let due_us = ts + self.config.latency_us;
+            state.in_flight.extend(trades.drain(..).map(|trade| (due_us, trade)));
+        }
+        state.orders = trades;
+
+        if self.config.track_quotes {
+            strategy.drain_ack_events(&mut state.acks);
+            for event in state.acks.drain(..) {
+                state.journal.record(JournalEvent::Ack(event));
+            }
+            state.tracker.observe(snapshot);
+            strategy.drain_quote_events(&mut state.events);
+            for event in state.events.drain(..) {
+                state.tracker.record(&event, idx);
+                state.journal.record(match event.kind {
+                    QuoteEventKind::Cancelled => {
+                        JournalEvent::Cancel(Trade::new(event.side, event.price, event.quantity, event.timestamp_us))
+                    }
+                    _ => JournalEvent::Quote(event),
+                });
+            }
+        }
+
+        match derived {
+            Some(derived) => state.recorder.record_derived(idx, ts, derived, &state.position),
+            None => state.recorder.record(idx, snapshot, &state.position),
+        }
+    }
+
+    fn finish<S: Strategy + ?Sized>(
+        &self,
+        state: RunState,
+        strategy: &mut S,
+        mut history: History<'_>,
+        start: Instant,
+        final_price: f64,
+    ) -> EngineRun {
+        let RunState {
+            mut position,
+            tracker,
+            mut journal,
+            in_flight,
+            mut orders_missed,
+            ..
+        } = state;
+        orders_missed += in_flight.len();
+        for (_, trade) in in_flight {
+            journal.record(JournalEvent::Missed(trade));
+        }
+        let duration = start.elapsed();
+        let run_alloc = state.alloc.finish();
+        let analytics_alloc = AllocProbe::start();
+
+        let liquidation = match (self.config.liquidation, history.last()) {
+            (Some(rule), Some(last)) => rule.liquidate(&mut position, last),
+            _ => None,
+        };
+        if let Some(report) = &liquidation {
+            journal.record(JournalEvent::Risk(RiskEvent::Liquidation, report.trade.clone()));
+        }
+
+        let strategy_stats = strategy.stats();
+        let mut result = BacktestResult::new(strategy.name().to_string());
+        result.calculate_from_position(
+            &position,
+            final_price,
+            strategy_stats.updates_processed,
+            strategy_stats.quotes_placed,
+        );
+        if let Some(report) = &liquidation {
+            result.apply_liquidation(report.cost, report.fee);
+        }
+        result.metrics.borrow_cost = state.borrow_cost;
+        result.set_timing(duration, history.len() - state.skipped);
+        result.timing.snapshots_skipped = state.skipped;
+
+        let horizon = self.config.adverse_selection_horizon;
+        let horizons = &self.config.markout_horizons_us;
+        let trades = position.trades();
+        let (outcomes, fill_markouts) = match &mut history {
+            History::Full(snapshots) => {
+                result.decompose_pnl(trades, snapshots);
+                result.set_data_timing(snapshots);
+                (tracker.finish(snapshots, horizon), tag_fills(trades, snapshots, horizons))
+            }
+            History::Streamed { marks, .. } => {
+                marks.track_trades(trades);
+                marks.close();
+                result.apply_spread_capture(spread_capture_pnl_with(trades, |i| marks.entry_mid(i)));
+                result.set_data_clock(marks.clock());
+                (
+                    tracker.finish_with(|idx| marks.quote_move(idx)),
+                    tag_fills_with(trades, horizons, |i, h| marks.markout_mid(i, h)),
+                )
+            }
+        };
+
+        let (quote_outcomes, quote_quality) = if self.config.track_quotes {
+            let report = QuoteQualityReport::from_outcomes(&outcomes, horizon);
+            (outcomes, Some(report))
+        } else {
+            (Vec::new(), None)
+        };
+
+        let markout_curve = MarkoutCurve::from_fills(&fill_markouts, horizons);
+        result.timing.strategy_latency = state.strategy_latency.as_ref().map(LatencyHistogram::summary);
+        result.timing.fill_latency = state.fill_latency.as_ref().map(LatencyHistogram::summary);
+        if alloc::is_installed() {
+            result.timing.allocations = Some(AllocProfile { run: run_alloc, analytics: analytics_alloc.finish() });
+        }
+
+        EngineRun {
+            result,
+            position,
+            strategy_stats,
+            quote_outcomes,
+            quote_quality,
+            fill_markouts,
+            markout_curve,
+            snapshots_out_of_session: state.out_of_session,
+            session_flattens: state.session_flattens,
+            halts: state.halts,
+            snapshots_halted: state.snapshots_halted,
+            liquidation,
+            raw_trades: state.raw_trades,
+            timeseries: state.recorder.finish(),
+            orders_submitted: state.orders_submitted,
+            orders_missed,
+            grid: state.grid,
+            journal,
+        }
+    }
+
+    fn execute(&self, state: &mut RunState, trades: &mut Vec<Trade>) {
+        if self.config.netting == NettingMode::Off {
+            for trade in trades.drain(..) {
+                state.journal.record(JournalEvent::Fill(trade.clone()));
+                state.position.execute_trade(trade);
+            }
+        } else {
+            let netted = net_trades(trades, self.config.netting);
+            for trade in netted.trades {
+                state.journal.record(JournalEvent::Fill(trade.clone()));
+                state.position.execute_trade(trade);
+            }
+            state.position.realized_pnl += netted.crossed_pnl;
+            state.raw_trades.append(trades);
+        }
+    }
+
+    fn conform(&self, trades: &mut Vec<Trade>, grid: &mut GridReport) {
+        let Some(instrument) = self.config.instrument else {
+            return;
+        };
+        trades.retain_mut(|trade| match instrument.conform(trade.side, trade.price, trade.quantity, grid) {
+            Ok((price, quantity)) => {
+                trade.price = price;
+                trade.quantity = quantity;
+                true
+            }
+            Err(_) => false,
+        });
+    }
+
+}
+
+
+
+enum History<'a> {
+    Full(&'a [L2Snapshot]),
+    Streamed { marks: Box<StreamMarks>, last: Option<&'a L2Snapshot> },
+}
+
+impl History<'_> {
+    fn len(&self) -> usize {
+        match self {
+            History::Full(snapshots) => snapshots.len(),
+            History::Streamed { marks, .. } => marks.len(),
+        }
+    }
+
+    fn last(&self) -> Option<&L2Snapshot> {
+        match self {
+            History::Full(snapshots) => snapshots.last(),
+            History::Streamed { last, .. } => *last,
+        }
+    }
+}
+
+
+
+struct RunState {
+    position: Position,
+    tracker: QuoteTracker,
+    events: Vec<QuoteEvent>,
+    acks: Vec<AckEvent>,
+    recorder: MetricsRecorder,
+    journal: Journal,
+    grid: GridReport,
+    raw_trades: Vec<Trade>,
+    orders: Vec<Trade>,
+    in_flight: VecDeque<(u64, Trade)>,
+    last_processed: Option<usize>,
+    last_timestamp_us: Option<u64>,
+    halted: bool,
+    skipped: usize,
+    out_of_session: usize,
+    session_flattens: usize,
+    halts: usize,
+    snapshots_halted: usize,
+    orders_submitted: usize,
+    orders_missed: usize,
+    borrow_cost: f64,
+    alloc: AllocProbe,
+    strategy_latency: Option<LatencyHistogram>,
+    fill_latency: Option<LatencyHistogram>,
+}
+
+impl RunState {
+    fn new(config: &EngineConfig) -> Self {
+        Self {
+            position: Position::new(),
+            tracker: QuoteTracker::new(),
+            events: Vec::new(),
+            acks: Vec::new(),
+            recorder: MetricsRecorder::new(config.sample_every),
+            journal: Journal::new(config.journal),
+            grid: GridReport::default(),
+            raw_trades: Vec::new(),
+            orders: Vec::new(),
+            in_flight: VecDeque::new(),
+            last_processed: None,
+            last_timestamp_us: None,
+            halted: false,
+            skipped: 0,
+            out_of_session: 0,
+            session_flattens: 0,
+            halts: 0,
+            snapshots_halted: 0,
+            orders_submitted: 0,
+            orders_missed: 0,
+            borrow_cost: 0.0,
+            alloc: AllocProbe::start(),
+            strategy_latency: config.latency_histograms.then(LatencyHistogram::new),
+            fill_latency: config.latency_histograms.then(LatencyHistogram::new),
+        }
+    }
+}
+
+fn record_latency(histogram: &mut Option<LatencyHistogram>, started: Option<Instant>) {
+    if let (Some(histogram), Some(started)) = (histogram, started) {
+        histogram.record(started.elapsed());
+    }
+}
+
+
+
+fn fill_on_arrival(mut trade: Trade, snapshot: &L2Snapshot) -> Option<Trade> {
+    let fill_price = match trade.side {
+        Side::Bid if snapshot.best_ask() <= trade.price => snapshot.best_ask(),
+        Side::Ask if snapshot.best_bid() >= trade.price => snapshot.best_bid(),
+        _ => return None,
+    };
+    trade.price = fill_price;
+    trade.timestamp_us = snapshot.timestamp_us;
+    Some(trade)
+}
+
+
+
+fn fill_with_tif(mut trade: Trade, snapshot: &L2Snapshot, fills: &mut Vec<Trade>) -> bool {
+    match trade.tif {
+        TimeInForce::Gtc => match fill_on_arrival(trade, snapshot) {
+            Some(fill) => {
+                fills.push(fill);
+                true
+            }
+            None => false,
+        },
+        TimeInForce::PostOnly => {
+            if crosses(trade.side, trade.price, snapshot) {
+                return false;
+            }
+            trade.timestamp_us = snapshot.timestamp_us;
+            fills.push(trade);
+            true
+        }
+        TimeInForce::Ioc | TimeInForce::Fok => {
+            let opposite = match trade.side {
+                Side::Bid => snapshot.asks(),
+                Side::Ask => snapshot.bids(),
+            };
+            let marketable: Vec<_> = opposite
+                .into_iter()
+                .filter(|level| {
+                    level.quantity > 0.0
+                        && match trade.side {
+                            Side::Bid => level.price <= trade.price,
+                            Side::Ask => level.price >= trade.price,
+                        }
+                })
+                .collect();
+            if trade.tif == TimeInForce::Fok {
+                let available: f64 = marketable.iter().map(|level| level.quantity).sum();
+                if available + 1e-12 < trade.quantity {
+                    return false;
+                }
+            }
+            let before = fills.len();
+            let mut remaining = trade.quantity;
+            for level in marketable {
+                if remaining <= 1e-12 {
+                    break;
+                }
+                let quantity = remaining.min(level.quantity);
+                fills.push(Trade::new(trade.side, level.price, quantity, snapshot.timestamp_us).with_tif(trade.tif));
+                remaining -= quantity;
+            }
+            fills.len() > before
+        }
+    }
+}
+
+
+
+fn fill_immediate(state: &mut RunState, snapshot: &L2Snapshot, trades: &mut Vec<Trade>) {
+    for trade in std::mem::take(trades) {
+        if trade.tif == TimeInForce::Gtc {
+            trades.push(trade);
+        } else if !fill_with_tif(trade.clone(), snapshot, trades) {
+            state.orders_missed += 1;
+            state.journal.record(JournalEvent::Missed(trade));
+        }
+    }
+}
+
+impl Default for BacktestEngine {
+    fn default() -> Self {
+        Self::new(EngineConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::quote_quality::QuoteEnd;
+    use crate::execution::AckModel;
+    use crate::strategy::market_maker::{MarketMaker, MarketMakerConfig};
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64, bid: f64, ask: f64) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_engine_tracks_quote_outcomes() {
+        let config = MarketMakerConfig {
+            spread_ticks: 1.0,
+            tick_size: 0.1,
+            quote_size: 0.5,
+            ..Default::default()
+        };
+        let mut mm = MarketMaker::new(config);
+        let snapshots = vec![
+            create_test_snapshot(0, 100.0, 100.1),
+            create_test_snapshot(1_000, 99.5, 99.8),
+            create_test_snapshot(2_000, 99.0, 99.2),
+        ];
+
+        let engine = BacktestEngine::new(EngineConfig {
+            adverse_selection_horizon: 1,
+            ..Default::default()
+        });
+        let run = engine.run(&mut mm, &snapshots);
+
+        assert_eq!(run.result.metrics.total_trades, 1);
+        let filled: Vec<_> = run.quote_outcomes.iter().filter(|o| o.end == QuoteEnd::Filled).collect();
+        assert_eq!(filled.len(), 1);
+        assert_eq!(filled[0].lifetime_us(), 1_000);
+        assert!((filled[0].adverse_move.unwrap() - 0.55).abs() < 1e-9);
+
+        let report = run.quote_quality.unwrap();
+        assert_eq!(report.filled, 1);
+        assert!(report.fill_ratio > 0.0);
+
+        assert_eq!(run.fill_markouts.len(), 1);
+        assert_eq!(run.markout_curve.points.len(), 3);
+    }
+
+    #[test]
+    fn test_derived_cache_matches_plain_run() {
+        let snapshots: Vec<L2Snapshot> = (0..200)
+            .map(|i| {
+                let mid = 100.0 + (i as f64 * 0.3).sin();
+                create_test_snapshot(i * 1_000, mid - 0.05, mid + 0.05)
+            })
+            .collect();
+        let config = MarketMakerConfig {
+            spread_ticks: 0.5,
+            tick_size: 0.1,
+            ..Default::default()
+        };
+
+        let plain = BacktestEngine::default().run(&mut MarketMaker::new(config.clone()), &snapshots);
+        let cached_engine = BacktestEngine::new(EngineConfig {
+            use_derived_cache: true,
+            ..Default::default()
+        });
+        let cached = cached_engine.run(&mut MarketMaker::new(config.clone()), &snapshots);
+        let shared = cached_engine
+            .run_with_cache(&mut MarketMaker::new(config), &snapshots, &DerivedCache::build(&snapshots))
+            .unwrap();
+
+        assert!(plain.result.metrics.total_trades > 0);
+        for run in [&cached, &shared] {
+            assert_eq!(run.result.metrics.total_trades, plain.result.metrics.total_trades);
+            assert_eq!(run.result.metrics.total_pnl.to_bits(), plain.result.metrics.total_pnl.to_bits());
+            assert_eq!(serde_json::to_string(&run.timeseries).unwrap(), serde_json::to_string(&plain.timeseries).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_streaming_run_matches_slice_run() {
+        use crate::market_data::MemorySource;
+
+        let snapshots: Vec<L2Snapshot> = (0..300u64)
+            .map(|i| {
+                let mid = 100.0 + ((i / 3) as f64 * 0.3).sin();
+                create_test_snapshot((i / 2) * 250_000, mid - 0.05, mid + 0.05)
+            })
+            .collect();
+        let config = MarketMakerConfig { spread_ticks: 0.5, tick_size: 0.1, ..Default::default() };
+        let engine = BacktestEngine::new(EngineConfig {
+            dedup: DedupMode::TopOfBook,
+            liquidation: Some(Liquidation { slippage_bps: 1.0, fee_bps: 0.5 }),
+            adverse_selection_horizon: 4,
+            ..Default::default()
+        });
+
+        let full = engine.run(&mut MarketMaker::new(config.clone()), &snapshots);
+        let streamed = engine
+            .run_source(&mut MarketMaker::new(config), &mut MemorySource::new(snapshots.clone()), None)
+            .unwrap();
+
+        assert!(full.result.metrics.total_trades > 0);
+        assert!(full.result.timing.snapshots_skipped > 0);
+        assert!(full.liquidation.is_some());
+        assert!(full.quote_outcomes.iter().any(|o| o.adverse_move.is_some()));
+        assert_eq!(streamed.fingerprint(), full.fingerprint());
+        assert_eq!(streamed.result.metrics.spread_capture_pnl.to_bits(), full.result.metrics.spread_capture_pnl.to_bits());
+        assert_eq!(streamed.result.timing.snapshots_processed, full.result.timing.snapshots_processed);
+        assert_eq!(streamed.result.timing.peak_data_rate, full.result.timing.peak_data_rate);
+        assert_eq!(streamed.liquidation.as_ref().map(|l| l.trade.price), full.liquidation.as_ref().map(|l| l.trade.price));
+        let markouts = |run: &EngineRun| run.fill_markouts.iter().map(|f| f.markouts.clone()).collect::<Vec<_>>();
+        assert_eq!(markouts(&streamed), markouts(&full));
+        let adverse = |run: &EngineRun| run.quote_outcomes.iter().map(|o| o.adverse_move).collect::<Vec<_>>();
+        assert_eq!(adverse(&streamed), adverse(&full));
+    }
+
+    #[test]
+    fn test_dedup_skips_unchanged_snapshots() {
+        let mut snapshots = vec![
+            create_test_snapshot(0, 100.0, 100.1),
+            create_test_snapshot(1_000, 100.0, 100.1),
+            create_test_snapshot(2_000, 100.0, 100.1),
+            create_test_snapshot(3_000, 99.9, 100.1),
+        ];
+        snapshots[2].bid_qty_5 = 3.0;
+
+        let run_with = |dedup| {
+            let engine = BacktestEngine::new(EngineConfig {
+                dedup,
+                ..Default::default()
+            });
+            engine.run(&mut MarketMaker::new(MarketMakerConfig::default()), &snapshots)
+        };
+
+        let off = run_with(DedupMode::Off);
+        assert_eq!(off.result.timing.snapshots_skipped, 0);
+        assert_eq!(off.strategy_stats.updates_processed, 4);
+
+        let touch = run_with(DedupMode::TopOfBook);
+        assert_eq!(touch.result.timing.snapshots_skipped, 2);
+        assert_eq!(touch.strategy_stats.updates_processed, 2);
+        assert_eq!(touch.result.timing.snapshots_processed, 2);
+
+        let all = run_with(DedupMode::AllLevels);
+        assert_eq!(all.result.timing.snapshots_skipped, 1);
+        assert_eq!(all.strategy_stats.updates_processed, 3);
+    }
+
+    #[test]
+    fn test_schedule_flattens_outside_session() {
+        let second = 1_000_000;
+        let snapshots = vec![
+            create_test_snapshot(0, 100.0, 100.1),
+            create_test_snapshot(30 * second, 99.5, 99.8),
+            create_test_snapshot(90 * second, 99.0, 99.2),
+            create_test_snapshot(120 * second, 99.0, 99.2),
+        ];
+        let config = MarketMakerConfig {
+            spread_ticks: 1.0,
+            tick_size: 0.1,
+            quote_size: 0.5,
+            ..Default::default()
+        };
+        let engine = BacktestEngine::new(EngineConfig {
+            schedule: Some(Schedule::utc((0, 0), (0, 2)).with_wind_down_minutes(1)),
+            ..Default::default()
+        });
+        let run = engine.run(&mut MarketMaker::new(config), &snapshots);
+
+        assert_eq!(run.snapshots_out_of_session, 2);
+        assert_eq!(run.session_flattens, 1);
+        assert!(run.position.is_flat());
+        assert_eq!(run.position.trades().last().unwrap().price, 99.0);
+    }
+
+    #[test]
+    fn test_halt_purges_resting_quotes() {
+        let second = 1_000_000;
+        let snapshots = vec![
+            create_test_snapshot(0, 100.0, 100.1),
+            create_test_snapshot(30 * second, 99.5, 99.8),
+            create_test_snapshot(90 * second, 99.5, 99.8),
+        ];
+        let config = MarketMakerConfig {
+            spread_ticks: 1.0,
+            tick_size: 0.1,
+            ..Default::default()
+        };
+        let baseline = BacktestEngine::default().run(&mut MarketMaker::new(config.clone()), &snapshots);
+        assert!(baseline.position.trade_count > 0);
+
+        let engine = BacktestEngine::new(EngineConfig {
+            halts: Some(Halts::new(vec![HaltWindow::new(10 * second, 60 * second)])),
+            ..Default::default()
+        });
+        let run = engine.run(&mut MarketMaker::new(config), &snapshots);
+
+        assert_eq!((run.halts, run.snapshots_halted), (1, 1));
+        assert_eq!(run.position.trade_count, 0);
+    }
+
+    #[test]
+    fn test_journal_replays_run() {
+        let snapshots: Vec<_> = (0..40)
+            .map(|i| {
+                let bid = 100.0 + ((i % 7) as f64 - 3.0) * 0.3;
+                create_test_snapshot(i * 1_000, bid, bid + 0.1)
+            })
+            .collect();
+        let config = MarketMakerConfig {
+            tick_size: 0.1,
+            trend_filter_ticks: 0.0,
+            max_position: 10.0,
+            ack_model: Some(AckModel { ack_latency_us: 1_000, reject_probability: 0.3, seed: 7 }),
+            ..Default::default()
+        };
+        let engine = BacktestEngine::new(EngineConfig {
+            journal: true,
+            ..Default::default()
+        });
+        let run = engine.run(&mut MarketMaker::new(config.clone()), &snapshots);
+        assert!(run.position.trade_count > 0);
+        let events = run.journal.events();
+        assert!(events.iter().any(|e| matches!(e, JournalEvent::Ack(AckEvent::Acked(_)))));
+        assert!(events.iter().any(|e| matches!(e, JournalEvent::Ack(AckEvent::Rejected(..)))));
+        assert!(events.iter().any(|e| matches!(e, JournalEvent::Cancel(_))));
+
+        let journal = Journal::from_bytes(&run.journal.to_bytes()).unwrap();
+        let replayed = JournalReplayer::new(&journal).finish();
+        assert_eq!(replayed.position.trade_count, run.position.trade_count);
+        assert_eq!(replayed.position.realized_pnl, run.position.realized_pnl);
+        assert_eq!(replayed.snapshot_index, Some(snapshots.len() - 1));
+
+        let rerun = engine.run(&mut MarketMaker::new(config), &snapshots);
+        assert_eq!(run.journal.first_divergence(&rerun.journal), None);
+    }
+
+    #[test]
+    fn test_latency_histograms_cover_each_update() {
+        let snapshots: Vec<_> = (0..30)
+            .map(|i| {
+                let bid = 100.0 + ((i % 5) as f64 - 2.0) * 0.3;
+                create_test_snapshot(i * 1_000, bid, bid + 0.1)
+            })
+            .collect();
+        let config = MarketMakerConfig { tick_size: 0.1, trend_filter_ticks: 0.0, ..Default::default() };
+
+        let plain = BacktestEngine::new(EngineConfig::default())
+            .run(&mut MarketMaker::new(config.clone()), &snapshots);
+        assert!(plain.result.timing.strategy_latency.is_none());
+
+        let run = BacktestEngine::new(EngineConfig { latency_histograms: true, ..Default::default() })
+            .run(&mut MarketMaker::new(config), &snapshots);
+        let strategy = run.result.timing.strategy_latency.unwrap();
+        assert_eq!(strategy.count, snapshots.len() as u64);
+        assert!(strategy.p50_ns <= strategy.p99_ns && strategy.p99_ns <= strategy.max_ns);
+        assert_eq!(run.result.timing.fill_latency.unwrap().count, snapshots.len() as u64);
+        assert_eq!(run.fingerprint(), plain.fingerprint());
+    }
+
+    #[test]
+    fn test_engine_without_quote_tracking() {
+        let mut mm = MarketMaker::new(MarketMakerConfig::default());
+        let snapshots = vec![create_test_snapshot(0, 100.0, 100.1)];
+
+        let engine = BacktestEngine::new(EngineConfig {
+            track_quotes: false,
+            ..Default::default()
+        });
+        let run = engine.run(&mut mm, &snapshots);
+
+        assert!(run.quote_outcomes.is_empty());
+        assert!(run.quote_quality.is_none());
+        assert_eq!(run.result.timing.snapshots_processed, 1);
+    }
+
+    #[test]
+    fn test_batched_run_matches_per_snapshot_run() {
+        use crate::execution::PositionView;
+
+        #[derive(Default)]
+        struct Cycler {
+            updates: usize,
+            batches: usize,
+            seen: Vec<u64>,
+        }
+
+        impl Strategy for Cycler {
+            fn on_market_data(&mut self, snapshot: &L2Snapshot, _position: &dyn PositionView) -> Vec<Trade> {
+                self.updates += 1;
+                self.seen.push(snapshot.timestamp_us);
+                match (snapshot.timestamp_us / 1_000) % 3 {
+                    0 => vec![Trade::new(Side::Bid, snapshot.best_ask(), 0.1, snapshot.timestamp_us)],
+                    1 => vec![Trade::new(Side::Ask, snapshot.best_bid(), 0.1, snapshot.timestamp_us)],
+                    _ => Vec::new(),
+                }
+            }
+
+            fn supports_batches(&self) -> bool {
+                true
+            }
+
+            fn on_market_data_batch(
+                &mut self,
+                snapshots: &[L2Snapshot],
+                position: &dyn PositionView,
+                out: &mut Vec<(usize, Trade)>,
+            ) {
+                self.batches += 1;
+                for (offset, snapshot) in snapshots.iter().enumerate() {
+                    out.extend(self.on_market_data(snapshot, position).into_iter().map(|t| (offset, t)));
+                }
+            }
+
+            fn name(&self) -> &str {
+                "Cycler"
+            }
+
+            fn stats(&self) -> StrategyStats {
+                StrategyStats { name: self.name().to_string(), updates_processed: self.updates, ..Default::default() }
+            }
+        }
+
+        let snapshots: Vec<_> = (0..50)
+            .map(|i| {
+                let bid = 100.0 + (i as f64 * 0.4).sin();
+                create_test_snapshot(i * 1_000, bid, bid + 0.1)
+            })
+            .collect();
+
+        let mut plain_strategy = Cycler::default();
+        let plain = BacktestEngine::new(EngineConfig::default()).run(&mut plain_strategy, &snapshots);
+        let mut batched_strategy = Cycler::default();
+        let batched = BacktestEngine::new(EngineConfig { batch_size: 8, ..Default::default() })
+            .run(&mut batched_strategy, &snapshots);
+
+        assert_eq!(plain_strategy.batches, 0);
+        assert_eq!(batched_strategy.batches, 7);
+        assert_eq!(batched.result.metrics.total_trades, plain.result.metrics.total_trades);
+        assert_eq!(batched.fingerprint(), plain.fingerprint());
+
+        let mut snapshots = snapshots;
+        for i in (3..snapshots.len()).step_by(4) {
+            snapshots[i] = L2Snapshot { timestamp_us: snapshots[i].timestamp_us, ..snapshots[i - 1].clone() };
+        }
+        let config = EngineConfig {
+            dedup: DedupMode::TopOfBook,
+            halts: Some(Halts::new(vec![HaltWindow::new(20_000, 27_000)])),
+            ..Default::default()
+        };
+        let mut plain_strategy = Cycler::default();
+        let plain = BacktestEngine::new(config.clone()).run(&mut plain_strategy, &snapshots);
+        let mut batched_strategy = Cycler::default();
+        let batched_engine = BacktestEngine::new(EngineConfig { batch_size: 8, ..config.clone() });
+        let batched = batched_engine.run(&mut batched_strategy, &snapshots);
+
+        assert!(plain.result.timing.snapshots_skipped > 0);
+        assert!(plain.snapshots_halted > 0);
+        assert_eq!(batched_strategy.seen, plain_strategy.seen);
+        assert_eq!(batched.fingerprint(), plain.fingerprint());
+
+        let cache = DerivedCache::build(&snapshots);
+        assert!(batched_engine.run_with_cache(&mut Cycler::default(), &snapshots, &cache).is_err());
+        assert!(BacktestEngine::try_new(EngineConfig { batch_size: 8, use_derived_cache: true, ..config.clone() }).is_err());
+        assert!(BacktestEngine::try_new(EngineConfig { batch_size: 8, latency_us: 500, ..config.clone() }).is_err());
+        let mut latency_strategy = Cycler::default();
+        BacktestEngine::new(EngineConfig { batch_size: 8, latency_us: 500, ..config }).run(&mut latency_strategy, &snapshots);
+        assert_eq!(latency_strategy.batches, 0);
+    }
+
+    #[test]
+    fn test_batched_market_maker_matches_per_snapshot_run() {
+        use crate::execution::PositionView;
+
+        struct Batched<S>(S);
+
+        impl<S: Strategy> Strategy for Batched<S> {
+            fn on_market_data(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) -> Vec<Trade> {
+                self.0.on_market_data(snapshot, position)
+            }
+
+            fn supports_batches(&self) -> bool {
+                true
+            }
+
+            fn name(&self) -> &str {
+                self.0.name()
+            }
+
+            fn stats(&self) -> StrategyStats {
+                self.0.stats()
+            }
+
+            fn set_quote_tracking(&mut self, enabled: bool) {
+                self.0.set_quote_tracking(enabled);
+            }
+
+            fn drain_quote_events(&mut self, events: &mut Vec<QuoteEvent>) {
+                self.0.drain_quote_events(events);
+            }
+        }
+
+        let snapshots: Vec<_> = (0..200)
+            .map(|i| {
+                let bid = 100.0 + (i as f64 * 0.3).sin() + (i as f64 * 0.07).cos();
+                create_test_snapshot(i * 1_000, bid, bid + 0.1)
+            })
+            .collect();
+        let config = MarketMakerConfig {
+            spread_ticks: 1.0,
+            tick_size: 0.1,
+            ..Default::default()
+        };
+        let outcomes = |run: &EngineRun| format!("{:?}", run.quote_outcomes);
+
+        let plain = BacktestEngine::default().run(&mut MarketMaker::new(config.clone()), &snapshots);
+        let batched = BacktestEngine::new(EngineConfig { batch_size: 8, ..Default::default() })
+            .run(&mut MarketMaker::new(config.clone()), &snapshots);
+        assert!(plain.position.quantity.abs() > 1e-9);
+        assert_eq!(batched.fingerprint(), plain.fingerprint());
+        assert_eq!(outcomes(&batched), outcomes(&plain));
+
+        let flat = MarketMakerConfig {
+            max_position: 1e9,
+            inventory_skew_ticks: 0.0,
+            trend_filter_ticks: 0.0,
+            hedge_inventory_ratio: f64::INFINITY,
+            ..config
+        };
+        let plain = BacktestEngine::default().run(&mut MarketMaker::new(flat.clone()), &snapshots);
+        let batched = BacktestEngine::new(EngineConfig { batch_size: 8, ..Default::default() })
+            .run(&mut Batched(MarketMaker::new(flat)), &snapshots);
+        assert!(!plain.quote_outcomes.is_empty());
+        assert_eq!(batched.fingerprint(), plain.fingerprint());
+        assert_eq!(outcomes(&batched), outcomes(&plain));
+    }
+
+    #[test]
+    fn test_engine_fills_respect_time_in_force() {
+        use crate::execution::PositionView;
+
+        struct Orders(Vec<Trade>);
+
+        impl Strategy for Orders {
+            fn on_market_data(&mut self, _snapshot: &L2Snapshot, _position: &dyn PositionView) -> Vec<Trade> {
+                std::mem::take(&mut self.0)
+            }
+
+            fn name(&self) -> &str {
+                "Orders"
+            }
+
+            fn stats(&self) -> StrategyStats {
+                StrategyStats { name: self.name().to_string(), ..Default::default() }
+            }
+        }
+
+        let orders = || {
+            Orders(vec![
+                Trade::new(Side::Bid, 100.1, 0.5, 0).with_tif(TimeInForce::PostOnly),
+                Trade::new(Side::Bid, 100.0, 0.5, 0).with_tif(TimeInForce::PostOnly),
+                Trade::new(Side::Bid, 101.1, 3.0, 0).with_tif(TimeInForce::Ioc),
+                Trade::new(Side::Ask, 99.0, 3.0, 0).with_tif(TimeInForce::Fok),
+                Trade::new(Side::Ask, 99.0, 1.5, 0).with_tif(TimeInForce::Fok),
+            ])
+        };
+        let snapshots = vec![create_test_snapshot(0, 100.0, 100.1), create_test_snapshot(1_000, 100.0, 100.1)];
+
+        for latency_us in [0, 500] {
+            let engine = BacktestEngine::new(EngineConfig { latency_us, ..Default::default() });
+            let run = engine.run(&mut orders(), &snapshots);
+            let fills: Vec<_> = run.position.trades().iter().map(|t| (t.side, t.price, t.quantity)).collect();
+
+            assert_eq!(run.orders_submitted, 5);
+            assert_eq!(run.orders_missed, 2);
+            assert_eq!(
+                fills,
+                vec![
+                    (Side::Bid, 100.0, 0.5),
+                    (Side::Bid, 100.1, 1.0),
+                    (Side::Bid, 101.1, 1.0),
+                    (Side::Ask, 100.0, 1.0),
+                    (Side::Ask, 99.0, 0.5),
+                ]
+            );
+        }
+    }
+}
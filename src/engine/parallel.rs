@@ -0,0 +1,184 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use super::{BacktestEngine, EngineRun};
+use crate::market_data::DerivedCache;
+use crate::strategy::Strategy;
+use crate::types::L2Snapshot;
+
+
+pub type StrategyBuilder = Box<dyn Fn() -> Box<dyn Strategy + Send> + Send + Sync>;
+
+
+
+pub struct StrategyJob {
+    pub label: String,
+    builder: StrategyBuilder,
+}
+
+impl StrategyJob {
+    pub fn new<F>(label: &str, builder: F) -> Self
+    where
+        F: Fn() -> Box<dyn Strategy + Send> + Send + Sync + 'static,
+    {
+        Self {
+            label: label.to_string(),
+            builder: Box::new(builder),
+        }
+    }
+
+    pub fn build(&self) -> Box<dyn Strategy + Send> {
+        (self.builder)()
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct JobResult {
+    pub job_index: usize,
+    pub label: String,
+    pub run: EngineRun,
+}
+
+impl BacktestEngine {
+    
+    
+    
+    pub fn run_parallel(&self, jobs: &[StrategyJob], snapshots: &[L2Snapshot]) -> Vec<JobResult> {
+        if jobs.is_empty() {
+            return Vec::new();
+        }
+
+        let threads = self.thread_count().min(jobs.len());
+        let cache = if self.config().use_derived_cache {
+            Some(DerivedCache::build(snapshots))
+        } else {
+            None
+        };
+        let cache = cache.as_ref();
+        let next_job = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for worker in 0..threads {
+                let sender = sender.clone();
+                let next_job = &next_job;
+                scope.spawn(move || {
+                    self.pin_worker(worker);
+                    loop {
+                        let job_index = next_job.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(job_index) else {
+                            break;
+                        };
+
+                        let mut strategy = job.build();
+                        let run = self.run_inner(strategy.as_mut(), snapshots, cache);
+                        let result = JobResult {
+                            job_index,
+                            label: job.label.clone(),
+                            run,
+                        };
+                        if sender.send(result).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        drop(sender);
+
+        let mut results: Vec<JobResult> = receiver.into_iter().collect();
+        if self.config().deterministic {
+            results.sort_by_key(|r| r.job_index);
+        }
+        results
+    }
+
+    fn thread_count(&self) -> usize {
+        if self.config().threads > 0 {
+            self.config().threads
+        } else {
+            thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::EngineConfig;
+    use crate::strategy::market_maker::{MarketMaker, MarketMakerConfig};
+    use crate::strategy::momentum::{MomentumConfig, MomentumStrategy};
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        let bid = mid - 0.05;
+        let ask = mid + 0.05;
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    fn jobs() -> Vec<StrategyJob> {
+        let mut jobs = Vec::new();
+        for spread_ticks in [0.5, 1.0, 2.0] {
+            jobs.push(StrategyJob::new(&format!("mm-{}", spread_ticks), move || {
+                Box::new(MarketMaker::new(MarketMakerConfig {
+                    spread_ticks,
+                    tick_size: 0.1,
+                    ..Default::default()
+                }))
+            }));
+        }
+        for lookback in [5, 20] {
+            jobs.push(StrategyJob::new(&format!("momentum-{}", lookback), move || {
+                Box::new(MomentumStrategy::new(MomentumConfig {
+                    trigger_threshold: 0.2,
+                    lookback,
+                    ..Default::default()
+                }))
+            }));
+        }
+        jobs
+    }
+
+    type RunFingerprint = (String, u64, Vec<(u64, u64, u64)>);
+
+    fn fingerprint(results: &[JobResult]) -> Vec<RunFingerprint> {
+        results
+            .iter()
+            .map(|r| {
+                let trades = r
+                    .run
+                    .position
+                    .trades()
+                    .iter()
+                    .map(|t| (t.timestamp_us, t.price.to_bits(), t.quantity.to_bits()))
+                    .collect();
+                (r.label.clone(), r.run.result.metrics.total_pnl.to_bits(), trades)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_results_identical_across_thread_counts() {
+        let snapshots: Vec<L2Snapshot> = (0..2_000)
+            .map(|i| create_test_snapshot(i * 1_000, 1000.0 + (i as f64 * 0.05).sin() * 2.0))
+            .collect();
+        let jobs = jobs();
+
+        let baseline = {
+            let engine = BacktestEngine::new(EngineConfig { threads: 1, ..Default::default() });
+            fingerprint(&engine.run_parallel(&jobs, &snapshots))
+        };
+        assert_eq!(baseline.len(), jobs.len());
+        assert!(baseline.iter().any(|(_, _, trades)| !trades.is_empty()));
+
+        for threads in [2, 3, 8] {
+            let engine = BacktestEngine::new(EngineConfig { threads, ..Default::default() });
+            let results = engine.run_parallel(&jobs, &snapshots);
+            assert_eq!(fingerprint(&results), baseline, "threads = {}", threads);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::analytics::quote_quality::{QuoteEnd, QuoteOutcome};
+use crate::types::{L2Snapshot, QuoteEvent, QuoteEventKind, Side};
+
+
+#[derive(Debug, Default)]
+pub struct QuoteTracker {
+    open_bid: Option<QuoteOutcome>,
+    open_ask: Option<QuoteOutcome>,
+    completed: Vec<QuoteOutcome>,
+    fills: Vec<(usize, usize)>,
+    touch: Option<(f64, f64)>,
+    bid_best_since: Option<u64>,
+    ask_best_since: Option<u64>,
+}
+
+impl QuoteTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    
+    pub fn fills(&self) -> &[(usize, usize)] {
+        &self.fills
+    }
+
+    
+    
+    pub fn observe(&mut self, snapshot: &L2Snapshot) {
+        let ts = snapshot.timestamp_us;
+        self.touch = Some((snapshot.best_bid(), snapshot.best_ask()));
+        for side in [Side::Bid, Side::Ask] {
+            let at_best = self.at_best(side);
+            match (at_best, *self.best_since(side)) {
+                (true, None) => *self.best_since(side) = Some(ts),
+                (false, Some(_)) => self.settle_best(side, ts),
+                _ => {}
+            }
+        }
+    }
+
+    
+    pub fn record(&mut self, event: &QuoteEvent, snapshot_index: usize) {
+        match event.kind {
+            QuoteEventKind::Placed | QuoteEventKind::Replaced => {
+                self.close(event.side, QuoteEnd::Replaced, event.timestamp_us);
+                *self.slot(event.side) = Some(QuoteOutcome {
+                    side: event.side,
+                    price: event.price,
+                    quantity: event.quantity,
+                    placed_us: event.timestamp_us,
+                    ended_us: event.timestamp_us,
+                    end: QuoteEnd::Open,
+                    adverse_move: None,
+                    time_at_best_us: 0,
+                });
+                if self.at_best(event.side) {
+                    *self.best_since(event.side) = Some(event.timestamp_us);
+                }
+            }
+            QuoteEventKind::Cancelled => {
+                self.close(event.side, QuoteEnd::Cancelled, event.timestamp_us);
+            }
+            QuoteEventKind::Filled => {
+                if self.slot(event.side).is_none() {
+                    *self.slot(event.side) = Some(QuoteOutcome {
+                        side: event.side,
+                        price: event.price,
+                        quantity: event.quantity,
+                        placed_us: event.timestamp_us,
+                        ended_us: event.timestamp_us,
+                        end: QuoteEnd::Open,
+                        adverse_move: None,
+                        time_at_best_us: 0,
+                    });
+                }
+                self.close(event.side, QuoteEnd::Filled, event.timestamp_us);
+                self.fills.push((self.completed.len() - 1, snapshot_index));
+            }
+        }
+    }
+
+    
+    
+    pub fn finish(self, snapshots: &[L2Snapshot], horizon: usize) -> Vec<QuoteOutcome> {
+        self.finish_with(|snapshot_idx| {
+            let later_idx = (snapshot_idx + horizon).min(snapshots.len().checked_sub(1)?);
+            Some(snapshots[later_idx].mid_price() - snapshots[snapshot_idx].mid_price())
+        })
+    }
+
+    
+    
+    pub fn finish_with<F: FnMut(usize) -> Option<f64>>(mut self, mut move_after: F) -> Vec<QuoteOutcome> {
+        for (outcome_idx, snapshot_idx) in &self.fills {
+            let Some(move_after) = move_after(*snapshot_idx) else {
+                continue;
+            };
+            let outcome = &mut self.completed[*outcome_idx];
+            outcome.adverse_move = Some(match outcome.side {
+                Side::Bid => -move_after,
+                Side::Ask => move_after,
+            });
+        }
+
+        self.completed.extend(self.open_bid.take());
+        self.completed.extend(self.open_ask.take());
+        self.completed
+    }
+
+    fn slot(&mut self, side: Side) -> &mut Option<QuoteOutcome> {
+        match side {
+            Side::Bid => &mut self.open_bid,
+            Side::Ask => &mut self.open_ask,
+        }
+    }
+
+    fn best_since(&mut self, side: Side) -> &mut Option<u64> {
+        match side {
+            Side::Bid => &mut self.bid_best_since,
+            Side::Ask => &mut self.ask_best_since,
+        }
+    }
+
+    
+    fn at_best(&self, side: Side) -> bool {
+        let Some((best_bid, best_ask)) = self.touch else {
+            return false;
+        };
+        match side {
+            Side::Bid => self.open_bid.as_ref().is_some_and(|q| q.price >= best_bid - 1e-9),
+            Side::Ask => self.open_ask.as_ref().is_some_and(|q| q.price <= best_ask + 1e-9),
+        }
+    }
+
+    fn settle_best(&mut self, side: Side, timestamp_us: u64) {
+        if let Some(since) = self.best_since(side).take() {
+            if let Some(outcome) = self.slot(side).as_mut() {
+                outcome.time_at_best_us += timestamp_us.saturating_sub(since);
+            }
+        }
+    }
+
+    fn close(&mut self, side: Side, end: QuoteEnd, timestamp_us: u64) {
+        self.settle_best(side, timestamp_us);
+        if let Some(mut outcome) = self.slot(side).take() {
+            outcome.end = end;
+            outcome.ended_us = timestamp_us;
+            self.completed.push(outcome);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn book(timestamp_us: u64, bid: f64, ask: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &[PriceLevel::new(bid, 1.0)], &[PriceLevel::new(ask, 1.0)])
+    }
+
+    #[test]
+    fn test_time_at_best_follows_the_touch() {
+        let mut tracker = QuoteTracker::new();
+        tracker.observe(&book(0, 100.0, 100.1));
+        tracker.record(&QuoteEvent::new(QuoteEventKind::Placed, Side::Bid, 100.0, 1.0, 0), 0);
+        tracker.record(&QuoteEvent::new(QuoteEventKind::Placed, Side::Ask, 100.2, 1.0, 0), 0);
+        tracker.observe(&book(1_000, 100.05, 100.1));
+        tracker.observe(&book(2_000, 99.9, 100.0));
+        tracker.record(&QuoteEvent::new(QuoteEventKind::Cancelled, Side::Bid, 100.0, 1.0, 3_000), 3);
+        tracker.record(&QuoteEvent::new(QuoteEventKind::Cancelled, Side::Ask, 100.2, 1.0, 3_000), 3);
+
+        let outcomes = tracker.finish_with(|_| None);
+        let bid = outcomes.iter().find(|o| o.side == Side::Bid).unwrap();
+        let ask = outcomes.iter().find(|o| o.side == Side::Ask).unwrap();
+        assert_eq!(bid.time_at_best_us, 2_000);
+        assert!((bid.time_at_best_pct() - 2_000.0 / 3_000.0 * 100.0).abs() < 1e-9);
+        assert_eq!(ask.time_at_best_us, 0);
+    }
+}
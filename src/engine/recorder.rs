@@ -0,0 +1,139 @@
+use crate::analytics::{TimeseriesData, TimeseriesPoint};
+use crate::execution::Position;
+use crate::market_data::DerivedValues;
+use crate::types::L2Snapshot;
+use std::collections::VecDeque;
+
+pub const DEFAULT_BAND_WINDOW: usize = 1_000;
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct MetricsRecorder {
+    sample_every: usize,
+    timeseries: TimeseriesData,
+    band_window: usize,
+    spreads: VecDeque<f64>,
+    imbalances: VecDeque<f64>,
+}
+
+impl MetricsRecorder {
+    pub fn new(sample_every: usize) -> Self {
+        Self {
+            sample_every: sample_every.max(1),
+            timeseries: TimeseriesData::default(),
+            band_window: DEFAULT_BAND_WINDOW,
+            spreads: VecDeque::new(),
+            imbalances: VecDeque::new(),
+        }
+    }
+
+    
+    pub fn with_band_window(mut self, window: usize) -> Self {
+        self.band_window = window.max(1);
+        self
+    }
+
+    pub fn sample_every(&self) -> usize {
+        self.sample_every
+    }
+
+    pub fn record(&mut self, index: usize, snapshot: &L2Snapshot, position: &Position) {
+        self.record_derived(index, snapshot.timestamp_us, &DerivedValues::compute(snapshot), position);
+    }
+
+    
+    pub fn record_derived(&mut self, index: usize, timestamp_us: u64, derived: &DerivedValues, position: &Position) {
+        for (window, value) in [(&mut self.spreads, derived.spread), (&mut self.imbalances, derived.imbalance)] {
+            if window.len() == self.band_window {
+                window.pop_front();
+            }
+            window.push_back(value);
+        }
+        if !index.is_multiple_of(self.sample_every) {
+            return;
+        }
+        let mid = derived.mid;
+        let pnl = position.total_pnl(mid);
+
+        let point = |value: f64| TimeseriesPoint {
+            snapshot: index,
+            timestamp_us,
+            value,
+        };
+        let series = &mut self.timeseries;
+        series.pnl_curve.push(point(pnl));
+        series.position_curve.push(point(position.quantity));
+        series.volume_curve.push(point(position.total_bought + position.total_sold));
+        series.spread_curve.push(point(derived.spread));
+        series.mid_curve.push(point(mid));
+        for (bands, window) in [(&mut series.spread_bands, &self.spreads), (&mut series.imbalance_bands, &self.imbalances)] {
+            let [p10, p50, p90] = percentiles(window);
+            bands.p10.push(point(p10));
+            bands.p50.push(point(p50));
+            bands.p90.push(point(p90));
+        }
+    }
+
+    pub fn timeseries(&self) -> &TimeseriesData {
+        &self.timeseries
+    }
+
+    pub fn finish(self) -> TimeseriesData {
+        self.timeseries.with_drawdown()
+    }
+}
+
+fn percentiles(window: &VecDeque<f64>) -> [f64; 3] {
+    let mut sorted: Vec<f64> = window.iter().copied().collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let last = sorted.len().saturating_sub(1) as f64;
+    [0.1, 0.5, 0.9].map(|q| sorted.get((last * q).round() as usize).copied().unwrap_or(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PriceLevel, Side, Trade};
+
+    #[test]
+    fn test_recorder_samples_at_cadence() {
+        let mut recorder = MetricsRecorder::new(2);
+        let mut position = Position::new();
+        position.execute_trade(Trade::new(Side::Bid, 100.0, 1.0, 0));
+
+        for (i, mid) in [101.0, 102.0, 99.0, 98.0, 103.0].into_iter().enumerate() {
+            let snapshot = L2Snapshot::from_levels(i, i as u64, String::new(), &[PriceLevel::new(mid - 0.5, 1.0)], &[PriceLevel::new(mid + 0.5, 1.0)]);
+            recorder.record(i, &snapshot, &position);
+        }
+
+        let series = recorder.finish();
+        let values = |curve: &[TimeseriesPoint]| curve.iter().map(|p| p.value).collect::<Vec<_>>();
+        assert_eq!(series.pnl_curve.iter().map(|p| p.snapshot).collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(values(&series.pnl_curve), vec![1.0, -1.0, 3.0]);
+        assert_eq!(values(&series.drawdown_curve), vec![0.0, 2.0, 0.0]);
+        assert_eq!(values(&series.spread_curve), vec![1.0; 3]);
+        assert_eq!(values(&series.volume_curve), vec![1.0; 3]);
+        assert_eq!(values(&series.mid_curve), vec![101.0, 99.0, 103.0]);
+        assert_eq!(values(&series.spread_bands.p50), vec![1.0; 3]);
+        assert_eq!(values(&series.imbalance_bands.p90), vec![0.0; 3]);
+    }
+
+    #[test]
+    fn test_rolling_percentile_bands() {
+        let mut recorder = MetricsRecorder::new(5).with_band_window(5);
+        let position = Position::new();
+        for i in 0..10 {
+            let half = (i + 1) as f64 / 2.0;
+            let snapshot = L2Snapshot::from_levels(i, i as u64, String::new(), &[PriceLevel::new(100.0 - half, 1.0)], &[PriceLevel::new(100.0 + half, 1.0)]);
+            recorder.record(i, &snapshot, &position);
+        }
+
+        let bands = &recorder.timeseries().spread_bands;
+        let values = |curve: &[TimeseriesPoint]| curve.iter().map(|p| p.value).collect::<Vec<_>>();
+        assert_eq!(values(&bands.p10), vec![1.0, 2.0]);
+        assert_eq!(values(&bands.p50), vec![1.0, 4.0]);
+        assert_eq!(values(&bands.p90), vec![1.0, 6.0]);
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use super::{BacktestEngine, EngineConfig, StrategyJob};
+use crate::types::{L2Snapshot, PriceLevel};
+use crate::utils::rng::SplitMix64;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResampleMethod {
+    
+    BlockBootstrap { block_size: usize },
+    
+    Shuffle { block_size: usize },
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RobustnessConfig {
+    pub method: ResampleMethod,
+    pub runs: usize,
+    pub seed: u64,
+}
+
+impl Default for RobustnessConfig {
+    fn default() -> Self {
+        Self {
+            method: ResampleMethod::BlockBootstrap { block_size: 500 },
+            runs: 50,
+            seed: 42,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RobustnessReport {
+    pub method: ResampleMethod,
+    pub actual_pnl: f64,
+    pub pnls: Vec<f64>,
+    pub mean_pnl: f64,
+    pub std_pnl: f64,
+    pub p5_pnl: f64,
+    pub p50_pnl: f64,
+    pub p95_pnl: f64,
+    pub profitable_pct: f64,
+    
+    pub actual_percentile: f64,
+}
+
+impl RobustnessReport {
+    pub fn print(&self) {
+        println!("\n🎲 Robustness ({} resampled paths)", self.pnls.len());
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   Actual PnL:        ${:.2}", self.actual_pnl);
+        println!("   Mean ± Std:        ${:.2} ± {:.2}", self.mean_pnl, self.std_pnl);
+        println!("   5% / 50% / 95%:    ${:.2} / ${:.2} / ${:.2}", self.p5_pnl, self.p50_pnl, self.p95_pnl);
+        println!("   Profitable Paths:  {:.1}%", self.profitable_pct);
+        println!("   Actual Percentile: {:.1}%", self.actual_percentile);
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+
+
+pub fn resample(snapshots: &[L2Snapshot], method: ResampleMethod, rng: &mut SplitMix64) -> Vec<L2Snapshot> {
+    let block_size = match method {
+        ResampleMethod::BlockBootstrap { block_size } | ResampleMethod::Shuffle { block_size } => block_size.max(1),
+    };
+    let blocks: Vec<&[L2Snapshot]> = snapshots.chunks(block_size).collect();
+    let order: Vec<usize> = match method {
+        ResampleMethod::BlockBootstrap { .. } => {
+            let mut order = Vec::new();
+            let mut len = 0;
+            while len < snapshots.len() {
+                let b = rng.below(blocks.len());
+                len += blocks[b].len();
+                order.push(b);
+            }
+            order
+        }
+        ResampleMethod::Shuffle { .. } => {
+            let mut order: Vec<usize> = (0..blocks.len()).collect();
+            for i in (1..order.len()).rev() {
+                order.swap(i, rng.below(i + 1));
+            }
+            order
+        }
+    };
+
+    let mut path: Vec<L2Snapshot> = Vec::with_capacity(snapshots.len());
+    for &b in &order {
+        let block = blocks[b];
+        let (time_shift, price_shift) = match path.last() {
+            Some(last) => (
+                last.timestamp_us as i64 + 1 - block[0].timestamp_us as i64,
+                last.mid_price() - block[0].mid_price(),
+            ),
+            None => (snapshots[0].timestamp_us as i64 - block[0].timestamp_us as i64, snapshots[0].mid_price() - block[0].mid_price()),
+        };
+        let shift = |levels: Vec<PriceLevel>| -> Vec<PriceLevel> {
+            levels.into_iter().map(|l| PriceLevel::new(l.price + price_shift, l.quantity)).collect()
+        };
+        for snapshot in block {
+            path.push(L2Snapshot::from_levels(
+                snapshot.row_index,
+                (snapshot.timestamp_us as i64 + time_shift).max(0) as u64,
+                snapshot.datetime.clone(),
+                &shift(snapshot.bids()),
+                &shift(snapshot.asks()),
+            ));
+        }
+    }
+    path.truncate(snapshots.len());
+    path
+}
+
+
+pub fn run_robustness(
+    config: &EngineConfig,
+    job: &StrategyJob,
+    snapshots: &[L2Snapshot],
+    robustness: &RobustnessConfig,
+) -> RobustnessReport {
+    let engine = BacktestEngine::new(config.clone());
+    let actual_pnl = engine.run(job.build().as_mut(), snapshots).result.metrics.total_pnl;
+
+    let mut rng = SplitMix64::new(robustness.seed);
+    let mut pnls: Vec<f64> = (0..robustness.runs)
+        .map(|_| {
+            let path = resample(snapshots, robustness.method, &mut rng);
+            engine.run(job.build().as_mut(), &path).result.metrics.total_pnl
+        })
+        .collect();
+    pnls.sort_by(|a, b| a.total_cmp(b));
+
+    let n = pnls.len().max(1) as f64;
+    let mean_pnl = pnls.iter().sum::<f64>() / n;
+    let std_pnl = (pnls.iter().map(|p| (p - mean_pnl).powi(2)).sum::<f64>() / n).sqrt();
+    let quantile = |q: f64| {
+        if pnls.is_empty() {
+            return 0.0;
+        }
+        pnls[((pnls.len() - 1) as f64 * q).round() as usize]
+    };
+
+    RobustnessReport {
+        method: robustness.method,
+        actual_pnl,
+        mean_pnl,
+        std_pnl,
+        p5_pnl: quantile(0.05),
+        p50_pnl: quantile(0.5),
+        p95_pnl: quantile(0.95),
+        profitable_pct: pnls.iter().filter(|p| **p > 0.0).count() as f64 / n * 100.0,
+        actual_percentile: pnls.iter().filter(|p| **p <= actual_pnl).count() as f64 / n * 100.0,
+        pnls,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::momentum::{MomentumConfig, MomentumStrategy};
+
+    fn snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(
+            0,
+            timestamp_us,
+            String::new(),
+            &[PriceLevel::new(mid - 0.05, 1.0)],
+            &[PriceLevel::new(mid + 0.05, 1.0)],
+        )
+    }
+
+    fn snapshots() -> Vec<L2Snapshot> {
+        (0..400).map(|i| snapshot(i * 1_000, 100.0 + (i as f64 * 0.07).sin() * 2.0)).collect()
+    }
+
+    #[test]
+    fn test_resample_preserves_blocks() {
+        let snapshots = snapshots();
+        let mut rng = SplitMix64::new(1);
+        let path = resample(&snapshots, ResampleMethod::Shuffle { block_size: 50 }, &mut rng);
+
+        assert_eq!(path.len(), snapshots.len());
+        assert!(path.windows(2).all(|w| w[0].timestamp_us < w[1].timestamp_us));
+        assert!((path[0].mid_price() - snapshots[0].mid_price()).abs() < 1e-9);
+        let step = |s: &[L2Snapshot], i: usize| s[i + 1].mid_price() - s[i].mid_price();
+        let first_block = (0..8).map(|b| b * 50).find(|&start| (step(&snapshots, start) - step(&path, 0)).abs() < 1e-9);
+        let start = first_block.unwrap();
+        for i in 0..49 {
+            assert!((step(&snapshots, start + i) - step(&path, i)).abs() < 1e-9);
+        }
+
+        let bootstrap = resample(&snapshots, ResampleMethod::BlockBootstrap { block_size: 64 }, &mut SplitMix64::new(1));
+        assert_eq!(bootstrap.len(), snapshots.len());
+    }
+
+    #[test]
+    fn test_robustness_report_is_reproducible() {
+        let snapshots = snapshots();
+        let job = StrategyJob::new("momentum", || {
+            Box::new(MomentumStrategy::new(MomentumConfig {
+                trigger_threshold: 0.1,
+                lookback: 5,
+                ..Default::default()
+            }))
+        });
+        let config = RobustnessConfig {
+            method: ResampleMethod::BlockBootstrap { block_size: 40 },
+            runs: 8,
+            seed: 3,
+        };
+
+        let a = run_robustness(&EngineConfig::default(), &job, &snapshots, &config);
+        let b = run_robustness(&EngineConfig::default(), &job, &snapshots, &config);
+
+        assert_eq!(a.pnls.len(), 8);
+        assert_eq!(a.pnls, b.pnls);
+        assert!(a.p5_pnl <= a.p50_pnl && a.p50_pnl <= a.p95_pnl);
+        assert!((0.0..=100.0).contains(&a.actual_percentile));
+    }
+}
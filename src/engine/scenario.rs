@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use super::{BacktestEngine, EngineConfig, StrategyJob};
+use crate::analytics::RiskMetrics;
+use crate::types::{L2Snapshot, PriceLevel};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Perturbation {
+    
+    
+    MidShock { pct: f64, start: usize, snapshots: usize },
+    
+    WidenSpread { factor: f64 },
+    
+    ScaleDepth { factor: f64 },
+}
+
+impl Perturbation {
+    fn apply(&self, idx: usize, mid: f64, level: PriceLevel) -> PriceLevel {
+        match *self {
+            Perturbation::MidShock { pct, start, snapshots } => {
+                if idx < start {
+                    return level;
+                }
+                let progress = ((idx - start + 1) as f64 / snapshots.max(1) as f64).min(1.0);
+                PriceLevel::new(level.price * (1.0 + pct / 100.0 * progress), level.quantity)
+            }
+            Perturbation::WidenSpread { factor } => PriceLevel::new(mid + (level.price - mid) * factor, level.quantity),
+            Perturbation::ScaleDepth { factor } => PriceLevel::new(level.price, level.quantity * factor),
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    pub perturbations: Vec<Perturbation>,
+}
+
+impl Scenario {
+    pub fn new(name: &str, perturbations: Vec<Perturbation>) -> Self {
+        Self {
+            name: name.to_string(),
+            perturbations,
+        }
+    }
+
+    
+    pub fn defaults() -> Vec<Scenario> {
+        vec![
+            Scenario::new("baseline", Vec::new()),
+            Scenario::new("mid_shock", vec![Perturbation::MidShock { pct: -5.0, start: 0, snapshots: 100 }]),
+            Scenario::new("wide_spread", vec![Perturbation::WidenSpread { factor: 5.0 }]),
+            Scenario::new("thin_book", vec![Perturbation::ScaleDepth { factor: 0.5 }]),
+            Scenario::new(
+                "combined",
+                vec![
+                    Perturbation::MidShock { pct: -5.0, start: 0, snapshots: 100 },
+                    Perturbation::WidenSpread { factor: 5.0 },
+                    Perturbation::ScaleDepth { factor: 0.5 },
+                ],
+            ),
+        ]
+    }
+
+    pub fn apply(&self, snapshots: &[L2Snapshot]) -> Vec<L2Snapshot> {
+        if self.perturbations.is_empty() {
+            return snapshots.to_vec();
+        }
+        snapshots
+            .iter()
+            .enumerate()
+            .map(|(idx, snapshot)| {
+                let mut bids = snapshot.bids();
+                let mut asks = snapshot.asks();
+                for perturbation in &self.perturbations {
+                    let (Some(bid), Some(ask)) = (bids.first(), asks.first()) else {
+                        break;
+                    };
+                    let mid = (bid.price + ask.price) / 2.0;
+                    for level in bids.iter_mut().chain(asks.iter_mut()) {
+                        *level = perturbation.apply(idx, mid, *level);
+                    }
+                }
+                L2Snapshot::from_levels(snapshot.row_index, snapshot.timestamp_us, snapshot.datetime.clone(), &bids, &asks)
+            })
+            .collect()
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub scenario: String,
+    pub total_pnl: f64,
+    pub total_trades: usize,
+    pub final_position: f64,
+    pub risk: RiskMetrics,
+}
+
+
+pub fn run_scenarios(
+    config: &EngineConfig,
+    job: &StrategyJob,
+    snapshots: &[L2Snapshot],
+    scenarios: &[Scenario],
+) -> Vec<ScenarioResult> {
+    let engine = BacktestEngine::new(config.clone());
+    scenarios
+        .iter()
+        .map(|scenario| {
+            let perturbed = scenario.apply(snapshots);
+            let mut strategy = job.build();
+            let run = engine.run(strategy.as_mut(), &perturbed);
+            let start_price = perturbed.first().map(|s| s.mid_price()).unwrap_or(0.0);
+            let final_price = perturbed.last().map(|s| s.mid_price()).unwrap_or(0.0);
+            ScenarioResult {
+                scenario: scenario.name.clone(),
+                total_pnl: run.result.metrics.total_pnl,
+                total_trades: run.result.metrics.total_trades,
+                final_position: run.result.metrics.final_position,
+                risk: RiskMetrics::calculate(run.position.trades(), &run.timeseries, start_price, final_price),
+            }
+        })
+        .collect()
+}
+
+pub fn print_scenarios(results: &[ScenarioResult]) {
+    println!("\n🧪 Stress Scenarios");
+    println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("   {:<14} {:>12} {:>8} {:>12} {:>10}", "Scenario", "PnL", "Trades", "Max DD", "Sharpe");
+    for result in results {
+        println!(
+            "   {:<14} {:>12.2} {:>8} {:>12.2} {:>10.3}",
+            result.scenario, result.total_pnl, result.total_trades, result.risk.max_drawdown, result.risk.sharpe_ratio
+        );
+    }
+    println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::momentum::{MomentumConfig, MomentumStrategy};
+
+    fn snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(
+            0,
+            timestamp_us,
+            String::new(),
+            &[PriceLevel::new(mid - 0.5, 2.0), PriceLevel::new(mid - 1.0, 4.0)],
+            &[PriceLevel::new(mid + 0.5, 2.0), PriceLevel::new(mid + 1.0, 4.0)],
+        )
+    }
+
+    #[test]
+    fn test_perturbations() {
+        let snapshots = vec![snapshot(0, 100.0), snapshot(1, 100.0), snapshot(2, 100.0)];
+
+        let shocked = Scenario::new("shock", vec![Perturbation::MidShock { pct: -10.0, start: 1, snapshots: 2 }]).apply(&snapshots);
+        let mids: Vec<f64> = shocked.iter().map(|s| s.mid_price()).collect();
+        assert_eq!(mids, vec![100.0, 95.0, 90.0]);
+
+        let wide = Scenario::new("wide", vec![Perturbation::WidenSpread { factor: 5.0 }]).apply(&snapshots);
+        assert!((wide[0].spread() - 5.0).abs() < 1e-9);
+        assert_eq!(wide[0].mid_price(), 100.0);
+
+        let thin = Scenario::new("thin", vec![Perturbation::ScaleDepth { factor: 0.5 }]).apply(&snapshots);
+        assert_eq!((thin[0].bid_qty_1, thin[0].asks()[1].quantity), (1.0, 2.0));
+    }
+
+    #[test]
+    fn test_run_scenarios() {
+        let snapshots: Vec<L2Snapshot> = (0..300)
+            .map(|i| snapshot(i * 1_000, 100.0 + (i as f64 * 0.1).sin() * 3.0))
+            .collect();
+        let job = StrategyJob::new("momentum", || {
+            Box::new(MomentumStrategy::new(MomentumConfig {
+                trigger_threshold: 0.2,
+                lookback: 5,
+                ..Default::default()
+            }))
+        });
+
+        let results = run_scenarios(&EngineConfig::default(), &job, &snapshots, &Scenario::defaults());
+
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0].scenario, "baseline");
+        assert!(results[0].total_trades > 0);
+        assert!(results.iter().any(|r| r.total_pnl != results[0].total_pnl));
+    }
+}
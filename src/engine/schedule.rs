@@ -0,0 +1,102 @@
+use crate::execution::Position;
+use crate::types::{L2Snapshot, Side, Trade};
+
+const SECONDS_PER_DAY: u32 = 86_400;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionPhase {
+    Trading,
+    
+    WindDown,
+    Closed,
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    pub start_secs: u32,
+    pub end_secs: u32,
+    
+    pub wind_down_secs: u32,
+    
+    pub flatten: bool,
+}
+
+impl Schedule {
+    
+    pub fn utc(start: (u32, u32), end: (u32, u32)) -> Self {
+        Self {
+            start_secs: (start.0 * 3_600 + start.1 * 60) % SECONDS_PER_DAY,
+            end_secs: (end.0 * 3_600 + end.1 * 60) % SECONDS_PER_DAY,
+            wind_down_secs: 0,
+            flatten: true,
+        }
+    }
+
+    pub fn with_wind_down_minutes(mut self, minutes: u32) -> Self {
+        self.wind_down_secs = minutes * 60;
+        self
+    }
+
+    pub fn with_flatten(mut self, flatten: bool) -> Self {
+        self.flatten = flatten;
+        self
+    }
+
+    fn session_length(&self) -> u32 {
+        match (self.end_secs + SECONDS_PER_DAY - self.start_secs) % SECONDS_PER_DAY {
+            0 => SECONDS_PER_DAY,
+            length => length,
+        }
+    }
+
+    pub fn phase(&self, timestamp_us: u64) -> SessionPhase {
+        let second_of_day = ((timestamp_us / 1_000_000) % SECONDS_PER_DAY as u64) as u32;
+        let into_session = (second_of_day + SECONDS_PER_DAY - self.start_secs) % SECONDS_PER_DAY;
+        let length = self.session_length();
+        if into_session >= length {
+            SessionPhase::Closed
+        } else if into_session + self.wind_down_secs >= length {
+            SessionPhase::WindDown
+        } else {
+            SessionPhase::Trading
+        }
+    }
+}
+
+
+pub fn flatten_trade(position: &Position, snapshot: &L2Snapshot) -> Option<Trade> {
+    if position.is_flat() {
+        return None;
+    }
+    Some(if position.is_long() {
+        Trade::new(Side::Ask, snapshot.best_bid(), position.quantity, snapshot.timestamp_us)
+    } else {
+        Trade::new(Side::Bid, snapshot.best_ask(), -position.quantity, snapshot.timestamp_us)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_US: u64 = 3_600_000_000;
+
+    #[test]
+    fn test_session_phases() {
+        let schedule = Schedule::utc((9, 0), (17, 0)).with_wind_down_minutes(30);
+        assert_eq!(schedule.phase(8 * HOUR_US), SessionPhase::Closed);
+        assert_eq!(schedule.phase(9 * HOUR_US), SessionPhase::Trading);
+        assert_eq!(schedule.phase(16 * HOUR_US + 29 * 60_000_000), SessionPhase::Trading);
+        assert_eq!(schedule.phase(16 * HOUR_US + 30 * 60_000_000), SessionPhase::WindDown);
+        assert_eq!(schedule.phase(17 * HOUR_US), SessionPhase::Closed);
+
+        let overnight = Schedule::utc((22, 0), (2, 0));
+        assert_eq!(overnight.phase(23 * HOUR_US), SessionPhase::Trading);
+        assert_eq!(overnight.phase(24 * HOUR_US + HOUR_US), SessionPhase::Trading);
+        assert_eq!(overnight.phase(12 * HOUR_US), SessionPhase::Closed);
+    }
+}
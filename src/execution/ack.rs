@@ -0,0 +1,48 @@
+use super::{Order, RejectReason};
+use serde::{Deserialize, Serialize};
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AckModel {
+    pub ack_latency_us: u64,
+    pub reject_probability: f64,
+    pub seed: u64,
+}
+
+impl Default for AckModel {
+    fn default() -> Self {
+        Self {
+            ack_latency_us: 0,
+            reject_probability: 0.0,
+            seed: 42,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AckEvent {
+    Acked(Order),
+    Rejected(Order, RejectReason),
+}
+
+
+
+
+pub trait OrderCallbacks {
+    fn on_order_acked(&mut self, _order: &Order) {}
+
+    fn on_order_rejected(&mut self, _order: &Order, _reason: RejectReason) {}
+}
+
+impl AckEvent {
+    pub fn deliver<C: OrderCallbacks + ?Sized>(&self, callbacks: &mut C) {
+        match self {
+            AckEvent::Acked(order) => callbacks.on_order_acked(order),
+            AckEvent::Rejected(order, reason) => callbacks.on_order_rejected(order, *reason),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::types::{L2Snapshot, Side};
+use serde::{Deserialize, Serialize};
+
+
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FillModel {
+    
+    pub hidden_liquidity: f64,
+    
+    
+    pub intensity: Option<FillIntensity>,
+    pub priority: MatchingPriority,
+}
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MatchingPriority {
+    #[default]
+    CrossOnly,
+    
+    PriceTime,
+    
+    ProRata,
+}
+
+impl MatchingPriority {
+    
+    
+    pub fn allocate(&self, traded: f64, queue_ahead: f64, level_quantity: f64, order_quantity: f64) -> (f64, f64) {
+        match self {
+            MatchingPriority::CrossOnly => (0.0, queue_ahead),
+            MatchingPriority::PriceTime => {
+                let through = (traded - queue_ahead).max(0.0);
+                (through.min(order_quantity), (queue_ahead - traded).max(0.0))
+            }
+            MatchingPriority::ProRata => {
+                let share = order_quantity / (level_quantity + order_quantity);
+                ((traded * share).min(order_quantity), 0.0)
+            }
+        }
+    }
+}
+
+impl FillModel {
+    pub fn with_hidden_liquidity(mut self, fraction: f64) -> Self {
+        self.hidden_liquidity = fraction.max(0.0);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: MatchingPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_intensity(mut self, intensity: FillIntensity) -> Self {
+        self.intensity = Some(intensity);
+        self
+    }
+
+    
+    pub fn available(&self, level: usize, displayed: f64) -> f64 {
+        if level == 0 {
+            displayed * (1.0 + self.hidden_liquidity)
+        } else {
+            displayed
+        }
+    }
+
+    
+    
+    pub fn touch_fill_fraction(&self) -> f64 {
+        1.0 / (1.0 + self.hidden_liquidity)
+    }
+}
+
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FillIntensity {
+    
+    pub touch_rate: f64,
+    
+    pub distance_decay: f64,
+    
+    pub imbalance_weight: f64,
+    pub seed: u64,
+}
+
+impl Default for FillIntensity {
+    fn default() -> Self {
+        Self {
+            touch_rate: 0.1,
+            distance_decay: 1.0,
+            imbalance_weight: 0.5,
+            seed: 42,
+        }
+    }
+}
+
+impl FillIntensity {
+    
+    
+    
+    pub fn calibrate(snapshots: &[L2Snapshot]) -> Self {
+        let mut events = 0usize;
+        let mut opportunities = 0usize;
+        for pair in snapshots.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            if prev.best_bid() > 0.0 {
+                opportunities += 1;
+                if next.best_bid() < prev.best_bid() || (next.best_bid() == prev.best_bid() && next.bid_qty_1 < prev.bid_qty_1) {
+                    events += 1;
+                }
+            }
+            if prev.best_ask() > 0.0 {
+                opportunities += 1;
+                if next.best_ask() > prev.best_ask() || (next.best_ask() == prev.best_ask() && next.ask_qty_1 < prev.ask_qty_1) {
+                    events += 1;
+                }
+            }
+        }
+        let mut intensity = Self::default();
+        if opportunities > 0 {
+            intensity.touch_rate = events as f64 / opportunities as f64;
+        }
+        intensity
+    }
+
+    
+    
+    pub fn probability(&self, side: Side, price: f64, snapshot: &L2Snapshot) -> f64 {
+        let half_spread = snapshot.spread() / 2.0;
+        if half_spread <= 0.0 {
+            return 0.0;
+        }
+        let distance = ((price - snapshot.mid_price()).abs() / half_spread - 1.0).max(0.0);
+        let imbalance = snapshot.imbalance();
+        let pressure = match side {
+            Side::Bid => -imbalance,
+            Side::Ask => imbalance,
+        };
+        let pressure = if pressure.is_finite() { pressure } else { 0.0 };
+        let probability = self.touch_rate * (-self.distance_decay * distance).exp() * (1.0 + self.imbalance_weight * pressure);
+        probability.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    #[test]
+    fn test_hidden_liquidity_scales_touch_only() {
+        let model = FillModel::default().with_hidden_liquidity(0.5);
+        assert_eq!(model.available(0, 2.0), 3.0);
+        assert_eq!(model.available(1, 2.0), 2.0);
+        assert!((model.touch_fill_fraction() - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(FillModel::default().touch_fill_fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_priority_allocation() {
+        assert_eq!(MatchingPriority::PriceTime.allocate(3.0, 5.0, 5.0, 1.0), (0.0, 2.0));
+        assert_eq!(MatchingPriority::PriceTime.allocate(2.5, 2.0, 5.0, 1.0), (0.5, 0.0));
+        assert_eq!(MatchingPriority::ProRata.allocate(3.0, 5.0, 5.0, 1.0), (0.5, 0.0));
+        assert_eq!(MatchingPriority::CrossOnly.allocate(3.0, 5.0, 5.0, 1.0), (0.0, 5.0));
+    }
+
+    #[test]
+    fn test_intensity_calibration_and_shape() {
+        let book = |bid: f64, bid_qty: f64, ask_qty: f64| {
+            L2Snapshot::from_levels(0, 0, String::new(), &[PriceLevel::new(bid, bid_qty)], &[PriceLevel::new(bid + 1.0, ask_qty)])
+        };
+        let snapshots = vec![book(100.0, 1.0, 1.0), book(99.0, 1.0, 1.0), book(99.0, 1.0, 1.0), book(99.0, 0.5, 1.0)];
+        let intensity = FillIntensity::calibrate(&snapshots);
+        assert!((intensity.touch_rate - 2.0 / 6.0).abs() < 1e-12);
+
+        let balanced = book(100.0, 1.0, 1.0);
+        let at_touch = intensity.probability(Side::Bid, 100.0, &balanced);
+        assert!((at_touch - 1.0 / 3.0).abs() < 1e-12);
+        assert!(intensity.probability(Side::Bid, 99.0, &balanced) < at_touch);
+        assert!(intensity.probability(Side::Bid, 100.0, &book(100.0, 1.0, 3.0)) > at_touch);
+    }
+}
@@ -0,0 +1,130 @@
+use super::Position;
+use crate::types::{L2Snapshot, Side, Trade};
+
+
+#[derive(Debug, Clone)]
+pub struct HedgeConfig {
+    pub symbol: String,
+    
+    pub hedge_ratio: f64,
+    
+    pub rebalance_threshold: f64,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            symbol: "HEDGE".to_string(),
+            hedge_ratio: 1.0,
+            rebalance_threshold: 0.05,
+        }
+    }
+}
+
+
+
+
+
+
+
+pub struct HedgeLeg {
+    config: HedgeConfig,
+    position: Position,
+    last_mids: Option<(f64, f64)>,
+    basis_pnl: f64,
+}
+
+impl HedgeLeg {
+    pub fn new(config: HedgeConfig) -> Self {
+        Self {
+            config,
+            position: Position::new(),
+            last_mids: None,
+            basis_pnl: 0.0,
+        }
+    }
+
+    pub fn config(&self) -> &HedgeConfig {
+        &self.config
+    }
+
+    pub fn position(&self) -> &Position {
+        &self.position
+    }
+
+    pub fn target_quantity(&self, primary_qty: f64) -> f64 {
+        -primary_qty * self.config.hedge_ratio
+    }
+
+    
+    
+    pub fn rebalance(&mut self, primary_qty: f64, hedge_snapshot: &L2Snapshot) -> Option<Trade> {
+        let delta = self.target_quantity(primary_qty) - self.position.quantity;
+        if delta.abs() < self.config.rebalance_threshold.max(1e-9) {
+            return None;
+        }
+
+        let trade = if delta > 0.0 {
+            Trade::new(Side::Bid, hedge_snapshot.best_ask(), delta, hedge_snapshot.timestamp_us)
+        } else {
+            Trade::new(Side::Ask, hedge_snapshot.best_bid(), -delta, hedge_snapshot.timestamp_us)
+        };
+        self.position.execute_trade(trade.clone());
+        Some(trade)
+    }
+
+    
+    
+    pub fn mark(&mut self, primary_mid: f64, hedge_mid: f64) {
+        if let Some((prev_primary, prev_hedge)) = self.last_mids {
+            let ratio = self.config.hedge_ratio;
+            if ratio > 0.0 {
+                let hedged_qty = -self.position.quantity / ratio;
+                let basis_move = (primary_mid - prev_primary) - ratio * (hedge_mid - prev_hedge);
+                self.basis_pnl += hedged_qty * basis_move;
+            }
+        }
+        self.last_mids = Some((primary_mid, hedge_mid));
+    }
+
+    pub fn hedge_pnl(&self, hedge_mid: f64) -> f64 {
+        self.position.total_pnl(hedge_mid)
+    }
+
+    pub fn basis_pnl(&self) -> f64 {
+        self.basis_pnl
+    }
+
+    
+    pub fn combined_pnl(&self, primary: &Position, primary_mid: f64, hedge_mid: f64) -> f64 {
+        primary.total_pnl(primary_mid) + self.hedge_pnl(hedge_mid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(bid: f64, ask: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(0, 0, String::new(), &[PriceLevel::new(bid, 1.0)], &[PriceLevel::new(ask, 1.0)])
+    }
+
+    #[test]
+    fn test_hedge_leg_tracks_basis() {
+        let mut leg = HedgeLeg::new(HedgeConfig::default());
+        let mut primary = Position::new();
+        primary.execute_trade(Trade::new(Side::Bid, 100.0, 1.0, 0));
+
+        let hedge = leg.rebalance(primary.quantity, &create_test_snapshot(99.0, 99.0)).unwrap();
+        assert_eq!(hedge.side, Side::Ask);
+        assert_eq!(leg.position().quantity, -1.0);
+        assert!(leg.rebalance(primary.quantity, &create_test_snapshot(99.0, 99.0)).is_none());
+
+        leg.mark(100.0, 99.0);
+        leg.mark(102.0, 100.5);
+
+        assert!((leg.basis_pnl() - 0.5).abs() < 1e-9);
+        assert!((leg.combined_pnl(&primary, 102.0, 100.5) - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,106 @@
+use super::RejectReason;
+use crate::types::Side;
+use serde::{Deserialize, Serialize};
+
+const GRID_EPSILON: f64 = 1e-9;
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Instrument {
+    pub tick_size: f64,
+    pub lot_size: f64,
+    pub min_notional: f64,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct GridReport {
+    pub prices_rounded: usize,
+    pub quantities_rounded: usize,
+    pub rejected: usize,
+}
+
+impl GridReport {
+    pub fn mismatches(&self) -> usize {
+        self.prices_rounded + self.quantities_rounded + self.rejected
+    }
+
+    pub fn print(&self) {
+        println!("\n📐 Instrument Grid:");
+        println!("   Prices Rounded:      {}", self.prices_rounded);
+        println!("   Quantities Rounded:  {}", self.quantities_rounded);
+        println!("   Rejected:            {}", self.rejected);
+    }
+}
+
+impl Instrument {
+    pub fn new(tick_size: f64, lot_size: f64, min_notional: f64) -> Self {
+        Self { tick_size, lot_size, min_notional }
+    }
+
+    
+    
+    pub fn round_price(&self, side: Side, price: f64) -> f64 {
+        if self.tick_size <= 0.0 {
+            return price;
+        }
+        let steps = price / self.tick_size;
+        let steps = match side {
+            Side::Bid => (steps + GRID_EPSILON).floor(),
+            Side::Ask => (steps - GRID_EPSILON).ceil(),
+        };
+        steps * self.tick_size
+    }
+
+    pub fn round_quantity(&self, quantity: f64) -> f64 {
+        if self.lot_size <= 0.0 {
+            return quantity;
+        }
+        (quantity / self.lot_size + GRID_EPSILON).floor() * self.lot_size
+    }
+
+    
+    
+    pub fn conform(&self, side: Side, price: f64, quantity: f64, report: &mut GridReport) -> Result<(f64, f64), RejectReason> {
+        let rounded_price = self.round_price(side, price);
+        let rounded_quantity = self.round_quantity(quantity);
+        if rounded_quantity <= GRID_EPSILON {
+            report.rejected += 1;
+            return Err(RejectReason::BelowLotSize);
+        }
+        if rounded_price * rounded_quantity + GRID_EPSILON < self.min_notional {
+            report.rejected += 1;
+            return Err(RejectReason::BelowMinNotional);
+        }
+        if (rounded_price - price).abs() > self.tick_size * GRID_EPSILON {
+            report.prices_rounded += 1;
+        }
+        if (rounded_quantity - quantity).abs() > self.lot_size * GRID_EPSILON {
+            report.quantities_rounded += 1;
+        }
+        Ok((rounded_price, rounded_quantity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conform_rounds_passively_and_rejects() {
+        let instrument = Instrument::new(0.5, 0.01, 10.0);
+        let mut report = GridReport::default();
+
+        assert_eq!(instrument.conform(Side::Bid, 100.3, 0.105, &mut report), Ok((100.0, 0.1)));
+        let (ask, _) = instrument.conform(Side::Ask, 100.3, 0.1, &mut report).unwrap();
+        assert_eq!(ask, 100.5);
+        assert_eq!(instrument.conform(Side::Bid, 100.5, 0.2, &mut report), Ok((100.5, 0.2)));
+        assert_eq!(instrument.conform(Side::Bid, 100.0, 0.005, &mut report), Err(RejectReason::BelowLotSize));
+        assert_eq!(instrument.conform(Side::Bid, 100.0, 0.05, &mut report), Err(RejectReason::BelowMinNotional));
+
+        assert_eq!(report, GridReport { prices_rounded: 2, quantities_rounded: 1, rejected: 2 });
+    }
+}
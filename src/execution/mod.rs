@@ -1,4 +1,22 @@
 
+pub mod ack;
+pub mod fill_model;
+pub mod hedge;
+pub mod instrument;
+pub mod netting;
+pub mod orders;
 pub mod position;
+pub mod router;
+pub mod sizing;
+pub mod throttle;
 
-pub use position::{Position, PositionStats};
+pub use ack::{AckEvent, AckModel, OrderCallbacks};
+pub use fill_model::{FillIntensity, FillModel, MatchingPriority};
+pub use hedge::{HedgeConfig, HedgeLeg};
+pub use instrument::{GridReport, Instrument};
+pub use netting::{net_trades, NettedFills, NettingMode};
+pub use orders::{Bracket, BracketExit, Order, OrderId, OrderManager, OrderManagerConfig, RejectReason, SelfTradePrevention, Submission, TimeInForce};
+pub use position::{Position, PositionStats, PositionView};
+pub use router::{RoutedOrder, SmartOrderRouter, Venue, VenueFill};
+pub use sizing::{Sizer, SizingContext};
+pub use throttle::Throttle;
@@ -0,0 +1,129 @@
+use crate::types::{Side, Trade};
+
+const QTY_EPSILON: f64 = 1e-12;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NettingMode {
+    #[default]
+    Off,
+    
+    Aggregate,
+    
+    
+    Net,
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct NettedFills {
+    pub trades: Vec<Trade>,
+    
+    pub crossed_pnl: f64,
+}
+
+#[derive(Default)]
+struct SideTotal {
+    quantity: f64,
+    notional: f64,
+}
+
+impl SideTotal {
+    fn add(&mut self, trade: &Trade) {
+        self.quantity += trade.quantity;
+        self.notional += trade.notional();
+    }
+
+    fn vwap(&self) -> f64 {
+        self.notional / self.quantity
+    }
+}
+
+
+
+pub fn net_trades(trades: &[Trade], mode: NettingMode) -> NettedFills {
+    if mode == NettingMode::Off {
+        return NettedFills {
+            trades: trades.to_vec(),
+            crossed_pnl: 0.0,
+        };
+    }
+
+    let mut netted = NettedFills::default();
+    let mut start = 0;
+    while start < trades.len() {
+        let timestamp_us = trades[start].timestamp_us;
+        let end = trades[start..]
+            .iter()
+            .position(|t| t.timestamp_us != timestamp_us)
+            .map_or(trades.len(), |offset| start + offset);
+
+        let mut buys = SideTotal::default();
+        let mut sells = SideTotal::default();
+        for trade in &trades[start..end] {
+            match trade.side {
+                Side::Bid => buys.add(trade),
+                Side::Ask => sells.add(trade),
+            }
+        }
+
+        match mode {
+            NettingMode::Net if buys.quantity > QTY_EPSILON && sells.quantity > QTY_EPSILON => {
+                let crossed = buys.quantity.min(sells.quantity);
+                netted.crossed_pnl += (sells.vwap() - buys.vwap()) * crossed;
+                let (side, total) = if buys.quantity > sells.quantity {
+                    (Side::Bid, &buys)
+                } else {
+                    (Side::Ask, &sells)
+                };
+                let remaining = total.quantity - crossed;
+                if remaining > QTY_EPSILON {
+                    netted.trades.push(Trade::new(side, total.vwap(), remaining, timestamp_us));
+                }
+            }
+            _ => {
+                for (side, total) in [(Side::Bid, &buys), (Side::Ask, &sells)] {
+                    if total.quantity > QTY_EPSILON {
+                        netted.trades.push(Trade::new(side, total.vwap(), total.quantity, timestamp_us));
+                    }
+                }
+            }
+        }
+        start = end;
+    }
+    netted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::Position;
+
+    #[test]
+    fn test_netting_preserves_pnl() {
+        let trades = vec![
+            Trade::new(Side::Bid, 100.0, 1.0, 0),
+            Trade::new(Side::Bid, 101.0, 1.0, 0),
+            Trade::new(Side::Ask, 102.0, 0.5, 0),
+            Trade::new(Side::Ask, 103.0, 1.0, 10),
+        ];
+
+        let aggregated = net_trades(&trades, NettingMode::Aggregate);
+        assert_eq!(aggregated.trades.len(), 3);
+        assert!((aggregated.trades[0].price - 100.5).abs() < 1e-12);
+
+        let netted = net_trades(&trades, NettingMode::Net);
+        assert_eq!(netted.trades.len(), 2);
+        assert_eq!(netted.trades[0].side, Side::Bid);
+        assert!((netted.trades[0].quantity - 1.5).abs() < 1e-12);
+        assert!((netted.crossed_pnl - 0.75).abs() < 1e-12);
+
+        let mut raw = Position::new();
+        trades.iter().for_each(|t| raw.execute_trade(t.clone()));
+        let mut net = Position::new();
+        netted.trades.iter().for_each(|t| net.execute_trade(t.clone()));
+        net.realized_pnl += netted.crossed_pnl;
+        assert!((raw.total_pnl(102.0) - net.total_pnl(102.0)).abs() < 1e-9);
+        assert!((raw.quantity - net.quantity).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,764 @@
+use super::{AckEvent, AckModel, FillModel, GridReport, Instrument, MatchingPriority};
+use crate::types::{L2Snapshot, Side, Trade};
+use crate::utils::rng::SplitMix64;
+use serde::{Deserialize, Serialize};
+
+pub use crate::types::TimeInForce;
+
+pub type OrderId = u64;
+
+const QTY_EPSILON: f64 = 1e-12;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    WouldCross,
+    InsufficientLiquidity,
+    SelfTrade,
+    BelowLotSize,
+    BelowMinNotional,
+    
+    Exchange,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SelfTradePrevention {
+    
+    #[default]
+    Off,
+    
+    CancelResting,
+    
+    CancelAggressing,
+    
+    
+    Decrement,
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct Submission {
+    pub fills: Vec<Trade>,
+    pub resting: Option<Order>,
+    pub rejected: Option<RejectReason>,
+    
+    
+    pub self_trade: Vec<Order>,
+}
+
+impl Submission {
+    fn rejected(reason: RejectReason) -> Self {
+        Self {
+            rejected: Some(reason),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.rejected.is_some()
+    }
+
+    pub fn filled_quantity(&self) -> f64 {
+        self.fills.iter().map(|t| t.quantity).sum()
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Order {
+    pub id: OrderId,
+    pub side: Side,
+    pub price: f64,
+    pub quantity: f64,
+    pub placed_us: u64,
+    pub tif: TimeInForce,
+}
+
+impl Order {
+    
+    pub fn age_us(&self, now_us: u64) -> u64 {
+        now_us.saturating_sub(self.placed_us)
+    }
+
+    
+    pub fn is_crossed_by(&self, snapshot: &L2Snapshot) -> bool {
+        crosses(self.side, self.price, snapshot)
+    }
+}
+
+
+pub fn crosses(side: Side, price: f64, snapshot: &L2Snapshot) -> bool {
+    match side {
+        Side::Bid => snapshot.best_ask() <= price,
+        Side::Ask => snapshot.best_bid() >= price,
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketExit {
+    TakeProfit,
+    StopLoss,
+}
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bracket {
+    pub exit_side: Side,
+    pub quantity: f64,
+    pub take_profit: f64,
+    pub stop_loss: f64,
+}
+
+impl Bracket {
+    
+    pub fn for_entry(entry_side: Side, entry_price: f64, quantity: f64, take_profit_distance: f64, stop_distance: f64) -> Self {
+        match entry_side {
+            Side::Bid => Self {
+                exit_side: Side::Ask,
+                quantity,
+                take_profit: entry_price + take_profit_distance,
+                stop_loss: entry_price - stop_distance,
+            },
+            Side::Ask => Self {
+                exit_side: Side::Bid,
+                quantity,
+                take_profit: entry_price - take_profit_distance,
+                stop_loss: entry_price + stop_distance,
+            },
+        }
+    }
+
+    
+    
+    pub fn check(&self, snapshot: &L2Snapshot) -> Option<(BracketExit, Trade)> {
+        let touch = match self.exit_side {
+            Side::Ask => snapshot.best_bid(),
+            Side::Bid => snapshot.best_ask(),
+        };
+        let (stopped, took_profit) = match self.exit_side {
+            Side::Ask => (touch <= self.stop_loss, touch >= self.take_profit),
+            Side::Bid => (touch >= self.stop_loss, touch <= self.take_profit),
+        };
+        let (exit, price) = if stopped {
+            (BracketExit::StopLoss, touch)
+        } else if took_profit {
+            (BracketExit::TakeProfit, self.take_profit)
+        } else {
+            return None;
+        };
+        Some((exit, Trade::new(self.exit_side, price, self.quantity, snapshot.timestamp_us)))
+    }
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct OrderManagerConfig {
+    
+    
+    pub min_quote_lifetime_us: u64,
+    pub self_trade_prevention: SelfTradePrevention,
+    
+    pub instrument: Option<Instrument>,
+    pub fill_model: FillModel,
+    
+    pub ack_model: Option<AckModel>,
+}
+
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct OrderManager {
+    config: OrderManagerConfig,
+    orders: Vec<Order>,
+    next_id: OrderId,
+    grid: GridReport,
+    rng: Option<SplitMix64>,
+    queues: Vec<QueuePosition>,
+    ack_rng: Option<SplitMix64>,
+    pending: Vec<PendingAck>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingAck {
+    id: OrderId,
+    due_us: u64,
+    reject: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct QueuePosition {
+    id: OrderId,
+    price: f64,
+    ahead: f64,
+    level_quantity: f64,
+}
+
+fn level_quantity(snapshot: &L2Snapshot, side: Side, price: f64) -> f64 {
+    let levels = match side {
+        Side::Bid => snapshot.bids(),
+        Side::Ask => snapshot.asks(),
+    };
+    levels.iter().filter(|level| (level.price - price).abs() < 1e-9).map(|level| level.quantity).sum()
+}
+
+impl OrderManager {
+    pub fn new(config: OrderManagerConfig) -> Self {
+        Self {
+            rng: config.fill_model.intensity.map(|intensity| SplitMix64::new(intensity.seed)),
+            ack_rng: config.ack_model.map(|model| SplitMix64::new(model.seed)),
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self) -> &OrderManagerConfig {
+        &self.config
+    }
+
+    pub fn grid_report(&self) -> &GridReport {
+        &self.grid
+    }
+
+    
+    pub fn place(&mut self, side: Side, price: f64, quantity: f64, timestamp_us: u64) -> Option<Order> {
+        let (price, quantity) = self.conform(side, price, quantity).ok()?;
+        Some(self.rest(side, price, quantity, TimeInForce::Gtc, timestamp_us))
+    }
+
+    fn conform(&mut self, side: Side, price: f64, quantity: f64) -> Result<(f64, f64), RejectReason> {
+        match self.config.instrument {
+            Some(instrument) => instrument.conform(side, price, quantity, &mut self.grid),
+            None => Ok((price, quantity)),
+        }
+    }
+
+    
+    
+    
+    pub fn submit(
+        &mut self,
+        side: Side,
+        price: f64,
+        quantity: f64,
+        tif: TimeInForce,
+        snapshot: &L2Snapshot,
+    ) -> Submission {
+        let timestamp_us = snapshot.timestamp_us;
+        let (price, quantity) = match self.conform(side, price, quantity) {
+            Ok(conformed) => conformed,
+            Err(reason) => return Submission::rejected(reason),
+        };
+
+        if tif == TimeInForce::PostOnly {
+            if crosses(side, price, snapshot) {
+                return Submission::rejected(RejectReason::WouldCross);
+            }
+            return Submission {
+                resting: Some(self.rest(side, price, quantity, tif, timestamp_us)),
+                ..Submission::default()
+            };
+        }
+
+        let own_crossing = move |order: &Order| {
+            order.side == side.opposite()
+                && match side {
+                    Side::Bid => order.price <= price,
+                    Side::Ask => order.price >= price,
+                }
+        };
+        let mut quantity = quantity;
+        let mut self_trade = Vec::new();
+        match self.config.self_trade_prevention {
+            SelfTradePrevention::Off => {}
+            SelfTradePrevention::CancelAggressing => {
+                if self.orders.iter().any(own_crossing) {
+                    return Submission::rejected(RejectReason::SelfTrade);
+                }
+            }
+            SelfTradePrevention::CancelResting => {
+                let (hit, kept): (Vec<Order>, Vec<Order>) = self.orders.drain(..).partition(own_crossing);
+                self.orders = kept;
+                self.pending.retain(|p| !hit.iter().any(|o| o.id == p.id));
+                self_trade = hit;
+            }
+            SelfTradePrevention::Decrement => {
+                for order in self.orders.iter_mut().filter(|order| own_crossing(order)) {
+                    if quantity <= QTY_EPSILON {
+                        break;
+                    }
+                    let overlap = quantity.min(order.quantity);
+                    order.quantity -= overlap;
+                    quantity -= overlap;
+                    self_trade.push(Order { quantity: overlap, ..*order });
+                }
+                let (consumed, kept): (Vec<Order>, Vec<Order>) =
+                    self.orders.drain(..).partition(|order| order.quantity <= QTY_EPSILON);
+                self.orders = kept;
+                self.pending.retain(|p| !consumed.iter().any(|o| o.id == p.id));
+                if quantity <= QTY_EPSILON {
+                    return Submission {
+                        self_trade,
+                        ..Submission::default()
+                    };
+                }
+            }
+        }
+
+        let opposite = match side {
+            Side::Bid => snapshot.asks(),
+            Side::Ask => snapshot.bids(),
+        };
+        let fill_model = self.config.fill_model;
+        let marketable: Vec<_> = opposite
+            .into_iter()
+            .filter(|level| level.quantity > 0.0)
+            .enumerate()
+            .map(|(idx, level)| (level.price, fill_model.available(idx, level.quantity)))
+            .filter(|&(level_price, _)| match side {
+                Side::Bid => level_price <= price,
+                Side::Ask => level_price >= price,
+            })
+            .collect();
+
+        if tif == TimeInForce::Fok {
+            let available: f64 = marketable.iter().map(|&(_, quantity)| quantity).sum();
+            if available + QTY_EPSILON < quantity {
+                return Submission::rejected(RejectReason::InsufficientLiquidity);
+            }
+        }
+
+        let mut fills = Vec::new();
+        let mut remaining = quantity;
+        for (level_price, level_quantity) in marketable {
+            if remaining <= QTY_EPSILON {
+                break;
+            }
+            let fill_qty = remaining.min(level_quantity);
+            fills.push(Trade::new(side, level_price, fill_qty, timestamp_us));
+            remaining -= fill_qty;
+        }
+
+        let resting = (tif == TimeInForce::Gtc && remaining > QTY_EPSILON)
+            .then(|| self.rest(side, price, remaining, tif, timestamp_us));
+
+        Submission {
+            fills,
+            resting,
+            rejected: None,
+            self_trade,
+        }
+    }
+
+    fn rest(&mut self, side: Side, price: f64, quantity: f64, tif: TimeInForce, timestamp_us: u64) -> Order {
+        let order = Order {
+            id: self.next_id,
+            side,
+            price,
+            quantity,
+            placed_us: timestamp_us,
+            tif,
+        };
+        self.next_id += 1;
+        self.orders.push(order);
+        if let (Some(model), Some(rng)) = (self.config.ack_model, self.ack_rng.as_mut()) {
+            self.pending.push(PendingAck {
+                id: order.id,
+                due_us: timestamp_us + model.ack_latency_us,
+                reject: rng.next_f64() < model.reject_probability,
+            });
+        }
+        order
+    }
+
+    pub fn is_acked(&self, id: OrderId) -> bool {
+        !self.pending.iter().any(|p| p.id == id)
+    }
+
+    
+    
+    pub fn poll_acks(&mut self, now_us: u64) -> Vec<AckEvent> {
+        let (due, waiting): (Vec<PendingAck>, Vec<PendingAck>) = self.pending.drain(..).partition(|p| p.due_us <= now_us);
+        self.pending = waiting;
+        let mut events = Vec::with_capacity(due.len());
+        for ack in due {
+            let Some(idx) = self.orders.iter().position(|o| o.id == ack.id) else {
+                continue;
+            };
+            if ack.reject {
+                events.push(AckEvent::Rejected(self.orders.remove(idx), RejectReason::Exchange));
+            } else {
+                events.push(AckEvent::Acked(self.orders[idx]));
+            }
+        }
+        events
+    }
+
+    
+    pub fn can_replace(&self, id: OrderId, timestamp_us: u64) -> bool {
+        self.get(id)
+            .is_some_and(|order| order.age_us(timestamp_us) >= self.config.min_quote_lifetime_us)
+    }
+
+    
+    
+    
+    pub fn replace(&mut self, id: OrderId, price: f64, quantity: f64, snapshot: &L2Snapshot) -> Option<Order> {
+        let timestamp_us = snapshot.timestamp_us;
+        if !self.can_replace(id, timestamp_us) {
+            return None;
+        }
+        let Order { side, tif, .. } = *self.get(id)?;
+        let (price, quantity) = self.conform(side, price, quantity).ok()?;
+        if tif == TimeInForce::PostOnly && crosses(side, price, snapshot) {
+            return None;
+        }
+        let order = self.orders.iter_mut().find(|o| o.id == id)?;
+        order.price = price;
+        order.quantity = quantity;
+        order.placed_us = timestamp_us;
+        Some(*order)
+    }
+
+    pub fn cancel(&mut self, id: OrderId) -> Option<Order> {
+        self.pending.retain(|p| p.id != id);
+        let idx = self.orders.iter().position(|o| o.id == id)?;
+        Some(self.orders.remove(idx))
+    }
+
+    pub fn cancel_side(&mut self, side: Side) -> Vec<Order> {
+        let (cancelled, kept): (Vec<Order>, Vec<Order>) = self.orders.drain(..).partition(|o| o.side == side);
+        self.orders = kept;
+        self.pending.retain(|p| !cancelled.iter().any(|o| o.id == p.id));
+        cancelled
+    }
+
+    pub fn get(&self, id: OrderId) -> Option<&Order> {
+        self.orders.iter().find(|o| o.id == id)
+    }
+
+    
+    pub fn first(&self, side: Side) -> Option<&Order> {
+        self.orders.iter().find(|o| o.side == side)
+    }
+
+    pub fn orders(&self) -> &[Order] {
+        &self.orders
+    }
+
+    
+    
+    pub fn match_resting(&mut self, snapshot: &L2Snapshot) -> Vec<Order> {
+        let pending = &self.pending;
+        let (mut unacked, live): (Vec<Order>, Vec<Order>) = self.orders.drain(..).partition(|o| pending.iter().any(|p| p.id == o.id));
+        let (mut crossed, resting): (Vec<Order>, Vec<Order>) = live.into_iter().partition(|o| o.is_crossed_by(snapshot));
+        self.orders = resting;
+        let mut queued = self.match_queues(snapshot);
+        if let (Some(intensity), Some(rng)) = (self.config.fill_model.intensity, self.rng.as_mut()) {
+            let (hit, kept): (Vec<Order>, Vec<Order>) = self
+                .orders
+                .drain(..)
+                .partition(|o| rng.next_f64() < intensity.probability(o.side, o.price, snapshot));
+            self.orders = kept;
+            crossed.extend(hit);
+        }
+        let fraction = self.config.fill_model.touch_fill_fraction();
+        let mut filled = Vec::with_capacity(crossed.len());
+        for mut order in crossed {
+            let touch = match order.side {
+                Side::Bid => snapshot.best_ask(),
+                Side::Ask => snapshot.best_bid(),
+            };
+            if touch == order.price && fraction < 1.0 {
+                let fill_qty = order.quantity * fraction;
+                let remaining = order.quantity - fill_qty;
+                if remaining > QTY_EPSILON {
+                    self.orders.push(Order { quantity: remaining, ..order });
+                }
+                order.quantity = fill_qty;
+            }
+            filled.push(order);
+        }
+        filled.append(&mut queued);
+        self.orders.append(&mut unacked);
+        filled
+    }
+
+    
+    
+    fn match_queues(&mut self, snapshot: &L2Snapshot) -> Vec<Order> {
+        let priority = self.config.fill_model.priority;
+        if priority == MatchingPriority::CrossOnly {
+            return Vec::new();
+        }
+        let mut filled = Vec::new();
+        let mut queues = Vec::with_capacity(self.orders.len());
+        for order in self.orders.iter_mut() {
+            let current = level_quantity(snapshot, order.side, order.price);
+            let queue = match self.queues.iter().find(|q| q.id == order.id && q.price == order.price) {
+                Some(queue) => {
+                    let traded = (queue.level_quantity - current).max(0.0);
+                    let (fill_qty, ahead) = priority.allocate(traded, queue.ahead, queue.level_quantity, order.quantity);
+                    if fill_qty > QTY_EPSILON {
+                        filled.push(Order { quantity: fill_qty, ..*order });
+                        order.quantity -= fill_qty;
+                    }
+                    QueuePosition { ahead, level_quantity: current, ..*queue }
+                }
+                None => QueuePosition { id: order.id, price: order.price, ahead: current, level_quantity: current },
+            };
+            queues.push(queue);
+        }
+        self.orders.retain(|order| order.quantity > QTY_EPSILON);
+        self.queues = queues;
+        filled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::FillIntensity;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(bid: f64, ask: f64) -> L2Snapshot {
+        let bids: Vec<_> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0)).collect();
+        let asks: Vec<_> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, 0, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_bracket_exits() {
+        let long = Bracket::for_entry(Side::Bid, 101.0, 1.0, 2.0, 1.0);
+        assert_eq!((long.exit_side, long.take_profit, long.stop_loss), (Side::Ask, 103.0, 100.0));
+        assert!(long.check(&create_test_snapshot(101.0, 102.0)).is_none());
+
+        let (exit, trade) = long.check(&create_test_snapshot(104.0, 105.0)).unwrap();
+        assert_eq!((exit, trade.side, trade.price), (BracketExit::TakeProfit, Side::Ask, 103.0));
+
+        let (exit, trade) = long.check(&create_test_snapshot(99.5, 100.5)).unwrap();
+        assert_eq!((exit, trade.price), (BracketExit::StopLoss, 99.5));
+
+        let short = Bracket::for_entry(Side::Ask, 100.0, 1.0, 2.0, 1.0);
+        let (exit, trade) = short.check(&create_test_snapshot(100.0, 101.5)).unwrap();
+        assert_eq!((exit, trade.side, trade.price), (BracketExit::StopLoss, Side::Bid, 101.5));
+    }
+
+    #[test]
+    fn test_min_quote_lifetime_blocks_replace() {
+        let mut manager = OrderManager::new(OrderManagerConfig {
+            min_quote_lifetime_us: 500,
+            ..Default::default()
+        });
+        let bid = manager.place(Side::Bid, 99.0, 1.0, 1_000).unwrap();
+        let at = |timestamp_us| {
+            let mut snapshot = create_test_snapshot(100.0, 101.0);
+            snapshot.timestamp_us = timestamp_us;
+            snapshot
+        };
+
+        assert!(manager.replace(bid.id, 99.5, 1.0, &at(1_200)).is_none());
+        assert_eq!(manager.get(bid.id).unwrap().price, 99.0);
+
+        let replaced = manager.replace(bid.id, 99.5, 1.0, &at(1_500)).unwrap();
+        assert_eq!(replaced.placed_us, 1_500);
+        assert!(!manager.can_replace(bid.id, 1_600));
+        assert!(manager.cancel(bid.id).is_some());
+        assert!(manager.replace(bid.id, 99.0, 1.0, &at(5_000)).is_none());
+    }
+
+    #[test]
+    fn test_match_resting() {
+        let mut manager = OrderManager::default();
+        manager.place(Side::Bid, 99.0, 1.0, 0);
+        manager.place(Side::Ask, 101.0, 1.0, 0);
+
+        let filled = manager.match_resting(&create_test_snapshot(98.0, 98.5));
+        assert_eq!(filled.len(), 1);
+        assert_eq!(filled[0].side, Side::Bid);
+        assert_eq!(manager.orders().len(), 1);
+        assert!(manager.first(Side::Bid).is_none());
+    }
+
+    #[test]
+    fn test_time_in_force() {
+        let snapshot = create_test_snapshot(99.0, 100.0);
+        let mut manager = OrderManager::default();
+
+        let post = manager.submit(Side::Bid, 100.0, 1.0, TimeInForce::PostOnly, &snapshot);
+        assert_eq!(post.rejected, Some(RejectReason::WouldCross));
+        let post = manager.submit(Side::Bid, 99.5, 1.0, TimeInForce::PostOnly, &snapshot);
+        let resting = post.resting.unwrap();
+        assert_eq!(resting.tif, TimeInForce::PostOnly);
+        assert!(manager.replace(resting.id, 100.0, 1.0, &snapshot).is_none());
+        assert_eq!(manager.get(resting.id).unwrap().price, 99.5);
+
+        let ioc = manager.submit(Side::Bid, 101.0, 3.0, TimeInForce::Ioc, &snapshot);
+        assert_eq!(ioc.fills.len(), 2);
+        assert!((ioc.filled_quantity() - 2.0).abs() < 1e-12);
+        assert!(ioc.resting.is_none());
+
+        let fok = manager.submit(Side::Ask, 98.0, 3.0, TimeInForce::Fok, &snapshot);
+        assert_eq!(fok.rejected, Some(RejectReason::InsufficientLiquidity));
+        let fok = manager.submit(Side::Ask, 97.0, 3.0, TimeInForce::Fok, &snapshot);
+        assert_eq!(fok.fills.iter().map(|t| t.price).collect::<Vec<_>>(), vec![99.0, 98.0, 97.0]);
+
+        let gtc = manager.submit(Side::Ask, 98.5, 2.0, TimeInForce::Gtc, &snapshot);
+        assert!((gtc.filled_quantity() - 1.0).abs() < 1e-12);
+        assert_eq!(gtc.resting.unwrap().quantity, 1.0);
+        assert_eq!(manager.orders().len(), 2);
+    }
+
+    #[test]
+    fn test_hidden_liquidity_fill_model() {
+        let snapshot = create_test_snapshot(99.0, 100.0);
+        let mut manager = OrderManager::new(OrderManagerConfig {
+            fill_model: FillModel::default().with_hidden_liquidity(1.0),
+            ..Default::default()
+        });
+
+        let ioc = manager.submit(Side::Bid, 101.0, 2.0, TimeInForce::Ioc, &snapshot);
+        assert_eq!(ioc.fills.iter().map(|t| t.price).collect::<Vec<_>>(), vec![100.0]);
+
+        manager.place(Side::Bid, 98.0, 1.0, 0);
+        manager.place(Side::Ask, 101.0, 1.0, 0);
+        let filled = manager.match_resting(&create_test_snapshot(97.0, 98.0));
+        assert_eq!(filled[0].quantity, 0.5);
+        assert_eq!(manager.first(Side::Bid).unwrap().quantity, 0.5);
+
+        let filled = manager.match_resting(&create_test_snapshot(96.0, 97.0));
+        assert_eq!(filled[0].quantity, 0.5);
+        assert!(manager.first(Side::Bid).is_none());
+    }
+
+    #[test]
+    fn test_probabilistic_fills_are_seeded() {
+        let fills = |seed| {
+            let mut manager = OrderManager::new(OrderManagerConfig {
+                fill_model: FillModel::default().with_intensity(FillIntensity { touch_rate: 0.3, seed, ..Default::default() }),
+                ..Default::default()
+            });
+            (0..50)
+                .map(|i| {
+                    manager.place(Side::Bid, 99.0, 1.0, i);
+                    manager.match_resting(&create_test_snapshot(99.0, 100.0)).len()
+                })
+                .collect::<Vec<_>>()
+        };
+        let run = fills(7);
+        assert_eq!(run, fills(7));
+        assert!(run.iter().sum::<usize>() > 0);
+        assert!(run.iter().sum::<usize>() < 50);
+    }
+
+    #[test]
+    fn test_matching_priority_allocates_queue_volume() {
+        let book = |bid_qty: f64| {
+            L2Snapshot::from_levels(0, 0, String::new(), &[PriceLevel::new(99.0, bid_qty)], &[PriceLevel::new(100.0, 1.0)])
+        };
+        let fills = |priority| {
+            let mut manager = OrderManager::new(OrderManagerConfig {
+                fill_model: FillModel::default().with_priority(priority),
+                ..Default::default()
+            });
+            manager.place(Side::Bid, 99.0, 1.0, 0);
+            [4.0, 6.0, 1.0]
+                .iter()
+                .map(|&qty| manager.match_resting(&book(qty)).iter().map(|o| o.quantity).sum::<f64>())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(fills(MatchingPriority::CrossOnly), vec![0.0, 0.0, 0.0]);
+        assert_eq!(fills(MatchingPriority::PriceTime), vec![0.0, 0.0, 1.0]);
+        let pro_rata = fills(MatchingPriority::ProRata);
+        assert!((pro_rata[2] - 5.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ack_latency_and_rejects() {
+        #[derive(Default)]
+        struct Counter {
+            acked: usize,
+            rejected: usize,
+        }
+        impl crate::execution::OrderCallbacks for Counter {
+            fn on_order_acked(&mut self, _order: &Order) {
+                self.acked += 1;
+            }
+            fn on_order_rejected(&mut self, _order: &Order, reason: RejectReason) {
+                assert_eq!(reason, RejectReason::Exchange);
+                self.rejected += 1;
+            }
+        }
+
+        let mut manager = OrderManager::new(OrderManagerConfig {
+            ack_model: Some(AckModel { ack_latency_us: 100, reject_probability: 0.0, seed: 1 }),
+            ..Default::default()
+        });
+        let bid = manager.place(Side::Bid, 99.0, 1.0, 0).unwrap();
+        assert!(manager.poll_acks(50).is_empty());
+        assert!(manager.match_resting(&create_test_snapshot(97.0, 98.0)).is_empty());
+        assert_eq!(manager.poll_acks(100), vec![AckEvent::Acked(bid)]);
+        assert!(manager.is_acked(bid.id));
+        assert_eq!(manager.match_resting(&create_test_snapshot(97.0, 98.0)).len(), 1);
+
+        let mut rejecting = OrderManager::new(OrderManagerConfig {
+            ack_model: Some(AckModel { reject_probability: 0.5, ..Default::default() }),
+            ..Default::default()
+        });
+        let mut counter = Counter::default();
+        for i in 0..40 {
+            rejecting.place(Side::Bid, 99.0, 1.0, i);
+            for event in rejecting.poll_acks(i) {
+                event.deliver(&mut counter);
+            }
+        }
+        assert_eq!(counter.acked + counter.rejected, 40);
+        assert!(counter.rejected > 0 && counter.acked > 0);
+        assert_eq!(rejecting.orders().len(), counter.acked);
+    }
+
+    #[test]
+    fn test_self_trade_prevention_modes() {
+        let snapshot = create_test_snapshot(99.0, 100.0);
+        let manager_with = |self_trade_prevention| {
+            let mut manager = OrderManager::new(OrderManagerConfig {
+                self_trade_prevention,
+                ack_model: Some(AckModel { ack_latency_us: 100, reject_probability: 0.0, seed: 1 }),
+                ..Default::default()
+            });
+            manager.place(Side::Ask, 99.5, 0.5, 0);
+            manager.place(Side::Ask, 101.5, 1.0, 0);
+            manager
+        };
+
+        let mut off = manager_with(SelfTradePrevention::Off);
+        let hedge = off.submit(Side::Bid, 100.0, 1.0, TimeInForce::Ioc, &snapshot);
+        assert!(hedge.self_trade.is_empty());
+        assert_eq!(off.orders().len(), 2);
+
+        let mut resting = manager_with(SelfTradePrevention::CancelResting);
+        let hedge = resting.submit(Side::Bid, 100.0, 1.0, TimeInForce::Ioc, &snapshot);
+        assert_eq!(hedge.self_trade.len(), 1);
+        assert!((hedge.filled_quantity() - 1.0).abs() < 1e-12);
+        assert_eq!(resting.orders().len(), 1);
+        assert!(resting.is_acked(hedge.self_trade[0].id));
+
+        let mut aggressing = manager_with(SelfTradePrevention::CancelAggressing);
+        let hedge = aggressing.submit(Side::Bid, 100.0, 1.0, TimeInForce::Ioc, &snapshot);
+        assert_eq!(hedge.rejected, Some(RejectReason::SelfTrade));
+        assert_eq!(aggressing.orders().len(), 2);
+
+        let mut decrement = manager_with(SelfTradePrevention::Decrement);
+        let hedge = decrement.submit(Side::Bid, 100.0, 1.0, TimeInForce::Ioc, &snapshot);
+        assert_eq!(hedge.self_trade[0].quantity, 0.5);
+        assert!((hedge.filled_quantity() - 0.5).abs() < 1e-12);
+        assert_eq!(decrement.orders().len(), 1);
+        assert!(decrement.is_acked(hedge.self_trade[0].id));
+        assert!(!decrement.is_acked(decrement.orders()[0].id));
+    }
+}
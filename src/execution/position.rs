@@ -10,6 +10,51 @@
 use crate::types::{Side, Trade};
 
 
+pub trait PositionView {
+    fn quantity(&self) -> f64;
+
+    fn avg_entry_price(&self) -> f64;
+
+    fn realized_pnl(&self) -> f64;
+
+    fn trade_count(&self) -> usize;
+
+    
+    fn last_fill_us(&self) -> Option<u64>;
+
+    fn unrealized_pnl(&self, current_price: f64) -> f64 {
+        let qty = self.quantity();
+        if qty.abs() < 1e-10 {
+            return 0.0;
+        }
+        if qty > 0.0 {
+            (current_price - self.avg_entry_price()) * qty
+        } else {
+            (self.avg_entry_price() - current_price) * qty.abs()
+        }
+    }
+
+    fn total_pnl(&self, current_price: f64) -> f64 {
+        self.realized_pnl() + self.unrealized_pnl(current_price)
+    }
+
+    fn equity(&self, starting_capital: f64, current_price: f64) -> f64 {
+        starting_capital + self.total_pnl(current_price)
+    }
+
+    fn is_long(&self) -> bool {
+        self.quantity() > 1e-10
+    }
+
+    fn is_short(&self) -> bool {
+        self.quantity() < -1e-10
+    }
+
+    fn is_flat(&self) -> bool {
+        self.quantity().abs() < 1e-10
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Position {
     
@@ -94,11 +139,7 @@ impl Position {
         }
 
         let is_long = self.quantity > 0.0;
-        let is_closing = match (is_long, side) {
-            (true, Side::Ask) => true,   
-            (false, Side::Bid) => true,  
-            _ => false,                  
-        };
+        let is_closing = matches!((is_long, side), (true, Side::Ask) | (false, Side::Bid));
 
         if !is_closing {
             return 0.0;
@@ -108,14 +149,11 @@ impl Position {
         let closing_qty = quantity.min(self.quantity.abs());
 
         
-        
-        let pnl = if is_long {
+        if is_long {
             (price - self.avg_entry_price) * closing_qty
         } else {
             (self.avg_entry_price - price) * closing_qty
-        };
-
-        pnl
+        }
     }
 
     
@@ -144,11 +182,7 @@ impl Position {
         }
 
         
-        let is_adding = match (old_long, side) {
-            (true, Side::Bid) => true,   
-            (false, Side::Ask) => true,  
-            _ => false,
-        };
+        let is_adding = matches!((old_long, side), (true, Side::Bid) | (false, Side::Ask));
 
         if is_adding && old_qty.abs() > 1e-10 {
             
@@ -184,6 +218,11 @@ impl Position {
     }
 
     
+    pub fn equity(&self, starting_capital: f64, current_price: f64) -> f64 {
+        starting_capital + self.total_pnl(current_price)
+    }
+
+    
     pub fn is_long(&self) -> bool {
         self.quantity > 1e-10
     }
@@ -253,6 +292,28 @@ impl Default for Position {
     }
 }
 
+impl PositionView for Position {
+    fn quantity(&self) -> f64 {
+        self.quantity
+    }
+
+    fn avg_entry_price(&self) -> f64 {
+        self.avg_entry_price
+    }
+
+    fn realized_pnl(&self) -> f64 {
+        self.realized_pnl
+    }
+
+    fn trade_count(&self) -> usize {
+        self.trade_count
+    }
+
+    fn last_fill_us(&self) -> Option<u64> {
+        self.trades.last().map(|t| t.timestamp_us)
+    }
+}
+
 
 #[derive(Debug, Clone)]
 pub struct PositionStats {
@@ -412,4 +473,31 @@ mod tests {
         assert!(pos.is_flat());
         assert_eq!(pos.realized_pnl, 10.0);  
     }
+
+    #[test]
+    fn test_naive_positions_match_through_view() {
+        use crate::trivial_approach::{CachedNaivePosition, NaivePosition};
+
+        let fills = [
+            Trade::new(Side::Bid, 100.0, 2.0, 10),
+            Trade::new(Side::Bid, 102.0, 1.0, 20),
+        ];
+        let mut position = Position::new();
+        let mut naive = NaivePosition::new();
+        let mut cached = CachedNaivePosition::new();
+        for fill in &fills {
+            position.execute_trade(fill.clone());
+            naive.execute_trade(fill.clone());
+            cached.execute_trade(fill.clone());
+        }
+
+        let views: [&dyn PositionView; 3] = [&position, &naive, &cached];
+        for view in views {
+            assert!((view.quantity() - 3.0).abs() < 1e-9);
+            assert!((view.avg_entry_price() - position.avg_entry_price).abs() < 1e-9);
+            assert_eq!(view.trade_count(), 2);
+            assert_eq!(view.last_fill_us(), Some(20));
+            assert!(view.is_long());
+        }
+    }
 }
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{L2Snapshot, Side, Trade};
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Venue {
+    pub name: String,
+    
+    pub fee_bps: f64,
+}
+
+impl Venue {
+    pub fn new(name: &str, fee_bps: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            fee_bps,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct VenueFill {
+    pub venue: String,
+    pub trade: Trade,
+    pub fee: f64,
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct RoutedOrder {
+    pub fills: Vec<VenueFill>,
+    pub filled: f64,
+    pub unfilled: f64,
+    
+    pub all_in_price: f64,
+}
+
+impl RoutedOrder {
+    pub fn fill_for(&self, venue: &str) -> Option<&VenueFill> {
+        self.fills.iter().find(|f| f.venue == venue)
+    }
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct SmartOrderRouter {
+    venues: Vec<Venue>,
+}
+
+impl SmartOrderRouter {
+    pub fn new(venues: Vec<Venue>) -> Self {
+        Self { venues }
+    }
+
+    pub fn venues(&self) -> &[Venue] {
+        &self.venues
+    }
+
+    
+    pub fn route(&self, side: Side, quantity: f64, books: &[&L2Snapshot]) -> RoutedOrder {
+        assert_eq!(books.len(), self.venues.len(), "one book per venue is required");
+
+        let sign = match side {
+            Side::Bid => 1.0,
+            Side::Ask => -1.0,
+        };
+        let mut candidates: Vec<(f64, usize, f64, f64)> = Vec::new();
+        for (v, (venue, book)) in self.venues.iter().zip(books).enumerate() {
+            let levels = match side {
+                Side::Bid => book.asks(),
+                Side::Ask => book.bids(),
+            };
+            for level in levels.into_iter().filter(|l| l.quantity > 0.0 && l.price > 0.0) {
+                let all_in = level.price * (1.0 + sign * venue.fee_bps / 10_000.0);
+                candidates.push((all_in, v, level.price, level.quantity));
+            }
+        }
+        candidates.sort_by(|a, b| (sign * a.0).total_cmp(&(sign * b.0)));
+
+        let mut per_venue = vec![(0.0, 0.0); self.venues.len()];
+        let mut remaining = quantity;
+        let mut all_in_notional = 0.0;
+        for (all_in, v, price, available) in candidates {
+            if remaining <= 0.0 {
+                break;
+            }
+            let take = remaining.min(available);
+            per_venue[v].0 += take;
+            per_venue[v].1 += take * price;
+            all_in_notional += take * all_in;
+            remaining -= take;
+        }
+
+        let timestamp_us = books.iter().map(|b| b.timestamp_us).max().unwrap_or(0);
+        let fills: Vec<VenueFill> = per_venue
+            .iter()
+            .zip(&self.venues)
+            .filter(|((qty, _), _)| *qty > 0.0)
+            .map(|(&(qty, notional), venue)| VenueFill {
+                venue: venue.name.clone(),
+                trade: Trade::new(side, notional / qty, qty, timestamp_us),
+                fee: notional * venue.fee_bps / 10_000.0,
+            })
+            .collect();
+        let filled = quantity - remaining.max(0.0);
+
+        RoutedOrder {
+            fills,
+            filled,
+            unfilled: remaining.max(0.0),
+            all_in_price: if filled > 0.0 { all_in_notional / filled } else { 0.0 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn book(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> L2Snapshot {
+        let levels = |side: &[(f64, f64)]| side.iter().map(|&(p, q)| PriceLevel::new(p, q)).collect::<Vec<_>>();
+        L2Snapshot::from_levels(0, 0, String::new(), &levels(bids), &levels(asks))
+    }
+
+    #[test]
+    fn test_routes_by_depth_and_fees() {
+        let router = SmartOrderRouter::new(vec![Venue::new("cheap_fee", 0.0), Venue::new("tight_book", 20.0)]);
+        let a = book(&[(99.9, 1.0)], &[(100.1, 1.0), (100.2, 2.0)]);
+        let b = book(&[(100.0, 1.0)], &[(100.0, 1.0), (100.5, 5.0)]);
+
+        let buy = router.route(Side::Bid, 3.0, &[&a, &b]);
+        assert_eq!((buy.filled, buy.unfilled), (3.0, 0.0));
+        assert_eq!(buy.fill_for("cheap_fee").unwrap().trade.quantity, 3.0);
+        assert!(buy.fill_for("tight_book").is_none());
+
+        let sell = router.route(Side::Ask, 1.5, &[&a, &b]);
+        let tight = sell.fill_for("tight_book").unwrap();
+        assert_eq!((tight.trade.quantity, tight.trade.price), (0.5, 100.0));
+        assert!((tight.fee - 0.1).abs() < 1e-9);
+        assert_eq!(sell.fill_for("cheap_fee").unwrap().trade.quantity, 1.0);
+        assert!((sell.all_in_price - (99.9 + 0.5 * 99.8) / 1.5).abs() < 1e-9);
+
+        let sweep = router.route(Side::Bid, 20.0, &[&a, &b]);
+        assert_eq!((sweep.filled, sweep.unfilled), (9.0, 11.0));
+    }
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Sizer {
+    
+    FixedQuantity { quantity: f64 },
+    
+    FixedNotional { notional: f64 },
+    
+    
+    VolatilityTarget { risk_bps: f64, max_quantity: f64 },
+    
+    
+    Kelly { fraction: f64, cap: f64 },
+}
+
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SizingContext {
+    pub equity: f64,
+    pub price: f64,
+    
+    pub volatility_bps: f64,
+    
+    pub edge_bps: f64,
+}
+
+impl Sizer {
+    
+    pub fn size(&self, ctx: &SizingContext) -> f64 {
+        if ctx.price <= 0.0 {
+            return 0.0;
+        }
+        let equity = ctx.equity.max(0.0);
+        let quantity = match *self {
+            Sizer::FixedQuantity { quantity } => quantity,
+            Sizer::FixedNotional { notional } => notional / ctx.price,
+            Sizer::VolatilityTarget { risk_bps, max_quantity } => {
+                if ctx.volatility_bps <= 0.0 {
+                    return 0.0;
+                }
+                (equity * risk_bps / (ctx.price * ctx.volatility_bps)).min(max_quantity)
+            }
+            Sizer::Kelly { fraction, cap } => {
+                if ctx.volatility_bps <= 0.0 || ctx.edge_bps <= 0.0 {
+                    return 0.0;
+                }
+                let variance = (ctx.volatility_bps / 10_000.0).powi(2);
+                let kelly = (ctx.edge_bps / 10_000.0) / variance;
+                equity * (kelly * fraction).min(cap) / ctx.price
+            }
+        };
+        quantity.max(0.0)
+    }
+
+    
+    pub fn needs_volatility(&self) -> bool {
+        matches!(self, Sizer::VolatilityTarget { .. } | Sizer::Kelly { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizers() {
+        let ctx = SizingContext {
+            equity: 100_000.0,
+            price: 50_000.0,
+            volatility_bps: 10.0,
+            edge_bps: 0.5,
+        };
+
+        assert_eq!(Sizer::FixedQuantity { quantity: 0.1 }.size(&ctx), 0.1);
+        assert!((Sizer::FixedNotional { notional: 5_000.0 }.size(&ctx) - 0.1).abs() < 1e-12);
+
+        let vol_target = Sizer::VolatilityTarget { risk_bps: 2.0, max_quantity: 1.0 };
+        assert!((vol_target.size(&ctx) - 0.4).abs() < 1e-12);
+        assert_eq!(vol_target.size(&SizingContext { volatility_bps: 0.1, ..ctx }), 1.0);
+        assert_eq!(vol_target.size(&SizingContext { volatility_bps: 0.0, ..ctx }), 0.0);
+
+        let kelly = Sizer::Kelly { fraction: 0.5, cap: 2.0 };
+        assert!((kelly.size(&ctx) - 2.0 * 100_000.0 / 50_000.0).abs() < 1e-9);
+        let small_edge = SizingContext { edge_bps: 0.005, ..ctx };
+        assert!((kelly.size(&small_edge) - 0.25 * 2.0).abs() < 1e-9);
+        assert_eq!(kelly.size(&SizingContext { edge_bps: -1.0, ..ctx }), 0.0);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+
+const WINDOW_US: u64 = 1_000_000;
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    max_per_second: usize,
+    sent: VecDeque<u64>,
+    accepted: usize,
+    rejected: usize,
+}
+
+impl Throttle {
+    pub fn new(max_per_second: usize) -> Self {
+        Self {
+            max_per_second,
+            sent: VecDeque::with_capacity(max_per_second),
+            accepted: 0,
+            rejected: 0,
+        }
+    }
+
+    
+    pub fn unlimited() -> Self {
+        Self::new(0)
+    }
+
+    pub fn is_limited(&self) -> bool {
+        self.max_per_second > 0
+    }
+
+    
+    
+    pub fn try_acquire(&mut self, timestamp_us: u64) -> bool {
+        if !self.is_limited() {
+            self.accepted += 1;
+            return true;
+        }
+
+        while self
+            .sent
+            .front()
+            .is_some_and(|&sent| timestamp_us.saturating_sub(sent) >= WINDOW_US)
+        {
+            self.sent.pop_front();
+        }
+
+        if self.sent.len() >= self.max_per_second {
+            self.rejected += 1;
+            return false;
+        }
+
+        self.sent.push_back(timestamp_us);
+        self.accepted += 1;
+        true
+    }
+
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    pub fn rejected(&self) -> usize {
+        self.rejected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_window() {
+        let mut throttle = Throttle::new(2);
+        assert!(throttle.try_acquire(0));
+        assert!(throttle.try_acquire(100));
+        assert!(!throttle.try_acquire(500_000));
+        assert!(!throttle.try_acquire(999_999));
+        assert!(throttle.try_acquire(1_000_000));
+        assert!(!throttle.try_acquire(1_000_050));
+        assert!(throttle.try_acquire(1_000_100));
+
+        assert_eq!(throttle.accepted(), 4);
+        assert_eq!(throttle.rejected(), 3);
+
+        let mut unlimited = Throttle::unlimited();
+        assert!((0..1_000).all(|_| unlimited.try_acquire(0)));
+        assert_eq!(unlimited.rejected(), 0);
+    }
+}
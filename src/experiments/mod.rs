@@ -0,0 +1,310 @@
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::analytics::BacktestResult;
+use crate::types::L2Snapshot;
+use crate::utils::hash::{hash_hex, Fnv1a};
+use crate::utils::provenance::git_commit;
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatasetRange {
+    pub path: String,
+    pub first_timestamp_us: u64,
+    pub last_timestamp_us: u64,
+    pub snapshots: usize,
+    #[serde(default)]
+    pub first_row: usize,
+    #[serde(default)]
+    pub last_row: usize,
+    
+    #[serde(default)]
+    pub content_hash: String,
+}
+
+impl DatasetRange {
+    pub fn from_snapshots(path: &Path, snapshots: &[L2Snapshot]) -> Self {
+        Self {
+            path: path.display().to_string(),
+            first_timestamp_us: snapshots.first().map(|s| s.timestamp_us).unwrap_or(0),
+            last_timestamp_us: snapshots.last().map(|s| s.timestamp_us).unwrap_or(0),
+            snapshots: snapshots.len(),
+            first_row: snapshots.first().map(|s| s.row_index).unwrap_or(0),
+            last_row: snapshots.last().map(|s| s.row_index).unwrap_or(0),
+            content_hash: content_hash(snapshots),
+        }
+    }
+}
+
+
+pub fn content_hash(snapshots: &[L2Snapshot]) -> String {
+    let mut hasher = Fnv1a::new();
+    for snapshot in snapshots {
+        hasher.write_u64(snapshot.timestamp_us);
+        for level in snapshot.bids().iter().chain(snapshot.asks().iter()) {
+            hasher.write_f64(level.price);
+            hasher.write_f64(level.quantity);
+        }
+    }
+    hasher.hex()
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadlineMetrics {
+    pub total_pnl: f64,
+    pub realized_pnl: f64,
+    pub total_trades: usize,
+    pub win_rate: f64,
+    pub total_volume: f64,
+    pub time_per_snapshot_ns: f64,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentRecord {
+    pub run_id: String,
+    pub timestamp: String,
+    pub strategy: String,
+    pub config_hash: String,
+    pub parameters: serde_json::Value,
+    pub git_commit: Option<String>,
+    pub dataset: DatasetRange,
+    pub metrics: HeadlineMetrics,
+}
+
+static RUN_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+impl ExperimentRecord {
+    pub fn new(result: &BacktestResult, parameters: serde_json::Value, dataset: DatasetRange) -> Self {
+        let config_hash = config_hash(&result.name, &parameters);
+        let now = chrono::Local::now();
+
+        Self {
+            run_id: format!(
+                "{}-{}-{}",
+                now.format("%Y%m%d%H%M%S%6f"),
+                &config_hash[..8],
+                RUN_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+            ),
+            timestamp: now.to_rfc3339(),
+            strategy: result.name.clone(),
+            config_hash,
+            parameters,
+            git_commit: git_commit(),
+            dataset,
+            metrics: HeadlineMetrics {
+                total_pnl: result.metrics.total_pnl,
+                realized_pnl: result.metrics.realized_pnl,
+                total_trades: result.metrics.total_trades,
+                win_rate: result.metrics.win_rate,
+                total_volume: result.metrics.total_volume,
+                time_per_snapshot_ns: result.timing.time_per_snapshot.as_nanos() as f64,
+            },
+        }
+    }
+}
+
+
+
+pub fn config_hash(strategy: &str, parameters: &serde_json::Value) -> String {
+    let canonical = format!("{}|{}", strategy, parameters);
+    hash_hex(canonical.as_bytes())
+}
+
+
+pub struct ExperimentRegistry {
+    path: PathBuf,
+}
+
+impl ExperimentRegistry {
+    pub fn open(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    
+    pub fn append(&self, record: &ExperimentRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .context(format!("Failed to create registry directory: {}", parent.display()))?;
+            }
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context(format!("Failed to open experiment registry: {}", self.path.display()))?;
+
+        writeln!(file, "{}", serde_json::to_string(record)?)
+            .context("Failed to append experiment record")?;
+        Ok(())
+    }
+
+    
+    pub fn load(&self) -> Result<Vec<ExperimentRecord>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = std::fs::File::open(&self.path)
+            .context(format!("Failed to open experiment registry: {}", self.path.display()))?;
+
+        let mut records = Vec::new();
+        for (line_no, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.context("Failed to read experiment registry")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: ExperimentRecord = serde_json::from_str(&line)
+                .context(format!("Failed to parse experiment record at line {}", line_no + 1))?;
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    
+    pub fn find(&self, id: &str) -> Result<Vec<ExperimentRecord>> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .filter(|r| r.run_id.starts_with(id) || r.config_hash.starts_with(id))
+            .collect())
+    }
+}
+
+
+pub fn print_runs(records: &[ExperimentRecord]) {
+    println!(
+        "{:<24} {:<24} {:>12} {:>8} {:>8} {:>12}",
+        "Run", "Strategy", "PnL", "Trades", "Win %", "ns/snap"
+    );
+    println!("{}", "─".repeat(94));
+    for record in records {
+        println!(
+            "{:<24} {:<24} {:>12.2} {:>8} {:>7.1}% {:>12.1}",
+            record.run_id,
+            record.strategy,
+            record.metrics.total_pnl,
+            record.metrics.total_trades,
+            record.metrics.win_rate * 100.0,
+            record.metrics.time_per_snapshot_ns,
+        );
+    }
+}
+
+
+pub fn print_comparison(records: &[ExperimentRecord]) {
+    if records.is_empty() {
+        return;
+    }
+
+    print_runs(records);
+    println!();
+
+    let mut keys: Vec<String> = Vec::new();
+    for record in records {
+        if let Some(params) = record.parameters.as_object() {
+            for key in params.keys() {
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+        }
+    }
+
+    println!("Parameters:");
+    for key in &keys {
+        let values: Vec<String> = records
+            .iter()
+            .map(|r| r.parameters.get(key).map(|v| v.to_string()).unwrap_or_else(|| "-".to_string()))
+            .collect();
+        let differs = values.windows(2).any(|w| w[0] != w[1]);
+        println!(
+            "  {}{:<24} {}",
+            if differs { "*" } else { " " },
+            key,
+            values.join("  |  ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, pnl: f64) -> ExperimentRecord {
+        let mut result = BacktestResult::new(name.to_string());
+        result.metrics.total_pnl = pnl;
+        let dataset = DatasetRange {
+            path: "data/test.csv".to_string(),
+            first_timestamp_us: 0,
+            last_timestamp_us: 10,
+            snapshots: 2,
+            ..Default::default()
+        };
+        ExperimentRecord::new(&result, serde_json::json!({ "lookback": 100 }), dataset)
+    }
+
+    #[test]
+    fn test_config_hash_is_stable() {
+        let params = serde_json::json!({ "a": 1, "b": 2.5 });
+        assert_eq!(config_hash("Momentum", &params), config_hash("Momentum", &params));
+        assert_ne!(config_hash("Momentum", &params), config_hash("Market Maker", &params));
+    }
+
+    #[test]
+    fn test_run_ids_are_unique_for_identical_runs() {
+        let first = record("Momentum", 1.0);
+        let second = record("Momentum", 1.0);
+        assert_eq!(first.config_hash, second.config_hash);
+        assert_ne!(first.run_id, second.run_id);
+    }
+
+    #[test]
+    fn test_dataset_range_identity() {
+        let snapshot = |row: usize, bid: f64| {
+            L2Snapshot::from_levels(row, row as u64 * 10, String::new(), &[crate::types::PriceLevel::new(bid, 1.0)], &[crate::types::PriceLevel::new(bid + 1.0, 1.0)])
+        };
+        let snapshots = vec![snapshot(5, 100.0), snapshot(6, 100.5)];
+        let range = DatasetRange::from_snapshots(Path::new("data/test.csv"), &snapshots);
+        assert_eq!((range.first_row, range.last_row, range.snapshots), (5, 6, 2));
+        assert_eq!(range.content_hash, content_hash(&snapshots));
+
+        let shifted = vec![snapshot(5, 100.0), snapshot(6, 100.25)];
+        assert_ne!(range.content_hash, content_hash(&shifted));
+    }
+
+    #[test]
+    fn test_registry_roundtrip() {
+        let path = std::env::temp_dir().join(format!("zaphft_experiments_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let registry = ExperimentRegistry::open(&path);
+
+        registry.append(&record("Momentum", 12.5)).unwrap();
+        registry.append(&record("Market Maker", -3.0)).unwrap();
+
+        let records = registry.load().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].strategy, "Momentum");
+        assert_eq!(records[1].metrics.total_pnl, -3.0);
+
+        let hash = records[0].config_hash.clone();
+        assert_eq!(registry.find(&hash).unwrap().len(), 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
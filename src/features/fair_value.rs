@@ -0,0 +1,115 @@
+use super::signal::Signal;
+use crate::types::L2Snapshot;
+use serde::{Deserialize, Serialize};
+
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FairValue {
+    pub levels: usize,
+    pub decay: f64,
+}
+
+impl Default for FairValue {
+    fn default() -> Self {
+        Self { levels: 5, decay: 0.5 }
+    }
+}
+
+impl FairValue {
+    pub fn new(levels: usize, decay: f64) -> Self {
+        Self {
+            levels: levels.max(1),
+            decay,
+        }
+    }
+
+    pub fn estimate(&self, snapshot: &L2Snapshot) -> Option<f64> {
+        let bids = snapshot.bids();
+        let asks = snapshot.asks();
+        let mut weight = 1.0;
+        let mut total_weight = 0.0;
+        let mut imbalance = 0.0;
+        for (bid, ask) in bids.iter().zip(&asks).take(self.levels) {
+            let depth = bid.quantity + ask.quantity;
+            if depth > 0.0 {
+                imbalance += weight * (bid.quantity - ask.quantity) / depth;
+                total_weight += weight;
+            }
+            weight *= self.decay;
+        }
+        if total_weight <= 0.0 {
+            return None;
+        }
+        Some(snapshot.mid_price() + snapshot.spread() / 2.0 * imbalance / total_weight)
+    }
+}
+
+impl Signal for FairValue {
+    fn name(&self) -> &str {
+        "fair_value"
+    }
+
+    fn update(&mut self, snapshot: &L2Snapshot) -> Option<f64> {
+        self.estimate(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+    use crate::utils::rng::SplitMix64;
+
+    fn book(mid: f64, bid_qty: &[f64], ask_qty: &[f64]) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = bid_qty.iter().enumerate().map(|(i, q)| PriceLevel::new(mid - 0.5 - i as f64, *q)).collect();
+        let asks: Vec<PriceLevel> = ask_qty.iter().enumerate().map(|(i, q)| PriceLevel::new(mid + 0.5 + i as f64, *q)).collect();
+        L2Snapshot::from_levels(0, 0, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_weights_levels() {
+        let snapshot = book(100.0, &[3.0, 1.0], &[1.0, 3.0]);
+        let top = FairValue::new(1, 0.5).estimate(&snapshot).unwrap();
+        assert!((top - 100.25).abs() < 1e-9);
+
+        let both = FairValue::new(2, 0.5).estimate(&snapshot).unwrap();
+        assert!((both - (100.0 + 0.5 * (0.5 - 0.5 * 0.5) / 1.5)).abs() < 1e-9);
+        assert_eq!(FairValue::new(3, 0.0).estimate(&snapshot), Some(top));
+    }
+
+    #[test]
+    fn test_calibration_against_next_mid_move() {
+        let mut rng = SplitMix64::new(11);
+        let fair_value = FairValue::default();
+        let mut mid = 100.0;
+        let (mut edges, mut micro_edges, mut moves) = (Vec::new(), Vec::new(), Vec::new());
+        for _ in 0..2_000 {
+            let bid_qty: Vec<f64> = (0..5).map(|_| 0.5 + rng.next_f64() * 4.0).collect();
+            let ask_qty: Vec<f64> = (0..5).map(|_| 0.5 + rng.next_f64() * 4.0).collect();
+            let snapshot = book(mid, &bid_qty, &ask_qty);
+            let pressure: f64 = (0..5)
+                .map(|i| 0.5f64.powi(i as i32) * (bid_qty[i] - ask_qty[i]) / (bid_qty[i] + ask_qty[i]))
+                .sum();
+            let next_mid = mid + 0.3 * pressure + (rng.next_f64() - 0.5) * 0.2;
+
+            edges.push(fair_value.estimate(&snapshot).unwrap() - snapshot.mid_price());
+            micro_edges.push(snapshot.microprice() - snapshot.mid_price());
+            moves.push(next_mid - mid);
+            mid = next_mid;
+        }
+
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+        let correlation = |edges: &[f64]| {
+            let (me, mm) = (mean(edges), mean(&moves));
+            let cov: f64 = edges.iter().zip(&moves).map(|(e, m)| (e - me) * (m - mm)).sum();
+            let var_e: f64 = edges.iter().map(|e| (e - me).powi(2)).sum();
+            let var_m: f64 = moves.iter().map(|m| (m - mm).powi(2)).sum();
+            cov / (var_e * var_m).sqrt()
+        };
+        let (depth, top) = (correlation(&edges), correlation(&micro_edges));
+        assert!(depth > 0.6 && depth > top, "fair value {} vs microprice {}", depth, top);
+    }
+}
@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+use crate::types::L2Snapshot;
+use std::collections::VecDeque;
+
+const BPS: f64 = 10_000.0;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VolatilityEstimator {
+    CloseToClose,
+    Parkinson,
+    Ewma,
+}
+
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VolatilityConfig {
+    
+    pub window: usize,
+    
+    pub bar_size: usize,
+    
+    pub ewma_lambda: f64,
+}
+
+impl Default for VolatilityConfig {
+    fn default() -> Self {
+        Self {
+            window: 500,
+            bar_size: 10,
+            ewma_lambda: 0.94,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct Volatility {
+    config: VolatilityConfig,
+    last_mid: Option<f64>,
+    returns: VecDeque<f64>,
+    sum: f64,
+    sum_sq: f64,
+    bar_high: f64,
+    bar_low: f64,
+    bar_len: usize,
+    ranges: VecDeque<f64>,
+    range_sum: f64,
+    ewma_var: Option<f64>,
+}
+
+impl Volatility {
+    pub fn new(config: VolatilityConfig) -> Self {
+        Self {
+            config,
+            last_mid: None,
+            returns: VecDeque::new(),
+            sum: 0.0,
+            sum_sq: 0.0,
+            bar_high: f64::NEG_INFINITY,
+            bar_low: f64::INFINITY,
+            bar_len: 0,
+            ranges: VecDeque::new(),
+            range_sum: 0.0,
+            ewma_var: None,
+        }
+    }
+
+    pub fn update(&mut self, mid: f64) {
+        if mid <= 0.0 {
+            return;
+        }
+
+        if let Some(prev) = self.last_mid {
+            let r = (mid / prev).ln();
+            self.returns.push_back(r);
+            self.sum += r;
+            self.sum_sq += r * r;
+            if self.returns.len() > self.config.window {
+                let old = self.returns.pop_front().unwrap();
+                self.sum -= old;
+                self.sum_sq -= old * old;
+            }
+
+            let lambda = self.config.ewma_lambda;
+            self.ewma_var = Some(match self.ewma_var {
+                Some(var) => lambda * var + (1.0 - lambda) * r * r,
+                None => r * r,
+            });
+        }
+        self.last_mid = Some(mid);
+
+        self.bar_high = self.bar_high.max(mid);
+        self.bar_low = self.bar_low.min(mid);
+        self.bar_len += 1;
+        if self.bar_len >= self.config.bar_size.max(1) {
+            let range = (self.bar_high / self.bar_low).ln().powi(2);
+            self.ranges.push_back(range);
+            self.range_sum += range;
+            let max_bars = (self.config.window / self.config.bar_size.max(1)).max(1);
+            if self.ranges.len() > max_bars {
+                self.range_sum -= self.ranges.pop_front().unwrap();
+            }
+            self.bar_high = f64::NEG_INFINITY;
+            self.bar_low = f64::INFINITY;
+            self.bar_len = 0;
+        }
+    }
+
+    
+    pub fn close_to_close(&self) -> Option<f64> {
+        let n = self.returns.len();
+        if n < 2 {
+            return None;
+        }
+        let mean = self.sum / n as f64;
+        let var = (self.sum_sq / n as f64 - mean * mean).max(0.0);
+        Some(var.sqrt() * BPS)
+    }
+
+    
+    
+    pub fn parkinson(&self) -> Option<f64> {
+        if self.ranges.is_empty() {
+            return None;
+        }
+        let bar_var = self.range_sum.max(0.0) / (self.ranges.len() as f64 * 4.0 * std::f64::consts::LN_2);
+        Some((bar_var / self.config.bar_size.max(1) as f64).sqrt() * BPS)
+    }
+
+    pub fn ewma(&self) -> Option<f64> {
+        self.ewma_var.map(|var| var.sqrt() * BPS)
+    }
+
+    pub fn get(&self, estimator: VolatilityEstimator) -> Option<f64> {
+        match estimator {
+            VolatilityEstimator::CloseToClose => self.close_to_close(),
+            VolatilityEstimator::Parkinson => self.parkinson(),
+            VolatilityEstimator::Ewma => self.ewma(),
+        }
+    }
+
+    
+    pub fn series(snapshots: &[L2Snapshot], config: VolatilityConfig, sample_every: usize) -> Vec<VolatilityPoint> {
+        let mut volatility = Volatility::new(config);
+        let mut points = Vec::new();
+        for (idx, snapshot) in snapshots.iter().enumerate() {
+            volatility.update(snapshot.mid_price());
+            if idx % sample_every.max(1) == 0 {
+                points.push(VolatilityPoint {
+                    timestamp_us: snapshot.timestamp_us,
+                    close_to_close_bps: volatility.close_to_close(),
+                    parkinson_bps: volatility.parkinson(),
+                    ewma_bps: volatility.ewma(),
+                });
+            }
+        }
+        points
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Ema {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(alpha: f64) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            value: None,
+        }
+    }
+
+    
+    pub fn with_span(span: usize) -> Self {
+        Self::new(2.0 / (span.max(1) as f64 + 1.0))
+    }
+
+    pub fn update(&mut self, x: f64) -> f64 {
+        let value = match self.value {
+            Some(prev) => prev + self.alpha * (x - prev),
+            None => x,
+        };
+        self.value = Some(value);
+        value
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolatilityPoint {
+    pub timestamp_us: u64,
+    pub close_to_close_bps: Option<f64>,
+    pub parkinson_bps: Option<f64>,
+    pub ewma_bps: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ema() {
+        let mut ema = Ema::with_span(3);
+        assert_eq!(ema.value(), None);
+        assert_eq!(ema.update(100.0), 100.0);
+        assert_eq!(ema.update(102.0), 101.0);
+        assert_eq!(ema.update(101.0), 101.0);
+    }
+
+    #[test]
+    fn test_constant_price_has_zero_volatility() {
+        let mut vol = Volatility::new(VolatilityConfig::default());
+        assert_eq!(vol.close_to_close(), None);
+        for _ in 0..50 {
+            vol.update(100.0);
+        }
+        assert_eq!(vol.close_to_close(), Some(0.0));
+        assert_eq!(vol.parkinson(), Some(0.0));
+        assert_eq!(vol.ewma(), Some(0.0));
+    }
+
+    #[test]
+    fn test_estimators_agree_on_alternating_moves() {
+        let config = VolatilityConfig {
+            window: 200,
+            bar_size: 2,
+            ewma_lambda: 0.9,
+        };
+        let mut vol = Volatility::new(config);
+        let step = 1.0 + 1.0 / BPS;
+        for i in 0..400 {
+            vol.update(if i % 2 == 0 { 100.0 } else { 100.0 * step });
+        }
+
+        let c2c = vol.close_to_close().unwrap();
+        let ewma = vol.ewma().unwrap();
+        assert!((c2c - 1.0).abs() < 0.01, "close-to-close {}", c2c);
+        assert!((ewma - 1.0).abs() < 0.01, "ewma {}", ewma);
+        assert!(vol.get(VolatilityEstimator::Parkinson).unwrap() > 0.0);
+    }
+}
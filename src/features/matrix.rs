@@ -0,0 +1,190 @@
+use super::indicators::{Volatility, VolatilityConfig};
+use super::pressure::{order_flow_imbalance, BookPressure};
+use crate::types::L2Snapshot;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+const BPS: f64 = 10_000.0;
+
+
+#[derive(Debug, Clone)]
+pub struct FeatureMatrixConfig {
+    
+    pub return_horizons: Vec<usize>,
+    
+    pub forward_horizons: Vec<usize>,
+    pub volatility: VolatilityConfig,
+}
+
+impl Default for FeatureMatrixConfig {
+    fn default() -> Self {
+        Self {
+            return_horizons: vec![1, 10, 100],
+            forward_horizons: vec![1, 10, 100],
+            volatility: VolatilityConfig::default(),
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureMatrix {
+    pub columns: Vec<String>,
+    pub timestamps: Vec<u64>,
+    pub rows: Vec<Vec<f64>>,
+}
+
+impl FeatureMatrix {
+    pub fn build(snapshots: &[L2Snapshot], config: &FeatureMatrixConfig) -> Self {
+        let mut columns: Vec<String> = ["mid", "spread", "spread_bps", "microprice", "ofi", "volatility_bps"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        columns.extend(BookPressure::column_names());
+        columns.extend(config.return_horizons.iter().map(|h| format!("ret_{}", h)));
+        columns.extend(config.forward_horizons.iter().map(|h| format!("fwd_ret_{}", h)));
+
+        let mids: Vec<f64> = snapshots.iter().map(|s| s.mid_price()).collect();
+        let change_bps = |from: usize, to: usize| (mids[to] - mids[from]) / mids[from] * BPS;
+        let mut volatility = Volatility::new(config.volatility.clone());
+
+        let rows = snapshots
+            .iter()
+            .enumerate()
+            .map(|(i, snapshot)| {
+                volatility.update(mids[i]);
+                let mut row = vec![
+                    mids[i],
+                    snapshot.spread(),
+                    snapshot.spread() / mids[i] * BPS,
+                    snapshot.microprice(),
+                    if i > 0 { order_flow_imbalance(&snapshots[i - 1], snapshot) } else { 0.0 },
+                    volatility.ewma().unwrap_or(f64::NAN),
+                ];
+                row.extend(BookPressure::from_snapshot(snapshot).to_vec());
+                row.extend(config.return_horizons.iter().map(|&h| {
+                    if i >= h { change_bps(i - h, i) } else { f64::NAN }
+                }));
+                row.extend(config.forward_horizons.iter().map(|&h| {
+                    if i + h < mids.len() { change_bps(i, i + h) } else { f64::NAN }
+                }));
+                row
+            })
+            .collect();
+
+        Self {
+            columns,
+            timestamps: snapshots.iter().map(|s| s.timestamp_us).collect(),
+            rows,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn column(&self, name: &str) -> Option<Vec<f64>> {
+        let index = self.columns.iter().position(|c| c == name)?;
+        Some(self.rows.iter().map(|row| row[index]).collect())
+    }
+
+    
+    pub fn write_csv(&self, path: &Path) -> Result<()> {
+        let mut writer = csv::Writer::from_path(path)
+            .context(format!("Failed to create feature matrix file: {}", path.display()))?;
+        writer.write_record(std::iter::once("timestamp_us").chain(self.columns.iter().map(String::as_str)))?;
+        for (timestamp, row) in self.timestamps.iter().zip(&self.rows) {
+            let record = std::iter::once(timestamp.to_string()).chain(row.iter().map(|v| {
+                if v.is_nan() { String::new() } else { v.to_string() }
+            }));
+            writer.write_record(record)?;
+        }
+        writer.flush().context("Failed to flush feature matrix file")?;
+        Ok(())
+    }
+
+    
+    #[cfg(feature = "arrow")]
+    pub fn to_arrow(&self) -> Result<arrow_array::RecordBatch> {
+        use arrow_array::{ArrayRef, Float64Array, RecordBatch, UInt64Array};
+        use arrow_schema::{DataType, Field, Schema};
+        use std::sync::Arc;
+
+        let mut fields = vec![Field::new("timestamp_us", DataType::UInt64, false)];
+        fields.extend(self.columns.iter().map(|c| Field::new(c.as_str(), DataType::Float64, true)));
+        let mut arrays: Vec<ArrayRef> = vec![Arc::new(UInt64Array::from(self.timestamps.clone()))];
+        for i in 0..self.columns.len() {
+            let values: Float64Array = self.rows.iter().map(|row| Some(row[i]).filter(|v| !v.is_nan())).collect();
+            arrays.push(Arc::new(values));
+        }
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+    }
+}
+
+
+
+pub fn export_matrix(snapshots: &[L2Snapshot], config: &FeatureMatrixConfig, path: &Path) -> Result<usize> {
+    let matrix = FeatureMatrix::build(snapshots, config);
+    matrix.write_csv(path)?;
+    Ok(matrix.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn snapshots() -> Vec<L2Snapshot> {
+        [100.0, 101.0, 102.0, 100.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &mid)| {
+                L2Snapshot::from_levels(i, i as u64 * 10, String::new(), &[PriceLevel::new(mid - 0.5, 1.0)], &[PriceLevel::new(mid + 0.5, 1.0)])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matrix_returns_and_labels() {
+        let config = FeatureMatrixConfig {
+            return_horizons: vec![1],
+            forward_horizons: vec![2],
+            ..Default::default()
+        };
+        let matrix = FeatureMatrix::build(&snapshots(), &config);
+
+        assert_eq!(matrix.len(), 4);
+        assert_eq!(matrix.columns.len(), matrix.rows[0].len());
+        assert_eq!(matrix.column("spread").unwrap(), vec![1.0; 4]);
+
+        let ret = matrix.column("ret_1").unwrap();
+        assert!(ret[0].is_nan());
+        assert!((ret[1] - 100.0).abs() < 1e-9);
+
+        let fwd = matrix.column("fwd_ret_2").unwrap();
+        assert!((fwd[0] - 200.0).abs() < 1e-9);
+        assert!(fwd[2].is_nan() && fwd[3].is_nan());
+    }
+
+    #[test]
+    fn test_export_matrix_csv() {
+        let path = std::env::temp_dir().join(format!("zaphft_features_{}.csv", std::process::id()));
+        let rows = export_matrix(&snapshots(), &FeatureMatrixConfig::default(), &path).unwrap();
+        assert_eq!(rows, 4);
+
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        let headers = reader.headers().unwrap().clone();
+        assert_eq!(&headers[0], "timestamp_us");
+        assert!(headers.iter().any(|h| h == "fwd_ret_100"));
+        let first = reader.records().next().unwrap().unwrap();
+        assert_eq!(&first[0], "0");
+        assert_eq!(first.len(), headers.len());
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,13 @@
+
+
+pub mod fair_value;
+pub mod indicators;
+pub mod matrix;
+pub mod pressure;
+pub mod signal;
+
+pub use fair_value::FairValue;
+pub use indicators::{Ema, Volatility, VolatilityConfig, VolatilityEstimator, VolatilityPoint};
+pub use matrix::{export_matrix, FeatureMatrix, FeatureMatrixConfig};
+pub use pressure::{order_flow_imbalance, BookPressure, PRESSURE_DEPTHS};
+pub use signal::{EmaMidSignal, MicropriceSignal, Signal, TradeCost, TrendStrengthSignal};
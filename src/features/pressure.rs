@@ -0,0 +1,111 @@
+use crate::types::{L2Snapshot, PriceLevel};
+
+pub const PRESSURE_DEPTHS: [usize; 4] = [1, 3, 5, 10];
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BookPressure {
+    pub quantity: [f64; PRESSURE_DEPTHS.len()],
+    pub notional: [f64; PRESSURE_DEPTHS.len()],
+}
+
+impl BookPressure {
+    pub fn from_snapshot(snapshot: &L2Snapshot) -> Self {
+        let bids = snapshot.bids();
+        let asks = snapshot.asks();
+        let mut pressure = Self::default();
+        for (i, &depth) in PRESSURE_DEPTHS.iter().enumerate() {
+            pressure.quantity[i] = imbalance(&bids, &asks, depth, |l| l.quantity);
+            pressure.notional[i] = imbalance(&bids, &asks, depth, |l| l.notional());
+        }
+        pressure
+    }
+
+    pub fn column_names() -> Vec<String> {
+        PRESSURE_DEPTHS
+            .iter()
+            .map(|d| format!("imbalance_{}", d))
+            .chain(PRESSURE_DEPTHS.iter().map(|d| format!("notional_imbalance_{}", d)))
+            .collect()
+    }
+
+    
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.quantity.iter().chain(self.notional.iter()).copied().collect()
+    }
+
+    pub fn at_depth(&self, depth: usize) -> Option<f64> {
+        PRESSURE_DEPTHS.iter().position(|&d| d == depth).map(|i| self.quantity[i])
+    }
+
+    pub fn notional_at_depth(&self, depth: usize) -> Option<f64> {
+        PRESSURE_DEPTHS.iter().position(|&d| d == depth).map(|i| self.notional[i])
+    }
+}
+
+
+
+
+pub fn order_flow_imbalance(prev: &L2Snapshot, cur: &L2Snapshot) -> f64 {
+    let (pb, pa) = (prev.best_bid(), prev.best_ask());
+    let (cb, ca) = (cur.best_bid(), cur.best_ask());
+    let mut ofi = 0.0;
+    if cb >= pb {
+        ofi += cur.bid_qty_1;
+    }
+    if cb <= pb {
+        ofi -= prev.bid_qty_1;
+    }
+    if ca <= pa {
+        ofi -= cur.ask_qty_1;
+    }
+    if ca >= pa {
+        ofi += prev.ask_qty_1;
+    }
+    ofi
+}
+
+fn imbalance(bids: &[PriceLevel], asks: &[PriceLevel], depth: usize, weight: impl Fn(&PriceLevel) -> f64) -> f64 {
+    let bid: f64 = bids.iter().take(depth).map(&weight).sum();
+    let ask: f64 = asks.iter().take(depth).map(&weight).sum();
+    if bid + ask > 0.0 {
+        (bid - ask) / (bid + ask)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pressure_by_depth() {
+        let bids: Vec<_> = (0..10).map(|i| PriceLevel::new(100.0 - i as f64, if i == 0 { 3.0 } else { 1.0 })).collect();
+        let asks: Vec<_> = (0..10).map(|i| PriceLevel::new(101.0 + i as f64, if i < 3 { 1.0 } else { 3.0 })).collect();
+        let snapshot = L2Snapshot::from_levels(0, 0, String::new(), &bids, &asks);
+        let pressure = BookPressure::from_snapshot(&snapshot);
+
+        assert_eq!(pressure.at_depth(1), Some(0.5));
+        assert_eq!(pressure.at_depth(3), Some(2.0 / 8.0));
+        assert!(pressure.at_depth(10).unwrap() < 0.0);
+        assert!(pressure.notional_at_depth(1).unwrap() < 0.5);
+        assert!((pressure.at_depth(10).unwrap() - snapshot.imbalance()).abs() < 1e-12);
+        assert_eq!(pressure.at_depth(2), None);
+        assert_eq!(pressure.to_vec().len(), BookPressure::column_names().len());
+    }
+
+    #[test]
+    fn test_order_flow_imbalance() {
+        let book = |bid: f64, bid_qty: f64, ask: f64, ask_qty: f64| {
+            L2Snapshot::from_levels(0, 0, String::new(), &[PriceLevel::new(bid, bid_qty)], &[PriceLevel::new(ask, ask_qty)])
+        };
+        let prev = book(100.0, 2.0, 101.0, 3.0);
+        assert_eq!(order_flow_imbalance(&prev, &book(100.0, 5.0, 101.0, 3.0)), 3.0);
+        assert_eq!(order_flow_imbalance(&prev, &book(100.5, 1.0, 101.0, 3.0)), 1.0);
+        assert_eq!(order_flow_imbalance(&prev, &book(100.0, 2.0, 101.5, 4.0)), 3.0);
+        assert_eq!(order_flow_imbalance(&prev, &book(99.5, 4.0, 101.0, 1.0)), 0.0);
+    }
+}
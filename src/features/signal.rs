@@ -0,0 +1,153 @@
+use super::indicators::Ema;
+use crate::orderbook::OrderBook;
+use crate::types::{L2Snapshot, Side};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+
+
+
+pub trait Signal: Send {
+    fn name(&self) -> &str;
+
+    
+    fn update(&mut self, snapshot: &L2Snapshot) -> Option<f64>;
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct MicropriceSignal;
+
+impl Signal for MicropriceSignal {
+    fn name(&self) -> &str {
+        "microprice"
+    }
+
+    fn update(&mut self, snapshot: &L2Snapshot) -> Option<f64> {
+        Some(snapshot.microprice())
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct EmaMidSignal {
+    ema: Ema,
+}
+
+impl EmaMidSignal {
+    pub fn new(span: usize) -> Self {
+        Self { ema: Ema::with_span(span) }
+    }
+}
+
+impl Signal for EmaMidSignal {
+    fn name(&self) -> &str {
+        "ema_mid"
+    }
+
+    fn update(&mut self, snapshot: &L2Snapshot) -> Option<f64> {
+        Some(self.ema.update(snapshot.mid_price()))
+    }
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct TrendStrengthSignal {
+    lookback: usize,
+    mids: VecDeque<f64>,
+}
+
+impl TrendStrengthSignal {
+    pub fn new(lookback: usize) -> Self {
+        Self {
+            lookback: lookback.max(1),
+            mids: VecDeque::with_capacity(lookback + 1),
+        }
+    }
+}
+
+impl Signal for TrendStrengthSignal {
+    fn name(&self) -> &str {
+        "trend_strength"
+    }
+
+    fn update(&mut self, snapshot: &L2Snapshot) -> Option<f64> {
+        self.mids.push_back(snapshot.mid_price());
+        if self.mids.len() > self.lookback + 1 {
+            self.mids.pop_front();
+        }
+        if self.mids.len() <= self.lookback {
+            return None;
+        }
+        let net = (self.mids[self.lookback] - self.mids[0]).abs();
+        let path: f64 = self.mids.iter().zip(self.mids.iter().skip(1)).map(|(a, b)| (b - a).abs()).sum();
+        Some(if path > 0.0 { net / path } else { 0.0 })
+    }
+}
+
+
+
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TradeCost {
+    pub fee_bps: f64,
+    
+    pub min_edge_bps: f64,
+}
+
+impl TradeCost {
+    
+    
+    pub fn expected_cost_bps(&self, book: &OrderBook, side: Side, quantity: f64) -> Option<f64> {
+        let snapshot = book.snapshot()?;
+        let mid = snapshot.mid_price();
+        let consumed = match side {
+            Side::Bid => Side::Ask,
+            Side::Ask => Side::Bid,
+        };
+        let (avg_price, _, _) = book.calculate_slippage(consumed, quantity)?;
+        let crossing_bps = (avg_price - mid).abs() / mid * 10_000.0;
+        Some(self.fee_bps + crossing_bps)
+    }
+
+    pub fn net_edge_bps(&self, edge_bps: f64, book: &OrderBook, side: Side, quantity: f64) -> Option<f64> {
+        self.expected_cost_bps(book, side, quantity).map(|cost| edge_bps - cost)
+    }
+
+    pub fn accepts(&self, edge_bps: f64, book: &OrderBook, side: Side, quantity: f64) -> bool {
+        self.net_edge_bps(edge_bps, book, side, quantity)
+            .is_some_and(|net| net > self.min_edge_bps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    #[test]
+    fn test_trade_cost_nets_fees_and_slippage() {
+        let mut book = OrderBook::new();
+        book.update(L2Snapshot::from_levels(
+            0,
+            0,
+            String::new(),
+            &[PriceLevel::new(99.99, 1.0), PriceLevel::new(99.9, 5.0)],
+            &[PriceLevel::new(100.01, 1.0), PriceLevel::new(100.1, 5.0)],
+        ));
+        let cost = TradeCost { fee_bps: 2.0, min_edge_bps: 0.0 };
+
+        let small = cost.expected_cost_bps(&book, Side::Bid, 1.0).unwrap();
+        assert!((small - 3.0).abs() < 1e-9);
+        let large = cost.expected_cost_bps(&book, Side::Ask, 2.0).unwrap();
+        assert!((large - 7.5).abs() < 1e-9);
+
+        assert!(cost.accepts(5.0, &book, Side::Bid, 1.0));
+        assert!(!cost.accepts(5.0, &book, Side::Bid, 2.0));
+        assert!(!cost.accepts(100.0, &book, Side::Bid, 10.0));
+    }
+}
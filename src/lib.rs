@@ -4,22 +4,28 @@ pub mod utils;
 pub mod market_data;
 pub mod orderbook;
 pub mod execution;
+pub mod features;
 pub mod strategy;
 pub mod analytics;
+pub mod engine;
+pub mod experiments;
+pub mod synthetic;
 pub mod trivial_approach;
 
 
-pub use types::{L2Snapshot, PriceLevel, Side, Trade};
-pub use market_data::{SnapshotReader, SnapshotStats};
-pub use orderbook::OrderBook;
-pub use execution::{Position, PositionStats};
-pub use strategy::{Strategy, StrategyStats};
-pub use strategy::market_maker::{MarketMaker, MarketMakerConfig};
+pub use types::{L2Snapshot, PriceLevel, QuoteEvent, QuoteEventKind, Side, Trade};
+pub use market_data::{DerivedCache, DerivedValues, SnapshotReader, SnapshotStats};
+pub use orderbook::{BookHistory, OrderBook};
+pub use execution::{Position, PositionStats, PositionView, Sizer, SizingContext};
+pub use strategy::{Strategy, StrategyFactory, StrategyStats};
+pub use strategy::market_maker::{InventoryBand, LeanMarketMaker, LossBackoff, MarketMaker, MarketMakerConfig, MarketMakerCore, ReferencePrice};
+pub use engine::{BacktestEngine, DebugFrame, Debugger, DedupMode, EngineConfig, EngineRun, Liquidation, Schedule, SessionPhase, StopReason};
 
 
 pub use trivial_approach::{
     NaiveMarketMaker,
     NaiveMarketMakerConfig,
     NaiveMomentumStrategy,
+    PureNaiveMarketMaker,
     PureNaiveMomentumStrategy,
 };
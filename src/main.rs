@@ -1,5 +1,6 @@
 use anyhow::Result;
 use rusthft::{OrderBook, Side, SnapshotReader, SnapshotStats};
+use rusthft::analytics::liquidity::{LiquidityProfile, DEFAULT_IMPACT_BPS};
 use std::path::Path;
 
 fn main() -> Result<()> {
@@ -39,7 +40,7 @@ fn main() -> Result<()> {
     println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
     let mut book = OrderBook::new();
-    book.update(snapshots[0].clone());
+    book.update_ref(&snapshots[0]);
 
     println!("   Snapshot #1:");
     println!("   Best Bid:    ${:.2}", book.best_bid().unwrap());
@@ -64,21 +65,10 @@ fn main() -> Result<()> {
     println!("\n   📊 Order Book Depth (Top 5 Levels):");
     println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    let bids = book.bids();
-    let asks = book.asks();
+    print!("{}", book.render_ladder(5));
 
-    println!("   {:>10} | {:>8} | {:>8} | {:>10}", "BID QTY", "BID", "ASK", "ASK QTY");
-    println!("   ───────────────────────────────────────────");
-
-    for i in 0..5 {
-        println!(
-            "   {:>10.3} | {:>8.2} | {:>8.2} | {:>10.3}",
-            bids[i].quantity,
-            bids[i].price,
-            asks[i].price,
-            asks[i].quantity
-        );
-    }
+    
+    LiquidityProfile::compute(&snapshots, &DEFAULT_IMPACT_BPS).print();
 
     
     println!("\n   ⚖️  Order Book Imbalance (first 100 snapshots):");
@@ -108,7 +98,7 @@ fn main() -> Result<()> {
     let mut book = OrderBook::new();
 
     for snap in &snapshots {
-        book.update(snap.clone());
+        book.update_ref(snap);
         let _ = book.best_bid();
         let _ = book.best_ask();
         let _ = book.spread();
@@ -0,0 +1,168 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use super::reader::open_input;
+use crate::types::L2Snapshot;
+use std::path::Path;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdjustmentKind {
+    Multiplicative,
+    Additive,
+}
+
+
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceAdjustment {
+    pub effective_us: u64,
+    pub kind: AdjustmentKind,
+    pub value: f64,
+}
+
+
+
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct AdjustmentSchedule {
+    adjustments: Vec<PriceAdjustment>,
+}
+
+impl AdjustmentSchedule {
+    pub fn new(mut adjustments: Vec<PriceAdjustment>) -> Self {
+        adjustments.sort_by_key(|a| a.effective_us);
+        Self { adjustments }
+    }
+
+    
+    pub fn from_csv(path: &Path) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .trim(csv::Trim::All)
+            .from_path(path)
+            .context(format!("Failed to open adjustment schedule: {}", path.display()))?;
+
+        let adjustments = reader
+            .deserialize()
+            .collect::<Result<Vec<PriceAdjustment>, _>>()
+            .context("Failed to parse adjustment schedule")?;
+
+        Ok(Self::new(adjustments))
+    }
+
+    pub fn adjustments(&self) -> &[PriceAdjustment] {
+        &self.adjustments
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjustments.is_empty()
+    }
+
+    
+    
+    pub fn transform_at(&self, timestamp_us: u64) -> (f64, f64) {
+        let first_pending = self.adjustments.partition_point(|a| a.effective_us <= timestamp_us);
+        self.adjustments[first_pending..]
+            .iter()
+            .fold((1.0, 0.0), |(scale, offset), adj| match adj.kind {
+                AdjustmentKind::Multiplicative => (scale * adj.value, offset * adj.value),
+                AdjustmentKind::Additive => (scale, offset + adj.value),
+            })
+    }
+
+    pub fn adjust(&self, snapshot: &mut L2Snapshot) {
+        let (scale, offset) = self.transform_at(snapshot.timestamp_us);
+        if scale != 1.0 || offset != 0.0 {
+            snapshot.map_prices(|p| p * scale + offset);
+        }
+    }
+
+    pub fn apply(&self, snapshots: &mut [L2Snapshot]) {
+        for snapshot in snapshots {
+            self.adjust(snapshot);
+        }
+    }
+}
+
+
+
+pub fn adjust_prices_file(input_path: &Path, output_path: &Path, schedule: &AdjustmentSchedule) -> Result<usize> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .from_reader(open_input(input_path)?);
+    let mut writer = csv::Writer::from_path(output_path)
+        .context(format!("Failed to create output file: {}", output_path.display()))?;
+
+    let mut rows = 0;
+    for result in reader.deserialize() {
+        let mut snapshot: L2Snapshot = result.context(format!("Failed to parse snapshot at row {}", rows))?;
+        schedule.adjust(&mut snapshot);
+        writer.serialize(&snapshot).context("Failed to write adjusted snapshot")?;
+        rows += 1;
+    }
+    writer.flush().context("Failed to flush output file")?;
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64, bid: f64, ask: f64) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_back_adjustment_removes_roll_gaps() {
+        let schedule = AdjustmentSchedule::new(vec![
+            PriceAdjustment { effective_us: 300, kind: AdjustmentKind::Multiplicative, value: 2.0 },
+            PriceAdjustment { effective_us: 200, kind: AdjustmentKind::Additive, value: 10.0 },
+        ]);
+        let mut snapshots = vec![
+            create_test_snapshot(100, 100.0, 101.0),
+            create_test_snapshot(200, 110.0, 111.0),
+            create_test_snapshot(300, 220.0, 222.0),
+        ];
+
+        schedule.apply(&mut snapshots);
+
+        assert_eq!(snapshots[0].best_bid(), 220.0);
+        assert_eq!(snapshots[0].best_ask(), 222.0);
+        assert_eq!(snapshots[0].bid_price_2, 218.0);
+        assert_eq!(snapshots[0].bid_qty_2, 1.0);
+        assert_eq!(snapshots[1].best_bid(), 220.0);
+        assert_eq!(snapshots[2].best_bid(), 220.0);
+        assert_eq!(schedule.transform_at(300), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_adjustment_leaves_empty_levels_untouched() {
+        let schedule = AdjustmentSchedule::new(vec![PriceAdjustment {
+            effective_us: 200,
+            kind: AdjustmentKind::Additive,
+            value: 10.0,
+        }]);
+        let mut snapshots = vec![L2Snapshot::from_levels(
+            0,
+            100,
+            String::new(),
+            &[PriceLevel::new(100.0, 1.0)],
+            &[PriceLevel::new(101.0, 1.0)],
+        )];
+
+        schedule.apply(&mut snapshots);
+
+        assert_eq!(snapshots[0].best_bid(), 110.0);
+        assert_eq!(snapshots[0].best_ask(), 111.0);
+        assert_eq!(snapshots[0].bid_price_2, 0.0);
+        assert_eq!(snapshots[0].ask_price_10, 0.0);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::types::{L2Snapshot, PriceLevel};
+
+pub const BOOK_DEPTH: usize = 10;
+
+
+
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotColumns {
+    pub row_index: Vec<u64>,
+    pub timestamp_us: Vec<u64>,
+    pub datetime: Vec<String>,
+    pub bid_price: [Vec<f64>; BOOK_DEPTH],
+    pub bid_qty: [Vec<f64>; BOOK_DEPTH],
+    pub ask_price: [Vec<f64>; BOOK_DEPTH],
+    pub ask_qty: [Vec<f64>; BOOK_DEPTH],
+}
+
+impl SnapshotColumns {
+    pub fn from_snapshots(snapshots: &[L2Snapshot]) -> Self {
+        let mut columns = Self::default();
+        for snapshot in snapshots {
+            columns.push(snapshot);
+        }
+        columns
+    }
+
+    pub fn push(&mut self, snapshot: &L2Snapshot) {
+        self.row_index.push(snapshot.row_index as u64);
+        self.timestamp_us.push(snapshot.timestamp_us);
+        self.datetime.push(snapshot.datetime.clone());
+        for (level, bid) in snapshot.bids().iter().enumerate() {
+            self.bid_price[level].push(bid.price);
+            self.bid_qty[level].push(bid.quantity);
+        }
+        for (level, ask) in snapshot.asks().iter().enumerate() {
+            self.ask_price[level].push(ask.price);
+            self.ask_qty[level].push(ask.quantity);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.timestamp_us.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timestamp_us.is_empty()
+    }
+
+    pub fn snapshot(&self, row: usize) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..BOOK_DEPTH)
+            .map(|level| PriceLevel::new(self.bid_price[level][row], self.bid_qty[level][row]))
+            .collect();
+        let asks: Vec<PriceLevel> = (0..BOOK_DEPTH)
+            .map(|level| PriceLevel::new(self.ask_price[level][row], self.ask_qty[level][row]))
+            .collect();
+        L2Snapshot::from_levels(
+            self.row_index[row] as usize,
+            self.timestamp_us[row],
+            self.datetime[row].clone(),
+            &bids,
+            &asks,
+        )
+    }
+
+    pub fn to_snapshots(&self) -> Vec<L2Snapshot> {
+        (0..self.len()).map(|row| self.snapshot(row)).collect()
+    }
+}
+
+#[cfg(feature = "arrow")]
+mod arrow_interop {
+    use super::{SnapshotColumns, BOOK_DEPTH};
+    use anyhow::{Context, Result};
+    use arrow_array::{Array, ArrayRef, Float64Array, RecordBatch, StringArray, UInt64Array};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    fn level_columns() -> impl Iterator<Item = (String, usize, bool, bool)> {
+        (0..BOOK_DEPTH).flat_map(|level| {
+            [
+                (format!("bid_price_{}", level + 1), level, true, true),
+                (format!("bid_qty_{}", level + 1), level, true, false),
+            ]
+        })
+        .chain((0..BOOK_DEPTH).flat_map(|level| {
+            [
+                (format!("ask_price_{}", level + 1), level, false, true),
+                (format!("ask_qty_{}", level + 1), level, false, false),
+            ]
+        }))
+    }
+
+    impl SnapshotColumns {
+        
+        pub fn schema() -> Schema {
+            let mut fields = vec![
+                Field::new("row_index", DataType::UInt64, false),
+                Field::new("timestamp_us", DataType::UInt64, false),
+                Field::new("datetime", DataType::Utf8, false),
+            ];
+            fields.extend(level_columns().map(|(name, ..)| Field::new(name, DataType::Float64, false)));
+            Schema::new(fields)
+        }
+
+        fn level(&self, level: usize, bid: bool, price: bool) -> &Vec<f64> {
+            match (bid, price) {
+                (true, true) => &self.bid_price[level],
+                (true, false) => &self.bid_qty[level],
+                (false, true) => &self.ask_price[level],
+                (false, false) => &self.ask_qty[level],
+            }
+        }
+
+        fn level_mut(&mut self, level: usize, bid: bool, price: bool) -> &mut Vec<f64> {
+            match (bid, price) {
+                (true, true) => &mut self.bid_price[level],
+                (true, false) => &mut self.bid_qty[level],
+                (false, true) => &mut self.ask_price[level],
+                (false, false) => &mut self.ask_qty[level],
+            }
+        }
+
+        pub fn to_arrow(&self) -> Result<RecordBatch> {
+            let mut arrays: Vec<ArrayRef> = vec![
+                Arc::new(UInt64Array::from(self.row_index.clone())),
+                Arc::new(UInt64Array::from(self.timestamp_us.clone())),
+                Arc::new(StringArray::from(self.datetime.clone())),
+            ];
+            for (_, level, bid, price) in level_columns() {
+                arrays.push(Arc::new(Float64Array::from(self.level(level, bid, price).clone())));
+            }
+            Ok(RecordBatch::try_new(Arc::new(Self::schema()), arrays)?)
+        }
+
+        pub fn from_arrow(batch: &RecordBatch) -> Result<Self> {
+            fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T> {
+                batch
+                    .column_by_name(name)
+                    .context(format!("Missing column: {}", name))?
+                    .as_any()
+                    .downcast_ref::<T>()
+                    .context(format!("Unexpected type for column: {}", name))
+            }
+
+            let mut columns = Self {
+                row_index: column::<UInt64Array>(batch, "row_index")?.values().to_vec(),
+                timestamp_us: column::<UInt64Array>(batch, "timestamp_us")?.values().to_vec(),
+                datetime: column::<StringArray>(batch, "datetime")?
+                    .iter()
+                    .map(|v| v.unwrap_or_default().to_string())
+                    .collect(),
+                ..Self::default()
+            };
+            for (name, level, bid, price) in level_columns() {
+                let values = column::<Float64Array>(batch, &name)?;
+                if values.null_count() > 0 {
+                    anyhow::bail!("Column {} contains nulls", name);
+                }
+                *columns.level_mut(level, bid, price) = values.values().to_vec();
+            }
+            Ok(columns)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_snapshot(timestamp_us: u64, bid: f64, ask: f64) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0 + i as f64)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 2.0 + i as f64)).collect();
+        L2Snapshot::from_levels(timestamp_us as usize, timestamp_us, "2023-01-09".to_string(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_columns_round_trip() {
+        let snapshots = vec![create_test_snapshot(1, 100.0, 100.5), create_test_snapshot(2, 101.0, 101.5)];
+        let columns = SnapshotColumns::from_snapshots(&snapshots);
+
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns.ask_qty[9], vec![11.0, 11.0]);
+        let restored = columns.to_snapshots();
+        assert_eq!(restored[1].best_bid(), 101.0);
+        assert_eq!(restored[1].bid_qty_10, 10.0);
+        assert_eq!(SnapshotColumns::from_snapshots(&restored), columns);
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_arrow_round_trip() {
+        let snapshots = vec![create_test_snapshot(1, 100.0, 100.5), create_test_snapshot(2, 101.0, 101.5)];
+        let columns = SnapshotColumns::from_snapshots(&snapshots);
+
+        let batch = columns.to_arrow().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 43);
+        assert_eq!(SnapshotColumns::from_arrow(&batch).unwrap(), columns);
+    }
+}
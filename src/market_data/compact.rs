@@ -0,0 +1,301 @@
+use super::columns::BOOK_DEPTH;
+use super::source::SnapshotSource;
+use crate::types::{L2Snapshot, PriceLevel};
+use anyhow::{Context, Result};
+use std::borrow::Cow;
+
+const LEVEL_VALUES: usize = BOOK_DEPTH * 2;
+const MAX_DECIMALS: u32 = 9;
+const EMPTY_PRICE: i32 = i32::MIN;
+
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LevelPrecision {
+    #[default]
+    F64,
+    F32,
+}
+
+
+
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct CompactSnapshots {
+    decimals: u32,
+    base_units: Option<i64>,
+    row_index: Vec<u64>,
+    timestamp_us: Vec<u64>,
+    prices: Vec<[i32; LEVEL_VALUES]>,
+    quantities: Vec<[f32; LEVEL_VALUES]>,
+}
+
+impl CompactSnapshots {
+    pub fn from_snapshots(snapshots: &[L2Snapshot]) -> Result<Self> {
+        let mut compact = Self::default();
+        for snapshot in snapshots {
+            compact.push(snapshot)?;
+        }
+        Ok(compact)
+    }
+
+    
+    
+    
+    pub fn push(&mut self, snapshot: &L2Snapshot) -> Result<()> {
+        let bids = snapshot.bids();
+        let asks = snapshot.asks();
+        let levels: Vec<&PriceLevel> = bids.iter().chain(asks.iter()).collect();
+        let decimals = levels
+            .iter()
+            .map(|level| price_decimals(level.price))
+            .try_fold(self.decimals, |max, d| d.map(|d| max.max(d)))
+            .with_context(|| format!("Row {} has prices off a decimal tick grid; use LevelPrecision::F64", snapshot.row_index))?;
+        self.rescale(decimals)?;
+
+        let scale = 10f64.powi(self.decimals as i32);
+        let base = *self.base_units.get_or_insert((snapshot.best_bid() * scale).round() as i64);
+        let mut prices = [EMPTY_PRICE; LEVEL_VALUES];
+        let mut quantities = [0.0f32; LEVEL_VALUES];
+        for (slot, level) in levels.iter().enumerate() {
+            quantities[slot] = level.quantity as f32;
+            if level.price != 0.0 {
+                prices[slot] = i32::try_from((level.price * scale).round() as i64 - base)
+                    .ok()
+                    .filter(|&units| units != EMPTY_PRICE)
+                    .with_context(|| format!("Row {} price {} is out of range for compact storage", snapshot.row_index, level.price))?;
+            }
+        }
+        self.row_index.push(snapshot.row_index as u64);
+        self.timestamp_us.push(snapshot.timestamp_us);
+        self.prices.push(prices);
+        self.quantities.push(quantities);
+        Ok(())
+    }
+
+    fn rescale(&mut self, decimals: u32) -> Result<()> {
+        if decimals <= self.decimals {
+            return Ok(());
+        }
+        let factor = 10i64.pow(decimals - self.decimals);
+        self.base_units = self.base_units.map(|base| base * factor);
+        for row in &mut self.prices {
+            for price in row.iter_mut().filter(|p| **p != EMPTY_PRICE) {
+                *price = i32::try_from(*price as i64 * factor)
+                    .context("Prices are out of range for compact storage after adding decimals")?;
+            }
+        }
+        self.decimals = decimals;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.timestamp_us.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timestamp_us.is_empty()
+    }
+
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    
+    
+    pub fn snapshot(&self, row: usize) -> L2Snapshot {
+        let scale = 10f64.powi(self.decimals as i32);
+        let base = self.base_units.unwrap_or(0);
+        let level = |slot: usize| {
+            let units = self.prices[row][slot];
+            let price = if units == EMPTY_PRICE { 0.0 } else { (base + units as i64) as f64 / scale };
+            PriceLevel::new(price, self.quantities[row][slot] as f64)
+        };
+        let bids: Vec<PriceLevel> = (0..BOOK_DEPTH).map(level).collect();
+        let asks: Vec<PriceLevel> = (BOOK_DEPTH..LEVEL_VALUES).map(level).collect();
+        L2Snapshot::from_levels(self.row_index[row] as usize, self.timestamp_us[row], String::new(), &bids, &asks)
+    }
+
+    pub fn to_snapshots(&self) -> Vec<L2Snapshot> {
+        (0..self.len()).map(|row| self.snapshot(row)).collect()
+    }
+
+    pub fn memory_bytes(&self) -> usize {
+        self.len() * (2 * std::mem::size_of::<u64>() + std::mem::size_of::<[i32; LEVEL_VALUES]>() + std::mem::size_of::<[f32; LEVEL_VALUES]>())
+    }
+}
+
+
+
+fn price_decimals(price: f64) -> Option<u32> {
+    (0..=MAX_DECIMALS).find(|&d| {
+        let scale = 10f64.powi(d as i32);
+        let units = (price * scale).round();
+        units.abs() < i64::MAX as f64 && units / scale == price
+    })
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub enum SnapshotStore {
+    Full(Vec<L2Snapshot>),
+    Compact(CompactSnapshots),
+}
+
+impl SnapshotStore {
+    pub fn load<S: SnapshotSource + ?Sized>(source: &mut S, precision: LevelPrecision, limit: Option<usize>) -> Result<Self> {
+        let mut store = match precision {
+            LevelPrecision::F64 => SnapshotStore::Full(Vec::new()),
+            LevelPrecision::F32 => SnapshotStore::Compact(CompactSnapshots::default()),
+        };
+        while limit.is_none_or(|max| store.len() < max) {
+            let Some(snapshot) = source.next_snapshot()? else {
+                break;
+            };
+            match &mut store {
+                SnapshotStore::Full(snapshots) => snapshots.push(snapshot),
+                SnapshotStore::Compact(compact) => compact.push(&snapshot)?,
+            }
+        }
+        Ok(store)
+    }
+
+    pub fn precision(&self) -> LevelPrecision {
+        match self {
+            SnapshotStore::Full(_) => LevelPrecision::F64,
+            SnapshotStore::Compact(_) => LevelPrecision::F32,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            SnapshotStore::Full(snapshots) => snapshots.len(),
+            SnapshotStore::Compact(compact) => compact.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn snapshot(&self, row: usize) -> Cow<'_, L2Snapshot> {
+        match self {
+            SnapshotStore::Full(snapshots) => Cow::Borrowed(&snapshots[row]),
+            SnapshotStore::Compact(compact) => Cow::Owned(compact.snapshot(row)),
+        }
+    }
+
+    
+    pub fn memory_bytes(&self) -> usize {
+        match self {
+            SnapshotStore::Full(snapshots) => snapshots
+                .iter()
+                .map(|s| std::mem::size_of::<L2Snapshot>() + s.datetime.capacity())
+                .sum(),
+            SnapshotStore::Compact(compact) => compact.memory_bytes(),
+        }
+    }
+
+    pub fn source(&self) -> StoreSource<'_> {
+        StoreSource { store: self, next: 0 }
+    }
+}
+
+
+pub struct StoreSource<'a> {
+    store: &'a SnapshotStore,
+    next: usize,
+}
+
+impl SnapshotSource for StoreSource<'_> {
+    fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>> {
+        if self.next >= self.store.len() {
+            return Ok(None);
+        }
+        let snapshot = self.store.snapshot(self.next).into_owned();
+        self.next += 1;
+        Ok(Some(snapshot))
+    }
+
+    fn describe(&self) -> String {
+        format!("{:?} store ({} of {} read)", self.store.precision(), self.next, self.store.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{BacktestEngine, EngineConfig};
+    use crate::market_data::MemorySource;
+    use crate::strategy::market_maker::{MarketMaker, MarketMakerConfig};
+
+    fn snapshots() -> Vec<L2Snapshot> {
+        (0..400u64)
+            .map(|i| {
+                let mid = ((43_250.0 + (i as f64 * 0.07).sin() * 12.5) * 10.0).round() / 10.0;
+                let bids: Vec<PriceLevel> = (0..BOOK_DEPTH).map(|l| PriceLevel::new(mid - 0.05 - l as f64 * 0.1, 0.137 + l as f64)).collect();
+                let asks: Vec<PriceLevel> = (0..BOOK_DEPTH).map(|l| PriceLevel::new(mid + 0.05 + l as f64 * 0.1, 0.291 + l as f64)).collect();
+                let round = |level: PriceLevel| PriceLevel::new((level.price * 100.0).round() / 100.0, level.quantity);
+                let bids: Vec<PriceLevel> = bids.into_iter().map(round).collect();
+                let asks: Vec<PriceLevel> = asks.into_iter().map(round).collect();
+                L2Snapshot::from_levels(i as usize, i * 1_000, "2023-01-09 22:17:40".to_string(), &bids, &asks)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_f32_storage_halves_memory_and_keeps_prices_exact() {
+        let original = snapshots();
+        let full = SnapshotStore::load(&mut MemorySource::new(original.clone()), LevelPrecision::F64, None).unwrap();
+        let compact = SnapshotStore::load(&mut MemorySource::new(original.clone()), LevelPrecision::F32, None).unwrap();
+        assert_eq!(compact.len(), original.len());
+        assert!(compact.memory_bytes() * 2 <= full.memory_bytes());
+
+        let mut max_qty_error = 0.0f64;
+        for (row, snapshot) in original.iter().enumerate() {
+            let decoded = compact.snapshot(row);
+            assert_eq!(decoded.timestamp_us, snapshot.timestamp_us);
+            assert!(decoded.datetime.is_empty());
+            for (a, b) in snapshot.bids().iter().chain(snapshot.asks().iter()).zip(decoded.bids().iter().chain(decoded.asks().iter())) {
+                assert_eq!(a.price.to_bits(), b.price.to_bits());
+                max_qty_error = max_qty_error.max(((a.quantity - b.quantity) / a.quantity).abs());
+            }
+        }
+        assert!(max_qty_error <= f32::EPSILON as f64);
+
+        let mut mixed = CompactSnapshots::default();
+        let coarse = L2Snapshot::from_levels(0, 0, String::new(), &[PriceLevel::new(100.5, 1.0)], &[PriceLevel::new(101.0, 1.0)]);
+        let fine = L2Snapshot::from_levels(1, 1, String::new(), &[PriceLevel::new(100.25, 1.0)], &[PriceLevel::new(100.75, 1.0)]);
+        mixed.push(&coarse).unwrap();
+        mixed.push(&fine).unwrap();
+        assert_eq!(mixed.decimals(), 2);
+        assert_eq!(mixed.snapshot(0).best_bid(), 100.5);
+        assert_eq!(mixed.snapshot(0).bid_price_2, 0.0);
+        assert_eq!(mixed.snapshot(1).best_ask(), 100.75);
+
+        let off_grid = L2Snapshot::from_levels(2, 2, String::new(), &[PriceLevel::new(1.0 / 3.0, 1.0)], &[PriceLevel::new(1.0, 1.0)]);
+        assert!(mixed.push(&off_grid).is_err());
+    }
+
+    #[test]
+    fn test_f32_backtest_replays_identical_fills() {
+        let original = snapshots();
+        let compact = SnapshotStore::load(&mut MemorySource::new(original.clone()), LevelPrecision::F32, None).unwrap();
+        let config = MarketMakerConfig { tick_size: 0.1, spread_ticks: 0.5, ..Default::default() };
+        let engine = BacktestEngine::new(EngineConfig::default());
+
+        let full = engine.run(&mut MarketMaker::new(config.clone()), &original);
+        let reduced = engine.run_source(&mut MarketMaker::new(config), &mut compact.source(), None).unwrap();
+
+        assert!(full.result.metrics.total_trades > 0);
+        let fills = |run: &crate::engine::EngineRun| {
+            run.position.trades().iter().map(|t| (t.side, t.price.to_bits(), t.quantity.to_bits(), t.timestamp_us)).collect::<Vec<_>>()
+        };
+        assert_eq!(fills(&reduced), fills(&full));
+        assert_eq!(reduced.fingerprint(), full.fingerprint());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::types::L2Snapshot;
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivedValues {
+    pub mid: f64,
+    pub spread: f64,
+    pub imbalance: f64,
+    pub microprice: f64,
+}
+
+impl DerivedValues {
+    pub fn compute(snapshot: &L2Snapshot) -> Self {
+        Self {
+            mid: (snapshot.best_bid() + snapshot.best_ask()) / 2.0,
+            spread: snapshot.spread(),
+            imbalance: snapshot.imbalance(),
+            microprice: snapshot.microprice(),
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct DerivedCache {
+    values: Vec<DerivedValues>,
+}
+
+impl DerivedCache {
+    pub fn build(snapshots: &[L2Snapshot]) -> Self {
+        Self {
+            values: snapshots.iter().map(DerivedValues::compute).collect(),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&DerivedValues> {
+        self.values.get(index)
+    }
+
+    pub fn values(&self) -> &[DerivedValues] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
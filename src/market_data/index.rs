@@ -0,0 +1,148 @@
+use super::reader::is_compressed;
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 4] = b"ZIDX";
+
+pub const DEFAULT_INDEX_STRIDE: usize = 1_000;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub timestamp_us: u64,
+    
+    pub offset: u64,
+    pub row: u64,
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotIndex {
+    pub stride: usize,
+    
+    pub source_len: u64,
+    pub entries: Vec<IndexEntry>,
+}
+
+
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".idx");
+    PathBuf::from(name)
+}
+
+impl SnapshotIndex {
+    
+    
+    pub fn build(path: &Path, stride: usize) -> Result<Self> {
+        if is_compressed(path) {
+            anyhow::bail!("Cannot index compressed input {}", path.display());
+        }
+        let stride = stride.max(1);
+        let file = File::open(path).context(format!("Failed to open snapshot file: {}", path.display()))?;
+        let source_len = file.metadata()?.len();
+        let mut reader = csv::ReaderBuilder::new().has_headers(true).from_reader(BufReader::new(file));
+        let ts_column = reader
+            .headers()?
+            .iter()
+            .position(|h| h == "timestamp_us")
+            .context("Snapshot file has no timestamp_us column")?;
+
+        let mut entries = Vec::new();
+        let mut record = csv::ByteRecord::new();
+        let mut row = 0u64;
+        loop {
+            let offset = reader.position().byte();
+            if !reader.read_byte_record(&mut record)? {
+                break;
+            }
+            if row.is_multiple_of(stride as u64) {
+                let timestamp_us = std::str::from_utf8(record.get(ts_column).unwrap_or_default())?
+                    .parse()
+                    .context(format!("Invalid timestamp at row {}", row))?;
+                entries.push(IndexEntry { timestamp_us, offset, row });
+            }
+            row += 1;
+        }
+
+        Ok(Self { stride, source_len, entries })
+    }
+
+    
+    pub fn lookup(&self, timestamp_us: u64) -> Option<&IndexEntry> {
+        let idx = self.entries.partition_point(|e| e.timestamp_us < timestamp_us);
+        self.entries.get(idx.saturating_sub(1))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(20 + self.entries.len() * 24);
+        buf.extend_from_slice(MAGIC);
+        buf.extend_from_slice(&(self.stride as u64).to_le_bytes());
+        buf.extend_from_slice(&self.source_len.to_le_bytes());
+        for entry in &self.entries {
+            buf.extend_from_slice(&entry.timestamp_us.to_le_bytes());
+            buf.extend_from_slice(&entry.offset.to_le_bytes());
+            buf.extend_from_slice(&entry.row.to_le_bytes());
+        }
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 20 || &bytes[..4] != MAGIC || !(bytes.len() - 20).is_multiple_of(24) {
+            anyhow::bail!("Not a snapshot index");
+        }
+        let word = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().expect("8-byte slice"));
+        let entries = (20..bytes.len())
+            .step_by(24)
+            .map(|at| IndexEntry { timestamp_us: word(at), offset: word(at + 8), row: word(at + 16) })
+            .collect();
+        Ok(Self { stride: word(4) as usize, source_len: word(12), entries })
+    }
+
+    pub fn write_sidecar(&self, data_path: &Path) -> Result<PathBuf> {
+        let path = sidecar_path(data_path);
+        std::fs::write(&path, self.to_bytes()).context(format!("Failed to write index {}", path.display()))?;
+        Ok(path)
+    }
+
+    
+    
+    pub fn load_sidecar(data_path: &Path) -> Result<Option<Self>> {
+        let path = sidecar_path(data_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = std::fs::read(&path).context(format!("Failed to read index {}", path.display()))?;
+        let index = Self::from_bytes(&bytes)?;
+        let data_len = std::fs::metadata(data_path)?.len();
+        Ok((index.source_len == data_len).then_some(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_round_trip_and_lookup() {
+        let index = SnapshotIndex {
+            stride: 2,
+            source_len: 100,
+            entries: vec![
+                IndexEntry { timestamp_us: 0, offset: 10, row: 0 },
+                IndexEntry { timestamp_us: 20, offset: 50, row: 2 },
+            ],
+        };
+        assert_eq!(SnapshotIndex::from_bytes(&index.to_bytes()).unwrap(), index);
+        assert_eq!(index.lookup(19).unwrap().row, 0);
+        assert_eq!(index.lookup(20).unwrap().row, 0);
+        assert_eq!(index.lookup(21).unwrap().row, 2);
+        assert_eq!(index.lookup(1_000).unwrap().row, 2);
+        assert!(SnapshotIndex::from_bytes(b"ZIDX").is_err());
+        assert_eq!(sidecar_path(Path::new("data/l2.csv")), PathBuf::from("data/l2.csv.idx"));
+    }
+}
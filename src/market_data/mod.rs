@@ -1,4 +1,22 @@
 
 pub mod reader;
+pub mod derived;
+pub mod tape;
+pub mod tick_size;
+pub mod adjust;
+pub mod columns;
+pub mod source;
+pub mod recorder;
+pub mod index;
+pub mod compact;
 
-pub use reader::{SnapshotReader, SnapshotStats};
+pub use reader::{SessionBoundary, SnapshotReader, SnapshotStats, SpreadWidthStats};
+pub use derived::{DerivedCache, DerivedValues};
+pub use tape::{EstimatedTrade, TapeEstimator, TradeTape};
+pub use tick_size::{infer_tick_size, tick_size_warning};
+pub use adjust::{AdjustmentKind, AdjustmentSchedule, PriceAdjustment};
+pub use columns::SnapshotColumns;
+pub use source::{MemorySource, SnapshotEncoding, SnapshotSource};
+pub use recorder::{Recorder, RecorderConfig};
+pub use index::{sidecar_path, IndexEntry, SnapshotIndex, DEFAULT_INDEX_STRIDE};
+pub use compact::{CompactSnapshots, LevelPrecision, SnapshotStore, StoreSource};
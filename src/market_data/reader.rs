@@ -2,63 +2,186 @@
 
 
 
+use super::index::{SnapshotIndex, DEFAULT_INDEX_STRIDE};
 use crate::types::L2Snapshot;
+use crate::utils::stats::{mean, percentile, sorted};
 use anyhow::{Context, Result};
 use csv::Reader;
+use std::collections::{BTreeMap, VecDeque};
 use std::fs::File;
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 
 
 pub struct SnapshotReader {
-    reader: Reader<File>,
+    reader: Reader<Box<dyn Read>>,
     snapshots_read: usize,
+    pending_files: VecDeque<PathBuf>,
+    sessions: Vec<SessionBoundary>,
+    pending: Option<L2Snapshot>,
+    keep_datetime: bool,
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionBoundary {
+    pub session: usize,
+    pub path: PathBuf,
+    
+    pub start_index: usize,
 }
 
 impl SnapshotReader {
     
     pub fn new(path: &Path) -> Result<Self> {
-        let file = File::open(path)
-            .context(format!("Failed to open snapshot file: {}", path.display()))?;
+        Self::from_files(vec![path.to_path_buf()])
+    }
 
-        let reader = csv::ReaderBuilder::new()
-            .has_headers(true)
-            .from_reader(file);
+    
+    
+    pub fn from_glob(pattern: &str) -> Result<Self> {
+        let mut files = Vec::new();
+        for entry in glob::glob(pattern).context(format!("Invalid glob pattern: {}", pattern))? {
+            files.push(entry.context("Failed to read glob entry")?);
+        }
+        if files.is_empty() {
+            anyhow::bail!("No files match pattern: {}", pattern);
+        }
+
+        let mut keyed = Vec::with_capacity(files.len());
+        for path in files {
+            let first_ts = Self::first_timestamp(&path)?;
+            keyed.push((first_ts, path));
+        }
+        keyed.sort();
+
+        Self::from_files(keyed.into_iter().map(|(_, path)| path).collect())
+    }
+
+    fn from_files(files: Vec<PathBuf>) -> Result<Self> {
+        let mut pending_files: VecDeque<PathBuf> = files.into();
+        let first = pending_files.pop_front().context("No input files")?;
 
         Ok(Self {
-            reader,
+            reader: Self::open_csv(&first)?,
             snapshots_read: 0,
+            pending_files,
+            sessions: vec![SessionBoundary { session: 0, path: first, start_index: 0 }],
+            pending: None,
+            keep_datetime: true,
         })
     }
 
     
     
     
-    pub fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>> {
-        let mut iter = self.reader.deserialize();
+    pub fn without_datetime(mut self) -> Result<Self> {
+        self.keep_datetime = false;
+        mask_datetime(&mut self.reader)?;
+        if let Some(snapshot) = &mut self.pending {
+            snapshot.datetime = String::new();
+        }
+        Ok(self)
+    }
 
-        match iter.next() {
-            Some(result) => {
-                let snapshot: L2Snapshot = result
-                    .context(format!("Failed to parse snapshot at row {}", self.snapshots_read))?;
+    
+    
+    
+    pub fn open_at(path: &Path, start_us: u64) -> Result<Self> {
+        let index = match SnapshotIndex::load_sidecar(path)? {
+            _ if is_compressed(path) => None,
+            Some(index) => Some(index),
+            None => Some(SnapshotIndex::build(path, DEFAULT_INDEX_STRIDE)?),
+        };
+        let mut reader = Self::new(path)?;
+        if let Some(entry) = index.as_ref().and_then(|index| index.lookup(start_us)) {
+            let mut file = BufReader::new(File::open(path)?);
+            let mut header = Vec::new();
+            file.read_until(b'\n', &mut header)?;
+            file.seek(SeekFrom::Start(entry.offset))?;
+            let chained: Box<dyn Read> = Box::new(std::io::Cursor::new(header).chain(file));
+            reader.reader = csv::ReaderBuilder::new().has_headers(true).from_reader(chained);
+            reader.snapshots_read = entry.row as usize;
+        }
+        while let Some(snapshot) = reader.next_snapshot()? {
+            if snapshot.timestamp_us >= start_us {
+                reader.snapshots_read -= 1;
+                reader.pending = Some(snapshot);
+                break;
+            }
+        }
+        Ok(reader)
+    }
+
+    fn open_csv(path: &Path) -> Result<Reader<Box<dyn Read>>> {
+        Ok(csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(open_input(path)?))
+    }
 
-                self.snapshots_read += 1;
-                Ok(Some(snapshot))
+    fn first_timestamp(path: &Path) -> Result<u64> {
+        let mut reader = Self::open_csv(path)?;
+        match reader.deserialize::<L2Snapshot>().next() {
+            Some(result) => Ok(result
+                .context(format!("Failed to parse first snapshot in {}", path.display()))?
+                .timestamp_us),
+            None => Ok(u64::MAX),
+        }
+    }
+
+    
+    
+    
+    pub fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>> {
+        if let Some(snapshot) = self.pending.take() {
+            self.snapshots_read += 1;
+            return Ok(Some(snapshot));
+        }
+        loop {
+            let mut iter = self.reader.deserialize();
+
+            match iter.next() {
+                Some(result) => {
+                    let snapshot: L2Snapshot = result
+                        .context(format!("Failed to parse snapshot at row {}", self.snapshots_read))?;
+
+                    self.snapshots_read += 1;
+                    return Ok(Some(snapshot));
+                }
+                None => {
+                    let Some(next) = self.pending_files.pop_front() else {
+                        return Ok(None);
+                    };
+                    self.reader = Self::open_csv(&next)?;
+                    if !self.keep_datetime {
+                        mask_datetime(&mut self.reader)?;
+                    }
+                    self.sessions.push(SessionBoundary {
+                        session: self.sessions.len(),
+                        path: next,
+                        start_index: self.snapshots_read,
+                    });
+                }
             }
-            None => Ok(None),
         }
     }
 
     
+    pub fn sessions(&self) -> &[SessionBoundary] {
+        &self.sessions
+    }
+
+    
+    pub fn current_session(&self) -> usize {
+        self.sessions.len() - 1
+    }
+
+    
     
     
     
     pub fn read_all(path: &Path) -> Result<Vec<L2Snapshot>> {
-        let file = File::open(path)
-            .context(format!("Failed to open snapshot file: {}", path.display()))?;
-
-        let mut reader = csv::ReaderBuilder::new()
-            .has_headers(true)
-            .from_reader(file);
+        let mut reader = Self::open_csv(path)?;
 
         let mut snapshots = Vec::new();
 
@@ -72,23 +195,148 @@ impl SnapshotReader {
     }
 
     
+    
+    
+    pub fn read_all_parallel(path: &Path, threads: usize) -> Result<Vec<L2Snapshot>> {
+        if is_compressed(path) {
+            return Self::read_all(path);
+        }
+        let bytes = std::fs::read(path).context(format!("Failed to open snapshot file: {}", path.display()))?;
+        let header_end = bytes.iter().position(|&b| b == b'\n').map(|i| i + 1).unwrap_or(bytes.len());
+        let (header, body) = bytes.split_at(header_end);
+        let threads = match threads {
+            0 => std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            n => n,
+        };
+        let chunks = line_aligned_chunks(body, threads);
+
+        let parsed: Vec<Result<Vec<L2Snapshot>>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = chunks
+                .iter()
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let mut reader = csv::ReaderBuilder::new().has_headers(true).from_reader(header.chain(*chunk));
+                        reader
+                            .deserialize()
+                            .map(|result| result.context("Failed to parse snapshot"))
+                            .collect::<Result<Vec<L2Snapshot>>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().expect("parse thread panicked")).collect()
+        });
+
+        let mut snapshots = Vec::new();
+        for (idx, chunk) in parsed.into_iter().enumerate() {
+            snapshots.extend(chunk.context(format!("Failed to parse chunk {} of {}", idx, path.display()))?);
+        }
+        Ok(snapshots)
+    }
+
+    
     pub fn count(&self) -> usize {
         self.snapshots_read
     }
 }
 
 
+
+
+fn line_aligned_chunks(body: &[u8], parts: usize) -> Vec<&[u8]> {
+    let target = body.len().div_ceil(parts.max(1)).max(1);
+    let mut chunks = Vec::with_capacity(parts);
+    let mut start = 0;
+    while start < body.len() {
+        let end = match body[(start + target).min(body.len())..].iter().position(|&b| b == b'\n') {
+            Some(newline) => start + target + newline + 1,
+            None => body.len(),
+        };
+        chunks.push(&body[start..end]);
+        start = end;
+    }
+    chunks
+}
+
+
+
+
+fn mask_datetime(reader: &mut Reader<Box<dyn Read>>) -> Result<()> {
+    let headers: csv::StringRecord = reader
+        .headers()?
+        .iter()
+        .map(|h| if h == "datetime" { "_datetime" } else { h })
+        .collect();
+    reader.set_headers(headers);
+    Ok(())
+}
+
+
+pub fn is_compressed(path: &Path) -> bool {
+    matches!(path.extension().and_then(|e| e.to_str()), Some("gz") | Some("zst"))
+}
+
+
+pub fn open_input(path: &Path) -> Result<Box<dyn Read>> {
+    let file = File::open(path)
+        .context(format!("Failed to open snapshot file: {}", path.display()))?;
+    let file = BufReader::new(file);
+
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("gz") => open_gzip(file),
+        Some("zst") => open_zstd(file),
+        _ => Ok(Box::new(file)),
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn open_gzip(file: BufReader<File>) -> Result<Box<dyn Read>> {
+    Ok(Box::new(flate2::read::MultiGzDecoder::new(file)))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn open_gzip(_file: BufReader<File>) -> Result<Box<dyn Read>> {
+    anyhow::bail!("Reading .gz input requires the `gzip` feature")
+}
+
+#[cfg(feature = "zstd")]
+fn open_zstd(file: BufReader<File>) -> Result<Box<dyn Read>> {
+    Ok(Box::new(zstd::Decoder::with_buffer(file)?))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn open_zstd(_file: BufReader<File>) -> Result<Box<dyn Read>> {
+    anyhow::bail!("Reading .zst input requires the `zstd` feature")
+}
+
+
 #[derive(Debug)]
 pub struct SnapshotStats {
     pub count: usize,
     pub start_time_us: u64,
     pub end_time_us: u64,
     pub duration_ms: u64,
-    pub min_spread: f64,
-    pub max_spread: f64,
     pub avg_spread: f64,
+    pub spread_p50: f64,
+    pub spread_p90: f64,
+    pub spread_p99: f64,
     pub min_price: f64,
     pub max_price: f64,
+    
+    pub bid_changes: usize,
+    pub ask_changes: usize,
+    
+    pub spread_widths: Vec<SpreadWidthStats>,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct SpreadWidthStats {
+    pub width: f64,
+    pub snapshots: usize,
+    pub time_us: u64,
+    pub time_pct: f64,
+    
+    pub avg_run_us: f64,
 }
 
 impl SnapshotStats {
@@ -101,9 +349,7 @@ impl SnapshotStats {
         let duration_ms = (end_time_us - start_time_us) / 1000;
 
         let spreads: Vec<f64> = snapshots.iter().map(|s| s.spread()).collect();
-        let min_spread = spreads.iter().copied().fold(f64::INFINITY, f64::min);
-        let max_spread = spreads.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-        let avg_spread = spreads.iter().sum::<f64>() / spreads.len() as f64;
+        let sorted_spreads = sorted(&spreads);
 
         let min_price = snapshots.iter()
             .map(|s| s.best_bid())
@@ -112,20 +358,41 @@ impl SnapshotStats {
             .map(|s| s.best_ask())
             .fold(f64::NEG_INFINITY, f64::max);
 
+        let bid_changes = snapshots.windows(2)
+            .filter(|w| w[0].best_bid() != w[1].best_bid())
+            .count();
+        let ask_changes = snapshots.windows(2)
+            .filter(|w| w[0].best_ask() != w[1].best_ask())
+            .count();
+
         Self {
             count,
             start_time_us,
             end_time_us,
             duration_ms,
-            min_spread,
-            max_spread,
-            avg_spread,
+            avg_spread: mean(&spreads),
+            spread_p50: percentile(&sorted_spreads, 50.0),
+            spread_p90: percentile(&sorted_spreads, 90.0),
+            spread_p99: percentile(&sorted_spreads, 99.0),
             min_price,
             max_price,
+            bid_changes,
+            ask_changes,
+            spread_widths: spread_width_stats(snapshots),
         }
     }
 
     
+    pub fn bid_change_pct(&self) -> f64 {
+        change_pct(self.bid_changes, self.count)
+    }
+
+    
+    pub fn ask_change_pct(&self) -> f64 {
+        change_pct(self.ask_changes, self.count)
+    }
+
+    
     pub fn print(&self) {
         println!("\n📊 Snapshot Statistics");
         println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -133,16 +400,69 @@ impl SnapshotStats {
         println!("   Duration:        {} ms ({:.2} hours)",
                  self.duration_ms, self.duration_ms as f64 / 3_600_000.0);
         println!("   Price Range:     ${:.2} - ${:.2}", self.min_price, self.max_price);
-        println!("   Spread (min):    ${:.4}", self.min_spread);
         println!("   Spread (avg):    ${:.4}", self.avg_spread);
-        println!("   Spread (max):    ${:.4}", self.max_spread);
+        println!("   Spread (p50):    ${:.4}", self.spread_p50);
+        println!("   Spread (p90):    ${:.4}", self.spread_p90);
+        println!("   Spread (p99):    ${:.4}", self.spread_p99);
+        println!("   Bid Changes:     {} ({:.1}% of updates)", self.bid_changes, self.bid_change_pct());
+        println!("   Ask Changes:     {} ({:.1}% of updates)", self.ask_changes, self.ask_change_pct());
+        println!("   ───────────────────────────────────────");
+        println!("   {:>10} {:>10} {:>12}", "Width", "Time %", "Avg Run");
+        for width in self.spread_widths.iter().take(5) {
+            println!("   {:>10.4} {:>9.1}% {:>9.0} us", width.width, width.time_pct, width.avg_run_us);
+        }
         println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     }
 }
 
+fn change_pct(changes: usize, count: usize) -> f64 {
+    if count < 2 {
+        return 0.0;
+    }
+    changes as f64 / (count - 1) as f64 * 100.0
+}
+
+
+
+
+fn spread_width_stats(snapshots: &[L2Snapshot]) -> Vec<SpreadWidthStats> {
+    let key = |s: &L2Snapshot| (s.spread() * 1e8).round() as i64;
+
+    let mut widths: BTreeMap<i64, (usize, u64, usize)> = BTreeMap::new();
+    let mut prev_key = None;
+    for (i, snapshot) in snapshots.iter().enumerate() {
+        let k = key(snapshot);
+        let dwell = snapshots.get(i + 1)
+            .map(|next| next.timestamp_us.saturating_sub(snapshot.timestamp_us))
+            .unwrap_or(0);
+        let entry = widths.entry(k).or_insert((0, 0, 0));
+        entry.0 += 1;
+        entry.1 += dwell;
+        if prev_key != Some(k) {
+            entry.2 += 1;
+        }
+        prev_key = Some(k);
+    }
+
+    let total_us: u64 = widths.values().map(|w| w.1).sum();
+    let mut stats: Vec<SpreadWidthStats> = widths
+        .into_iter()
+        .map(|(k, (snapshots, time_us, runs))| SpreadWidthStats {
+            width: k as f64 / 1e8,
+            snapshots,
+            time_us,
+            time_pct: if total_us > 0 { time_us as f64 / total_us as f64 * 100.0 } else { 0.0 },
+            avg_run_us: time_us as f64 / runs as f64,
+        })
+        .collect();
+    stats.sort_by(|a, b| b.time_us.cmp(&a.time_us).then(b.snapshots.cmp(&a.snapshots)));
+    stats
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::PriceLevel;
 
     #[test]
     fn test_snapshot_reader() {
@@ -169,4 +489,190 @@ mod tests {
         println!("✓ Successfully read snapshot: bid={}, ask={}, spread={}",
                  snapshot.best_bid(), snapshot.best_ask(), snapshot.spread());
     }
+
+    fn create_test_snapshot(timestamp_us: u64, bid: f64, ask: f64) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_from_glob_chains_files_in_timestamp_order() {
+        let dir = std::env::temp_dir().join(format!("zaphft_glob_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, start) in [("a_day2.csv", 1_000), ("b_day1.csv", 0)] {
+            let mut writer = csv::Writer::from_path(dir.join(name)).unwrap();
+            for i in 0..3 {
+                writer.serialize(create_test_snapshot(start + i * 10, 100.0, 100.5)).unwrap();
+            }
+            writer.flush().unwrap();
+        }
+
+        let pattern = format!("{}/*.csv", dir.display());
+        let mut reader = SnapshotReader::from_glob(&pattern).unwrap();
+        let mut timestamps = Vec::new();
+        while let Some(snapshot) = reader.next_snapshot().unwrap() {
+            timestamps.push(snapshot.timestamp_us);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(timestamps, vec![0, 10, 20, 1_000, 1_010, 1_020]);
+        assert_eq!(reader.count(), 6);
+        assert_eq!(reader.current_session(), 1);
+        assert_eq!(reader.sessions()[1].start_index, 3);
+        assert!(reader.sessions()[0].path.ends_with("b_day1.csv"));
+        assert!(SnapshotReader::from_glob(&pattern).is_err());
+    }
+
+    #[test]
+    fn test_open_at_seeks_with_sidecar_index() {
+        let path = std::env::temp_dir().join(format!("zaphft_index_{}.csv", std::process::id()));
+        let mut writer = csv::Writer::from_path(&path).unwrap();
+        for i in 0..25 {
+            writer.serialize(create_test_snapshot(i * 10, 100.0, 100.5)).unwrap();
+        }
+        for i in 0..6 {
+            writer.serialize(create_test_snapshot(250 + (i / 2) * 10, 100.0, 100.5)).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let index = SnapshotIndex::build(&path, 4).unwrap();
+        assert_eq!(index.entries.len(), 8);
+        assert_eq!(index.entries[7].timestamp_us, 260);
+        let sidecar = index.write_sidecar(&path).unwrap();
+        assert_eq!(SnapshotIndex::load_sidecar(&path).unwrap(), Some(index));
+
+        let mut reader = SnapshotReader::open_at(&path, 135).unwrap();
+        let first = reader.next_snapshot().unwrap().unwrap();
+        let mut rest = 0;
+        while reader.next_snapshot().unwrap().is_some() {
+            rest += 1;
+        }
+        let mut tied = SnapshotReader::open_at(&path, 260).unwrap();
+        let tied_first = tied.next_snapshot().unwrap().unwrap();
+        let tied_count = tied.count();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&sidecar).unwrap();
+
+        assert_eq!(first.timestamp_us, 140);
+        assert_eq!(rest, 16);
+        assert_eq!(reader.count(), 31);
+        assert_eq!(tied_first.timestamp_us, 260);
+        assert_eq!(tied_count, 28);
+    }
+
+    #[test]
+    fn test_parallel_load_matches_sequential() {
+        let path = std::env::temp_dir().join(format!("zaphft_parallel_{}.csv", std::process::id()));
+        let mut writer = csv::Writer::from_path(&path).unwrap();
+        for i in 0..101 {
+            writer.serialize(create_test_snapshot(i * 10, 100.0 + i as f64, 100.5 + i as f64)).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let sequential = SnapshotReader::read_all(&path).unwrap();
+        for threads in [1, 3, 8, 500] {
+            let parallel = SnapshotReader::read_all_parallel(&path, threads).unwrap();
+            assert_eq!(
+                parallel.iter().map(|s| (s.timestamp_us, s.best_bid())).collect::<Vec<_>>(),
+                sequential.iter().map(|s| (s.timestamp_us, s.best_bid())).collect::<Vec<_>>(),
+                "threads = {}",
+                threads
+            );
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_without_datetime_skips_column() {
+        let path = std::env::temp_dir().join(format!("zaphft_datetime_{}.csv", std::process::id()));
+        let mut writer = csv::Writer::from_path(&path).unwrap();
+        for i in 0..3 {
+            let mut snapshot = create_test_snapshot(1_673_302_660_000_000 + i, 100.0, 100.5);
+            snapshot.datetime = "2023-01-09 22:17:40".to_string();
+            writer.serialize(snapshot).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let mut full = SnapshotReader::new(&path).unwrap();
+        let mut lean = SnapshotReader::new(&path).unwrap().without_datetime().unwrap();
+        let with = full.next_snapshot().unwrap().unwrap();
+        let without = lean.next_snapshot().unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(with.datetime, "2023-01-09 22:17:40");
+        assert!(without.datetime.is_empty());
+        assert_eq!(without.datetime.capacity(), 0);
+        assert_eq!(without.best_bid(), with.best_bid());
+        assert_eq!(without.display_time(), with.display_time());
+    }
+
+    fn write_compressed(path: &Path, wrap: impl FnOnce(File) -> Box<dyn std::io::Write>) {
+        let mut writer = csv::Writer::from_writer(wrap(File::create(path).unwrap()));
+        for i in 0..5 {
+            writer.serialize(create_test_snapshot(i, 100.0, 100.5)).unwrap();
+        }
+        writer.flush().unwrap();
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_reads_gzip_input() {
+        let path = std::env::temp_dir().join(format!("zaphft_gz_{}.csv.gz", std::process::id()));
+        write_compressed(&path, |f| {
+            Box::new(flate2::write::GzEncoder::new(f, flate2::Compression::fast()))
+        });
+        let snapshots = SnapshotReader::read_all(&path).unwrap();
+        let mut seeked = SnapshotReader::open_at(&path, 3).unwrap();
+        let first = seeked.next_snapshot().unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(snapshots.len(), 5);
+        assert_eq!(first.timestamp_us, 3);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_reads_zstd_input() {
+        let path = std::env::temp_dir().join(format!("zaphft_zst_{}.csv.zst", std::process::id()));
+        write_compressed(&path, |f| Box::new(zstd::Encoder::new(f, 1).unwrap().auto_finish()));
+        let snapshots = SnapshotReader::read_all(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(snapshots.len(), 5);
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn test_gzip_input_requires_feature() {
+        let path = std::env::temp_dir().join(format!("zaphft_nogz_{}.csv.gz", std::process::id()));
+        write_compressed(&path, |f| Box::new(f));
+        let err = SnapshotReader::new(&path).err().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(err.to_string().contains("gzip"));
+    }
+
+    #[test]
+    fn test_spread_distribution_and_stability() {
+        let snapshots = vec![
+            create_test_snapshot(0, 100.0, 100.5),
+            create_test_snapshot(100, 100.0, 100.5),
+            create_test_snapshot(200, 100.0, 101.0),
+            create_test_snapshot(500, 100.5, 101.0),
+            create_test_snapshot(600, 100.5, 101.0),
+        ];
+
+        let stats = SnapshotStats::from_snapshots(&snapshots);
+
+        assert_eq!(stats.spread_p50, 0.5);
+        assert_eq!(stats.bid_changes, 1);
+        assert_eq!(stats.ask_changes, 1);
+        assert_eq!(stats.ask_change_pct(), 25.0);
+
+        let top = &stats.spread_widths[0];
+        assert_eq!(top.width, 0.5);
+        assert_eq!(top.snapshots, 4);
+        assert_eq!(top.time_us, 300);
+        assert_eq!(top.avg_run_us, 150.0);
+        assert_eq!(stats.spread_widths[1].width, 1.0);
+        assert!((top.time_pct - 50.0).abs() < 1e-9);
+    }
 }
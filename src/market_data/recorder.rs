@@ -0,0 +1,207 @@
+use anyhow::{Context, Result};
+use super::source::SnapshotSource;
+use crate::types::L2Snapshot;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    pub directory: PathBuf,
+    pub prefix: String,
+    
+    pub max_file_bytes: Option<u64>,
+    
+    pub max_file_duration_us: Option<u64>,
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("data/recorded"),
+            prefix: "l2".to_string(),
+            max_file_bytes: Some(256 * 1024 * 1024),
+            max_file_duration_us: Some(3_600_000_000),
+        }
+    }
+}
+
+struct CountingWriter {
+    inner: BufWriter<File>,
+    bytes: u64,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+struct OpenFile {
+    writer: csv::Writer<CountingWriter>,
+    first_timestamp_us: u64,
+}
+
+
+
+
+
+
+pub struct Recorder {
+    config: RecorderConfig,
+    current: Option<OpenFile>,
+    files: Vec<PathBuf>,
+    recorded: usize,
+}
+
+impl Recorder {
+    pub fn new(config: RecorderConfig) -> Result<Self> {
+        std::fs::create_dir_all(&config.directory)
+            .context(format!("Failed to create recording directory: {}", config.directory.display()))?;
+
+        Ok(Self {
+            config,
+            current: None,
+            files: Vec::new(),
+            recorded: 0,
+        })
+    }
+
+    pub fn record(&mut self, snapshot: &L2Snapshot) -> Result<()> {
+        if self.should_rotate(snapshot.timestamp_us) {
+            self.close_current()?;
+        }
+        if self.current.is_none() {
+            self.open_file(snapshot.timestamp_us)?;
+        }
+
+        let file = self.current.as_mut().unwrap();
+        file.writer.serialize(snapshot).context("Failed to write snapshot")?;
+        self.recorded += 1;
+        Ok(())
+    }
+
+    
+    pub fn record_source<S: SnapshotSource + ?Sized>(&mut self, source: &mut S, limit: Option<usize>) -> Result<usize> {
+        let mut count = 0;
+        while limit.is_none_or(|max| count < max) {
+            match source.next_snapshot()? {
+                Some(snapshot) => self.record(&snapshot)?,
+                None => break,
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    pub fn recorded(&self) -> usize {
+        self.recorded
+    }
+
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    
+    pub fn finish(mut self) -> Result<Vec<PathBuf>> {
+        self.close_current()?;
+        Ok(std::mem::take(&mut self.files))
+    }
+
+    fn should_rotate(&self, timestamp_us: u64) -> bool {
+        let Some(file) = &self.current else {
+            return false;
+        };
+        let too_big = self
+            .config
+            .max_file_bytes
+            .is_some_and(|max| file.writer.get_ref().bytes >= max);
+        let too_old = self
+            .config
+            .max_file_duration_us
+            .is_some_and(|max| timestamp_us.saturating_sub(file.first_timestamp_us) >= max);
+        too_big || too_old
+    }
+
+    fn open_file(&mut self, first_timestamp_us: u64) -> Result<()> {
+        let path = self.config.directory.join(format!(
+            "{}_{}_{:04}.csv",
+            self.config.prefix,
+            first_timestamp_us,
+            self.files.len()
+        ));
+        let file = File::create(&path).context(format!("Failed to create recording file: {}", path.display()))?;
+        let writer = csv::Writer::from_writer(CountingWriter {
+            inner: BufWriter::new(file),
+            bytes: 0,
+        });
+
+        self.files.push(path);
+        self.current = Some(OpenFile {
+            writer,
+            first_timestamp_us,
+        });
+        Ok(())
+    }
+
+    fn close_current(&mut self) -> Result<()> {
+        if let Some(mut file) = self.current.take() {
+            file.writer.flush().context("Failed to flush recording file")?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.close_current();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::{MemorySource, SnapshotReader};
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64) -> L2Snapshot {
+        let bids = [PriceLevel::new(100.0, 1.0)];
+        let asks = [PriceLevel::new(100.5, 2.0)];
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_rotation_by_time_produces_readable_files() {
+        let directory = std::env::temp_dir().join(format!("zaphft_recorder_{}", std::process::id()));
+        let mut recorder = Recorder::new(RecorderConfig {
+            directory: directory.clone(),
+            max_file_bytes: None,
+            max_file_duration_us: Some(100),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut source = MemorySource::new((0..6).map(|i| create_test_snapshot(i * 40)).collect());
+        assert_eq!(recorder.record_source(&mut source, None).unwrap(), 6);
+        let files = recorder.finish().unwrap();
+
+        assert_eq!(files.len(), 2);
+        let pattern = format!("{}/l2_*.csv", directory.display());
+        let mut reader = SnapshotReader::from_glob(&pattern).unwrap();
+        let mut timestamps = Vec::new();
+        while let Some(snapshot) = reader.next_snapshot().unwrap() {
+            timestamps.push(snapshot.timestamp_us);
+        }
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(timestamps, vec![0, 40, 80, 120, 160, 200]);
+        assert_eq!(reader.sessions()[1].start_index, 3);
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::Message;
+use super::{SnapshotEncoding, SnapshotSource};
+use crate::types::L2Snapshot;
+use std::time::Duration;
+
+
+#[derive(Debug, Clone)]
+pub struct KafkaConfig {
+    pub brokers: String,
+    pub group_id: String,
+    pub topic: String,
+    pub encoding: SnapshotEncoding,
+    
+    pub idle_timeout: Duration,
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        Self {
+            brokers: "localhost:9092".to_string(),
+            group_id: "zaphft".to_string(),
+            topic: "l2-snapshots".to_string(),
+            encoding: SnapshotEncoding::Json,
+            idle_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+pub struct KafkaSource {
+    consumer: BaseConsumer,
+    config: KafkaConfig,
+}
+
+impl KafkaSource {
+    pub fn connect(config: KafkaConfig) -> Result<Self> {
+        let consumer: BaseConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .set("group.id", &config.group_id)
+            .set("auto.offset.reset", "earliest")
+            .set("enable.partition.eof", "false")
+            .create()
+            .context("Failed to create Kafka consumer")?;
+        consumer
+            .subscribe(&[&config.topic])
+            .context(format!("Failed to subscribe to topic: {}", config.topic))?;
+
+        Ok(Self { consumer, config })
+    }
+}
+
+impl SnapshotSource for KafkaSource {
+    fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>> {
+        loop {
+            let message = match self.consumer.poll(self.config.idle_timeout) {
+                Some(message) => message.context("Kafka consumer error")?,
+                None => return Ok(None),
+            };
+            if let Some(payload) = message.payload() {
+                return self.config.encoding.decode(payload).map(Some);
+            }
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("kafka {}/{}", self.config.brokers, self.config.topic)
+    }
+}
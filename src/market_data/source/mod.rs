@@ -0,0 +1,142 @@
+#[cfg(feature = "kafka")]
+pub mod kafka_source;
+#[cfg(feature = "redis")]
+pub mod redis_source;
+pub mod prefetch;
+
+#[cfg(feature = "kafka")]
+pub use kafka_source::{KafkaConfig, KafkaSource};
+#[cfg(feature = "redis")]
+pub use redis_source::{RedisStreamConfig, RedisStreamSource};
+pub use prefetch::{PrefetchSource, DEFAULT_PREFETCH_BATCH, DEFAULT_PREFETCH_DEPTH};
+
+use anyhow::{Context, Result};
+use crate::market_data::SnapshotReader;
+use crate::types::L2Snapshot;
+use std::collections::VecDeque;
+
+
+
+
+pub trait SnapshotSource {
+    
+    fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>>;
+
+    fn describe(&self) -> String;
+}
+
+impl SnapshotSource for SnapshotReader {
+    fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>> {
+        SnapshotReader::next_snapshot(self)
+    }
+
+    fn describe(&self) -> String {
+        match self.sessions().last() {
+            Some(session) => format!("file {}", session.path.display()),
+            None => "file".to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    snapshots: VecDeque<L2Snapshot>,
+}
+
+impl MemorySource {
+    pub fn new(snapshots: Vec<L2Snapshot>) -> Self {
+        Self { snapshots: snapshots.into() }
+    }
+}
+
+impl SnapshotSource for MemorySource {
+    fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>> {
+        Ok(self.snapshots.pop_front())
+    }
+
+    fn describe(&self) -> String {
+        format!("memory ({} pending)", self.snapshots.len())
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotEncoding {
+    Json,
+    Bincode,
+}
+
+impl SnapshotEncoding {
+    pub fn decode(&self, payload: &[u8]) -> Result<L2Snapshot> {
+        match self {
+            SnapshotEncoding::Json => serde_json::from_slice(payload).context("Failed to decode JSON snapshot"),
+            SnapshotEncoding::Bincode => decode_bincode(payload),
+        }
+    }
+
+    pub fn encode(&self, snapshot: &L2Snapshot) -> Result<Vec<u8>> {
+        match self {
+            SnapshotEncoding::Json => Ok(serde_json::to_vec(snapshot)?),
+            SnapshotEncoding::Bincode => encode_bincode(snapshot),
+        }
+    }
+}
+
+#[cfg(feature = "bincode")]
+fn decode_bincode(payload: &[u8]) -> Result<L2Snapshot> {
+    bincode::deserialize(payload).context("Failed to decode bincode snapshot")
+}
+
+#[cfg(not(feature = "bincode"))]
+fn decode_bincode(_payload: &[u8]) -> Result<L2Snapshot> {
+    anyhow::bail!("Decoding bincode snapshots requires the `bincode` feature")
+}
+
+#[cfg(feature = "bincode")]
+fn encode_bincode(snapshot: &L2Snapshot) -> Result<Vec<u8>> {
+    Ok(bincode::serialize(snapshot)?)
+}
+
+#[cfg(not(feature = "bincode"))]
+fn encode_bincode(_snapshot: &L2Snapshot) -> Result<Vec<u8>> {
+    anyhow::bail!("Encoding bincode snapshots requires the `bincode` feature")
+}
+
+
+pub fn drain<S: SnapshotSource + ?Sized>(source: &mut S, limit: Option<usize>) -> Result<Vec<L2Snapshot>> {
+    let mut snapshots = Vec::new();
+    while limit.is_none_or(|max| snapshots.len() < max) {
+        match source.next_snapshot()? {
+            Some(snapshot) => snapshots.push(snapshot),
+            None => break,
+        }
+    }
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64) -> L2Snapshot {
+        let bids = [PriceLevel::new(100.0, 1.0)];
+        let asks = [PriceLevel::new(100.5, 2.0)];
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_json_round_trip_and_drain() {
+        let encoding = SnapshotEncoding::Json;
+        let payload = encoding.encode(&create_test_snapshot(7)).unwrap();
+        let decoded = encoding.decode(&payload).unwrap();
+        assert_eq!(decoded.timestamp_us, 7);
+        assert_eq!(decoded.ask_qty_1, 2.0);
+
+        let mut source = MemorySource::new((0..5).map(create_test_snapshot).collect());
+        assert_eq!(drain(&mut source, Some(3)).unwrap().len(), 3);
+        assert_eq!(drain(&mut source, None).unwrap().len(), 2);
+        assert!(source.next_snapshot().unwrap().is_none());
+    }
+}
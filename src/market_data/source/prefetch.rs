@@ -0,0 +1,140 @@
+use super::SnapshotSource;
+use crate::types::L2Snapshot;
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::thread::{self, JoinHandle};
+
+pub const DEFAULT_PREFETCH_BATCH: usize = 512;
+pub const DEFAULT_PREFETCH_DEPTH: usize = 8;
+
+
+
+
+pub struct PrefetchSource {
+    receiver: Receiver<Result<Vec<L2Snapshot>>>,
+    buffer: VecDeque<L2Snapshot>,
+    handle: Option<JoinHandle<()>>,
+    description: String,
+}
+
+impl PrefetchSource {
+    
+    
+    
+    pub fn spawn<S, F>(description: impl Into<String>, open: F, batch: usize, depth: usize) -> Self
+    where
+        S: SnapshotSource,
+        F: FnOnce() -> Result<S> + Send + 'static,
+    {
+        let batch = batch.max(1);
+        let (sender, receiver) = sync_channel(depth.max(1));
+        let handle = thread::spawn(move || {
+            let mut source = match open() {
+                Ok(source) => source,
+                Err(e) => {
+                    let _ = sender.send(Err(e));
+                    return;
+                }
+            };
+            loop {
+                let mut chunk = Vec::with_capacity(batch);
+                let mut failed = None;
+                while chunk.len() < batch {
+                    match source.next_snapshot() {
+                        Ok(Some(snapshot)) => chunk.push(snapshot),
+                        Ok(None) => break,
+                        Err(e) => {
+                            failed = Some(e);
+                            break;
+                        }
+                    }
+                }
+                let done = chunk.len() < batch;
+                if !chunk.is_empty() && sender.send(Ok(chunk)).is_err() {
+                    return;
+                }
+                if let Some(e) = failed {
+                    let _ = sender.send(Err(e));
+                    return;
+                }
+                if done {
+                    return;
+                }
+            }
+        });
+        Self {
+            receiver,
+            buffer: VecDeque::new(),
+            handle: Some(handle),
+            description: description.into(),
+        }
+    }
+
+    fn join(&mut self) -> Result<()> {
+        match self.handle.take() {
+            Some(handle) => handle.join().map_err(|_| anyhow::anyhow!("Prefetch thread panicked")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl SnapshotSource for PrefetchSource {
+    fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>> {
+        if let Some(snapshot) = self.buffer.pop_front() {
+            return Ok(Some(snapshot));
+        }
+        match self.receiver.recv() {
+            Ok(chunk) => {
+                self.buffer.extend(chunk?);
+                Ok(self.buffer.pop_front())
+            }
+            Err(_) => {
+                self.join()?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("prefetch {}", self.description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{BacktestEngine, EngineConfig};
+    use crate::market_data::source::MemorySource;
+    use crate::strategy::market_maker::{MarketMaker, MarketMakerConfig};
+    use crate::types::PriceLevel;
+
+    fn snapshots() -> Vec<L2Snapshot> {
+        (0..200u64)
+            .map(|i| {
+                let mid = 100.0 + ((i % 17) as f64 - 8.0) * 0.05;
+                let bids = [PriceLevel::new(mid - 0.05, 2.0), PriceLevel::new(mid - 0.1, 4.0)];
+                let asks = [PriceLevel::new(mid + 0.05, 2.0), PriceLevel::new(mid + 0.1, 4.0)];
+                L2Snapshot::from_levels(i as usize, i * 1_000, String::new(), &bids, &asks)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_prefetched_stream_matches_batch_run() {
+        let data = snapshots();
+        let engine = BacktestEngine::new(EngineConfig::default());
+        let batch = engine.run(&mut MarketMaker::new(MarketMakerConfig::default()), &data);
+
+        let owned = data.clone();
+        let mut source = PrefetchSource::spawn("memory", move || Ok(MemorySource::new(owned)), 7, 2);
+        let streamed = engine
+            .run_source(&mut MarketMaker::new(MarketMakerConfig::default()), &mut source, None)
+            .unwrap();
+        assert_eq!(streamed.fingerprint(), batch.fingerprint());
+        assert!(source.next_snapshot().unwrap().is_none());
+
+        let mut failing = PrefetchSource::spawn("missing", || -> Result<MemorySource> { anyhow::bail!("boom") }, 4, 1);
+        assert!(failing.next_snapshot().is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use redis::streams::{StreamReadOptions, StreamReadReply};
+use redis::Commands;
+use super::{SnapshotEncoding, SnapshotSource};
+use crate::types::L2Snapshot;
+use std::collections::VecDeque;
+
+
+#[derive(Debug, Clone)]
+pub struct RedisStreamConfig {
+    pub url: String,
+    pub stream: String,
+    
+    pub field: String,
+    pub encoding: SnapshotEncoding,
+    
+    pub start_id: String,
+    pub batch_size: usize,
+    
+    pub block_ms: usize,
+}
+
+impl Default for RedisStreamConfig {
+    fn default() -> Self {
+        Self {
+            url: "redis://127.0.0.1/".to_string(),
+            stream: "l2-snapshots".to_string(),
+            field: "data".to_string(),
+            encoding: SnapshotEncoding::Json,
+            start_id: "0".to_string(),
+            batch_size: 500,
+            block_ms: 5_000,
+        }
+    }
+}
+
+pub struct RedisStreamSource {
+    connection: redis::Connection,
+    config: RedisStreamConfig,
+    last_id: String,
+    buffer: VecDeque<L2Snapshot>,
+}
+
+impl RedisStreamSource {
+    pub fn connect(config: RedisStreamConfig) -> Result<Self> {
+        let client = redis::Client::open(config.url.as_str())
+            .context(format!("Invalid Redis URL: {}", config.url))?;
+        let connection = client.get_connection().context("Failed to connect to Redis")?;
+
+        Ok(Self {
+            connection,
+            last_id: config.start_id.clone(),
+            config,
+            buffer: VecDeque::new(),
+        })
+    }
+
+    fn fetch(&mut self) -> Result<()> {
+        let options = StreamReadOptions::default()
+            .count(self.config.batch_size)
+            .block(self.config.block_ms);
+        let reply: Option<StreamReadReply> = self
+            .connection
+            .xread_options(&[&self.config.stream], &[&self.last_id], &options)
+            .context("XREAD failed")?;
+
+        for key in reply.map(|r| r.keys).unwrap_or_default() {
+            for entry in key.ids {
+                let payload: Vec<u8> = entry
+                    .get(&self.config.field)
+                    .context(format!("Stream entry {} has no field {}", entry.id, self.config.field))?;
+                self.buffer.push_back(self.config.encoding.decode(&payload)?);
+                self.last_id = entry.id;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SnapshotSource for RedisStreamSource {
+    fn next_snapshot(&mut self) -> Result<Option<L2Snapshot>> {
+        if self.buffer.is_empty() {
+            self.fetch()?;
+        }
+        Ok(self.buffer.pop_front())
+    }
+
+    fn describe(&self) -> String {
+        format!("redis {} stream {}", self.config.url, self.config.stream)
+    }
+}
@@ -0,0 +1,209 @@
+use crate::types::{L2Snapshot, PriceLevel, Side, Trade};
+
+const PRICE_EPSILON: f64 = 1e-9;
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EstimatedTrade {
+    pub timestamp_us: u64,
+    pub aggressor: Side,
+    pub price: f64,
+    pub quantity: f64,
+}
+
+impl EstimatedTrade {
+    pub fn notional(&self) -> f64 {
+        self.price * self.quantity
+    }
+
+    pub fn to_trade(&self) -> Trade {
+        Trade::new(self.aggressor, self.price, self.quantity, self.timestamp_us)
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct TapeEstimator {
+    prev_bids: Vec<PriceLevel>,
+    prev_asks: Vec<PriceLevel>,
+}
+
+impl TapeEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    
+    pub fn update(&mut self, snapshot: &L2Snapshot) -> Vec<EstimatedTrade> {
+        let bids = snapshot.bids();
+        let asks = snapshot.asks();
+        let mut trades = Vec::new();
+
+        if let (Some(best_bid), Some(best_ask)) = (bids.first(), asks.first()) {
+            if let Some(trade) = consumed(&self.prev_bids, best_bid, Side::Bid, snapshot.timestamp_us) {
+                trades.push(trade);
+            }
+            if let Some(trade) = consumed(&self.prev_asks, best_ask, Side::Ask, snapshot.timestamp_us) {
+                trades.push(trade);
+            }
+        }
+
+        self.prev_bids = bids;
+        self.prev_asks = asks;
+        trades
+    }
+}
+
+
+
+
+fn consumed(prev_levels: &[PriceLevel], best: &PriceLevel, book_side: Side, timestamp_us: u64) -> Option<EstimatedTrade> {
+    let mut quantity = 0.0;
+    let mut notional = 0.0;
+
+    for level in prev_levels {
+        let through = match book_side {
+            Side::Bid => level.price > best.price + PRICE_EPSILON,
+            Side::Ask => level.price < best.price - PRICE_EPSILON,
+        };
+        if through {
+            quantity += level.quantity;
+            notional += level.notional();
+            continue;
+        }
+        if (level.price - best.price).abs() <= PRICE_EPSILON && level.quantity > best.quantity {
+            let taken = level.quantity - best.quantity;
+            quantity += taken;
+            notional += taken * level.price;
+        }
+        break;
+    }
+
+    if quantity <= 0.0 {
+        return None;
+    }
+
+    Some(EstimatedTrade {
+        timestamp_us,
+        aggressor: book_side.opposite(),
+        price: notional / quantity,
+        quantity,
+    })
+}
+
+
+#[derive(Debug, Clone, Default)]
+pub struct TradeTape {
+    trades: Vec<EstimatedTrade>,
+}
+
+impl TradeTape {
+    pub fn from_trades(mut trades: Vec<EstimatedTrade>) -> Self {
+        trades.sort_by_key(|t| t.timestamp_us);
+        Self { trades }
+    }
+
+    pub fn from_snapshots(snapshots: &[L2Snapshot]) -> Self {
+        let mut estimator = TapeEstimator::new();
+        let trades = snapshots.iter().flat_map(|s| estimator.update(s)).collect();
+        Self { trades }
+    }
+
+    pub fn trades(&self) -> &[EstimatedTrade] {
+        &self.trades
+    }
+
+    pub fn len(&self) -> usize {
+        self.trades.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trades.is_empty()
+    }
+
+    pub fn buy_volume(&self) -> f64 {
+        self.side_volume(Side::Bid)
+    }
+
+    pub fn sell_volume(&self) -> f64 {
+        self.side_volume(Side::Ask)
+    }
+
+    fn side_volume(&self, aggressor: Side) -> f64 {
+        self.trades
+            .iter()
+            .filter(|t| t.aggressor == aggressor)
+            .map(|t| t.quantity)
+            .sum()
+    }
+
+    
+    pub fn volume_between(&self, start_us: u64, end_us: u64) -> f64 {
+        let start = self.trades.partition_point(|t| t.timestamp_us < start_us);
+        let end = self.trades.partition_point(|t| t.timestamp_us < end_us);
+        self.trades[start..end.max(start)].iter().map(|t| t.quantity).sum()
+    }
+
+    pub fn vwap_between(&self, start_us: u64, end_us: u64) -> Option<f64> {
+        let start = self.trades.partition_point(|t| t.timestamp_us < start_us);
+        let end = self.trades.partition_point(|t| t.timestamp_us < end_us);
+        let window = &self.trades[start..end.max(start)];
+        let volume: f64 = window.iter().map(|t| t.quantity).sum();
+        if volume <= 0.0 {
+            return None;
+        }
+        Some(window.iter().map(|t| t.notional()).sum::<f64>() / volume)
+    }
+
+    pub fn print(&self) {
+        let total: f64 = self.trades.iter().map(|t| t.quantity).sum();
+        let notional: f64 = self.trades.iter().map(|t| t.notional()).sum();
+        println!("\n🧾 Estimated Trade Tape");
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("   Prints:          {}", self.trades.len());
+        println!("   Volume:          {:.4}", total);
+        println!("   Notional:        ${:.2}", notional);
+        println!("   Buy Volume:      {:.4}", self.buy_volume());
+        println!("   Sell Volume:     {:.4}", self.sell_volume());
+        println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_snapshot(timestamp_us: u64, bid: f64, bid_qty: f64, ask: f64, ask_qty: f64) -> L2Snapshot {
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - i as f64, if i == 0 { bid_qty } else { 2.0 })).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + i as f64, if i == 0 { ask_qty } else { 2.0 })).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_touch_decrease_and_through_move() {
+        let snapshots = vec![
+            create_test_snapshot(0, 100.0, 5.0, 101.0, 5.0),
+            create_test_snapshot(1, 100.0, 3.0, 101.0, 5.0),
+            create_test_snapshot(2, 100.0, 3.0, 102.0, 1.5),
+            create_test_snapshot(3, 100.0, 4.0, 102.0, 1.5),
+        ];
+
+        let tape = TradeTape::from_snapshots(&snapshots);
+
+        assert_eq!(tape.len(), 2);
+        assert_eq!(tape.trades()[0].aggressor, Side::Ask);
+        assert_eq!(tape.trades()[0].price, 100.0);
+        assert_eq!(tape.trades()[0].quantity, 2.0);
+
+        let lift = tape.trades()[1];
+        assert_eq!(lift.aggressor, Side::Bid);
+        assert!((lift.quantity - 5.5).abs() < 1e-9);
+        assert!((lift.price - (5.0 * 101.0 + 0.5 * 102.0) / 5.5).abs() < 1e-9);
+
+        assert_eq!(tape.sell_volume(), 2.0);
+        assert!((tape.volume_between(2, 3) - 5.5).abs() < 1e-9);
+        assert_eq!(tape.volume_between(3, 10), 0.0);
+    }
+}
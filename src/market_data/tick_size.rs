@@ -0,0 +1,94 @@
+use crate::types::L2Snapshot;
+
+const PRICE_SCALE: f64 = 1e8;
+const MAX_SAMPLED_SNAPSHOTS: usize = 10_000;
+
+
+
+
+pub fn infer_tick_size(snapshots: &[L2Snapshot]) -> Option<f64> {
+    let mut prices: Vec<i64> = snapshots
+        .iter()
+        .take(MAX_SAMPLED_SNAPSHOTS)
+        .flat_map(|s| s.bids().into_iter().chain(s.asks()))
+        .filter(|level| level.price > 0.0)
+        .map(|level| (level.price * PRICE_SCALE).round() as i64)
+        .collect();
+    prices.sort_unstable();
+    prices.dedup();
+
+    let tick = prices
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .fold(0, gcd);
+
+    if tick == 0 {
+        None
+    } else {
+        Some(tick as f64 / PRICE_SCALE)
+    }
+}
+
+
+
+
+pub fn tick_size_warning(configured: f64, inferred: f64) -> Option<String> {
+    if configured <= 0.0 || inferred <= 0.0 {
+        return None;
+    }
+    let ratio = configured / inferred;
+    if (ratio - 1.0).abs() < 1e-6 {
+        return None;
+    }
+    if (ratio - ratio.round()).abs() < 1e-6 {
+        return Some(format!(
+            "tick_size {} is {}x the data tick size {}",
+            configured,
+            ratio.round(),
+            inferred
+        ));
+    }
+    Some(format!(
+        "tick_size {} is not a multiple of the data tick size {}; quotes will land off the price grid",
+        configured, inferred
+    ))
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(bid: f64, ask: f64) -> L2Snapshot {
+        let bid_offsets = [0.0, 0.3, 0.5, 0.6, 0.9, 1.0, 1.2, 1.5, 1.6, 2.0];
+        let ask_offsets = [0.0, 0.2, 0.3, 0.5, 0.8, 1.0, 1.1, 1.4, 1.7, 2.0];
+        let bids: Vec<PriceLevel> = bid_offsets.iter().map(|o| PriceLevel::new(bid - o, 1.0)).collect();
+        let asks: Vec<PriceLevel> = ask_offsets.iter().map(|o| PriceLevel::new(ask + o, 1.0)).collect();
+        L2Snapshot::from_levels(0, 0, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_infer_tick_size_from_price_grid() {
+        let snapshots = vec![
+            create_test_snapshot(17181.6, 17181.7),
+            create_test_snapshot(17182.1, 17182.3),
+        ];
+        assert_eq!(infer_tick_size(&snapshots), Some(0.1));
+        assert_eq!(infer_tick_size(&[]), None);
+    }
+
+    #[test]
+    fn test_tick_size_warning() {
+        assert!(tick_size_warning(0.1, 0.1).is_none());
+        assert!(tick_size_warning(0.2, 0.1).unwrap().contains("2x"));
+        assert!(tick_size_warning(0.05, 0.1).unwrap().contains("off the price grid"));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::types::L2Snapshot;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct BookHistory<'a> {
+    snapshots: VecDeque<Cow<'a, L2Snapshot>>,
+    capacity: usize,
+}
+
+impl<'a> BookHistory<'a> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, snapshot: L2Snapshot) {
+        self.push_cow(Cow::Owned(snapshot));
+    }
+
+    pub fn push_ref(&mut self, snapshot: &'a L2Snapshot) {
+        self.push_cow(Cow::Borrowed(snapshot));
+    }
+
+    pub(crate) fn push_cow(&mut self, snapshot: Cow<'a, L2Snapshot>) {
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn oldest(&self) -> Option<&L2Snapshot> {
+        self.snapshots.front().map(|s| s.as_ref())
+    }
+
+    pub fn latest(&self) -> Option<&L2Snapshot> {
+        self.snapshots.back().map(|s| s.as_ref())
+    }
+
+    
+    fn index_at(&self, timestamp_us: u64) -> Option<usize> {
+        self.snapshots
+            .partition_point(|s| s.timestamp_us <= timestamp_us)
+            .checked_sub(1)
+    }
+
+    
+    
+    pub fn book_at(&self, timestamp_us: u64) -> Option<&L2Snapshot> {
+        self.index_at(timestamp_us).map(|i| self.snapshots[i].as_ref())
+    }
+
+    pub fn mid_at(&self, timestamp_us: u64) -> Option<f64> {
+        self.book_at(timestamp_us).map(|s| s.mid_price())
+    }
+
+    
+    
+    pub fn max_spread_since(&self, timestamp_us: u64) -> Option<f64> {
+        let start = self.index_at(timestamp_us).unwrap_or(0);
+        self.snapshots
+            .range(start..)
+            .map(|s| s.spread())
+            .reduce(f64::max)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &L2Snapshot> {
+        self.snapshots.iter().map(|s| s.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64, bid: f64, ask: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &[PriceLevel::new(bid, 1.0)], &[PriceLevel::new(ask, 1.0)])
+    }
+
+    #[test]
+    fn test_time_travel_queries() {
+        let mut history = BookHistory::new(3);
+        history.push(create_test_snapshot(100, 99.0, 101.0));
+        history.push(create_test_snapshot(200, 100.0, 100.5));
+        history.push(create_test_snapshot(300, 100.0, 101.0));
+        history.push(create_test_snapshot(400, 100.5, 100.75));
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.oldest().unwrap().timestamp_us, 200);
+        assert!(history.book_at(150).is_none());
+        assert_eq!(history.book_at(250).unwrap().timestamp_us, 200);
+        assert_eq!(history.mid_at(1_000), Some(100.625));
+        assert_eq!(history.max_spread_since(250), Some(1.0));
+        assert_eq!(history.max_spread_since(400), Some(0.25));
+    }
+}
@@ -3,33 +3,69 @@
 
 
 
+pub mod history;
+
+pub use history::BookHistory;
+
 use crate::types::{L2Snapshot, PriceLevel, Side};
+use std::borrow::Cow;
+use std::fmt::Write;
+
+const LADDER_BAR_WIDTH: usize = 30;
 
 
 #[derive(Debug, Clone)]
-pub struct OrderBook {
-    current_snapshot: Option<L2Snapshot>,
+pub struct OrderBook<'a> {
+    current_snapshot: Option<Cow<'a, L2Snapshot>>,
     update_count: u64,
+    history: Option<BookHistory<'a>>,
 }
 
-impl OrderBook {
+impl<'a> OrderBook<'a> {
     
     pub fn new() -> Self {
         Self {
             current_snapshot: None,
             update_count: 0,
+            history: None,
+        }
+    }
+
+    
+    pub fn with_history(capacity: usize) -> Self {
+        Self {
+            history: Some(BookHistory::new(capacity)),
+            ..Self::new()
         }
     }
 
     
     pub fn update(&mut self, snapshot: L2Snapshot) {
+        self.set(Cow::Owned(snapshot));
+    }
+
+    
+    
+    pub fn update_ref(&mut self, snapshot: &'a L2Snapshot) {
+        self.set(Cow::Borrowed(snapshot));
+    }
+
+    fn set(&mut self, snapshot: Cow<'a, L2Snapshot>) {
+        if let Some(history) = &mut self.history {
+            history.push_cow(snapshot.clone());
+        }
         self.current_snapshot = Some(snapshot);
         self.update_count += 1;
     }
 
     
     pub fn snapshot(&self) -> Option<&L2Snapshot> {
-        self.current_snapshot.as_ref()
+        self.current_snapshot.as_deref()
+    }
+
+    
+    pub fn history(&self) -> Option<&BookHistory<'a>> {
+        self.history.as_ref()
     }
 
     
@@ -57,7 +93,7 @@ impl OrderBook {
         self.current_snapshot
             .as_ref()
             .map(|s| s.bids())
-            .unwrap_or_else(Vec::new)
+            .unwrap_or_default()
     }
 
     
@@ -65,7 +101,7 @@ impl OrderBook {
         self.current_snapshot
             .as_ref()
             .map(|s| s.asks())
-            .unwrap_or_else(Vec::new)
+            .unwrap_or_default()
     }
 
     
@@ -148,6 +184,47 @@ impl OrderBook {
     }
 
     
+    
+    pub fn render_ladder(&self, depth: usize) -> String {
+        let bids = self.bids();
+        let asks = self.asks();
+        let depth = depth.min(bids.len()).min(asks.len());
+
+        let mut out = String::new();
+        if depth == 0 {
+            out.push_str("   (empty book)\n");
+            return out;
+        }
+
+        let _ = writeln!(out, "   {:>10} | {:>10} | {:>10} | {:>10}", "BID QTY", "BID", "ASK", "ASK QTY");
+        let _ = writeln!(out, "   ─────────────────────────────────────────────────");
+        for i in 0..depth {
+            let _ = writeln!(
+                out,
+                "   {:>10.3} | {:>10.2} | {:>10.2} | {:>10.3}",
+                bids[i].quantity, bids[i].price, asks[i].price, asks[i].quantity
+            );
+        }
+
+        let max_qty = bids[..depth]
+            .iter()
+            .chain(&asks[..depth])
+            .map(|l| l.quantity)
+            .fold(0.0_f64, f64::max);
+
+        let _ = writeln!(out);
+        for level in asks[..depth].iter().rev() {
+            let _ = writeln!(out, "   ASK {:>10.2} {} {:.3}", level.price, depth_bar(level.quantity, max_qty), level.quantity);
+        }
+        let _ = writeln!(out, "   {:─^width$}", " spread ", width = LADDER_BAR_WIDTH + 16);
+        for level in &bids[..depth] {
+            let _ = writeln!(out, "   BID {:>10.2} {} {:.3}", level.price, depth_bar(level.quantity, max_qty), level.quantity);
+        }
+
+        out
+    }
+
+    
     pub fn update_count(&self) -> u64 {
         self.update_count
     }
@@ -158,7 +235,17 @@ impl OrderBook {
     }
 }
 
-impl Default for OrderBook {
+fn depth_bar(quantity: f64, max_qty: f64) -> String {
+    let filled = if max_qty > 0.0 {
+        ((quantity / max_qty) * LADDER_BAR_WIDTH as f64).round() as usize
+    } else {
+        0
+    };
+    let filled = filled.min(LADDER_BAR_WIDTH);
+    format!("{}{}", "█".repeat(filled), " ".repeat(LADDER_BAR_WIDTH - filled))
+}
+
+impl Default for OrderBook<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -236,6 +323,39 @@ mod tests {
         assert_eq!(book.best_ask(), Some(101.0));
     }
 
+    #[test]
+    fn test_update_records_history() {
+        let mut book = OrderBook::with_history(2);
+        assert!(OrderBook::new().history().is_none());
+
+        for ts in [10, 20, 30] {
+            let mut snapshot = create_test_snapshot();
+            snapshot.timestamp_us = ts;
+            book.update(snapshot);
+        }
+
+        let history = book.history().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.book_at(25).unwrap().timestamp_us, 20);
+    }
+
+    #[test]
+    fn test_update_ref_borrows_snapshot() {
+        let snapshots: Vec<L2Snapshot> = [10, 20, 30]
+            .into_iter()
+            .map(|ts| L2Snapshot { timestamp_us: ts, ..create_test_snapshot() })
+            .collect();
+        let mut book = OrderBook::with_history(2);
+        for snapshot in &snapshots {
+            book.update_ref(snapshot);
+        }
+
+        assert!(std::ptr::eq(book.snapshot().unwrap(), &snapshots[2]));
+        assert!(std::ptr::eq(book.history().unwrap().oldest().unwrap(), &snapshots[1]));
+        assert_eq!(book.update_count(), 3);
+        assert_eq!(book.best_bid(), Some(100.0));
+    }
+
     #[test]
     fn test_calculate_slippage() {
         let mut book = OrderBook::new();
@@ -252,5 +372,23 @@ mod tests {
         assert!(slippage_bps > 30.0 && slippage_bps < 35.0);
         assert_eq!(levels, 2);
     }
+
+    #[test]
+    fn test_render_ladder() {
+        let mut book = OrderBook::new();
+        assert_eq!(book.render_ladder(5), "   (empty book)\n");
+
+        book.update(create_test_snapshot());
+        let ladder = book.render_ladder(3);
+
+        assert!(ladder.contains("100.00"));
+        assert!(ladder.contains("103.00"));
+        assert!(!ladder.contains("104.00"));
+        assert_eq!(ladder.lines().filter(|l| l.starts_with("   ASK ")).count(), 3);
+
+        let full_bar = "█".repeat(LADDER_BAR_WIDTH);
+        let top_ask = ladder.lines().find(|l| l.contains("ASK     103.00")).unwrap();
+        assert!(top_ask.contains(&full_bar));
+    }
 }
 
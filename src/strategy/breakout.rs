@@ -0,0 +1,264 @@
+use super::{Strategy, StrategyStats};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use crate::execution::PositionView;
+use crate::market_data::DerivedValues;
+use crate::types::{L2Snapshot, Side, Trade};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    
+    #[default]
+    Donchian,
+    
+    Keltner,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BreakoutConfig {
+    pub channel: Channel,
+
+    
+    pub lookback: usize,
+
+    
+    pub keltner_multiplier: f64,
+
+    
+    pub stop_multiplier: f64,
+
+    pub trade_size: f64,
+}
+
+impl Default for BreakoutConfig {
+    fn default() -> Self {
+        Self {
+            channel: Channel::Donchian,
+            lookback: 100,
+            keltner_multiplier: 2.0,
+            stop_multiplier: 3.0,
+            trade_size: 0.1,
+        }
+    }
+}
+
+pub struct BreakoutStrategy {
+    config: BreakoutConfig,
+
+    
+    mids: VecDeque<f64>,
+
+    
+    stop_price: Option<f64>,
+
+    updates_processed: usize,
+    trades_generated: usize,
+    breakouts: usize,
+    stops_hit: usize,
+}
+
+impl BreakoutStrategy {
+    pub fn new(config: BreakoutConfig) -> Self {
+        Self {
+            mids: VecDeque::with_capacity(config.lookback + 1),
+            config,
+            stop_price: None,
+            updates_processed: 0,
+            trades_generated: 0,
+            breakouts: 0,
+            stops_hit: 0,
+        }
+    }
+
+    
+    fn average_range(&self) -> f64 {
+        let n = self.mids.len();
+        if n < 2 {
+            return 0.0;
+        }
+        let sum: f64 = self.mids.iter().zip(self.mids.iter().skip(1)).map(|(a, b)| (b - a).abs()).sum();
+        sum / (n - 1) as f64
+    }
+
+    
+    
+    pub fn channel(&self) -> Option<(f64, f64)> {
+        if self.mids.len() < self.config.lookback.max(2) {
+            return None;
+        }
+        match self.config.channel {
+            Channel::Donchian => {
+                let high = self.mids.iter().copied().fold(f64::MIN, f64::max);
+                let low = self.mids.iter().copied().fold(f64::MAX, f64::min);
+                Some((low, high))
+            }
+            Channel::Keltner => {
+                let mean = self.mids.iter().sum::<f64>() / self.mids.len() as f64;
+                let width = self.config.keltner_multiplier * self.average_range();
+                Some((mean - width, mean + width))
+            }
+        }
+    }
+
+    pub fn stop_price(&self) -> Option<f64> {
+        self.stop_price
+    }
+
+    fn process_update(&mut self, snapshot: &L2Snapshot, mid: f64, position: &dyn PositionView) -> Vec<Trade> {
+        self.updates_processed += 1;
+        let mut trades = Vec::new();
+
+        if position.is_flat() {
+            self.stop_price = None;
+        }
+
+        if let Some(stop) = self.stop_price {
+            let stopped = (position.is_long() && mid <= stop) || (position.is_short() && mid >= stop);
+            if stopped {
+                let (side, price) = if position.is_long() {
+                    (Side::Ask, snapshot.best_bid())
+                } else {
+                    (Side::Bid, snapshot.best_ask())
+                };
+                trades.push(Trade::new(side, price, position.quantity().abs(), snapshot.timestamp_us));
+                self.stop_price = None;
+                self.stops_hit += 1;
+            }
+        }
+
+        if trades.is_empty() {
+            if let Some((low, high)) = self.channel() {
+                let stop_distance = self.config.stop_multiplier * self.average_range();
+                let entry = if mid > high && !position.is_long() {
+                    Some((Side::Bid, snapshot.best_ask(), mid - stop_distance))
+                } else if mid < low && !position.is_short() {
+                    Some((Side::Ask, snapshot.best_bid(), mid + stop_distance))
+                } else {
+                    None
+                };
+                if let Some((side, price, stop)) = entry {
+                    let quantity = self.config.trade_size + position.quantity().abs();
+                    trades.push(Trade::new(side, price, quantity, snapshot.timestamp_us));
+                    self.stop_price = Some(stop);
+                    self.breakouts += 1;
+                }
+            }
+        }
+
+        self.mids.push_back(mid);
+        if self.mids.len() > self.config.lookback {
+            self.mids.pop_front();
+        }
+
+        self.trades_generated += trades.len();
+        trades
+    }
+}
+
+impl Strategy for BreakoutStrategy {
+    fn on_market_data(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) -> Vec<Trade> {
+        let mid = (snapshot.best_bid() + snapshot.best_ask()) / 2.0;
+        self.process_update(snapshot, mid, position)
+    }
+
+    fn on_market_data_derived(
+        &mut self,
+        snapshot: &L2Snapshot,
+        derived: &DerivedValues,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        self.process_update(snapshot, derived.mid, position)
+    }
+
+    fn name(&self) -> &str {
+        "Breakout Strategy"
+    }
+
+    fn stats(&self) -> StrategyStats {
+        StrategyStats {
+            name: self.name().to_string(),
+            updates_processed: self.updates_processed,
+            trades_generated: self.trades_generated,
+            ..Default::default()
+        }
+        .with_metric("breakouts", self.breakouts as f64)
+        .with_metric("stops_hit", self.stops_hit as f64)
+    }
+
+    fn debug_state(&self) -> String {
+        let channel = match self.channel() {
+            Some((low, high)) => format!("{:.4} - {:.4}", low, high),
+            None => "warming up".to_string(),
+        };
+        let stop = match self.stop_price {
+            Some(stop) => format!("{:.4}", stop),
+            None => "none".to_string(),
+        };
+        format!("channel: {}\nstop: {}\nbreakouts: {}", channel, stop, self.breakouts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::Position;
+    use crate::types::PriceLevel;
+
+    fn snapshot(ts: u64, mid: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(0, ts, String::new(), &[PriceLevel::new(mid - 0.5, 1.0)], &[PriceLevel::new(mid + 0.5, 1.0)])
+    }
+
+    fn run(strategy: &mut BreakoutStrategy, position: &mut Position, mids: &[f64]) -> Vec<Trade> {
+        let mut all = Vec::new();
+        for (i, &mid) in mids.iter().enumerate() {
+            for trade in strategy.on_market_data(&snapshot(i as u64, mid), position) {
+                position.execute_trade(trade.clone());
+                all.push(trade);
+            }
+        }
+        all
+    }
+
+    #[test]
+    fn test_donchian_breakout_and_stop() {
+        let mut strategy = BreakoutStrategy::new(BreakoutConfig {
+            lookback: 4,
+            stop_multiplier: 2.0,
+            trade_size: 1.0,
+            ..Default::default()
+        });
+        let mut position = Position::new();
+
+        let trades = run(&mut strategy, &mut position, &[100.0, 101.0, 100.0, 101.0, 103.0]);
+        assert_eq!(trades.len(), 1);
+        assert_eq!((trades[0].side, trades[0].price), (Side::Bid, 103.5));
+        assert_eq!(strategy.stop_price(), Some(101.0));
+
+        let trades = run(&mut strategy, &mut position, &[102.0, 100.5]);
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].side, Side::Ask);
+        assert!(position.is_flat());
+        assert_eq!(strategy.stats().metric("stops_hit"), Some(1.0));
+    }
+
+    #[test]
+    fn test_keltner_reverses_on_opposite_break() {
+        let mut strategy = BreakoutStrategy::new(BreakoutConfig {
+            channel: Channel::Keltner,
+            lookback: 4,
+            keltner_multiplier: 1.0,
+            stop_multiplier: 100.0,
+            trade_size: 1.0,
+        });
+        let mut position = Position::new();
+
+        run(&mut strategy, &mut position, &[100.0, 101.0, 100.0, 101.0, 98.0]);
+        assert_eq!(position.quantity, -1.0);
+
+        run(&mut strategy, &mut position, &[98.0, 99.0, 98.0, 101.0]);
+        assert_eq!(position.quantity, 1.0);
+        assert_eq!(strategy.stats().metric("breakouts"), Some(2.0));
+    }
+}
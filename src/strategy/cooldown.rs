@@ -0,0 +1,170 @@
+use super::{retain_reducing, Strategy, StrategyStats};
+use crate::execution::{AckEvent, PositionView};
+use crate::market_data::DerivedValues;
+use crate::types::{L2Snapshot, QuoteEvent, Trade};
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct CooldownConfig {
+    pub snapshots: usize,
+    pub duration_us: u64,
+}
+
+
+
+
+pub struct CooldownStrategy<S: Strategy> {
+    inner: S,
+    config: CooldownConfig,
+    name: String,
+    seen_fills: usize,
+    last_fill: Option<(usize, u64)>,
+    updates: usize,
+    trades_suppressed: usize,
+}
+
+impl<S: Strategy> CooldownStrategy<S> {
+    pub fn new(inner: S, config: CooldownConfig) -> Self {
+        let name = format!("{} [cooldown]", inner.name());
+        Self {
+            inner,
+            config,
+            name,
+            seen_fills: 0,
+            last_fill: None,
+            updates: 0,
+            trades_suppressed: 0,
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    
+    fn cooling_down(&self, timestamp_us: u64) -> bool {
+        let Some((fill_update, fill_us)) = self.last_fill else {
+            return false;
+        };
+        self.updates - fill_update <= self.config.snapshots
+            || timestamp_us.saturating_sub(fill_us) < self.config.duration_us
+    }
+
+    fn observe_fills(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) {
+        self.updates += 1;
+        if position.trade_count() > self.seen_fills {
+            let fill_us = position.last_fill_us().unwrap_or(snapshot.timestamp_us);
+            self.last_fill = Some((self.updates - 1, fill_us));
+        }
+        self.seen_fills = position.trade_count();
+    }
+
+    fn gate(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView, trades: Vec<Trade>) -> Vec<Trade> {
+        if trades.is_empty() || !self.cooling_down(snapshot.timestamp_us) {
+            return trades;
+        }
+        let (allowed, blocked) = retain_reducing(position.quantity(), trades);
+        self.trades_suppressed += blocked;
+        allowed
+    }
+}
+
+impl<S: Strategy> Strategy for CooldownStrategy<S> {
+    fn on_market_data(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) -> Vec<Trade> {
+        self.observe_fills(snapshot, position);
+        let trades = self.inner.on_market_data(snapshot, position);
+        self.gate(snapshot, position, trades)
+    }
+
+    fn on_market_data_derived(
+        &mut self,
+        snapshot: &L2Snapshot,
+        derived: &DerivedValues,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        self.observe_fills(snapshot, position);
+        let trades = self.inner.on_market_data_derived(snapshot, derived, position);
+        self.gate(snapshot, position, trades)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn stats(&self) -> StrategyStats {
+        let mut stats = self.inner.stats();
+        stats.name = self.name.clone();
+        stats.trades_generated = stats.trades_generated.saturating_sub(self.trades_suppressed);
+        stats.with_metric("cooldown_suppressed", self.trades_suppressed as f64)
+    }
+
+    fn set_quote_tracking(&mut self, enabled: bool) {
+        self.inner.set_quote_tracking(enabled);
+    }
+
+    fn drain_quote_events(&mut self, events: &mut Vec<QuoteEvent>) {
+        self.inner.drain_quote_events(events);
+    }
+
+    fn drain_ack_events(&mut self, events: &mut Vec<AckEvent>) {
+        self.inner.drain_ack_events(events);
+    }
+
+    fn on_halt(&mut self, halted: bool, timestamp_us: u64) {
+        self.inner.on_halt(halted, timestamp_us);
+    }
+
+    fn debug_state(&self) -> String {
+        format!("cooldown suppressed: {}\n{}", self.trades_suppressed, self.inner.debug_state())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::Position;
+    use crate::strategy::momentum::{MomentumConfig, MomentumStrategy};
+    use crate::types::PriceLevel;
+
+    fn run<S: Strategy>(strategy: &mut S, mids: &[f64], step_us: u64) -> Vec<u64> {
+        let mut position = Position::new();
+        let mut fills = Vec::new();
+        for (i, &mid) in mids.iter().enumerate() {
+            let ts = i as u64 * step_us;
+            let snapshot = L2Snapshot::from_levels(0, ts, String::new(), &[PriceLevel::new(mid - 0.05, 1.0)], &[PriceLevel::new(mid + 0.05, 1.0)]);
+            for trade in strategy.on_market_data(&snapshot, &position) {
+                position.execute_trade(trade);
+                fills.push(ts);
+            }
+        }
+        fills
+    }
+
+    fn momentum() -> MomentumStrategy {
+        MomentumStrategy::new(MomentumConfig {
+            trigger_threshold: 0.5,
+            lookback: 2,
+            trade_size: 1.0,
+            max_position: 100.0,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_snapshot_cooldown_spaces_entries() {
+        let trend: Vec<f64> = (0..10).map(|i| 100.0 + i as f64).collect();
+        assert_eq!(run(&mut momentum(), &trend, 1).len(), 9);
+
+        let mut strategy = CooldownStrategy::new(momentum(), CooldownConfig { snapshots: 2, duration_us: 0 });
+        assert_eq!(run(&mut strategy, &trend, 1), vec![1, 4, 7]);
+        assert_eq!(strategy.stats().metric("cooldown_suppressed"), Some(6.0));
+    }
+
+    #[test]
+    fn test_time_cooldown() {
+        let trend: Vec<f64> = (0..10).map(|i| 100.0 + i as f64).collect();
+        let mut strategy = CooldownStrategy::new(momentum(), CooldownConfig { snapshots: 0, duration_us: 300 });
+        assert_eq!(run(&mut strategy, &trend, 100), vec![100, 400, 700]);
+    }
+}
@@ -0,0 +1,211 @@
+use super::{retain_reducing, Strategy, StrategyStats};
+use crate::execution::{AckEvent, PositionView};
+use crate::features::Signal;
+use crate::market_data::DerivedValues;
+use crate::types::{L2Snapshot, QuoteEvent, Trade};
+
+
+
+pub struct RegimeFilter {
+    signal: Box<dyn Signal>,
+    min: f64,
+    max: f64,
+}
+
+impl RegimeFilter {
+    pub fn new(signal: Box<dyn Signal>, min: f64, max: f64) -> Self {
+        Self { signal, min, max }
+    }
+
+    pub fn above(signal: Box<dyn Signal>, threshold: f64) -> Self {
+        Self::new(signal, threshold, f64::INFINITY)
+    }
+
+    pub fn below(signal: Box<dyn Signal>, threshold: f64) -> Self {
+        Self::new(signal, f64::NEG_INFINITY, threshold)
+    }
+
+    
+    fn update(&mut self, snapshot: &L2Snapshot) -> Option<f64> {
+        self.signal.update(snapshot)
+    }
+
+    fn allows(&self, value: Option<f64>) -> bool {
+        value.is_some_and(|v| v >= self.min && v <= self.max)
+    }
+}
+
+
+
+
+pub struct FilteredStrategy<S: Strategy> {
+    inner: S,
+    filter: RegimeFilter,
+    name: String,
+    active: bool,
+    last_value: Option<f64>,
+    active_updates: usize,
+    trades_blocked: usize,
+}
+
+impl<S: Strategy> FilteredStrategy<S> {
+    pub fn new(inner: S, filter: RegimeFilter) -> Self {
+        let name = format!("{} [{}]", inner.name(), filter.signal.name());
+        Self {
+            inner,
+            filter,
+            name,
+            active: false,
+            last_value: None,
+            active_updates: 0,
+            trades_blocked: 0,
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn gate(&mut self, position: &dyn PositionView, trades: Vec<Trade>) -> Vec<Trade> {
+        if self.active {
+            return trades;
+        }
+        let (allowed, blocked) = retain_reducing(position.quantity(), trades);
+        self.trades_blocked += blocked;
+        allowed
+    }
+
+    fn refresh(&mut self, snapshot: &L2Snapshot) {
+        self.last_value = self.filter.update(snapshot);
+        self.active = self.filter.allows(self.last_value);
+        if self.active {
+            self.active_updates += 1;
+        }
+    }
+}
+
+impl<S: Strategy> Strategy for FilteredStrategy<S> {
+    fn on_market_data(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) -> Vec<Trade> {
+        self.refresh(snapshot);
+        let trades = self.inner.on_market_data(snapshot, position);
+        self.gate(position, trades)
+    }
+
+    fn on_market_data_derived(
+        &mut self,
+        snapshot: &L2Snapshot,
+        derived: &DerivedValues,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        self.refresh(snapshot);
+        let trades = self.inner.on_market_data_derived(snapshot, derived, position);
+        self.gate(position, trades)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn stats(&self) -> StrategyStats {
+        let mut stats = self.inner.stats();
+        stats.name = self.name.clone();
+        stats.trades_generated = stats.trades_generated.saturating_sub(self.trades_blocked);
+        stats
+            .with_metric("regime_active_updates", self.active_updates as f64)
+            .with_metric("regime_trades_blocked", self.trades_blocked as f64)
+    }
+
+    fn set_quote_tracking(&mut self, enabled: bool) {
+        self.inner.set_quote_tracking(enabled);
+    }
+
+    fn drain_quote_events(&mut self, events: &mut Vec<QuoteEvent>) {
+        self.inner.drain_quote_events(events);
+    }
+
+    fn drain_ack_events(&mut self, events: &mut Vec<AckEvent>) {
+        self.inner.drain_ack_events(events);
+    }
+
+    fn on_halt(&mut self, halted: bool, timestamp_us: u64) {
+        self.inner.on_halt(halted, timestamp_us);
+    }
+
+    fn debug_state(&self) -> String {
+        let value = match self.last_value {
+            Some(v) => format!("{:.4}", v),
+            None => "warming up".to_string(),
+        };
+        format!(
+            "regime {}: {} ({})\n{}",
+            self.filter.signal.name(),
+            value,
+            if self.active { "active" } else { "inactive" },
+            self.inner.debug_state()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::Position;
+    use crate::features::TrendStrengthSignal;
+    use crate::strategy::momentum::{MomentumConfig, MomentumStrategy};
+    use crate::types::{PriceLevel, Side};
+
+    fn snapshot(ts: u64, mid: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(0, ts, String::new(), &[PriceLevel::new(mid - 0.05, 1.0)], &[PriceLevel::new(mid + 0.05, 1.0)])
+    }
+
+    fn run<S: Strategy>(strategy: &mut S, position: &mut Position, mids: &[f64]) -> usize {
+        let mut count = 0;
+        for (i, &mid) in mids.iter().enumerate() {
+            for trade in strategy.on_market_data(&snapshot(i as u64, mid), position) {
+                position.execute_trade(trade);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn momentum() -> MomentumStrategy {
+        MomentumStrategy::new(MomentumConfig {
+            trigger_threshold: 0.5,
+            lookback: 2,
+            trade_size: 1.0,
+            max_position: 10.0,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_choppy_regime_blocks_entries() {
+        let choppy = [100.0, 101.0, 100.0, 101.0, 100.0, 101.5, 100.0];
+        let mut position = Position::new();
+        let mut filtered = FilteredStrategy::new(momentum(), RegimeFilter::above(Box::new(TrendStrengthSignal::new(4)), 0.8));
+        assert_eq!(run(&mut filtered, &mut position, &choppy), 0);
+        assert!(filtered.stats().metric("regime_trades_blocked").unwrap() > 0.0);
+
+        let trending = [100.0, 101.0, 102.0, 103.0, 104.0, 105.0];
+        let mut position = Position::new();
+        let mut filtered = FilteredStrategy::new(momentum(), RegimeFilter::above(Box::new(TrendStrengthSignal::new(4)), 0.8));
+        assert_eq!(run(&mut filtered, &mut position, &trending), 2);
+        assert!(filtered.is_active());
+    }
+
+    #[test]
+    fn test_inactive_regime_still_allows_exits() {
+        let mut position = Position::new();
+        position.execute_trade(Trade::new(Side::Bid, 100.0, 1.0, 0));
+        let mut filtered = FilteredStrategy::new(momentum(), RegimeFilter::above(Box::new(TrendStrengthSignal::new(50)), 0.5));
+
+        let trades = run(&mut filtered, &mut position, &[103.0, 102.0, 101.0]);
+        assert_eq!(trades, 1);
+        assert!(position.is_flat());
+    }
+}
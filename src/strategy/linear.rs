@@ -0,0 +1,315 @@
+use super::{Strategy, StrategyStats};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use crate::execution::PositionView;
+use crate::features::{order_flow_imbalance, BookPressure};
+use crate::types::{L2Snapshot, Side, Trade};
+
+const BPS: f64 = 10_000.0;
+
+const FEATURES: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinearModelConfig {
+    
+    pub training_snapshots: usize,
+    
+    pub horizon: usize,
+    pub ridge_lambda: f64,
+    
+    pub refit_every: usize,
+    
+    pub entry_threshold_bps: f64,
+    pub trade_size: f64,
+    pub max_position: f64,
+}
+
+impl Default for LinearModelConfig {
+    fn default() -> Self {
+        Self {
+            training_snapshots: 5_000,
+            horizon: 10,
+            ridge_lambda: 1.0,
+            refit_every: 1_000,
+            entry_threshold_bps: 1.0,
+            trade_size: 0.1,
+            max_position: 1.0,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct OnlineRidge {
+    xtx: [[f64; FEATURES]; FEATURES],
+    xty: [f64; FEATURES],
+    samples: usize,
+    lambda: f64,
+}
+
+impl OnlineRidge {
+    pub fn new(lambda: f64) -> Self {
+        Self {
+            xtx: [[0.0; FEATURES]; FEATURES],
+            xty: [0.0; FEATURES],
+            samples: 0,
+            lambda,
+        }
+    }
+
+    pub fn observe(&mut self, x: &[f64; FEATURES], y: f64) {
+        for i in 0..FEATURES {
+            for j in 0..FEATURES {
+                self.xtx[i][j] += x[i] * x[j];
+            }
+            self.xty[i] += x[i] * y;
+        }
+        self.samples += 1;
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    
+    pub fn solve(&self) -> Option<[f64; FEATURES]> {
+        let mut a = self.xtx;
+        let mut b = self.xty;
+        for (i, row) in a.iter_mut().enumerate() {
+            row[i] += self.lambda;
+        }
+
+        for col in 0..FEATURES {
+            let pivot = (col..FEATURES).max_by(|&r, &s| a[r][col].abs().total_cmp(&a[s][col].abs()))?;
+            if a[pivot][col].abs() < 1e-12 {
+                return None;
+            }
+            a.swap(col, pivot);
+            b.swap(col, pivot);
+            for row in col + 1..FEATURES {
+                let factor = a[row][col] / a[col][col];
+                let pivot_row = a[col];
+                for (cell, pivot_cell) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                    *cell -= factor * pivot_cell;
+                }
+                b[row] -= factor * b[col];
+            }
+        }
+
+        let mut w = [0.0; FEATURES];
+        for row in (0..FEATURES).rev() {
+            let tail: f64 = (row + 1..FEATURES).map(|k| a[row][k] * w[k]).sum();
+            w[row] = (b[row] - tail) / a[row][row];
+        }
+        Some(w)
+    }
+}
+
+fn predict(weights: &[f64; FEATURES], x: &[f64; FEATURES]) -> f64 {
+    weights.iter().zip(x).map(|(w, v)| w * v).sum()
+}
+
+
+
+
+pub struct LinearModelStrategy {
+    config: LinearModelConfig,
+    model: OnlineRidge,
+    weights: Option<[f64; FEATURES]>,
+    pending: VecDeque<([f64; FEATURES], f64)>,
+    prev: Option<L2Snapshot>,
+    last_prediction: Option<f64>,
+
+    updates_processed: usize,
+    trades_generated: usize,
+    refits: usize,
+    predictions: usize,
+    correct_direction: usize,
+    scored: usize,
+    scored_predictions: VecDeque<f64>,
+}
+
+impl LinearModelStrategy {
+    pub fn new(config: LinearModelConfig) -> Self {
+        Self {
+            model: OnlineRidge::new(config.ridge_lambda),
+            config,
+            weights: None,
+            pending: VecDeque::new(),
+            prev: None,
+            last_prediction: None,
+            updates_processed: 0,
+            trades_generated: 0,
+            refits: 0,
+            predictions: 0,
+            correct_direction: 0,
+            scored: 0,
+            scored_predictions: VecDeque::new(),
+        }
+    }
+
+    pub fn weights(&self) -> Option<&[f64; FEATURES]> {
+        self.weights.as_ref()
+    }
+
+    fn features(&self, snapshot: &L2Snapshot, mid: f64) -> [f64; FEATURES] {
+        let pressure = BookPressure::from_snapshot(snapshot);
+        let (ofi, ret_bps) = match &self.prev {
+            Some(prev) => (
+                order_flow_imbalance(prev, snapshot),
+                (mid - prev.mid_price()) / prev.mid_price() * BPS,
+            ),
+            None => (0.0, 0.0),
+        };
+        [
+            1.0,
+            pressure.quantity[0],
+            pressure.quantity[1],
+            pressure.quantity[2],
+            pressure.quantity[3],
+            snapshot.spread() / mid * BPS,
+            ofi,
+            ret_bps,
+        ]
+    }
+
+    fn trading(&self) -> bool {
+        self.updates_processed > self.config.training_snapshots
+    }
+}
+
+impl Strategy for LinearModelStrategy {
+    fn on_market_data(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) -> Vec<Trade> {
+        self.updates_processed += 1;
+        let mid = snapshot.mid_price();
+        let x = self.features(snapshot, mid);
+        self.prev = Some(snapshot.clone());
+
+        self.pending.push_back((x, mid));
+        if self.pending.len() > self.config.horizon {
+            if let Some((past_x, past_mid)) = self.pending.pop_front() {
+                let label = (mid - past_mid) / past_mid * BPS;
+                self.model.observe(&past_x, label);
+                if let Some(predicted) = self.scored_predictions.pop_front().filter(|p| !p.is_nan()) {
+                    self.scored += 1;
+                    if predicted.signum() == label.signum() {
+                        self.correct_direction += 1;
+                    }
+                }
+            }
+        }
+
+        let refit_due = self.updates_processed == self.config.training_snapshots
+            || (self.trading() && self.config.refit_every > 0 && self.updates_processed.is_multiple_of(self.config.refit_every));
+        if refit_due {
+            if let Some(weights) = self.model.solve() {
+                self.weights = Some(weights);
+                self.refits += 1;
+            }
+        }
+
+        let prediction = self.weights.filter(|_| self.trading()).map(|w| predict(&w, &x));
+        self.last_prediction = prediction;
+        self.scored_predictions.push_back(prediction.unwrap_or(f64::NAN));
+        if self.scored_predictions.len() > self.config.horizon {
+            self.scored_predictions.pop_front();
+        }
+
+        let Some(prediction) = prediction else {
+            return Vec::new();
+        };
+        self.predictions += 1;
+
+        let threshold = self.config.entry_threshold_bps;
+        let trade = if prediction > threshold && position.quantity() < self.config.max_position {
+            let quantity = self.config.trade_size.min(self.config.max_position - position.quantity());
+            Trade::new(Side::Bid, snapshot.best_ask(), quantity, snapshot.timestamp_us)
+        } else if prediction < -threshold && position.quantity() > -self.config.max_position {
+            let quantity = self.config.trade_size.min(self.config.max_position + position.quantity());
+            Trade::new(Side::Ask, snapshot.best_bid(), quantity, snapshot.timestamp_us)
+        } else {
+            return Vec::new();
+        };
+        self.trades_generated += 1;
+        vec![trade]
+    }
+
+    fn name(&self) -> &str {
+        "Linear Model Strategy"
+    }
+
+    fn stats(&self) -> StrategyStats {
+        StrategyStats {
+            name: self.name().to_string(),
+            updates_processed: self.updates_processed,
+            trades_generated: self.trades_generated,
+            ..Default::default()
+        }
+        .with_metric("training_samples", self.model.samples() as f64)
+        .with_metric("refits", self.refits as f64)
+        .with_metric("predictions", self.predictions as f64)
+        .with_metric("hit_rate", self.correct_direction as f64 / self.scored.max(1) as f64)
+    }
+
+    fn debug_state(&self) -> String {
+        let prediction = match self.last_prediction {
+            Some(p) => format!("{:.4} bps", p),
+            None if self.trading() => "no model".to_string(),
+            None => format!("training ({} / {})", self.updates_processed, self.config.training_snapshots),
+        };
+        format!("prediction: {}\nrefits: {}", prediction, self.refits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::Position;
+    use crate::types::PriceLevel;
+
+    #[test]
+    fn test_ridge_recovers_weights() {
+        let mut model = OnlineRidge::new(1e-6);
+        let truth = [0.5, 2.0, -1.0, 0.0, 0.0, 0.0, 0.0, 3.0];
+        for i in 0..200 {
+            let t = i as f64;
+            let x = [1.0, (t * 0.37).sin(), (t * 0.11).cos(), (t * 0.53).sin(), (t * 0.29).cos(), (t * 0.07).sin(), (t * 0.91).cos(), (t * 0.17).sin()];
+            model.observe(&x, predict(&truth, &x));
+        }
+        let w = model.solve().unwrap();
+        for (fitted, expected) in w.iter().zip(truth) {
+            assert!((fitted - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_trades_on_learned_imbalance() {
+        let mut strategy = LinearModelStrategy::new(LinearModelConfig {
+            training_snapshots: 200,
+            horizon: 1,
+            ridge_lambda: 1e-3,
+            entry_threshold_bps: 1.0,
+            trade_size: 1.0,
+            max_position: 100.0,
+            ..Default::default()
+        });
+        let position = Position::new();
+        let mut mid = 100.0;
+        let mut trades = Vec::new();
+        for i in 0..400u64 {
+            let buy_pressure = i % 3 == 0;
+            let (bid_qty, ask_qty) = if buy_pressure { (3.0, 1.0) } else { (1.0, 3.0) };
+            let snapshot = L2Snapshot::from_levels(0, i, String::new(), &[PriceLevel::new(mid - 0.01, bid_qty)], &[PriceLevel::new(mid + 0.01, ask_qty)]);
+            trades.extend(strategy.on_market_data(&snapshot, &position).into_iter().map(|t| (buy_pressure, t.side)));
+            mid += if buy_pressure { 0.1 } else { -0.05 };
+        }
+
+        assert!(strategy.weights().is_some());
+        assert!(trades.len() > 150);
+        assert!(trades.iter().all(|&(buy, side)| (side == Side::Bid) == buy));
+        assert!(strategy.stats().metric("hit_rate").unwrap() > 0.9);
+    }
+}
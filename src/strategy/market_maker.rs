@@ -1,21 +1,115 @@
 use super::{Strategy, StrategyStats};
-use crate::execution::Position;
-use crate::types::{L2Snapshot, Side, Trade};
+use serde::{Deserialize, Serialize};
+use crate::execution::{AckEvent, AckModel, FillModel, Instrument, Order, OrderCallbacks, OrderManager, OrderManagerConfig, RejectReason, PositionView, SelfTradePrevention, Sizer, SizingContext, Throttle};
+use crate::features::{EmaMidSignal, FairValue, MicropriceSignal, Signal, Volatility, VolatilityConfig};
+use crate::market_data::{infer_tick_size, tick_size_warning, DerivedValues};
+use crate::types::{L2Snapshot, QuoteEvent, QuoteEventKind, Side, TimeInForce, Trade};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
 
-#[derive(Debug, Clone, Copy)]
-struct LimitOrder {
-    price: f64,
-    quantity: f64,
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ReferencePrice {
+    
+    #[default]
+    Touch,
+    Mid,
+    Microprice,
+    EmaMid { span: usize },
+    FairValue { levels: usize, decay: f64 },
+    
+    Signal,
+}
+
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LossBackoff {
+    
+    pub losing_fills: usize,
+    
+    pub markout_snapshots: usize,
+    
+    pub backoff_snapshots: usize,
+    
+    pub widen_ticks: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct BackoffState {
+    pending: VecDeque<(Side, f64, usize)>,
+    streak: usize,
+    remaining: usize,
+    triggers: usize,
+}
+
+impl BackoffState {
+    fn record_fill(&mut self, rule: &LossBackoff, side: Side, price: f64, update: usize) {
+        self.pending.push_back((side, price, update + rule.markout_snapshots));
+    }
+
+    fn on_update(&mut self, rule: &LossBackoff, mid_price: f64, update: usize) {
+        self.remaining = self.remaining.saturating_sub(1);
+        while let Some(&(side, price, due)) = self.pending.front() {
+            if due > update {
+                break;
+            }
+            self.pending.pop_front();
+            let markout = match side {
+                Side::Bid => mid_price - price,
+                Side::Ask => price - mid_price,
+            };
+            if markout < 0.0 {
+                self.streak += 1;
+            } else {
+                self.streak = 0;
+            }
+            if rule.losing_fills > 0 && self.streak >= rule.losing_fills {
+                self.streak = 0;
+                self.remaining = rule.backoff_snapshots;
+                self.triggers += 1;
+            }
+        }
+    }
+
+    fn active(&self) -> bool {
+        self.remaining > 0
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct InventoryBand {
+    pub target: f64,
+    pub half_width: f64,
+    
+    pub size_multiplier: f64,
 }
 
-impl LimitOrder {
-    fn new(price: f64, quantity: f64) -> Self {
-        Self { price, quantity }
+impl InventoryBand {
+    
+    pub fn size_factor(&self, side: Side, position_qty: f64) -> f64 {
+        let multiplier = self.size_multiplier.max(1.0);
+        let reducing = match side {
+            Side::Bid => position_qty < self.target - self.half_width,
+            Side::Ask => position_qty > self.target + self.half_width,
+        };
+        let adding = match side {
+            Side::Bid => position_qty > self.target + self.half_width,
+            Side::Ask => position_qty < self.target - self.half_width,
+        };
+        if reducing {
+            multiplier
+        } else if adding {
+            1.0 / multiplier
+        } else {
+            1.0
+        }
     }
 }
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MarketMakerConfig {
     pub spread_ticks: f64,
     pub quote_size: f64,
@@ -26,6 +120,35 @@ pub struct MarketMakerConfig {
     pub trend_filter_ticks: f64,
     
     pub hedge_inventory_ratio: f64,
+    
+    
+    pub volatility_spread_factor: f64,
+    
+    
+    pub sizer: Option<Sizer>,
+    pub starting_capital: f64,
+    
+    
+    pub max_quotes_per_sec: usize,
+    
+    pub min_quote_lifetime_us: u64,
+    
+    pub post_only: bool,
+    
+    
+    pub inventory_band: Option<InventoryBand>,
+    
+    
+    pub reference_price: ReferencePrice,
+    
+    pub loss_backoff: Option<LossBackoff>,
+    
+    pub instrument: Option<Instrument>,
+    pub fill_model: FillModel,
+    
+    pub ack_model: Option<AckModel>,
+    
+    pub self_trade_prevention: SelfTradePrevention,
 }
 
 impl Default for MarketMakerConfig {
@@ -40,51 +163,230 @@ impl Default for MarketMakerConfig {
             trend_filter_ticks: 0.5,
             
             hedge_inventory_ratio: 0.5,
+            volatility_spread_factor: 0.0,
+            sizer: None,
+            starting_capital: 100_000.0,
+            max_quotes_per_sec: 0,
+            min_quote_lifetime_us: 0,
+            post_only: false,
+            inventory_band: None,
+            reference_price: ReferencePrice::Touch,
+            loss_backoff: None,
+            instrument: None,
+            fill_model: FillModel::default(),
+            ack_model: None,
+            self_trade_prevention: SelfTradePrevention::Off,
         }
     }
 }
 
-pub struct MarketMaker {
+impl MarketMakerConfig {
+    
+    pub fn tick_size_warning(&self, snapshots: &[L2Snapshot]) -> Option<String> {
+        tick_size_warning(self.tick_size, infer_tick_size(snapshots)?)
+    }
+}
+
+pub trait SkewModel {
+    const SKEWS: bool = true;
+
+    fn skew(config: &MarketMakerConfig, position_qty: f64) -> f64;
+}
+
+
+pub struct LinearSkew;
+
+impl SkewModel for LinearSkew {
+    fn skew(config: &MarketMakerConfig, position_qty: f64) -> f64 {
+        let inventory_ratio = (position_qty / config.max_position).clamp(-1.0, 1.0);
+        inventory_ratio * config.inventory_skew_ticks * config.tick_size
+    }
+}
+
+
+pub struct NoSkew;
+
+impl SkewModel for NoSkew {
+    const SKEWS: bool = false;
+
+    fn skew(_config: &MarketMakerConfig, _position_qty: f64) -> f64 {
+        0.0
+    }
+}
+
+
+
+
+pub type MarketMaker = MarketMakerCore<true, true, LinearSkew>;
+
+
+pub type LeanMarketMaker = MarketMakerCore<false, false, NoSkew>;
+
+pub struct MarketMakerCore<const TREND: bool, const HEDGE: bool, K: SkewModel> {
     config: MarketMakerConfig,
     updates_processed: usize,
     trades_generated: usize,
     quotes_placed: usize,
-    active_bid: Option<LimitOrder>,
-    active_ask: Option<LimitOrder>,
+    quotes_held: usize,
+    orders: OrderManager,
     last_mid_price: Option<f64>,
+    current_timestamp_us: u64,
+    track_quotes: bool,
+    quote_events: Vec<QuoteEvent>,
+    ack_events: Vec<AckEvent>,
+    volatility: Volatility,
+    order_size: f64,
+    throttle: Throttle,
+    reference_signal: Option<Box<dyn Signal>>,
+    backoff: BackoffState,
+    orders_acked: usize,
+    orders_rejected: usize,
+    skew: PhantomData<K>,
 }
 
-impl MarketMaker {
+impl<const TREND: bool, const HEDGE: bool, K: SkewModel> MarketMakerCore<TREND, HEDGE, K> {
+    
     pub fn new(config: MarketMakerConfig) -> Self {
+        Self::build(Self::without_omitted(config))
+    }
+
+    
+    pub fn default_config() -> MarketMakerConfig {
+        Self::without_omitted(MarketMakerConfig::default())
+    }
+
+    fn without_omitted(mut config: MarketMakerConfig) -> MarketMakerConfig {
+        if !TREND {
+            config.trend_filter_ticks = 0.0;
+        }
+        if !HEDGE {
+            config.hedge_inventory_ratio = f64::INFINITY;
+        }
+        if !K::SKEWS {
+            config.inventory_skew_ticks = 0.0;
+        }
+        config
+    }
+
+    
+    
+    pub fn try_new(config: MarketMakerConfig) -> anyhow::Result<Self> {
+        if !TREND && config.trend_filter_ticks > 0.0 {
+            anyhow::bail!("trend_filter_ticks {} requires the trend filter, which this variant omits", config.trend_filter_ticks);
+        }
+        if !HEDGE && config.hedge_inventory_ratio != f64::INFINITY {
+            anyhow::bail!("hedge_inventory_ratio {} requires hedging, which this variant omits; use f64::INFINITY", config.hedge_inventory_ratio);
+        }
+        if !K::SKEWS && config.inventory_skew_ticks != 0.0 {
+            anyhow::bail!("inventory_skew_ticks {} requires an inventory skew model, which this variant omits", config.inventory_skew_ticks);
+        }
+        Ok(Self::build(config))
+    }
+
+    fn build(config: MarketMakerConfig) -> Self {
+        let orders = OrderManager::new(OrderManagerConfig {
+            min_quote_lifetime_us: config.min_quote_lifetime_us,
+            instrument: config.instrument,
+            fill_model: config.fill_model,
+            ack_model: config.ack_model,
+            self_trade_prevention: config.self_trade_prevention,
+        });
+        let throttle = Throttle::new(config.max_quotes_per_sec);
+        let order_size = config.quote_size;
+        let reference_signal: Option<Box<dyn Signal>> = match config.reference_price {
+            ReferencePrice::Microprice => Some(Box::new(MicropriceSignal)),
+            ReferencePrice::EmaMid { span } => Some(Box::new(EmaMidSignal::new(span))),
+            ReferencePrice::FairValue { levels, decay } => Some(Box::new(FairValue::new(levels, decay))),
+            _ => None,
+        };
         Self {
             config,
             updates_processed: 0,
             trades_generated: 0,
             quotes_placed: 0,
-            active_bid: None,
-            active_ask: None,
+            quotes_held: 0,
+            orders,
             last_mid_price: None,
+            current_timestamp_us: 0,
+            track_quotes: false,
+            quote_events: Vec::new(),
+            ack_events: Vec::new(),
+            volatility: Volatility::new(VolatilityConfig::default()),
+            order_size,
+            throttle,
+            reference_signal,
+            backoff: BackoffState::default(),
+            orders_acked: 0,
+            orders_rejected: 0,
+            skew: PhantomData,
         }
     }
 
     
+    
+    pub fn with_reference_signal(mut self, signal: Box<dyn Signal>) -> Self {
+        self.config.reference_price = ReferencePrice::Signal;
+        self.reference_signal = Some(signal);
+        self
+    }
+
+    
+    
+    fn reference_quotes(&mut self, snapshot: &L2Snapshot, mid_price: f64) -> (f64, f64) {
+        let touch = (snapshot.best_bid(), snapshot.best_ask());
+        let reference = match self.config.reference_price {
+            ReferencePrice::Touch => return touch,
+            ReferencePrice::Mid => Some(mid_price),
+            _ => self.reference_signal.as_mut().and_then(|signal| signal.update(snapshot)),
+        };
+        match reference {
+            Some(reference) => {
+                let half_spread = snapshot.spread() / 2.0;
+                (reference - half_spread, reference + half_spread)
+            }
+            None => touch,
+        }
+    }
+
+    
+    fn compute_order_size(&self, position: &dyn PositionView, mid_price: f64) -> f64 {
+        let Some(sizer) = self.config.sizer else {
+            return self.config.quote_size;
+        };
+        sizer.size(&SizingContext {
+            equity: position.equity(self.config.starting_capital, mid_price),
+            price: mid_price,
+            volatility_bps: self.volatility.ewma().unwrap_or(0.0),
+            edge_bps: self.config.spread_ticks * self.config.tick_size / mid_price * 10_000.0,
+        })
+    }
+
+    
     fn calculate_bid_price(&self, best_bid: f64, position_qty: f64) -> f64 {
-        let base_bid = best_bid - (self.config.spread_ticks * self.config.tick_size);
+        let base_bid = best_bid - (self.config.spread_ticks * self.config.tick_size) - self.volatility_widening(best_bid);
         let skew = self.inventory_price_skew(position_qty);
         base_bid - skew
     }
 
     
     fn calculate_ask_price(&self, best_ask: f64, position_qty: f64) -> f64 {
-        let base_ask = best_ask + (self.config.spread_ticks * self.config.tick_size);
+        let base_ask = best_ask + (self.config.spread_ticks * self.config.tick_size) + self.volatility_widening(best_ask);
         let skew = self.inventory_price_skew(position_qty);
         base_ask - skew
     }
 
     
+    fn volatility_widening(&self, price: f64) -> f64 {
+        if self.config.volatility_spread_factor <= 0.0 {
+            return 0.0;
+        }
+        let vol_bps = self.volatility.ewma().unwrap_or(0.0);
+        self.config.volatility_spread_factor * vol_bps / 10_000.0 * price
+    }
+
+    
     fn inventory_price_skew(&self, position_qty: f64) -> f64 {
-        let inventory_ratio = (position_qty / self.config.max_position).clamp(-1.0, 1.0);
-        inventory_ratio * self.config.inventory_skew_ticks * self.config.tick_size
+        K::skew(&self.config, position_qty)
     }
 
     
@@ -104,48 +406,64 @@ impl MarketMaker {
         let ratio = position_qty / self.config.max_position;
         ratio > -self.config.inventory_threshold
     }
-}
 
-impl Strategy for MarketMaker {
-    fn on_market_data(
+    fn process_update(
         &mut self,
         snapshot: &L2Snapshot,
-        position: &Position,
-    ) -> Vec<Trade> {
+        mid_price: f64,
+        position: &dyn PositionView,
+        trades: &mut Vec<Trade>,
+    ) {
         self.updates_processed += 1;
+        self.current_timestamp_us = snapshot.timestamp_us;
 
-        let mut trades = Vec::new();
-        let position_qty = position.quantity;
+        let position_qty = position.quantity();
 
-        let best_bid = snapshot.best_bid();
-        let best_ask = snapshot.best_ask();
-        let mid_price = (best_bid + best_ask) / 2.0;
         let trend = match self.last_mid_price {
             Some(prev) => mid_price - prev,
             None => 0.0,
         };
         self.last_mid_price = Some(mid_price);
+        if self.config.volatility_spread_factor > 0.0 || self.config.sizer.is_some_and(|s| s.needs_volatility()) {
+            self.volatility.update(mid_price);
+        }
+        self.order_size = self.compute_order_size(position, mid_price);
+        if let Some(rule) = self.config.loss_backoff {
+            self.backoff.on_update(&rule, mid_price, self.updates_processed);
+        }
 
         
-        self.check_resting_order_fills(snapshot, &mut trades);
+        self.check_resting_order_fills(snapshot, trades);
 
         
         
         
-        self.hedge_inventory(snapshot, position_qty, &mut trades);
+        if HEDGE {
+            self.hedge_inventory(snapshot, position_qty, trades);
+        }
 
         
-        let desired_bid_price = self.calculate_bid_price(best_bid, position_qty);
-        let desired_ask_price = self.calculate_ask_price(best_ask, position_qty);
+        let (bid_base, ask_base) = self.reference_quotes(snapshot, mid_price);
+        let backoff_widening = match self.config.loss_backoff {
+            Some(rule) if self.backoff.active() => rule.widen_ticks * self.config.tick_size,
+            _ => 0.0,
+        };
+        let desired_bid_price = self.calculate_bid_price(bid_base, position_qty) - backoff_widening;
+        let desired_ask_price = self.calculate_ask_price(ask_base, position_qty) + backoff_widening;
 
         let mut placed_new_order = false;
 
         
         let mut quote_bid = self.should_quote_bid(position_qty);
         let mut quote_ask = self.should_quote_ask(position_qty);
+        let paused = self.backoff.active() && backoff_widening <= 0.0;
+        if self.order_size <= 0.0 || paused {
+            quote_bid = false;
+            quote_ask = false;
+        }
         let trend_threshold = self.config.trend_filter_ticks * self.config.tick_size;
         
-        if trend_threshold > 0.0 {
+        if TREND && trend_threshold > 0.0 {
             if trend > trend_threshold && position_qty <= 0.0 {
                 quote_ask = false;
             }
@@ -155,24 +473,71 @@ impl Strategy for MarketMaker {
         }
 
         if quote_bid {
-            placed_new_order |= self.update_resting_bid(desired_bid_price);
+            placed_new_order |= self.update_resting(Side::Bid, desired_bid_price, position_qty, snapshot);
         } else {
-            self.active_bid = None;
+            self.cancel_side(Side::Bid);
         }
 
         if quote_ask {
-            placed_new_order |= self.update_resting_ask(desired_ask_price);
+            placed_new_order |= self.update_resting(Side::Ask, desired_ask_price, position_qty, snapshot);
         } else {
-            self.active_ask = None;
+            self.cancel_side(Side::Ask);
         }
 
         if placed_new_order {
-            self.check_resting_order_fills(snapshot, &mut trades);
+            self.check_resting_order_fills(snapshot, trades);
         }
+    }
+}
+
+impl<const TREND: bool, const HEDGE: bool, K: SkewModel> OrderCallbacks for MarketMakerCore<TREND, HEDGE, K> {
+    fn on_order_acked(&mut self, _order: &Order) {
+        self.orders_acked += 1;
+    }
+
+    fn on_order_rejected(&mut self, order: &Order, _reason: RejectReason) {
+        self.orders_rejected += 1;
+        self.record_quote_event(QuoteEventKind::Cancelled, *order);
+    }
+}
 
+impl<const TREND: bool, const HEDGE: bool, K: SkewModel> Strategy for MarketMakerCore<TREND, HEDGE, K> {
+    fn on_market_data(
+        &mut self,
+        snapshot: &L2Snapshot,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        let mut trades = Vec::new();
+        self.on_market_data_into(snapshot, position, &mut trades);
         trades
     }
 
+    fn on_market_data_derived(
+        &mut self,
+        snapshot: &L2Snapshot,
+        derived: &DerivedValues,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        let mut trades = Vec::new();
+        self.process_update(snapshot, derived.mid, position, &mut trades);
+        trades
+    }
+
+    fn on_market_data_into(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView, out: &mut Vec<Trade>) {
+        let mid_price = (snapshot.best_bid() + snapshot.best_ask()) / 2.0;
+        self.process_update(snapshot, mid_price, position, out);
+    }
+
+    fn on_market_data_derived_into(
+        &mut self,
+        snapshot: &L2Snapshot,
+        derived: &DerivedValues,
+        position: &dyn PositionView,
+        out: &mut Vec<Trade>,
+    ) {
+        self.process_update(snapshot, derived.mid, position, out);
+    }
+
     fn name(&self) -> &str {
         "Market Maker"
     }
@@ -183,11 +548,59 @@ impl Strategy for MarketMaker {
             updates_processed: self.updates_processed,
             trades_generated: self.trades_generated,
             quotes_placed: self.quotes_placed,
+            quotes_throttled: self.throttle.rejected(),
+            quotes_held: self.quotes_held,
+            backoff_triggers: self.backoff.triggers,
+            ..Default::default()
+        }
+        .with_metric("resting_orders", self.orders.orders().len() as f64)
+        .with_metric("grid_mismatches", self.orders.grid_report().mismatches() as f64)
+        .with_metric("orders_acked", self.orders_acked as f64)
+        .with_metric("orders_rejected", self.orders_rejected as f64)
+    }
+
+    fn set_quote_tracking(&mut self, enabled: bool) {
+        self.track_quotes = enabled;
+    }
+
+    fn drain_quote_events(&mut self, events: &mut Vec<QuoteEvent>) {
+        events.append(&mut self.quote_events);
+    }
+
+    fn drain_ack_events(&mut self, events: &mut Vec<AckEvent>) {
+        events.append(&mut self.ack_events);
+    }
+
+    fn on_halt(&mut self, halted: bool, timestamp_us: u64) {
+        if halted {
+            self.current_timestamp_us = timestamp_us;
+            self.cancel_side(Side::Bid);
+            self.cancel_side(Side::Ask);
         }
     }
+
+    fn debug_state(&self) -> String {
+        let fmt_order = |order: Option<&Order>| match order {
+            Some(o) => format!("{:.2} x {:.4}", o.price, o.quantity),
+            None => "-".to_string(),
+        };
+        let last_mid = match self.last_mid_price {
+            Some(mid) => format!("{:.2}", mid),
+            None => "-".to_string(),
+        };
+        format!(
+            "resting bid: {}\nresting ask: {}\nlast mid: {}\nupdates: {}, trades: {}, quotes: {}",
+            fmt_order(self.orders.first(Side::Bid)),
+            fmt_order(self.orders.first(Side::Ask)),
+            last_mid,
+            self.updates_processed,
+            self.trades_generated,
+            self.quotes_placed
+        )
+    }
 }
 
-impl MarketMaker {
+impl<const TREND: bool, const HEDGE: bool, K: SkewModel> MarketMakerCore<TREND, HEDGE, K> {
     
     
     
@@ -200,33 +613,29 @@ impl MarketMaker {
         let hedge_threshold = self.config.max_position * self.config.hedge_inventory_ratio;
 
         
-        if position_qty > hedge_threshold {
-            
-            let reduce_qty = (position_qty - hedge_threshold).min(self.config.quote_size);
-            if reduce_qty < 1e-9 { return; }
-
-            let trade = Trade::new(Side::Ask, snapshot.best_bid(), reduce_qty, snapshot.timestamp_us);
-            trades.push(trade);
-            self.trades_generated += 1;
-            
-            
-            self.active_bid = None;
-            self.active_ask = None;
-
-        
+        let (side, price, excess) = if position_qty > hedge_threshold {
+            (Side::Ask, snapshot.best_bid(), position_qty - hedge_threshold)
         } else if position_qty < -hedge_threshold {
-            
-            let reduce_qty = (position_qty.abs() - hedge_threshold).min(self.config.quote_size);
-            if reduce_qty < 1e-9 { return; }
+            (Side::Bid, snapshot.best_ask(), position_qty.abs() - hedge_threshold)
+        } else {
+            return;
+        };
+        let reduce_qty = excess.min(self.config.quote_size);
+        if reduce_qty < 1e-9 { return; }
 
-            let trade = Trade::new(Side::Bid, snapshot.best_ask(), reduce_qty, snapshot.timestamp_us);
-            trades.push(trade);
-            self.trades_generated += 1;
-            
-            
-            self.active_bid = None;
-            self.active_ask = None;
+        
+        let submission = self.orders.submit(side, price, reduce_qty, TimeInForce::Ioc, snapshot);
+        for order in submission.self_trade {
+            if self.orders.get(order.id).is_none() {
+                self.record_quote_event(QuoteEventKind::Cancelled, order);
+            }
         }
+        self.trades_generated += submission.fills.len();
+        trades.extend(submission.fills);
+
+        
+        self.cancel_side(Side::Bid);
+        self.cancel_side(Side::Ask);
     }
 
     
@@ -235,67 +644,85 @@ impl MarketMaker {
         snapshot: &L2Snapshot,
         trades: &mut Vec<Trade>,
     ) {
-        
-        if let Some(order) = self.active_bid {
-            if snapshot.best_ask() <= order.price {
-                let trade = Trade::new(
-                    Side::Bid,
-                    order.price,
-                    order.quantity,
-                    snapshot.timestamp_us,
-                );
-                trades.push(trade);
-                self.trades_generated += 1;
-                self.active_bid = None;
+        for event in self.orders.poll_acks(snapshot.timestamp_us) {
+            event.deliver(self);
+            if self.track_quotes {
+                self.ack_events.push(event);
             }
         }
-
-        
-        if let Some(order) = self.active_ask {
-            if snapshot.best_bid() >= order.price {
-                let trade = Trade::new(
-                    Side::Ask,
-                    order.price,
-                    order.quantity,
-                    snapshot.timestamp_us,
-                );
-                trades.push(trade);
-                self.trades_generated += 1;
-                self.active_ask = None;
+        for order in self.orders.match_resting(snapshot) {
+            if let Some(rule) = self.config.loss_backoff {
+                self.backoff.record_fill(&rule, order.side, order.price, self.updates_processed);
             }
+            trades.push(Trade::new(order.side, order.price, order.quantity, snapshot.timestamp_us));
+            self.trades_generated += 1;
+            self.record_quote_event(QuoteEventKind::Filled, order);
         }
     }
 
     
-    fn update_resting_bid(&mut self, desired_price: f64) -> bool {
-        let needs_new_order = match self.active_bid {
-            Some(order) => (order.price - desired_price).abs() >= self.config.tick_size * 0.5,
-            None => true,
-        };
-
-        if needs_new_order {
-            self.active_bid = Some(LimitOrder::new(desired_price, self.config.quote_size));
-            self.quotes_placed += 1;
-            return true;
+    fn cancel_side(&mut self, side: Side) {
+        for order in self.orders.cancel_side(side) {
+            self.record_quote_event(QuoteEventKind::Cancelled, order);
         }
+    }
 
-        false
+    fn record_quote_event(&mut self, kind: QuoteEventKind, order: Order) {
+        if self.track_quotes {
+            self.quote_events.push(QuoteEvent::new(
+                kind,
+                order.side,
+                order.price,
+                order.quantity,
+                self.current_timestamp_us,
+            ));
+        }
     }
 
     
-    fn update_resting_ask(&mut self, desired_price: f64) -> bool {
-        let needs_new_order = match self.active_ask {
-            Some(order) => (order.price - desired_price).abs() >= self.config.tick_size * 0.5,
-            None => true,
+    
+    fn update_resting(&mut self, side: Side, desired_price: f64, position_qty: f64, snapshot: &L2Snapshot) -> bool {
+        let now = self.current_timestamp_us;
+        let size = match self.config.inventory_band {
+            Some(band) => self.order_size * band.size_factor(side, position_qty),
+            None => self.order_size,
         };
+        let resting = self.orders.first(side).copied();
+        if let Some(order) = resting {
+            if (order.price - desired_price).abs() < self.config.tick_size * 0.5
+                && (order.quantity - size).abs() < 1e-9
+            {
+                return false;
+            }
+            if !self.orders.can_replace(order.id, now) {
+                self.quotes_held += 1;
+                return false;
+            }
+        }
 
-        if needs_new_order {
-            self.active_ask = Some(LimitOrder::new(desired_price, self.config.quote_size));
-            self.quotes_placed += 1;
-            return true;
+        if !self.throttle.try_acquire(now) {
+            return false;
         }
 
-        false
+        let (kind, order) = match resting {
+            Some(order) => match self.orders.replace(order.id, desired_price, size, snapshot) {
+                Some(order) => (QuoteEventKind::Replaced, order),
+                None => return false,
+            },
+            None if self.config.post_only => {
+                match self.orders.submit(side, desired_price, size, TimeInForce::PostOnly, snapshot).resting {
+                    Some(order) => (QuoteEventKind::Placed, order),
+                    None => return false,
+                }
+            }
+            None => match self.orders.place(side, desired_price, size, now) {
+                Some(order) => (QuoteEventKind::Placed, order),
+                None => return false,
+            },
+        };
+        self.quotes_placed += 1;
+        self.record_quote_event(kind, order);
+        true
     }
 }
 
@@ -373,6 +800,30 @@ mod tests {
         assert!((trades[0].price - 99.9).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_into_buffer_appends_and_reuses_capacity() {
+        let config = MarketMakerConfig {
+            spread_ticks: 1.0,
+            tick_size: 0.1,
+            quote_size: 0.5,
+            ..Default::default()
+        };
+        let mut mm = MarketMaker::new(config);
+        let position = Position::new();
+        let mut out = Vec::with_capacity(4);
+
+        mm.on_market_data_into(&create_test_snapshot(100.0, 100.1), &position, &mut out);
+        assert!(out.is_empty());
+        mm.on_market_data_into(&create_test_snapshot(99.5, 99.8), &position, &mut out);
+        assert_eq!(out.len(), 1);
+        assert!((out[0].price - 99.9).abs() < 1e-6);
+
+        let ptr = out.as_ptr();
+        out.clear();
+        mm.on_market_data_into(&create_test_snapshot(99.5, 99.8), &position, &mut out);
+        assert_eq!(out.as_ptr(), ptr);
+    }
+
     #[test]
     fn test_passive_ask_fill_when_market_moves_up() {
         let config = MarketMakerConfig {
@@ -392,6 +843,225 @@ mod tests {
         assert!((trades[0].price - 100.2).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_quote_throttle_limits_requotes() {
+        let config = MarketMakerConfig {
+            tick_size: 0.1,
+            trend_filter_ticks: 0.0,
+            max_quotes_per_sec: 4,
+            ..Default::default()
+        };
+        let mut mm = MarketMaker::new(config);
+        let position = Position::new();
+
+        for i in 0..20 {
+            let bid = 100.0 + i as f64;
+            let mut snapshot = create_test_snapshot(bid, bid + 0.1);
+            snapshot.timestamp_us = i * 100_000;
+            mm.on_market_data(&snapshot, &position);
+        }
+
+        let stats = mm.stats();
+        assert_eq!(stats.quotes_placed, 8);
+        assert_eq!(stats.quotes_throttled, 32);
+    }
+
+    #[test]
+    fn test_min_quote_lifetime_reduces_churn() {
+        let run = |min_quote_lifetime_us| {
+            let mut mm = MarketMaker::new(MarketMakerConfig {
+                tick_size: 0.1,
+                trend_filter_ticks: 0.0,
+                min_quote_lifetime_us,
+                ..Default::default()
+            });
+            let position = Position::new();
+            for i in 0..10 {
+                let bid = 100.0 + (i % 2) as f64 * 0.3;
+                let mut snapshot = create_test_snapshot(bid, bid + 0.5);
+                snapshot.timestamp_us = i * 1_000;
+                mm.on_market_data(&snapshot, &position);
+            }
+            mm
+        };
+
+        let churned = run(0);
+        let steady = run(5_000);
+        assert_eq!(churned.stats().quotes_placed, 20);
+        assert_eq!(steady.stats().quotes_placed, 4);
+        assert_eq!(steady.stats().quotes_held, 8);
+    }
+
+    #[test]
+    fn test_post_only_never_crosses() {
+        let config = MarketMakerConfig {
+            spread_ticks: -2.0,
+            tick_size: 0.1,
+            post_only: true,
+            ..Default::default()
+        };
+        let mut mm = MarketMaker::new(config.clone());
+        let position = Position::new();
+        assert!(mm.on_market_data(&create_test_snapshot(100.0, 100.1), &position).is_empty());
+        assert!(mm.orders.orders().is_empty());
+
+        let mut taker = MarketMaker::new(MarketMakerConfig {
+            post_only: false,
+            ..config
+        });
+        assert_eq!(taker.on_market_data(&create_test_snapshot(100.0, 100.1), &position).len(), 2);
+    }
+
+    #[test]
+    fn test_inventory_band_sizes() {
+        let config = MarketMakerConfig {
+            quote_size: 0.2,
+            inventory_band: Some(InventoryBand {
+                target: 0.0,
+                half_width: 0.3,
+                size_multiplier: 2.0,
+            }),
+            ..Default::default()
+        };
+        let mut mm = MarketMaker::new(config);
+        let mut position = Position::new();
+
+        mm.on_market_data(&create_test_snapshot(100.0, 100.1), &position);
+        assert_eq!(mm.orders.first(Side::Bid).unwrap().quantity, 0.2);
+        assert_eq!(mm.orders.first(Side::Ask).unwrap().quantity, 0.2);
+
+        position.execute_trade(Trade::new(Side::Bid, 100.0, 0.4, 0));
+        mm.on_market_data(&create_test_snapshot(100.0, 100.1), &position);
+        assert!((mm.orders.first(Side::Bid).unwrap().quantity - 0.1).abs() < 1e-12);
+        assert!((mm.orders.first(Side::Ask).unwrap().quantity - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reference_price_centers_quotes() {
+        let mut snapshot = create_test_snapshot(100.0, 101.0);
+        snapshot.bid_qty_1 = 3.0;
+        let position = Position::new();
+        let quotes = |reference_price| {
+            let mut mm = MarketMaker::new(MarketMakerConfig {
+                spread_ticks: 0.0,
+                tick_size: 0.1,
+                reference_price,
+                ..Default::default()
+            });
+            mm.on_market_data(&snapshot, &position);
+            (mm.orders.first(Side::Bid).unwrap().price, mm.orders.first(Side::Ask).unwrap().price)
+        };
+
+        assert_eq!(quotes(ReferencePrice::Touch), (100.0, 101.0));
+        assert_eq!(quotes(ReferencePrice::Mid), (100.0, 101.0));
+        assert_eq!(quotes(ReferencePrice::Microprice), (100.25, 101.25));
+        assert_eq!(quotes(ReferencePrice::FairValue { levels: 1, decay: 0.5 }), (100.25, 101.25));
+
+        struct Fixed;
+        impl Signal for Fixed {
+            fn name(&self) -> &str {
+                "fixed"
+            }
+            fn update(&mut self, _snapshot: &L2Snapshot) -> Option<f64> {
+                Some(99.0)
+            }
+        }
+        let mut mm = MarketMaker::new(MarketMakerConfig {
+            spread_ticks: 0.0,
+            ..Default::default()
+        })
+        .with_reference_signal(Box::new(Fixed));
+        mm.on_market_data(&snapshot, &position);
+        assert_eq!(mm.orders.first(Side::Bid).unwrap().price, 98.5);
+    }
+
+    #[test]
+    fn test_loss_backoff_pauses_after_losing_fills() {
+        let run = |loss_backoff| {
+            let mut mm = MarketMaker::new(MarketMakerConfig {
+                tick_size: 0.1,
+                trend_filter_ticks: 0.0,
+                max_position: 100.0,
+                loss_backoff,
+                ..Default::default()
+            });
+            let position = Position::new();
+            let mut trades = 0;
+            for i in 0..40 {
+                let bid = 100.0 - i as f64 * 0.5;
+                trades += mm.on_market_data(&create_test_snapshot(bid, bid + 0.1), &position).len();
+            }
+            (trades, mm.stats().backoff_triggers)
+        };
+
+        let (baseline_trades, baseline_triggers) = run(None);
+        let (trades, triggers) = run(Some(LossBackoff {
+            losing_fills: 2,
+            markout_snapshots: 1,
+            backoff_snapshots: 10,
+            widen_ticks: 0.0,
+        }));
+
+        assert_eq!(baseline_triggers, 0);
+        assert!(triggers > 0);
+        assert!(trades < baseline_trades);
+    }
+
+    #[test]
+    fn test_sizer_sets_quote_quantity() {
+        let config = MarketMakerConfig {
+            spread_ticks: 1.0,
+            tick_size: 0.1,
+            sizer: Some(Sizer::FixedNotional { notional: 1_000.0 }),
+            ..Default::default()
+        };
+        let mut mm = MarketMaker::new(config);
+        let position = Position::new();
+
+        mm.on_market_data(&create_test_snapshot(100.0, 100.1), &position);
+        let trades = mm.on_market_data(&create_test_snapshot(99.5, 99.8), &position);
+        assert_eq!(trades.len(), 1);
+        assert!((trades[0].quantity - 1_000.0 / 100.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_instrument_grid_rounds_quotes() {
+        let mut mm = MarketMaker::new(MarketMakerConfig {
+            tick_size: 0.1,
+            sizer: Some(Sizer::FixedNotional { notional: 1_000.0 }),
+            instrument: Some(Instrument::new(0.25, 0.01, 0.0)),
+            ..Default::default()
+        });
+        mm.on_market_data(&create_test_snapshot(100.1, 100.2), &Position::new());
+
+        let bid = mm.orders.first(Side::Bid).unwrap();
+        let ask = mm.orders.first(Side::Ask).unwrap();
+        assert_eq!((bid.price, ask.price), (100.0, 100.25));
+        assert!((bid.quantity - 9.98).abs() < 1e-9);
+        assert!(mm.orders.grid_report().mismatches() > 0);
+    }
+
+    #[test]
+    fn test_volatility_widens_quotes() {
+        let config = MarketMakerConfig {
+            tick_size: 0.1,
+            volatility_spread_factor: 2.0,
+            ..Default::default()
+        };
+        let mut mm = MarketMaker::new(config);
+        let position = Position::new();
+        let base_bid = 100.0 - 0.5 * 0.1;
+        assert!((mm.calculate_bid_price(100.0, 0.0) - base_bid).abs() < 1e-9);
+
+        for i in 0..50 {
+            let bid = if i % 2 == 0 { 100.0 } else { 101.0 };
+            mm.on_market_data(&create_test_snapshot(bid, bid + 0.1), &position);
+        }
+
+        assert!(mm.calculate_bid_price(100.0, 0.0) < base_bid - 0.1);
+        assert!(mm.calculate_ask_price(100.1, 0.0) > 100.1 + 0.05 + 0.1);
+    }
+
     #[test]
     fn test_hedge_inventory_reduces_long() {
         let config = MarketMakerConfig {
@@ -405,7 +1075,7 @@ mod tests {
         position.quantity = 0.8; 
 
         
-        let trades = mm.on_market_data(&create_test_snapshot(100.0, 100.1), &position);
+        let _trades = mm.on_market_data(&create_test_snapshot(100.0, 100.1), &position);
         
         
         
@@ -444,4 +1114,77 @@ mod tests {
         
         assert!((trades[0].quantity - 0.2).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_hedge_applies_self_trade_prevention() {
+        let snapshot = create_test_snapshot(100.0, 100.1);
+        let hedge = |self_trade_prevention| {
+            let mut mm = MarketMaker::new(MarketMakerConfig {
+                max_position: 1.0,
+                quote_size: 0.2,
+                hedge_inventory_ratio: 0.5,
+                self_trade_prevention,
+                ..Default::default()
+            });
+            mm.set_quote_tracking(true);
+            mm.orders.place(Side::Bid, 100.0, 0.1, 0).unwrap();
+            let mut trades = Vec::new();
+            mm.hedge_inventory(&snapshot, 0.8, &mut trades);
+            let mut events = Vec::new();
+            mm.drain_quote_events(&mut events);
+            assert!(mm.orders.orders().is_empty());
+            assert!(events.iter().all(|e| e.kind == QuoteEventKind::Cancelled && e.side == Side::Bid));
+            (trades.iter().map(|t| t.quantity).sum::<f64>(), events.len())
+        };
+
+        assert_eq!(hedge(SelfTradePrevention::Off), (0.2, 1));
+        assert_eq!(hedge(SelfTradePrevention::CancelResting), (0.2, 1));
+        assert_eq!(hedge(SelfTradePrevention::CancelAggressing), (0.0, 1));
+        let (filled, cancelled) = hedge(SelfTradePrevention::Decrement);
+        assert!((filled - 0.1).abs() < 1e-12);
+        assert_eq!(cancelled, 1);
+    }
+
+    #[test]
+    fn test_lean_variant_matches_equivalent_runtime_config() {
+        let config = MarketMakerConfig {
+            spread_ticks: 0.5,
+            tick_size: 0.1,
+            trend_filter_ticks: 0.0,
+            inventory_skew_ticks: 0.0,
+            hedge_inventory_ratio: f64::INFINITY,
+            ..Default::default()
+        };
+        let mut runtime = MarketMaker::new(config.clone());
+        let mut lean = LeanMarketMaker::new(config);
+        let mut runtime_position = Position::new();
+        let mut lean_position = Position::new();
+
+        for i in 0..200 {
+            let bid = 100.0 + (i as f64 * 0.35).sin() * 0.8;
+            let snapshot = create_test_snapshot(bid, bid + 0.1);
+            let expected = runtime.on_market_data(&snapshot, &runtime_position);
+            let actual = lean.on_market_data(&snapshot, &lean_position);
+            assert_eq!(actual.len(), expected.len());
+            for (a, e) in actual.iter().zip(&expected) {
+                assert_eq!((a.side, a.price, a.quantity), (e.side, e.price, e.quantity));
+            }
+            expected.into_iter().for_each(|t| runtime_position.execute_trade(t));
+            actual.into_iter().for_each(|t| lean_position.execute_trade(t));
+        }
+        assert!(runtime.stats().trades_generated > 0);
+        assert_eq!(runtime.stats().trades_generated, lean.stats().trades_generated);
+    }
+
+    #[test]
+    fn test_lean_variant_rejects_compiled_out_options() {
+        let lean = LeanMarketMaker::default_config();
+        assert!(LeanMarketMaker::try_new(lean.clone()).is_ok());
+        assert!(LeanMarketMaker::try_new(MarketMakerConfig::default()).is_err());
+        assert_eq!(LeanMarketMaker::new(MarketMakerConfig::default()).config.hedge_inventory_ratio, f64::INFINITY);
+        assert!(LeanMarketMaker::try_new(MarketMakerConfig { trend_filter_ticks: 0.5, ..lean.clone() }).is_err());
+        assert!(LeanMarketMaker::try_new(MarketMakerConfig { hedge_inventory_ratio: 0.5, ..lean.clone() }).is_err());
+        assert!(LeanMarketMaker::try_new(MarketMakerConfig { inventory_skew_ticks: 0.5, ..lean }).is_err());
+        assert!(MarketMaker::try_new(MarketMakerConfig::default()).is_ok());
+    }
 }
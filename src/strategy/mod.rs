@@ -1,9 +1,22 @@
 
+pub mod breakout;
+pub mod cooldown;
+pub mod filtered;
+pub mod linear;
 pub mod market_maker;
 pub mod momentum;
+pub mod plugin;
+pub mod registry;
+pub mod scalper;
+pub mod triangular;
 
-use crate::types::{L2Snapshot, Trade};
-use crate::execution::Position;
+pub use cooldown::{CooldownConfig, CooldownStrategy};
+pub use filtered::{FilteredStrategy, RegimeFilter};
+pub use registry::StrategyFactory;
+
+use crate::types::{L2Snapshot, QuoteEvent, Side, Trade};
+use crate::execution::{AckEvent, PositionView};
+use crate::market_data::DerivedValues;
 
 
 
@@ -15,32 +28,160 @@ pub trait Strategy {
     fn on_market_data(
         &mut self,
         snapshot: &L2Snapshot,
-        position: &Position,
+        position: &dyn PositionView,
     ) -> Vec<Trade>;
 
     
+    
+    fn on_market_data_derived(
+        &mut self,
+        snapshot: &L2Snapshot,
+        _derived: &DerivedValues,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        self.on_market_data(snapshot, position)
+    }
+
+    
+    
+    
+    fn on_market_data_into(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView, out: &mut Vec<Trade>) {
+        out.extend(self.on_market_data(snapshot, position));
+    }
+
+    
+    fn on_market_data_derived_into(
+        &mut self,
+        snapshot: &L2Snapshot,
+        derived: &DerivedValues,
+        position: &dyn PositionView,
+        out: &mut Vec<Trade>,
+    ) {
+        out.extend(self.on_market_data_derived(snapshot, derived, position));
+    }
+
+    
+    
+    fn supports_batches(&self) -> bool {
+        false
+    }
+
+    
+    
+    
+    fn on_market_data_batch(
+        &mut self,
+        snapshots: &[L2Snapshot],
+        position: &dyn PositionView,
+        out: &mut Vec<(usize, Trade)>,
+    ) {
+        let mut trades = Vec::new();
+        for (offset, snapshot) in snapshots.iter().enumerate() {
+            self.on_market_data_into(snapshot, position, &mut trades);
+            out.extend(trades.drain(..).map(|trade| (offset, trade)));
+        }
+    }
+
+    
     fn name(&self) -> &str;
 
     
     fn stats(&self) -> StrategyStats;
+
+    
+    
+    fn set_quote_tracking(&mut self, _enabled: bool) {}
+
+    
+    fn drain_quote_events(&mut self, _events: &mut Vec<QuoteEvent>) {}
+
+    
+    fn drain_ack_events(&mut self, _events: &mut Vec<AckEvent>) {}
+
+    
+    
+    fn on_halt(&mut self, _halted: bool, _timestamp_us: u64) {}
+
+    
+    
+    fn debug_state(&self) -> String {
+        String::new()
+    }
 }
 
 
-#[derive(Debug, Clone)]
+
+pub(crate) fn retain_reducing(position_qty: f64, trades: Vec<Trade>) -> (Vec<Trade>, usize) {
+    let mut quantity = position_qty;
+    let mut allowed = Vec::new();
+    let mut blocked = 0;
+    for mut trade in trades {
+        let reducing = match trade.side {
+            Side::Bid => quantity < 0.0,
+            Side::Ask => quantity > 0.0,
+        };
+        let closable = if reducing { trade.quantity.min(quantity.abs()) } else { 0.0 };
+        if closable < trade.quantity {
+            blocked += 1;
+        }
+        if closable <= 0.0 {
+            continue;
+        }
+        trade.quantity = closable;
+        quantity += match trade.side {
+            Side::Bid => closable,
+            Side::Ask => -closable,
+        };
+        allowed.push(trade);
+    }
+    (allowed, blocked)
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct StrategyStats {
     pub name: String,
     pub updates_processed: usize,
     pub trades_generated: usize,
     pub quotes_placed: usize,
+    
+    pub quotes_throttled: usize,
+    
+    pub quotes_held: usize,
+    
+    pub backoff_triggers: usize,
+    
+    
+    pub custom: Vec<(String, f64)>,
 }
 
 impl StrategyStats {
+    pub fn with_metric(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.custom.push((name.into(), value));
+        self
+    }
+
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        self.custom.iter().find(|(n, _)| n == name).map(|(_, v)| *v)
+    }
+
     pub fn print(&self) {
         println!("\n📈 Strategy Statistics: {}", self.name);
         println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         println!("   Updates Processed: {}", self.updates_processed);
         println!("   Trades Generated:  {}", self.trades_generated);
         println!("   Quotes Placed:     {}", self.quotes_placed);
+        if self.quotes_throttled > 0 {
+            println!("   Quotes Throttled:  {}", self.quotes_throttled);
+        }
+        if self.quotes_held > 0 {
+            println!("   Quotes Held:       {}", self.quotes_held);
+        }
+        if self.backoff_triggers > 0 {
+            println!("   Backoff Triggers:  {}", self.backoff_triggers);
+        }
+        for (name, value) in &self.custom {
+            println!("   {:<18} {:.4}", format!("{}:", name), value);
+        }
         println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     }
 }
@@ -3,10 +3,74 @@
 
 
 use super::{Strategy, StrategyStats};
-use crate::execution::Position;
+use serde::{Deserialize, Serialize};
+use crate::execution::{PositionView, Sizer, SizingContext};
+use crate::features::{TradeCost, Volatility, VolatilityConfig};
+use crate::orderbook::OrderBook;
+use crate::market_data::DerivedValues;
 use crate::types::{L2Snapshot, Side, Trade};
 
-#[derive(Debug, Clone)]
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Pyramiding {
+    
+    pub step: f64,
+    pub max_adds: usize,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExitDistance {
+    Dollars { amount: f64 },
+    
+    Ticks { ticks: f64, tick_size: f64 },
+}
+
+impl ExitDistance {
+    
+    pub fn dollars(&self, quantity: f64) -> f64 {
+        match *self {
+            ExitDistance::Dollars { amount } => amount,
+            ExitDistance::Ticks { ticks, tick_size } => ticks * tick_size * quantity.abs(),
+        }
+    }
+}
+
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MomentumExits {
+    
+    pub trailing_stop: Option<ExitDistance>,
+    pub profit_target: Option<ExitDistance>,
+    pub max_holding_snapshots: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExitReason {
+    TrailingStop,
+    ProfitTarget,
+    TimeLimit,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OpenTrade {
+    opened_at: usize,
+    peak_unrealized: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PyramidState {
+    direction: Side,
+    units: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MomentumConfig {
     
     pub trigger_threshold: f64,
@@ -19,6 +83,20 @@ pub struct MomentumConfig {
 
     
     pub lookback: usize,
+
+    
+    
+    pub sizer: Option<Sizer>,
+    pub starting_capital: f64,
+
+    
+    
+    pub pyramiding: Option<Pyramiding>,
+
+    pub exits: MomentumExits,
+
+    
+    pub trade_cost: Option<TradeCost>,
 }
 
 impl Default for MomentumConfig {
@@ -28,6 +106,11 @@ impl Default for MomentumConfig {
             trade_size: 0.1,
             max_position: 2.0,
             lookback: 100,  
+            sizer: None,
+            starting_capital: 100_000.0,
+            pyramiding: None,
+            exits: MomentumExits::default(),
+            trade_cost: None,
         }
     }
 }
@@ -42,6 +125,19 @@ pub struct MomentumStrategy {
     updates_processed: usize,
     trades_generated: usize,
     signals_generated: usize,
+    momentum_abs_sum: f64,
+    momentum_samples: usize,
+
+    volatility: Volatility,
+    pyramid: Option<PyramidState>,
+    scale_ins: usize,
+    scale_outs: usize,
+    open_trade: Option<OpenTrade>,
+    trailing_stop_exits: usize,
+    profit_target_exits: usize,
+    time_exits: usize,
+    book: OrderBook<'static>,
+    cost_skipped: usize,
 }
 
 impl MomentumStrategy {
@@ -52,10 +148,35 @@ impl MomentumStrategy {
             updates_processed: 0,
             trades_generated: 0,
             signals_generated: 0,
+            momentum_abs_sum: 0.0,
+            momentum_samples: 0,
+            volatility: Volatility::new(VolatilityConfig::default()),
+            pyramid: None,
+            scale_ins: 0,
+            scale_outs: 0,
+            open_trade: None,
+            trailing_stop_exits: 0,
+            profit_target_exits: 0,
+            time_exits: 0,
+            book: OrderBook::new(),
+            cost_skipped: 0,
         }
     }
 
     
+    fn trade_size(&self, position: &dyn PositionView, mid_price: f64, momentum: f64) -> f64 {
+        let Some(sizer) = self.config.sizer else {
+            return self.config.trade_size;
+        };
+        sizer.size(&SizingContext {
+            equity: position.equity(self.config.starting_capital, mid_price),
+            price: mid_price,
+            volatility_bps: self.volatility.ewma().unwrap_or(0.0),
+            edge_bps: momentum.abs() / mid_price * 10_000.0,
+        })
+    }
+
+    
     fn calculate_momentum(&self) -> Option<f64> {
         if self.price_history.len() < self.config.lookback {
             return None;
@@ -76,55 +197,224 @@ impl MomentumStrategy {
     fn should_sell(&self, position_qty: f64, momentum: f64) -> bool {
         momentum < -self.config.trigger_threshold && position_qty > -self.config.max_position
     }
-}
 
-impl Strategy for MomentumStrategy {
-    fn on_market_data(
+    
+    
+    
+    fn pyramid_trade(
         &mut self,
+        pyramiding: Pyramiding,
         snapshot: &L2Snapshot,
-        position: &Position,
+        position: &dyn PositionView,
+        momentum: f64,
+        unit_size: f64,
+    ) -> Option<Trade> {
+        if position.is_flat() {
+            self.pyramid = None;
+        }
+        let threshold = self.config.trigger_threshold;
+        let level = |units: usize| threshold + units as f64 * pyramiding.step;
+        let trade = |side: Side, quantity: f64| {
+            let price = match side {
+                Side::Bid => snapshot.best_ask(),
+                Side::Ask => snapshot.best_bid(),
+            };
+            Trade::new(side, price, quantity, snapshot.timestamp_us)
+        };
+        let entry_side = if momentum > self.config.trigger_threshold {
+            Some(Side::Bid)
+        } else if momentum < -self.config.trigger_threshold {
+            Some(Side::Ask)
+        } else {
+            None
+        };
+
+        let Some(state) = self.pyramid else {
+            let side = entry_side?;
+            if !self.cost_ok(side, unit_size, momentum) {
+                return None;
+            }
+            self.pyramid = Some(PyramidState { direction: side, units: 1 });
+            return Some(trade(side, unit_size.min(self.config.max_position)));
+        };
+
+        let exit_side = match state.direction {
+            Side::Bid => Side::Ask,
+            Side::Ask => Side::Bid,
+        };
+        let strength = match state.direction {
+            Side::Bid => momentum,
+            Side::Ask => -momentum,
+        };
+
+        if entry_side == Some(exit_side) {
+            if !self.cost_ok(exit_side, unit_size, momentum) {
+                return None;
+            }
+            self.pyramid = Some(PyramidState { direction: exit_side, units: 1 });
+            return Some(trade(exit_side, position.quantity().abs() + unit_size.min(self.config.max_position)));
+        }
+
+        let room = self.config.max_position - position.quantity().abs();
+        if state.units <= pyramiding.max_adds && strength > level(state.units) && room > 1e-10 {
+            if !self.cost_ok(state.direction, unit_size.min(room), momentum) {
+                return None;
+            }
+            self.pyramid = Some(PyramidState { units: state.units + 1, ..state });
+            self.scale_ins += 1;
+            return Some(trade(state.direction, unit_size.min(room)));
+        }
+
+        if strength < level(state.units - 1) - pyramiding.step {
+            let quantity = position.quantity().abs() / state.units as f64;
+            self.pyramid = (state.units > 1).then_some(PyramidState { units: state.units - 1, ..state });
+            self.scale_outs += 1;
+            return Some(trade(exit_side, quantity));
+        }
+
+        None
+    }
+
+    
+    fn cost_ok(&mut self, side: Side, quantity: f64, momentum: f64) -> bool {
+        let Some(cost) = self.config.trade_cost else {
+            return true;
+        };
+        let Some(mid) = self.book.snapshot().map(|s| s.mid_price()) else {
+            return true;
+        };
+        let edge_bps = momentum.abs() / mid * 10_000.0;
+        let accepted = cost.accepts(edge_bps, &self.book, side, quantity);
+        if !accepted {
+            self.cost_skipped += 1;
+        }
+        accepted
+    }
+
+    
+    fn check_exit(&mut self, position: &dyn PositionView, mid_price: f64) -> Option<ExitReason> {
+        if position.is_flat() {
+            self.open_trade = None;
+            return None;
+        }
+        let unrealized = position.unrealized_pnl(mid_price);
+        let open = self.open_trade.get_or_insert(OpenTrade {
+            opened_at: self.updates_processed - 1,
+            peak_unrealized: unrealized,
+        });
+        open.peak_unrealized = open.peak_unrealized.max(unrealized);
+        let exits = self.config.exits;
+
+        if exits.profit_target.is_some_and(|target| unrealized >= target.dollars(position.quantity())) {
+            return Some(ExitReason::ProfitTarget);
+        }
+        if exits
+            .trailing_stop
+            .is_some_and(|stop| open.peak_unrealized - unrealized >= stop.dollars(position.quantity()))
+        {
+            return Some(ExitReason::TrailingStop);
+        }
+        if exits
+            .max_holding_snapshots
+            .is_some_and(|limit| self.updates_processed - open.opened_at >= limit)
+        {
+            return Some(ExitReason::TimeLimit);
+        }
+        None
+    }
+
+    fn process_update(
+        &mut self,
+        snapshot: &L2Snapshot,
+        mid_price: f64,
+        position: &dyn PositionView,
     ) -> Vec<Trade> {
         self.updates_processed += 1;
 
         let mut trades = Vec::new();
 
+        if self.config.trade_cost.is_some() {
+            self.book.update(snapshot.clone());
+        }
+
         
-        let mid_price = (snapshot.best_bid() + snapshot.best_ask()) / 2.0;
 
         
         self.price_history.push(mid_price);
+        if self.config.sizer.is_some_and(|s| s.needs_volatility()) {
+            self.volatility.update(mid_price);
+        }
 
         
         if self.price_history.len() > self.config.lookback + 100 {
             self.price_history.remove(0);
         }
 
+        if let Some(reason) = self.check_exit(position, mid_price) {
+            let (side, price) = if position.is_long() {
+                (Side::Ask, snapshot.best_bid())
+            } else {
+                (Side::Bid, snapshot.best_ask())
+            };
+            trades.push(Trade::new(side, price, position.quantity().abs(), snapshot.timestamp_us));
+            self.trades_generated += 1;
+            self.open_trade = None;
+            self.pyramid = None;
+            match reason {
+                ExitReason::TrailingStop => self.trailing_stop_exits += 1,
+                ExitReason::ProfitTarget => self.profit_target_exits += 1,
+                ExitReason::TimeLimit => self.time_exits += 1,
+            }
+            return trades;
+        }
+
         
         let momentum = match self.calculate_momentum() {
             Some(m) => m,
             None => return trades,  
         };
+        self.momentum_abs_sum += momentum.abs();
+        self.momentum_samples += 1;
+
+        let position_qty = position.quantity();
+        let trade_size = self.trade_size(position, mid_price, momentum);
+        if trade_size <= 0.0 {
+            return trades;
+        }
 
-        let position_qty = position.quantity;
+        if let Some(pyramiding) = self.config.pyramiding {
+            if let Some(trade) = self.pyramid_trade(pyramiding, snapshot, position, momentum, trade_size) {
+                trades.push(trade);
+                self.trades_generated += 1;
+                self.signals_generated += 1;
+            }
+            return trades;
+        }
 
         
         if self.should_buy(position_qty, momentum) {
+            if position_qty >= 0.0 && !self.cost_ok(Side::Bid, trade_size, momentum) {
+                return trades;
+            }
             
             let trade = Trade::new(
                 Side::Bid,
                 snapshot.best_ask(),  
-                self.config.trade_size,
+                trade_size,
                 snapshot.timestamp_us,
             );
             trades.push(trade);
             self.trades_generated += 1;
             self.signals_generated += 1;
         } else if self.should_sell(position_qty, momentum) {
+            if position_qty <= 0.0 && !self.cost_ok(Side::Ask, trade_size, momentum) {
+                return trades;
+            }
             
             let trade = Trade::new(
                 Side::Ask,
                 snapshot.best_bid(),  
-                self.config.trade_size,
+                trade_size,
                 snapshot.timestamp_us,
             );
             trades.push(trade);
@@ -134,6 +424,26 @@ impl Strategy for MomentumStrategy {
 
         trades
     }
+}
+
+impl Strategy for MomentumStrategy {
+    fn on_market_data(
+        &mut self,
+        snapshot: &L2Snapshot,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        let mid_price = (snapshot.best_bid() + snapshot.best_ask()) / 2.0;
+        self.process_update(snapshot, mid_price, position)
+    }
+
+    fn on_market_data_derived(
+        &mut self,
+        snapshot: &L2Snapshot,
+        derived: &DerivedValues,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        self.process_update(snapshot, derived.mid, position)
+    }
 
     fn name(&self) -> &str {
         "Momentum Strategy"
@@ -145,6 +455,161 @@ impl Strategy for MomentumStrategy {
             updates_processed: self.updates_processed,
             trades_generated: self.trades_generated,
             quotes_placed: self.signals_generated,
+            ..Default::default()
+        }
+        .with_metric("signals_generated", self.signals_generated as f64)
+        .with_metric("avg_abs_momentum", self.momentum_abs_sum / self.momentum_samples.max(1) as f64)
+        .with_metric("scale_ins", self.scale_ins as f64)
+        .with_metric("scale_outs", self.scale_outs as f64)
+        .with_metric("trailing_stop_exits", self.trailing_stop_exits as f64)
+        .with_metric("profit_target_exits", self.profit_target_exits as f64)
+        .with_metric("time_exits", self.time_exits as f64)
+        .with_metric("cost_skipped", self.cost_skipped as f64)
+    }
+
+    fn debug_state(&self) -> String {
+        let momentum = match self.calculate_momentum() {
+            Some(m) => format!("{:.4}", m),
+            None => "warming up".to_string(),
+        };
+        format!(
+            "price history: {} / {}\nmomentum: {}\nsignals: {}",
+            self.price_history.len(),
+            self.config.lookback,
+            momentum,
+            self.signals_generated
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::Position;
+    use crate::types::PriceLevel;
+
+    #[test]
+    fn test_custom_metrics() {
+        let mut strategy = MomentumStrategy::new(MomentumConfig {
+            trigger_threshold: 0.5,
+            lookback: 2,
+            ..Default::default()
+        });
+        let position = Position::new();
+        for i in 0..5 {
+            let bid = 100.0 + i as f64;
+            let snapshot = L2Snapshot::from_levels(0, i, String::new(), &[PriceLevel::new(bid, 1.0)], &[PriceLevel::new(bid + 0.1, 1.0)]);
+            strategy.on_market_data(&snapshot, &position);
         }
+
+        let stats = strategy.stats();
+        assert_eq!(stats.metric("signals_generated"), Some(4.0));
+        assert_eq!(stats.metric("avg_abs_momentum"), Some(1.0));
+        assert_eq!(stats.metric("missing"), None);
+    }
+
+    #[test]
+    fn test_pyramiding_scales_in_and_out() {
+        let mut strategy = MomentumStrategy::new(MomentumConfig {
+            trigger_threshold: 1.0,
+            trade_size: 1.0,
+            max_position: 10.0,
+            lookback: 2,
+            pyramiding: Some(Pyramiding { step: 1.0, max_adds: 2 }),
+            ..Default::default()
+        });
+        let mut position = Position::new();
+        let mut quantities = Vec::new();
+        let mids = [100.0, 100.0, 101.5, 104.0, 107.5, 111.0, 112.5, 113.0, 112.5, 112.5];
+        for (i, &mid) in mids.iter().enumerate() {
+            let snapshot = L2Snapshot::from_levels(0, i as u64, String::new(), &[PriceLevel::new(mid, 1.0)], &[PriceLevel::new(mid, 1.0)]);
+            for trade in strategy.on_market_data(&snapshot, &position) {
+                position.execute_trade(trade);
+            }
+            quantities.push(position.quantity);
+        }
+
+        assert_eq!(quantities, vec![0.0, 0.0, 1.0, 2.0, 3.0, 3.0, 2.0, 1.0, 0.0, 0.0]);
+        let stats = strategy.stats();
+        assert_eq!(stats.metric("scale_ins"), Some(2.0));
+        assert_eq!(stats.metric("scale_outs"), Some(3.0));
+    }
+
+    fn run_exits(exits: MomentumExits, mids: &[f64]) -> (Vec<f64>, StrategyStats) {
+        let mut strategy = MomentumStrategy::new(MomentumConfig {
+            trigger_threshold: 0.5,
+            trade_size: 1.0,
+            max_position: 1.0,
+            lookback: 2,
+            exits,
+            ..Default::default()
+        });
+        let mut position = Position::new();
+        let mut quantities = Vec::new();
+        for (i, &mid) in mids.iter().enumerate() {
+            let snapshot = L2Snapshot::from_levels(0, i as u64, String::new(), &[PriceLevel::new(mid, 1.0)], &[PriceLevel::new(mid, 1.0)]);
+            for trade in strategy.on_market_data(&snapshot, &position) {
+                position.execute_trade(trade);
+            }
+            quantities.push(position.quantity);
+        }
+        (quantities, strategy.stats())
+    }
+
+    #[test]
+    fn test_unrealized_pnl_exits() {
+        let (quantities, stats) = run_exits(
+            MomentumExits {
+                trailing_stop: Some(ExitDistance::Ticks { ticks: 4.0, tick_size: 0.5 }),
+                ..Default::default()
+            },
+            &[100.0, 101.0, 101.0, 104.0, 104.0, 102.0, 102.0],
+        );
+        assert_eq!(quantities, vec![0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(stats.metric("trailing_stop_exits"), Some(1.0));
+
+        let (quantities, stats) = run_exits(
+            MomentumExits {
+                profit_target: Some(ExitDistance::Dollars { amount: 2.5 }),
+                ..Default::default()
+            },
+            &[100.0, 101.0, 101.0, 104.0, 104.0],
+        );
+        assert_eq!(quantities, vec![0.0, 1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(stats.metric("profit_target_exits"), Some(1.0));
+
+        let (quantities, stats) = run_exits(
+            MomentumExits {
+                max_holding_snapshots: Some(2),
+                ..Default::default()
+            },
+            &[100.0, 101.0, 101.0, 101.0, 101.0],
+        );
+        assert_eq!(quantities, vec![0.0, 1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(stats.metric("time_exits"), Some(1.0));
+    }
+
+    #[test]
+    fn test_trade_cost_skips_negative_edge() {
+        let run = |trade_cost: Option<TradeCost>| {
+            let mut strategy = MomentumStrategy::new(MomentumConfig {
+                trigger_threshold: 0.005,
+                trade_size: 1.0,
+                max_position: 10.0,
+                lookback: 2,
+                trade_cost,
+                ..Default::default()
+            });
+            let position = Position::new();
+            let mut trades = 0;
+            for (i, mid) in [100.0, 100.01, 100.02, 100.5].into_iter().enumerate() {
+                let snapshot = L2Snapshot::from_levels(0, i as u64, String::new(), &[PriceLevel::new(mid - 0.01, 1.0)], &[PriceLevel::new(mid + 0.01, 1.0)]);
+                trades += strategy.on_market_data(&snapshot, &position).len();
+            }
+            (trades, strategy.stats().metric("cost_skipped"))
+        };
+
+        assert_eq!(run(None), (3, Some(0.0)));
+        assert_eq!(run(Some(TradeCost { fee_bps: 2.0, min_edge_bps: 0.0 })), (1, Some(2.0)));
     }
 }
@@ -0,0 +1,279 @@
+use super::{Strategy, StrategyStats};
+use crate::execution::PositionView;
+use crate::types::{L2Snapshot, Side, Trade};
+use std::any::Any;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::sync::Arc;
+
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+
+pub const PLUGIN_ENTRY_SYMBOL: &[u8] = b"zaphft_plugin_v1";
+
+const MAX_TRADES_PER_UPDATE: usize = 16;
+
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CSnapshot {
+    pub timestamp_us: u64,
+    pub bid_price: [f64; 10],
+    pub bid_qty: [f64; 10],
+    pub ask_price: [f64; 10],
+    pub ask_qty: [f64; 10],
+}
+
+impl CSnapshot {
+    pub fn from_snapshot(snapshot: &L2Snapshot) -> Self {
+        let mut c = Self {
+            timestamp_us: snapshot.timestamp_us,
+            bid_price: [0.0; 10],
+            bid_qty: [0.0; 10],
+            ask_price: [0.0; 10],
+            ask_qty: [0.0; 10],
+        };
+        for (i, level) in snapshot.bids().iter().enumerate() {
+            c.bid_price[i] = level.price;
+            c.bid_qty[i] = level.quantity;
+        }
+        for (i, level) in snapshot.asks().iter().enumerate() {
+            c.ask_price[i] = level.price;
+            c.ask_qty[i] = level.quantity;
+        }
+        c
+    }
+}
+
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CPosition {
+    pub quantity: f64,
+    pub avg_entry_price: f64,
+    pub realized_pnl: f64,
+}
+
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CTrade {
+    
+    pub side: u8,
+    pub price: f64,
+    pub quantity: f64,
+}
+
+
+
+
+
+#[repr(C)]
+pub struct PluginVTable {
+    pub abi_version: u32,
+    pub name: *const c_char,
+    pub create: extern "C" fn(params_json: *const c_char) -> *mut c_void,
+    pub destroy: extern "C" fn(state: *mut c_void),
+    pub on_market_data: extern "C" fn(
+        state: *mut c_void,
+        snapshot: *const CSnapshot,
+        position: *const CPosition,
+        out: *mut CTrade,
+        out_capacity: usize,
+    ) -> usize,
+}
+
+unsafe impl Sync for PluginVTable {}
+
+
+pub type PluginEntry = extern "C" fn() -> *const PluginVTable;
+
+
+
+
+pub struct PluginStrategy {
+    vtable: &'static PluginVTable,
+    state: *mut c_void,
+    name: String,
+    updates_processed: usize,
+    trades_generated: usize,
+    _library: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+
+unsafe impl Send for PluginStrategy {}
+
+impl PluginStrategy {
+    
+    
+    pub fn from_vtable(
+        vtable: &'static PluginVTable,
+        params_json: &str,
+        library: Option<Arc<dyn Any + Send + Sync>>,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            vtable.abi_version == PLUGIN_ABI_VERSION,
+            "plugin ABI version {} is not supported (expected {})",
+            vtable.abi_version,
+            PLUGIN_ABI_VERSION
+        );
+        let name = plugin_name(vtable);
+        let params = CString::new(params_json)?;
+        let state = (vtable.create)(params.as_ptr());
+        anyhow::ensure!(!state.is_null(), "plugin '{}' rejected its parameters", name);
+        Ok(Self {
+            vtable,
+            state,
+            name,
+            updates_processed: 0,
+            trades_generated: 0,
+            _library: library,
+        })
+    }
+}
+
+fn plugin_name(vtable: &PluginVTable) -> String {
+    if vtable.name.is_null() {
+        return "Plugin".to_string();
+    }
+    unsafe { CStr::from_ptr(vtable.name) }.to_string_lossy().into_owned()
+}
+
+impl Strategy for PluginStrategy {
+    fn on_market_data(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) -> Vec<Trade> {
+        self.updates_processed += 1;
+        let c_snapshot = CSnapshot::from_snapshot(snapshot);
+        let c_position = CPosition {
+            quantity: position.quantity(),
+            avg_entry_price: position.avg_entry_price(),
+            realized_pnl: position.realized_pnl(),
+        };
+        let mut out = [CTrade::default(); MAX_TRADES_PER_UPDATE];
+        let count = (self.vtable.on_market_data)(
+            self.state,
+            &c_snapshot,
+            &c_position,
+            out.as_mut_ptr(),
+            out.len(),
+        )
+        .min(out.len());
+
+        let trades: Vec<Trade> = out[..count]
+            .iter()
+            .filter(|t| t.quantity > 0.0)
+            .map(|t| {
+                let side = if t.side == 0 { Side::Bid } else { Side::Ask };
+                Trade::new(side, t.price, t.quantity, snapshot.timestamp_us)
+            })
+            .collect();
+        self.trades_generated += trades.len();
+        trades
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn stats(&self) -> StrategyStats {
+        StrategyStats {
+            name: self.name.clone(),
+            updates_processed: self.updates_processed,
+            trades_generated: self.trades_generated,
+            ..Default::default()
+        }
+    }
+}
+
+impl Drop for PluginStrategy {
+    fn drop(&mut self) {
+        (self.vtable.destroy)(self.state);
+    }
+}
+
+
+
+#[cfg(feature = "plugin")]
+pub fn load_plugin(path: &std::path::Path) -> anyhow::Result<(Arc<libloading::Library>, &'static PluginVTable)> {
+    use anyhow::Context;
+
+    let library = unsafe { libloading::Library::new(path) }
+        .with_context(|| format!("failed to load plugin {}", path.display()))?;
+    let vtable = unsafe {
+        let entry: libloading::Symbol<PluginEntry> = library
+            .get(PLUGIN_ENTRY_SYMBOL)
+            .with_context(|| format!("{} does not export zaphft_plugin_v1", path.display()))?;
+        entry()
+    };
+    anyhow::ensure!(!vtable.is_null(), "plugin {} returned a null vtable", path.display());
+    Ok((Arc::new(library), unsafe { &*vtable }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::Position;
+    use crate::types::PriceLevel;
+
+    struct TouchBuyer {
+        quantity: f64,
+    }
+
+    extern "C" fn create(params: *const c_char) -> *mut c_void {
+        let params = unsafe { CStr::from_ptr(params) }.to_str().unwrap_or("");
+        let quantity = match serde_json::from_str::<serde_json::Value>(params) {
+            Ok(value) => value["quantity"].as_f64().unwrap_or(1.0),
+            Err(_) => return std::ptr::null_mut(),
+        };
+        Box::into_raw(Box::new(TouchBuyer { quantity })) as *mut c_void
+    }
+
+    extern "C" fn destroy(state: *mut c_void) {
+        drop(unsafe { Box::from_raw(state as *mut TouchBuyer) });
+    }
+
+    extern "C" fn on_market_data(
+        state: *mut c_void,
+        snapshot: *const CSnapshot,
+        position: *const CPosition,
+        out: *mut CTrade,
+        out_capacity: usize,
+    ) -> usize {
+        let (state, snapshot, position) = unsafe { (&*(state as *const TouchBuyer), &*snapshot, &*position) };
+        if position.quantity > 0.0 || out_capacity == 0 {
+            return 0;
+        }
+        unsafe {
+            *out = CTrade {
+                side: 0,
+                price: snapshot.ask_price[0],
+                quantity: state.quantity,
+            };
+        }
+        1
+    }
+
+    static VTABLE: PluginVTable = PluginVTable {
+        abi_version: PLUGIN_ABI_VERSION,
+        name: c"Touch Buyer".as_ptr(),
+        create,
+        destroy,
+        on_market_data,
+    };
+
+    #[test]
+    fn test_plugin_strategy_roundtrip() {
+        let mut strategy = PluginStrategy::from_vtable(&VTABLE, r#"{"quantity": 0.5}"#, None).unwrap();
+        assert_eq!(strategy.name(), "Touch Buyer");
+
+        let snapshot = L2Snapshot::from_levels(0, 7, String::new(), &[PriceLevel::new(99.0, 1.0)], &[PriceLevel::new(101.0, 1.0)]);
+        let mut position = Position::new();
+        let trades = strategy.on_market_data(&snapshot, &position);
+        assert_eq!(trades.len(), 1);
+        assert_eq!((trades[0].side, trades[0].price, trades[0].quantity, trades[0].timestamp_us), (Side::Bid, 101.0, 0.5, 7));
+
+        position.execute_trade(trades[0].clone());
+        assert!(strategy.on_market_data(&snapshot, &position).is_empty());
+        assert_eq!(strategy.stats().updates_processed, 2);
+
+        assert!(PluginStrategy::from_vtable(&VTABLE, "not json", None).is_err());
+    }
+}
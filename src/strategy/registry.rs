@@ -0,0 +1,140 @@
+use super::breakout::BreakoutStrategy;
+use super::linear::LinearModelStrategy;
+use super::market_maker::MarketMaker;
+use super::momentum::MomentumStrategy;
+use super::scalper::ScalperStrategy;
+use super::Strategy;
+use crate::engine::StrategyJob;
+use anyhow::{anyhow, Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+type Constructor = Arc<dyn Fn(&Value) -> Result<Box<dyn Strategy + Send>> + Send + Sync>;
+
+
+
+
+
+#[derive(Clone, Default)]
+pub struct StrategyFactory {
+    constructors: BTreeMap<String, Constructor>,
+}
+
+impl StrategyFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    
+    pub fn with_builtins() -> Self {
+        let mut factory = Self::new();
+        factory.register_config("breakout", BreakoutStrategy::new);
+        factory.register_config("linear_model", LinearModelStrategy::new);
+        factory.register_config("market_maker", MarketMaker::new);
+        factory.register_config("momentum", MomentumStrategy::new);
+        factory.register_config("scalper", ScalperStrategy::new);
+        factory
+    }
+
+    pub fn register<F>(&mut self, name: &str, constructor: F) -> &mut Self
+    where
+        F: Fn(&Value) -> Result<Box<dyn Strategy + Send>> + Send + Sync + 'static,
+    {
+        self.constructors.insert(name.to_string(), Arc::new(constructor));
+        self
+    }
+
+    
+    
+    pub fn register_config<C, S>(&mut self, name: &str, new: fn(C) -> S) -> &mut Self
+    where
+        C: DeserializeOwned + 'static,
+        S: Strategy + Send + 'static,
+    {
+        let label = name.to_string();
+        self.register(name, move |params| {
+            let params = if params.is_null() { Value::Object(Default::default()) } else { params.clone() };
+            let config: C = serde_json::from_value(params)
+                .with_context(|| format!("invalid parameters for strategy '{}'", label))?;
+            Ok(Box::new(new(config)) as Box<dyn Strategy + Send>)
+        })
+    }
+
+    
+    
+    #[cfg(feature = "plugin")]
+    pub fn register_plugin(&mut self, path: &std::path::Path) -> Result<String> {
+        use super::plugin::{load_plugin, PluginStrategy};
+        use std::any::Any;
+
+        let (library, vtable) = load_plugin(path)?;
+        let probe = PluginStrategy::from_vtable(vtable, "{}", Some(library.clone() as Arc<dyn Any + Send + Sync>))?;
+        let name = probe.name().to_string();
+        drop(probe);
+        self.register(&name, move |params| {
+            let library = library.clone() as Arc<dyn Any + Send + Sync>;
+            let strategy = PluginStrategy::from_vtable(vtable, &params.to_string(), Some(library))?;
+            Ok(Box::new(strategy) as Box<dyn Strategy + Send>)
+        });
+        Ok(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.constructors.keys().map(String::as_str).collect()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.contains_key(name)
+    }
+
+    pub fn create(&self, name: &str, params: &Value) -> Result<Box<dyn Strategy + Send>> {
+        let constructor = self.constructors.get(name).ok_or_else(|| {
+            anyhow!("unknown strategy '{}' (available: {})", name, self.names().join(", "))
+        })?;
+        constructor(params)
+    }
+
+    pub fn create_from_json(&self, name: &str, params: &str) -> Result<Box<dyn Strategy + Send>> {
+        let params: Value = serde_json::from_str(params).context("strategy parameters are not valid JSON")?;
+        self.create(name, &params)
+    }
+
+    
+    
+    pub fn job(&self, name: &str, label: &str, params: Value) -> Result<StrategyJob> {
+        self.create(name, &params)?;
+        let constructor = Arc::clone(&self.constructors[name]);
+        Ok(StrategyJob::new(label, move || {
+            constructor(&params).expect("parameters were validated when the job was created")
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_builtin_construction() {
+        let factory = StrategyFactory::with_builtins();
+        assert_eq!(factory.names(), vec!["breakout", "linear_model", "market_maker", "momentum", "scalper"]);
+
+        let mm = factory.create("market_maker", &json!({ "spread_ticks": 2.0 })).unwrap();
+        assert_eq!(mm.name(), "Market Maker");
+        let momentum = factory.create_from_json("momentum", r#"{"lookback": 10}"#).unwrap();
+        assert_eq!(momentum.stats().updates_processed, 0);
+        assert!(factory.create("momentum", &Value::Null).is_ok());
+
+        let err = factory.create("market_maker", &json!({ "spread_ticks": "wide" })).err().unwrap();
+        assert!(err.to_string().contains("market_maker"));
+        let err = factory.create("arbitrage", &Value::Null).err().unwrap();
+        assert!(err.to_string().contains("available: breakout, linear_model, market_maker, momentum, scalper"));
+
+        let job = factory.job("momentum", "m10", json!({ "lookback": 10 })).unwrap();
+        assert_eq!(job.label, "m10");
+        assert!(factory.job("momentum", "bad", json!({ "lookback": -1 })).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use super::{Strategy, StrategyStats};
+use serde::{Deserialize, Serialize};
+use crate::execution::{Bracket, BracketExit, PositionView};
+use crate::types::{L2Snapshot, Side, Trade};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScalperConfig {
+    pub tick_size: f64,
+
+    
+    pub max_spread_ticks: f64,
+
+    
+    pub imbalance_threshold: f64,
+
+    pub take_profit_ticks: f64,
+    pub stop_loss_ticks: f64,
+    pub trade_size: f64,
+}
+
+impl Default for ScalperConfig {
+    fn default() -> Self {
+        Self {
+            tick_size: 0.05,
+            max_spread_ticks: 1.0,
+            imbalance_threshold: 0.3,
+            take_profit_ticks: 2.0,
+            stop_loss_ticks: 2.0,
+            trade_size: 0.1,
+        }
+    }
+}
+
+pub struct ScalperStrategy {
+    config: ScalperConfig,
+    bracket: Option<Bracket>,
+
+    updates_processed: usize,
+    trades_generated: usize,
+    entries: usize,
+    take_profits: usize,
+    stop_losses: usize,
+}
+
+impl ScalperStrategy {
+    pub fn new(config: ScalperConfig) -> Self {
+        Self {
+            config,
+            bracket: None,
+            updates_processed: 0,
+            trades_generated: 0,
+            entries: 0,
+            take_profits: 0,
+            stop_losses: 0,
+        }
+    }
+
+    pub fn bracket(&self) -> Option<&Bracket> {
+        self.bracket.as_ref()
+    }
+
+    
+    fn entry_side(&self, snapshot: &L2Snapshot) -> Option<Side> {
+        let max_spread = self.config.max_spread_ticks * self.config.tick_size + 1e-9;
+        if snapshot.spread() > max_spread {
+            return None;
+        }
+        let imbalance = snapshot.imbalance();
+        if imbalance >= self.config.imbalance_threshold {
+            Some(Side::Bid)
+        } else if imbalance <= -self.config.imbalance_threshold {
+            Some(Side::Ask)
+        } else {
+            None
+        }
+    }
+}
+
+impl Strategy for ScalperStrategy {
+    fn on_market_data(&mut self, snapshot: &L2Snapshot, position: &dyn PositionView) -> Vec<Trade> {
+        self.updates_processed += 1;
+
+        if position.is_flat() {
+            self.bracket = None;
+        }
+
+        let trade = if let Some(bracket) = self.bracket {
+            let Some((exit, trade)) = bracket.check(snapshot) else {
+                return Vec::new();
+            };
+            match exit {
+                BracketExit::TakeProfit => self.take_profits += 1,
+                BracketExit::StopLoss => self.stop_losses += 1,
+            }
+            self.bracket = None;
+            trade
+        } else if position.is_flat() {
+            let Some(side) = self.entry_side(snapshot) else {
+                return Vec::new();
+            };
+            let price = match side {
+                Side::Bid => snapshot.best_ask(),
+                Side::Ask => snapshot.best_bid(),
+            };
+            self.bracket = Some(Bracket::for_entry(
+                side,
+                price,
+                self.config.trade_size,
+                self.config.take_profit_ticks * self.config.tick_size,
+                self.config.stop_loss_ticks * self.config.tick_size,
+            ));
+            self.entries += 1;
+            Trade::new(side, price, self.config.trade_size, snapshot.timestamp_us)
+        } else {
+            return Vec::new();
+        };
+
+        self.trades_generated += 1;
+        vec![trade]
+    }
+
+    fn name(&self) -> &str {
+        "Scalper Strategy"
+    }
+
+    fn stats(&self) -> StrategyStats {
+        StrategyStats {
+            name: self.name().to_string(),
+            updates_processed: self.updates_processed,
+            trades_generated: self.trades_generated,
+            ..Default::default()
+        }
+        .with_metric("entries", self.entries as f64)
+        .with_metric("take_profits", self.take_profits as f64)
+        .with_metric("stop_losses", self.stop_losses as f64)
+    }
+
+    fn debug_state(&self) -> String {
+        match &self.bracket {
+            Some(b) => format!(
+                "exit side: {:?}\ntake profit: {:.4}\nstop loss: {:.4}",
+                b.exit_side, b.take_profit, b.stop_loss
+            ),
+            None => "flat".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::Position;
+    use crate::types::PriceLevel;
+
+    fn snapshot(ts: u64, bid: f64, ask: f64, bid_qty: f64, ask_qty: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(0, ts, String::new(), &[PriceLevel::new(bid, bid_qty)], &[PriceLevel::new(ask, ask_qty)])
+    }
+
+    #[test]
+    fn test_scalper_take_profit_and_stop() {
+        let mut strategy = ScalperStrategy::new(ScalperConfig {
+            tick_size: 0.5,
+            take_profit_ticks: 2.0,
+            stop_loss_ticks: 1.0,
+            trade_size: 1.0,
+            ..Default::default()
+        });
+        let mut position = Position::new();
+        let mut step = |strategy: &mut ScalperStrategy, snap: L2Snapshot| {
+            let trades = strategy.on_market_data(&snap, &position);
+            for trade in &trades {
+                position.execute_trade(trade.clone());
+            }
+            trades
+        };
+
+        assert!(step(&mut strategy, snapshot(0, 100.0, 101.0, 3.0, 1.0)).is_empty());
+
+        let entry = step(&mut strategy, snapshot(1, 100.0, 100.5, 3.0, 1.0));
+        assert_eq!((entry[0].side, entry[0].price), (Side::Bid, 100.5));
+        assert_eq!(strategy.bracket().map(|b| (b.take_profit, b.stop_loss)), Some((101.5, 100.0)));
+
+        assert!(step(&mut strategy, snapshot(2, 101.0, 101.5, 1.0, 1.0)).is_empty());
+        let exit = step(&mut strategy, snapshot(3, 101.5, 102.0, 1.0, 1.0));
+        assert_eq!((exit[0].side, exit[0].price), (Side::Ask, 101.5));
+
+        let entry = step(&mut strategy, snapshot(4, 100.0, 100.5, 1.0, 3.0));
+        assert_eq!((entry[0].side, entry[0].price), (Side::Ask, 100.0));
+        let exit = step(&mut strategy, snapshot(5, 100.0, 101.0, 1.0, 1.0));
+        assert_eq!((exit[0].side, exit[0].price), (Side::Bid, 101.0));
+
+        let stats = strategy.stats();
+        assert_eq!(stats.metric("take_profits"), Some(1.0));
+        assert_eq!(stats.metric("stop_losses"), Some(1.0));
+        assert!(position.is_flat());
+    }
+}
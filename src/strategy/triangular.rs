@@ -0,0 +1,198 @@
+use super::StrategyStats;
+use serde::{Deserialize, Serialize};
+use crate::types::{L2Snapshot, Side, Trade};
+
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TriangularConfig {
+    
+    pub fee_bps: f64,
+    pub min_edge_bps: f64,
+    
+    pub max_notional: f64,
+}
+
+impl Default for TriangularConfig {
+    fn default() -> Self {
+        Self {
+            fee_bps: 7.5,
+            min_edge_bps: 1.0,
+            max_notional: 1_000.0,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cycle {
+    
+    Forward,
+    
+    Reverse,
+}
+
+
+
+#[derive(Debug, Clone)]
+pub struct ArbGroup {
+    pub cycle: Cycle,
+    pub edge_bps: f64,
+    pub notional: f64,
+    pub legs: [Trade; 3],
+}
+
+impl ArbGroup {
+    
+    pub fn profit(&self) -> f64 {
+        self.notional * self.edge_bps / 10_000.0
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct TriangularArbitrage {
+    config: TriangularConfig,
+}
+
+impl TriangularArbitrage {
+    pub fn new(config: TriangularConfig) -> Self {
+        Self { config }
+    }
+
+    
+    pub fn cycle_edges_bps(&self, books: [&L2Snapshot; 3]) -> Option<(f64, f64)> {
+        let [ab, cb, cq] = books;
+        if [ab.best_ask(), cb.best_ask(), cq.best_ask()].iter().any(|p| *p <= 0.0) {
+            return None;
+        }
+        let fees = (1.0 - self.config.fee_bps / 10_000.0).powi(3);
+        let forward = cq.best_bid() / (ab.best_ask() * cb.best_ask()) * fees;
+        let reverse = ab.best_bid() * cb.best_bid() / cq.best_ask() * fees;
+        Some(((forward - 1.0) * 10_000.0, (reverse - 1.0) * 10_000.0))
+    }
+
+    
+    pub fn detect(&self, books: [&L2Snapshot; 3]) -> Option<ArbGroup> {
+        let (forward, reverse) = self.cycle_edges_bps(books)?;
+        let (cycle, edge_bps) = if forward >= reverse { (Cycle::Forward, forward) } else { (Cycle::Reverse, reverse) };
+        if edge_bps <= self.config.min_edge_bps {
+            return None;
+        }
+
+        let [ab, cb, cq] = books;
+        let timestamp_us = books.iter().map(|b| b.timestamp_us).max().unwrap_or(0);
+        let c_mid_quote = cq.mid_price();
+        let (notional, legs) = match cycle {
+            Cycle::Forward => {
+                let notional = self
+                    .config
+                    .max_notional
+                    .min(ab.ask_qty_1 * ab.best_ask())
+                    .min(cb.ask_qty_1 * c_mid_quote)
+                    .min(cq.bid_qty_1 * cq.best_bid());
+                let b_qty = notional / ab.best_ask();
+                let c_qty = b_qty / cb.best_ask();
+                (notional, [
+                    Trade::new(Side::Bid, ab.best_ask(), b_qty, timestamp_us),
+                    Trade::new(Side::Bid, cb.best_ask(), c_qty, timestamp_us),
+                    Trade::new(Side::Ask, cq.best_bid(), c_qty, timestamp_us),
+                ])
+            }
+            Cycle::Reverse => {
+                let notional = self
+                    .config
+                    .max_notional
+                    .min(cq.ask_qty_1 * cq.best_ask())
+                    .min(cb.bid_qty_1 * c_mid_quote)
+                    .min(ab.bid_qty_1 * ab.best_bid());
+                let c_qty = notional / cq.best_ask();
+                let b_qty = c_qty * cb.best_bid();
+                (notional, [
+                    Trade::new(Side::Bid, cq.best_ask(), c_qty, timestamp_us),
+                    Trade::new(Side::Ask, cb.best_bid(), c_qty, timestamp_us),
+                    Trade::new(Side::Ask, ab.best_bid(), b_qty, timestamp_us),
+                ])
+            }
+        };
+        if notional <= 0.0 {
+            return None;
+        }
+        Some(ArbGroup { cycle, edge_bps, notional, legs })
+    }
+}
+
+
+
+pub struct TriangularArbStrategy {
+    detector: TriangularArbitrage,
+    updates_processed: usize,
+    groups: usize,
+    expected_profit: f64,
+}
+
+impl TriangularArbStrategy {
+    pub fn new(config: TriangularConfig) -> Self {
+        Self {
+            detector: TriangularArbitrage::new(config),
+            updates_processed: 0,
+            groups: 0,
+            expected_profit: 0.0,
+        }
+    }
+
+    pub fn on_snapshot_set(&mut self, books: [&L2Snapshot; 3]) -> Option<ArbGroup> {
+        self.updates_processed += 1;
+        let group = self.detector.detect(books)?;
+        self.groups += 1;
+        self.expected_profit += group.profit();
+        Some(group)
+    }
+
+    pub fn stats(&self) -> StrategyStats {
+        StrategyStats {
+            name: "Triangular Arbitrage".to_string(),
+            updates_processed: self.updates_processed,
+            trades_generated: self.groups * 3,
+            ..Default::default()
+        }
+        .with_metric("arb_groups", self.groups as f64)
+        .with_metric("expected_profit", self.expected_profit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn book(bid: f64, ask: f64, qty: f64) -> L2Snapshot {
+        L2Snapshot::from_levels(0, 0, String::new(), &[PriceLevel::new(bid, qty)], &[PriceLevel::new(ask, qty)])
+    }
+
+    #[test]
+    fn test_detects_forward_cycle_after_fees() {
+        let btc_usdt = book(49_990.0, 50_000.0, 1.0);
+        let eth_btc = book(0.0499, 0.05, 10.0);
+        let eth_usdt = book(2_520.0, 2_521.0, 10.0);
+
+        let mut strategy = TriangularArbStrategy::new(TriangularConfig {
+            fee_bps: 10.0,
+            ..Default::default()
+        });
+        let group = strategy.on_snapshot_set([&btc_usdt, &eth_btc, &eth_usdt]).unwrap();
+
+        let expected = (2_520.0 / (50_000.0 * 0.05) * 0.999f64.powi(3) - 1.0) * 10_000.0;
+        assert_eq!(group.cycle, Cycle::Forward);
+        assert!((group.edge_bps - expected).abs() < 1e-9);
+        assert_eq!(group.notional, 1_000.0);
+        assert!((group.legs[0].quantity - 0.02).abs() < 1e-12);
+        assert!((group.legs[1].quantity - 0.4).abs() < 1e-9);
+        assert_eq!((group.legs[2].side, group.legs[2].quantity), (Side::Ask, group.legs[1].quantity));
+        assert_eq!(strategy.stats().metric("arb_groups"), Some(1.0));
+
+        let fair = book(2_499.0, 2_500.0, 10.0);
+        assert!(strategy.on_snapshot_set([&btc_usdt, &eth_btc, &fair]).is_none());
+    }
+}
@@ -0,0 +1,200 @@
+use serde::Serialize;
+use crate::market_data::{EstimatedTrade, TradeTape};
+use crate::types::{L2Snapshot, Side};
+use crate::utils::rng::SplitMix64;
+
+const US_PER_SEC: f64 = 1_000_000.0;
+
+
+
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HawkesConfig {
+    
+    pub baseline_per_sec: f64,
+    
+    pub alpha: f64,
+    
+    pub beta: f64,
+    
+    pub mean_size: f64,
+    pub seed: u64,
+}
+
+impl Default for HawkesConfig {
+    fn default() -> Self {
+        Self {
+            baseline_per_sec: 1.0,
+            alpha: 0.6,
+            beta: 1.0,
+            mean_size: 0.05,
+            seed: 42,
+        }
+    }
+}
+
+impl HawkesConfig {
+    
+    
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.beta.is_nan() || self.beta <= 0.0 {
+            anyhow::bail!("hawkes beta must be positive, got {}", self.beta);
+        }
+        if self.alpha.is_nan() || self.alpha < 0.0 || self.alpha >= self.beta {
+            anyhow::bail!("hawkes alpha {} must satisfy 0 <= alpha < beta ({}) for a stationary process", self.alpha, self.beta);
+        }
+        Ok(())
+    }
+
+    
+    pub fn branching_ratio(&self) -> f64 {
+        self.alpha / self.beta
+    }
+
+    
+    pub fn stationary_rate(&self) -> f64 {
+        self.baseline_per_sec / (1.0 - self.branching_ratio())
+    }
+
+    
+    
+    pub fn calibrate(snapshots: &[L2Snapshot], alpha: f64, beta: f64, seed: u64) -> Self {
+        let tape = TradeTape::from_snapshots(snapshots);
+        let duration_sec = match (snapshots.first(), snapshots.last()) {
+            (Some(first), Some(last)) => (last.timestamp_us.saturating_sub(first.timestamp_us)) as f64 / US_PER_SEC,
+            _ => 0.0,
+        };
+        let observed_rate = if duration_sec > 0.0 {
+            tape.len() as f64 / duration_sec
+        } else {
+            0.0
+        };
+        let volume: f64 = tape.trades().iter().map(|t| t.quantity).sum();
+        let defaults = Self::default();
+
+        Self {
+            baseline_per_sec: observed_rate * (1.0 - (alpha / beta).min(0.99)),
+            alpha,
+            beta,
+            mean_size: if tape.is_empty() { defaults.mean_size } else { volume / tape.len() as f64 },
+            seed,
+        }
+    }
+}
+
+
+
+
+
+
+pub struct HawkesSimulator {
+    config: HawkesConfig,
+}
+
+impl HawkesSimulator {
+    pub fn new(config: HawkesConfig) -> anyhow::Result<Self> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    pub fn config(&self) -> &HawkesConfig {
+        &self.config
+    }
+
+    
+    pub fn simulate(&self, snapshots: &[L2Snapshot]) -> Vec<EstimatedTrade> {
+        let (Some(first), Some(last)) = (snapshots.first(), snapshots.last()) else {
+            return Vec::new();
+        };
+        let mu = self.config.baseline_per_sec;
+        if mu <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut rng = SplitMix64::new(self.config.seed);
+        let horizon_sec = last.timestamp_us.saturating_sub(first.timestamp_us) as f64 / US_PER_SEC;
+        let mut t = 0.0;
+        let mut excitation = 0.0;
+        let mut arrivals = Vec::new();
+
+        loop {
+            let upper = mu + excitation;
+            let wait = rng.exponential(upper);
+            t += wait;
+            if t > horizon_sec {
+                break;
+            }
+            excitation *= (-self.config.beta * wait).exp();
+            if rng.next_f64() * upper > mu + excitation {
+                continue;
+            }
+            excitation += self.config.alpha;
+
+            let timestamp_us = first.timestamp_us + (t * US_PER_SEC) as u64;
+            let idx = snapshots.partition_point(|s| s.timestamp_us <= timestamp_us).saturating_sub(1);
+            let snapshot = &snapshots[idx];
+
+            let buy_prob = (1.0 + snapshot.imbalance()) / 2.0;
+            let (aggressor, price) = if rng.next_f64() < buy_prob {
+                (Side::Bid, snapshot.best_ask())
+            } else {
+                (Side::Ask, snapshot.best_bid())
+            };
+
+            arrivals.push(EstimatedTrade {
+                timestamp_us,
+                aggressor,
+                price,
+                quantity: rng.exponential(1.0 / self.config.mean_size),
+            });
+        }
+
+        arrivals
+    }
+
+    pub fn simulate_tape(&self, snapshots: &[L2Snapshot]) -> TradeTape {
+        TradeTape::from_trades(self.simulate(snapshots))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PriceLevel;
+
+    fn create_test_snapshot(timestamp_us: u64, mid: f64) -> L2Snapshot {
+        let (bid, ask) = (mid - 0.05, mid + 0.05);
+        let bids: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(bid - 0.1 * i as f64, 1.0)).collect();
+        let asks: Vec<PriceLevel> = (0..10).map(|i| PriceLevel::new(ask + 0.1 * i as f64, 1.0)).collect();
+        L2Snapshot::from_levels(0, timestamp_us, String::new(), &bids, &asks)
+    }
+
+    #[test]
+    fn test_arrival_rate_matches_stationary_rate() {
+        let snapshots: Vec<L2Snapshot> = (0..=2_000)
+            .map(|i| create_test_snapshot(i * 1_000_000, 100.0))
+            .collect();
+        let config = HawkesConfig {
+            baseline_per_sec: 2.0,
+            alpha: 0.5,
+            beta: 1.0,
+            ..Default::default()
+        };
+        let simulator = HawkesSimulator::new(config.clone()).unwrap();
+
+        let arrivals = simulator.simulate(&snapshots);
+        let rate = arrivals.len() as f64 / 2_000.0;
+        assert!((rate - config.stationary_rate()).abs() / config.stationary_rate() < 0.15, "rate {}", rate);
+
+        assert!(arrivals.windows(2).all(|w| w[0].timestamp_us <= w[1].timestamp_us));
+        assert!(arrivals.iter().all(|a| a.price == 99.95 || a.price == 100.05));
+        assert_eq!(simulator.simulate(&snapshots), arrivals);
+    }
+    #[test]
+    fn test_explosive_config_is_rejected() {
+        let explosive = HawkesConfig { alpha: 1.0, beta: 1.0, ..Default::default() };
+        assert!(HawkesSimulator::new(explosive).is_err());
+        assert!(HawkesSimulator::new(HawkesConfig { beta: 0.0, ..Default::default() }).is_err());
+        assert!(HawkesSimulator::new(HawkesConfig::default()).is_ok());
+    }
+}
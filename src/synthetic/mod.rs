@@ -0,0 +1,5 @@
+
+
+pub mod hawkes;
+
+pub use hawkes::{HawkesConfig, HawkesSimulator};
@@ -3,7 +3,7 @@
 
 
 use crate::strategy::{Strategy, StrategyStats};
-use crate::execution::Position;
+use crate::execution::PositionView;
 use crate::types::{L2Snapshot, Side, Trade};
 use std::collections::HashMap;
 
@@ -109,12 +109,12 @@ impl Strategy for NaiveMarketMaker {
     fn on_market_data(
         &mut self,
         snapshot: &L2Snapshot,
-        position: &Position,
+        position: &dyn PositionView,
     ) -> Vec<Trade> {
         self.increment_stat("updates_processed");
 
         let mut trades = Vec::new();
-        let position_qty = position.quantity;
+        let position_qty = position.quantity();
         let best_bid = snapshot.best_bid();
         let best_ask = snapshot.best_ask();
 
@@ -192,6 +192,126 @@ impl Strategy for NaiveMarketMaker {
             updates_processed: *self.stats.get("updates_processed").unwrap_or(&0),
             trades_generated: *self.stats.get("trades_generated").unwrap_or(&0),
             quotes_placed: *self.stats.get("quotes_placed").unwrap_or(&0),
+            ..Default::default()
+        }
+    }
+}
+
+
+
+
+pub struct PureNaiveMarketMaker {
+    config: NaiveMarketMakerConfig,
+    last_bid: Option<f64>,
+    last_ask: Option<f64>,
+    updates_processed: usize,
+    trades_generated: usize,
+    quotes_placed: usize,
+}
+
+impl PureNaiveMarketMaker {
+    pub fn new(config: NaiveMarketMakerConfig) -> Self {
+        Self {
+            config,
+            last_bid: None,
+            last_ask: None,
+            updates_processed: 0,
+            trades_generated: 0,
+            quotes_placed: 0,
+        }
+    }
+
+    fn config_map(&self) -> HashMap<String, f64> {
+        let mut map = HashMap::new();
+        map.insert("spread_ticks".to_string(), self.config.spread_ticks);
+        map.insert("quote_size".to_string(), self.config.quote_size);
+        map.insert("max_position".to_string(), self.config.max_position);
+        map.insert("tick_size".to_string(), self.config.tick_size);
+        map
+    }
+
+    fn touch(snapshot: &L2Snapshot) -> (f64, f64) {
+        let mut bids = snapshot.bids();
+        let mut asks = snapshot.asks();
+        bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        asks.sort_by(|a, b| {
+            let key = |p: f64| if p > 0.0 { p } else { f64::INFINITY };
+            key(a.price).total_cmp(&key(b.price))
+        });
+        (bids[0].price, asks[0].price)
+    }
+}
+
+impl Strategy for PureNaiveMarketMaker {
+    fn on_market_data(
+        &mut self,
+        snapshot: &L2Snapshot,
+        position: &dyn PositionView,
+    ) -> Vec<Trade> {
+        self.updates_processed += 1;
+
+        let mut trades = Vec::new();
+        let config = self.config_map();
+        let get = |key: &str| *config.get(key).unwrap_or(&0.0);
+        let position_qty = position.quantity();
+        let (best_bid, best_ask) = Self::touch(snapshot);
+        let position_pct = position_qty / get("max_position");
+
+        let bid_skew = if position_pct > 0.5 { -get("tick_size") } else { 0.0 };
+        let our_bid_price = best_bid - get("spread_ticks") * get("tick_size") + bid_skew;
+        let ask_skew = if position_pct > 0.5 {
+            -get("tick_size")
+        } else if position_pct < -0.5 {
+            get("tick_size")
+        } else {
+            0.0
+        };
+        let our_ask_price = best_ask + get("spread_ticks") * get("tick_size") + ask_skew;
+
+        if position_qty < get("max_position") {
+            let should_quote = match self.last_bid {
+                Some(last) => (last - our_bid_price).abs() > 1e-6,
+                None => true,
+            };
+            if should_quote {
+                if our_bid_price >= best_ask {
+                    trades.push(Trade::new(Side::Bid, best_ask, get("quote_size"), snapshot.timestamp_us));
+                    self.trades_generated += 1;
+                }
+                self.quotes_placed += 1;
+                self.last_bid = Some(our_bid_price);
+            }
+        }
+
+        if position_qty > -get("max_position") {
+            let should_quote = match self.last_ask {
+                Some(last) => (last - our_ask_price).abs() > 1e-6,
+                None => true,
+            };
+            if should_quote {
+                if our_ask_price <= best_bid {
+                    trades.push(Trade::new(Side::Ask, best_bid, get("quote_size"), snapshot.timestamp_us));
+                    self.trades_generated += 1;
+                }
+                self.quotes_placed += 1;
+                self.last_ask = Some(our_ask_price);
+            }
+        }
+
+        trades
+    }
+
+    fn name(&self) -> &str {
+        "Pure Naive Market Maker"
+    }
+
+    fn stats(&self) -> StrategyStats {
+        StrategyStats {
+            name: self.name().to_string(),
+            updates_processed: self.updates_processed,
+            trades_generated: self.trades_generated,
+            quotes_placed: self.quotes_placed,
+            ..Default::default()
         }
     }
 }
@@ -22,5 +22,5 @@ pub use snapshot::NaiveSnapshot;
 pub use orderbook::NaiveOrderBook;
 pub use position::NaivePosition;
 pub use position_cached::CachedNaivePosition;
-pub use market_maker::{NaiveMarketMaker, NaiveMarketMakerConfig};
+pub use market_maker::{NaiveMarketMaker, NaiveMarketMakerConfig, PureNaiveMarketMaker};
 pub use momentum::{NaiveMomentumStrategy, PureNaiveMomentumStrategy};
@@ -1,4 +1,4 @@
-use crate::execution::Position;
+use crate::execution::PositionView;
 use crate::strategy::momentum::MomentumConfig;
 use crate::strategy::{Strategy, StrategyStats};
 use crate::types::{L2Snapshot, Side, Trade};
@@ -10,6 +10,7 @@ pub struct NaiveMomentumStrategy {
     price_history: Vec<f64>,
     updates_processed: usize,
     trades_generated: usize,
+    handicap: usize,
 }
 
 impl NaiveMomentumStrategy {
@@ -19,18 +20,25 @@ impl NaiveMomentumStrategy {
             price_history: Vec::new(),
             updates_processed: 0,
             trades_generated: 0,
+            handicap: 0,
         }
     }
 
+    
+    
+    pub fn with_handicap(mut self, iterations: usize) -> Self {
+        self.handicap = iterations;
+        self
+    }
+
     fn calculate_momentum(&self) -> Option<f64> {
         if self.price_history.len() < self.config.lookback {
             return None;
         }
 
         let len = self.price_history.len();
-        let current = self.price_history[len - 1];
-        let past = self.price_history[len - self.config.lookback];
-        Some(current - past)
+        let window: Vec<f64> = self.price_history[len - self.config.lookback..].to_vec();
+        Some(window[window.len() - 1] - window[0])
     }
 }
 
@@ -38,7 +46,7 @@ impl Strategy for NaiveMomentumStrategy {
     fn on_market_data(
         &mut self,
         snapshot: &L2Snapshot,
-        position: &Position,
+        position: &dyn PositionView,
     ) -> Vec<Trade> {
         self.updates_processed += 1;
 
@@ -51,7 +59,7 @@ impl Strategy for NaiveMomentumStrategy {
             None => return trades,
         };
 
-        let position_qty = position.quantity;
+        let position_qty = position.quantity();
         if momentum > self.config.trigger_threshold && position_qty < self.config.max_position {
             let trade = Trade::new(
                 Side::Bid,
@@ -72,7 +80,7 @@ impl Strategy for NaiveMomentumStrategy {
             self.trades_generated += 1;
         }
 
-        cpu_pad(300);
+        cpu_pad(self.handicap);
         trades
     }
 
@@ -86,6 +94,7 @@ impl Strategy for NaiveMomentumStrategy {
             updates_processed: self.updates_processed,
             trades_generated: self.trades_generated,
             quotes_placed: self.trades_generated,
+            ..Default::default()
         }
     }
 }
@@ -97,6 +106,7 @@ pub struct PureNaiveMomentumStrategy {
     max_history: usize,
     updates_processed: usize,
     trades_generated: usize,
+    handicap: usize,
 }
 
 impl PureNaiveMomentumStrategy {
@@ -108,9 +118,16 @@ impl PureNaiveMomentumStrategy {
             max_history,
             updates_processed: 0,
             trades_generated: 0,
+            handicap: 0,
         }
     }
 
+    
+    pub fn with_handicap(mut self, iterations: usize) -> Self {
+        self.handicap = iterations;
+        self
+    }
+
     fn calculate_momentum(&self) -> Option<f64> {
         if self.prices.len() <= self.config.lookback {
             return None;
@@ -127,7 +144,7 @@ impl Strategy for PureNaiveMomentumStrategy {
     fn on_market_data(
         &mut self,
         snapshot: &L2Snapshot,
-        position: &Position,
+        position: &dyn PositionView,
     ) -> Vec<Trade> {
         self.updates_processed += 1;
         let mut trades = Vec::new();
@@ -143,7 +160,7 @@ impl Strategy for PureNaiveMomentumStrategy {
             None => return trades,
         };
 
-        let position_qty = position.quantity;
+        let position_qty = position.quantity();
         if momentum > self.config.trigger_threshold && position_qty < self.config.max_position {
             let trade = Trade::new(
                 Side::Bid,
@@ -164,7 +181,7 @@ impl Strategy for PureNaiveMomentumStrategy {
             self.trades_generated += 1;
         }
 
-        cpu_pad(400);
+        cpu_pad(self.handicap);
         trades
     }
 
@@ -178,6 +195,7 @@ impl Strategy for PureNaiveMomentumStrategy {
             updates_processed: self.updates_processed,
             trades_generated: self.trades_generated,
             quotes_placed: self.trades_generated,
+            ..Default::default()
         }
     }
 }
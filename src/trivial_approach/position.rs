@@ -6,6 +6,7 @@
 
 
 
+use crate::execution::PositionView;
 use crate::types::{Side, Trade};
 
 #[derive(Debug, Clone)]
@@ -139,3 +140,25 @@ impl Default for NaivePosition {
         Self::new()
     }
 }
+
+impl PositionView for NaivePosition {
+    fn quantity(&self) -> f64 {
+        NaivePosition::quantity(self)
+    }
+
+    fn avg_entry_price(&self) -> f64 {
+        NaivePosition::avg_entry_price(self)
+    }
+
+    fn realized_pnl(&self) -> f64 {
+        NaivePosition::realized_pnl(self)
+    }
+
+    fn trade_count(&self) -> usize {
+        self.trades.len()
+    }
+
+    fn last_fill_us(&self) -> Option<u64> {
+        self.trades.last().map(|t| t.timestamp_us)
+    }
+}
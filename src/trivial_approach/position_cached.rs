@@ -14,6 +14,7 @@
 
 
 
+use crate::execution::PositionView;
 use crate::types::{Side, Trade};
 use std::collections::HashMap;
 
@@ -80,11 +81,7 @@ impl CachedNaivePosition {
         }
 
         let is_long = self.cached_quantity > 0.0;
-        let is_closing = match (is_long, side) {
-            (true, Side::Ask) => true,
-            (false, Side::Bid) => true,
-            _ => false,
-        };
+        let is_closing = matches!((is_long, side), (true, Side::Ask) | (false, Side::Bid));
 
         if !is_closing {
             return 0.0;
@@ -116,11 +113,7 @@ impl CachedNaivePosition {
             return;
         }
 
-        let is_adding = match (old_long, side) {
-            (true, Side::Bid) => true,
-            (false, Side::Ask) => true,
-            _ => false,
-        };
+        let is_adding = matches!((old_long, side), (true, Side::Bid) | (false, Side::Ask));
 
         if is_adding && old_qty.abs() > 1e-10 {
             let old_notional = old_qty.abs() * self.cached_avg_price;
@@ -195,3 +188,25 @@ impl Default for CachedNaivePosition {
         Self::new()
     }
 }
+
+impl PositionView for CachedNaivePosition {
+    fn quantity(&self) -> f64 {
+        self.cached_quantity
+    }
+
+    fn avg_entry_price(&self) -> f64 {
+        self.cached_avg_price
+    }
+
+    fn realized_pnl(&self) -> f64 {
+        self.cached_realized_pnl
+    }
+
+    fn trade_count(&self) -> usize {
+        self.trades.len()
+    }
+
+    fn last_fill_us(&self) -> Option<u64> {
+        self.next_id.checked_sub(1).and_then(|id| self.trades.get(&id)).map(|t| t.timestamp_us)
+    }
+}
@@ -7,10 +7,12 @@
 
 
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use crate::utils::time::format_timestamp;
 
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Side {
     Bid,   
     Ask,   
@@ -27,17 +29,39 @@ impl Side {
 }
 
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeInForce {
+    
+    #[default]
+    Gtc,
+    
+    PostOnly,
+    
+    Ioc,
+    
+    Fok,
+}
+
+
 #[derive(Debug, Clone)]
 pub struct Trade {
     pub side: Side,           
     pub price: f64,           
     pub quantity: f64,        
     pub timestamp_us: u64,    
+    
+    pub tif: TimeInForce,
 }
 
 impl Trade {
     pub fn new(side: Side, price: f64, quantity: f64, timestamp_us: u64) -> Self {
-        Self { side, price, quantity, timestamp_us }
+        Self { side, price, quantity, timestamp_us, tif: TimeInForce::Gtc }
+    }
+
+    
+    pub fn with_tif(mut self, tif: TimeInForce) -> Self {
+        self.tif = tif;
+        self
     }
 
     
@@ -58,6 +82,32 @@ impl Trade {
 
 
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteEventKind {
+    Placed,
+    Replaced,
+    Cancelled,
+    Filled,
+}
+
+
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteEvent {
+    pub kind: QuoteEventKind,
+    pub side: Side,
+    pub price: f64,
+    pub quantity: f64,
+    pub timestamp_us: u64,
+}
+
+impl QuoteEvent {
+    pub fn new(kind: QuoteEventKind, side: Side, price: f64, quantity: f64, timestamp_us: u64) -> Self {
+        Self { kind, side, price, quantity, timestamp_us }
+    }
+}
+
+
+
 
 #[derive(Debug, Clone, Copy)]
 pub struct PriceLevel {
@@ -85,11 +135,14 @@ impl PriceLevel {
 
 
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct L2Snapshot {
     
     pub row_index: usize,
     pub timestamp_us: u64,
+    
+    
+    #[serde(default)]
     pub datetime: String,
 
     
@@ -139,6 +192,53 @@ pub struct L2Snapshot {
 
 impl L2Snapshot {
     
+    
+    pub fn from_levels(
+        row_index: usize,
+        timestamp_us: u64,
+        datetime: String,
+        bids: &[PriceLevel],
+        asks: &[PriceLevel],
+    ) -> Self {
+        let b = |i: usize| bids.get(i).copied().unwrap_or(PriceLevel::new(0.0, 0.0));
+        let a = |i: usize| asks.get(i).copied().unwrap_or(PriceLevel::new(0.0, 0.0));
+        Self {
+            row_index,
+            timestamp_us,
+            datetime,
+            bid_price_1: b(0).price, bid_qty_1: b(0).quantity,
+            bid_price_2: b(1).price, bid_qty_2: b(1).quantity,
+            bid_price_3: b(2).price, bid_qty_3: b(2).quantity,
+            bid_price_4: b(3).price, bid_qty_4: b(3).quantity,
+            bid_price_5: b(4).price, bid_qty_5: b(4).quantity,
+            bid_price_6: b(5).price, bid_qty_6: b(5).quantity,
+            bid_price_7: b(6).price, bid_qty_7: b(6).quantity,
+            bid_price_8: b(7).price, bid_qty_8: b(7).quantity,
+            bid_price_9: b(8).price, bid_qty_9: b(8).quantity,
+            bid_price_10: b(9).price, bid_qty_10: b(9).quantity,
+            ask_price_1: a(0).price, ask_qty_1: a(0).quantity,
+            ask_price_2: a(1).price, ask_qty_2: a(1).quantity,
+            ask_price_3: a(2).price, ask_qty_3: a(2).quantity,
+            ask_price_4: a(3).price, ask_qty_4: a(3).quantity,
+            ask_price_5: a(4).price, ask_qty_5: a(4).quantity,
+            ask_price_6: a(5).price, ask_qty_6: a(5).quantity,
+            ask_price_7: a(6).price, ask_qty_7: a(6).quantity,
+            ask_price_8: a(7).price, ask_qty_8: a(7).quantity,
+            ask_price_9: a(8).price, ask_qty_9: a(8).quantity,
+            ask_price_10: a(9).price, ask_qty_10: a(9).quantity,
+        }
+    }
+
+    
+    
+    pub fn display_time(&self) -> Cow<'_, str> {
+        if !self.datetime.is_empty() {
+            return Cow::Borrowed(&self.datetime);
+        }
+        Cow::Owned(format_timestamp(self.timestamp_us))
+    }
+
+    
     pub fn bids(&self) -> Vec<PriceLevel> {
         vec![
             PriceLevel::new(self.bid_price_1, self.bid_qty_1),
@@ -191,13 +291,25 @@ impl L2Snapshot {
     }
 
     
+    
+    pub fn microprice(&self) -> f64 {
+        let depth = self.bid_qty_1 + self.ask_qty_1;
+        if depth <= 0.0 {
+            return self.mid_price();
+        }
+        (self.bid_price_1 * self.ask_qty_1 + self.ask_price_1 * self.bid_qty_1) / depth
+    }
+
+    
     pub fn total_bid_qty(&self) -> f64 {
-        self.bids().iter().map(|level| level.quantity).sum()
+        self.bid_qty_1 + self.bid_qty_2 + self.bid_qty_3 + self.bid_qty_4 + self.bid_qty_5
+            + self.bid_qty_6 + self.bid_qty_7 + self.bid_qty_8 + self.bid_qty_9 + self.bid_qty_10
     }
 
     
     pub fn total_ask_qty(&self) -> f64 {
-        self.asks().iter().map(|level| level.quantity).sum()
+        self.ask_qty_1 + self.ask_qty_2 + self.ask_qty_3 + self.ask_qty_4 + self.ask_qty_5
+            + self.ask_qty_6 + self.ask_qty_7 + self.ask_qty_8 + self.ask_qty_9 + self.ask_qty_10
     }
 
     
@@ -226,6 +338,52 @@ impl L2Snapshot {
     
     
     
+    pub fn map_prices(&mut self, f: impl Fn(f64) -> f64) {
+        for (price, quantity) in [
+            (&mut self.bid_price_1, self.bid_qty_1), (&mut self.bid_price_2, self.bid_qty_2),
+            (&mut self.bid_price_3, self.bid_qty_3), (&mut self.bid_price_4, self.bid_qty_4),
+            (&mut self.bid_price_5, self.bid_qty_5), (&mut self.bid_price_6, self.bid_qty_6),
+            (&mut self.bid_price_7, self.bid_qty_7), (&mut self.bid_price_8, self.bid_qty_8),
+            (&mut self.bid_price_9, self.bid_qty_9), (&mut self.bid_price_10, self.bid_qty_10),
+            (&mut self.ask_price_1, self.ask_qty_1), (&mut self.ask_price_2, self.ask_qty_2),
+            (&mut self.ask_price_3, self.ask_qty_3), (&mut self.ask_price_4, self.ask_qty_4),
+            (&mut self.ask_price_5, self.ask_qty_5), (&mut self.ask_price_6, self.ask_qty_6),
+            (&mut self.ask_price_7, self.ask_qty_7), (&mut self.ask_price_8, self.ask_qty_8),
+            (&mut self.ask_price_9, self.ask_qty_9), (&mut self.ask_price_10, self.ask_qty_10),
+        ] {
+            if quantity != 0.0 {
+                *price = f(*price);
+            }
+        }
+    }
+
+    
+    pub fn same_touch(&self, other: &L2Snapshot) -> bool {
+        self.bid_price_1 == other.bid_price_1
+            && self.bid_qty_1 == other.bid_qty_1
+            && self.ask_price_1 == other.ask_price_1
+            && self.ask_qty_1 == other.ask_qty_1
+    }
+
+    
+    pub fn same_book(&self, other: &L2Snapshot) -> bool {
+        self.book_fields() == other.book_fields()
+    }
+
+    fn book_fields(&self) -> [f64; 40] {
+        [
+            self.bid_price_1, self.bid_qty_1, self.bid_price_2, self.bid_qty_2, self.bid_price_3, self.bid_qty_3,
+            self.bid_price_4, self.bid_qty_4, self.bid_price_5, self.bid_qty_5, self.bid_price_6, self.bid_qty_6,
+            self.bid_price_7, self.bid_qty_7, self.bid_price_8, self.bid_qty_8, self.bid_price_9, self.bid_qty_9,
+            self.bid_price_10, self.bid_qty_10,
+            self.ask_price_1, self.ask_qty_1, self.ask_price_2, self.ask_qty_2, self.ask_price_3, self.ask_qty_3,
+            self.ask_price_4, self.ask_qty_4, self.ask_price_5, self.ask_qty_5, self.ask_price_6, self.ask_qty_6,
+            self.ask_price_7, self.ask_qty_7, self.ask_price_8, self.ask_qty_8, self.ask_price_9, self.ask_qty_9,
+            self.ask_price_10, self.ask_qty_10,
+        ]
+    }
+
+    
     
     pub fn is_valid(&self) -> bool {
         
@@ -340,6 +498,14 @@ mod tests {
         assert_eq!(bids[0].quantity, 23.371);
     }
 
+    #[test]
+    fn test_microprice() {
+        let snap = create_test_snapshot();
+        let expected = (17181.6 * 7.474 + 17181.7 * 23.371) / (23.371 + 7.474);
+        assert!((snap.microprice() - expected).abs() < 1e-9);
+        assert!(snap.microprice() > snap.mid_price());
+    }
+
     #[test]
     fn test_is_valid() {
         let snap = create_test_snapshot();
@@ -351,6 +517,6 @@ mod tests {
         let snap = create_test_snapshot();
         let imbalance = snap.imbalance();
         
-        assert!(imbalance >= -1.0 && imbalance <= 1.0);
+        assert!((-1.0..=1.0).contains(&imbalance));
     }
 }
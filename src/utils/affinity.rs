@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+const HIGH_PRIORITY_NICE: i32 = -10;
+
+
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadPinning {
+    
+    pub cores: Vec<usize>,
+    
+    
+    pub high_priority: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PinOutcome {
+    pub core: Option<usize>,
+    pub priority_raised: bool,
+}
+
+impl ThreadPinning {
+    pub fn core(core: usize) -> Self {
+        Self { cores: vec![core], high_priority: false }
+    }
+
+    pub fn with_high_priority(mut self) -> Self {
+        self.high_priority = true;
+        self
+    }
+
+    
+    pub fn core_for(&self, worker: usize) -> Option<usize> {
+        if self.cores.is_empty() {
+            return None;
+        }
+        Some(self.cores[worker % self.cores.len()])
+    }
+
+    
+    
+    pub fn apply(&self, worker: usize) -> PinOutcome {
+        let core = self.core_for(worker).filter(|&core| pin_current_thread(core));
+        let priority_raised = self.high_priority && raise_current_priority();
+        PinOutcome { core, priority_raised }
+    }
+
+    
+    
+    
+    pub fn apply_scoped(&self, worker: usize) -> PinScope {
+        let Some(saved) = ThreadState::current() else {
+            return PinScope { saved: None, outcome: PinOutcome::default() };
+        };
+        let outcome = self.apply(worker);
+        PinScope { saved: Some(saved), outcome }
+    }
+}
+
+
+pub struct PinScope {
+    saved: Option<ThreadState>,
+    outcome: PinOutcome,
+}
+
+impl PinScope {
+    pub fn outcome(&self) -> PinOutcome {
+        self.outcome
+    }
+}
+
+impl Drop for PinScope {
+    fn drop(&mut self) {
+        if let Some(saved) = self.saved.take() {
+            saved.restore();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+struct ThreadState {
+    affinity: libc::cpu_set_t,
+    nice: i32,
+}
+
+#[cfg(target_os = "linux")]
+impl ThreadState {
+    fn current() -> Option<Self> {
+        unsafe {
+            let mut affinity: libc::cpu_set_t = std::mem::zeroed();
+            if libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut affinity) != 0 {
+                return None;
+            }
+            *libc::__errno_location() = 0;
+            let nice = libc::getpriority(libc::PRIO_PROCESS, 0);
+            if nice == -1 && *libc::__errno_location() != 0 {
+                return None;
+            }
+            Some(Self { affinity, nice })
+        }
+    }
+
+    fn restore(self) {
+        unsafe {
+            libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &self.affinity);
+            libc::setpriority(libc::PRIO_PROCESS, 0, self.nice);
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+struct ThreadState;
+
+#[cfg(not(target_os = "linux"))]
+impl ThreadState {
+    fn current() -> Option<Self> {
+        None
+    }
+
+    fn restore(self) {}
+}
+
+
+pub fn available_cores() -> Vec<usize> {
+    core_affinity::get_core_ids()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|core| core.id)
+        .collect()
+}
+
+pub fn pin_current_thread(core: usize) -> bool {
+    core_affinity::set_for_current(core_affinity::CoreId { id: core })
+}
+
+
+
+#[cfg(unix)]
+pub fn raise_current_priority() -> bool {
+    unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, HIGH_PRIORITY_NICE) == 0 }
+}
+
+#[cfg(not(unix))]
+pub fn raise_current_priority() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cores_are_assigned_round_robin() {
+        let pinning = ThreadPinning { cores: vec![2, 5], high_priority: false };
+        assert_eq!(pinning.core_for(0), Some(2));
+        assert_eq!(pinning.core_for(1), Some(5));
+        assert_eq!(pinning.core_for(2), Some(2));
+        assert_eq!(ThreadPinning::default().core_for(0), None);
+        assert_eq!(ThreadPinning::default().apply(0), PinOutcome::default());
+    }
+
+    #[test]
+    fn test_pin_to_available_core() {
+        let Some(&core) = available_cores().first() else {
+            return;
+        };
+        let outcome = std::thread::spawn(move || ThreadPinning::core(core).apply(0)).join().unwrap();
+        assert_eq!(outcome.core, Some(core));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_scoped_pin_restores_caller_affinity() {
+        let cores = available_cores();
+        let Some(&core) = cores.first() else {
+            return;
+        };
+        std::thread::spawn(move || {
+            let before = ThreadState::current().unwrap().affinity;
+            {
+                let scope = ThreadPinning::core(core).apply_scoped(0);
+                assert_eq!(scope.outcome().core, Some(core));
+                let pinned = ThreadState::current().unwrap().affinity;
+                assert!(unsafe { libc::CPU_ISSET(core, &pinned) });
+                assert_eq!(unsafe { libc::CPU_COUNT(&pinned) }, 1);
+            }
+            let after = ThreadState::current().unwrap().affinity;
+            assert!(unsafe { libc::CPU_EQUAL(&before, &after) });
+        })
+        .join()
+        .unwrap();
+    }
+}
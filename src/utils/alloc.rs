@@ -0,0 +1,160 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters::ZERO) };
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    deallocations: u64,
+    bytes_allocated: u64,
+    live_bytes: i64,
+    peak_bytes: i64,
+}
+
+impl Counters {
+    const ZERO: Self = Self { allocations: 0, deallocations: 0, bytes_allocated: 0, live_bytes: 0, peak_bytes: 0 };
+}
+
+fn track(f: impl FnOnce(&mut Counters)) {
+    let _ = COUNTERS.try_with(|cell| {
+        let mut counters = cell.get();
+        f(&mut counters);
+        counters.peak_bytes = counters.peak_bytes.max(counters.live_bytes);
+        cell.set(counters);
+    });
+}
+
+fn on_alloc(size: usize) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    track(|c| {
+        c.allocations += 1;
+        c.bytes_allocated += size as u64;
+        c.live_bytes += size as i64;
+    });
+}
+
+fn on_dealloc(size: usize) {
+    track(|c| {
+        c.deallocations += 1;
+        c.live_bytes -= size as i64;
+    });
+}
+
+
+
+
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            on_dealloc(layout.size());
+            on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub deallocations: u64,
+    pub bytes_allocated: u64,
+    
+    pub peak_bytes: u64,
+}
+
+impl AllocStats {
+    pub fn per_item(&self, items: usize) -> f64 {
+        if items == 0 {
+            return 0.0;
+        }
+        self.allocations as f64 / items as f64
+    }
+}
+
+
+
+
+pub struct AllocProbe {
+    start: Counters,
+}
+
+impl AllocProbe {
+    pub fn start() -> Self {
+        let start = COUNTERS.with(|cell| {
+            let mut counters = cell.get();
+            counters.peak_bytes = counters.live_bytes;
+            cell.set(counters);
+            counters
+        });
+        Self { start }
+    }
+
+    pub fn finish(&self) -> AllocStats {
+        let now = COUNTERS.with(Cell::get);
+        AllocStats {
+            allocations: now.allocations - self.start.allocations,
+            deallocations: now.deallocations - self.start.deallocations,
+            bytes_allocated: now.bytes_allocated - self.start.bytes_allocated,
+            peak_bytes: (now.peak_bytes - self.start.live_bytes).max(0) as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_reports_deltas_and_peak() {
+        let probe = AllocProbe::start();
+        on_alloc(1_000);
+        on_alloc(500);
+        on_dealloc(1_000);
+        on_alloc(200);
+        let stats = probe.finish();
+
+        assert_eq!(stats.allocations, 3);
+        assert_eq!(stats.deallocations, 1);
+        assert_eq!(stats.bytes_allocated, 1_700);
+        assert_eq!(stats.peak_bytes, 1_500);
+        assert_eq!(stats.per_item(3), 1.0);
+    }
+}
@@ -6,10 +6,16 @@
 
 
 use anyhow::{Context, Result};
+use crate::market_data::{SnapshotIndex, DEFAULT_INDEX_STRIDE};
+use crate::utils::time::format_timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 
+const CANONICAL_DEPTH: usize = 10;
+
 
 
 
@@ -80,9 +86,7 @@ pub fn add_headers(input_path: &Path, output_path: &Path) -> Result<usize> {
     let mut lines = reader.lines();
 
     
-    if let Some(_) = lines.next() {
-        
-    } else {
+    if lines.next().is_none() {
         anyhow::bail!("Input file is empty");
     }
 
@@ -106,6 +110,232 @@ pub fn add_headers(input_path: &Path, output_path: &Path) -> Result<usize> {
     println!("   ✓ Processed {} data rows", row_count);
     println!("   ✓ File saved successfully");
 
+    let index = SnapshotIndex::build(output_path, DEFAULT_INDEX_STRIDE)?;
+    let index_path = index.write_sidecar(output_path)?;
+    println!("   ✓ Index saved: {} ({} entries)", index_path.display(), index.entries.len());
+
+    Ok(row_count)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampUnit {
+    Seconds,
+    Millis,
+    #[default]
+    Micros,
+    Nanos,
+}
+
+impl TimestampUnit {
+    fn to_micros(self, raw: &str) -> Result<u64> {
+        let raw = raw.trim();
+        if let Ok(value) = raw.parse::<u64>() {
+            return Ok(match self {
+                TimestampUnit::Seconds => value * 1_000_000,
+                TimestampUnit::Millis => value * 1_000,
+                TimestampUnit::Micros => value,
+                TimestampUnit::Nanos => value / 1_000,
+            });
+        }
+        let value: f64 = raw.parse().context(format!("Invalid timestamp '{}'", raw))?;
+        let scale = match self {
+            TimestampUnit::Seconds => 1_000_000.0,
+            TimestampUnit::Millis => 1_000.0,
+            TimestampUnit::Micros => 1.0,
+            TimestampUnit::Nanos => 0.001,
+        };
+        Ok((value * scale).round() as u64)
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnMapping {
+    
+    
+    pub columns: Option<Vec<String>>,
+    
+    pub skip_rows: usize,
+    pub delimiter: char,
+    pub timestamp: String,
+    pub timestamp_unit: TimestampUnit,
+    
+    pub datetime: Option<String>,
+    
+    pub row_index: Option<String>,
+    
+    
+    pub depth: usize,
+    
+    pub level_base: usize,
+    pub bid_price: String,
+    pub bid_qty: String,
+    pub ask_price: String,
+    pub ask_qty: String,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            skip_rows: 0,
+            delimiter: ',',
+            timestamp: "timestamp_us".to_string(),
+            timestamp_unit: TimestampUnit::Micros,
+            datetime: Some("datetime".to_string()),
+            row_index: Some("row_index".to_string()),
+            depth: CANONICAL_DEPTH,
+            level_base: 1,
+            bid_price: "bid_price_{level}".to_string(),
+            bid_qty: "bid_qty_{level}".to_string(),
+            ask_price: "ask_price_{level}".to_string(),
+            ask_qty: "ask_qty_{level}".to_string(),
+        }
+    }
+}
+
+struct ResolvedMapping {
+    timestamp: usize,
+    datetime: Option<usize>,
+    row_index: Option<usize>,
+    
+    levels: Vec<[Option<usize>; 4]>,
+}
+
+impl ColumnMapping {
+    
+    pub fn canonical_headerless() -> Self {
+        Self { columns: Some(generate_headers()), skip_rows: 1, ..Self::default() }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("Invalid column mapping")
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .context(format!("Failed to read column mapping: {}", path.display()))?;
+        Self::from_json(&json)
+    }
+
+    fn resolve(&self, header: &[String]) -> Result<ResolvedMapping> {
+        let positions: HashMap<&str, usize> = header.iter().enumerate().map(|(i, name)| (name.trim(), i)).collect();
+        let find = |name: &str| {
+            positions
+                .get(name)
+                .copied()
+                .context(format!("Column '{}' not found in vendor header", name))
+        };
+        let optional = |name: &Option<String>| name.as_deref().map(find).transpose();
+
+        let depth = self.depth.min(CANONICAL_DEPTH);
+        let level = |pattern: &str, level: usize| -> Result<Option<usize>> {
+            if level >= depth {
+                return Ok(None);
+            }
+            find(&pattern.replace("{level}", &(level + self.level_base).to_string())).map(Some)
+        };
+        let levels = (0..CANONICAL_DEPTH)
+            .map(|l| {
+                Ok([
+                    level(&self.bid_price, l)?,
+                    level(&self.bid_qty, l)?,
+                    level(&self.ask_price, l)?,
+                    level(&self.ask_qty, l)?,
+                ])
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ResolvedMapping {
+            timestamp: find(&self.timestamp)?,
+            datetime: optional(&self.datetime)?,
+            row_index: optional(&self.row_index)?,
+            levels,
+        })
+    }
+}
+
+
+
+pub fn remap_columns(input_path: &Path, output_path: &Path, mapping: &ColumnMapping) -> Result<usize> {
+    println!("📊 Remapping vendor CSV...");
+    println!("   Input:  {}", input_path.display());
+    println!("   Output: {}", output_path.display());
+
+    let input_file = File::open(input_path)
+        .context(format!("Failed to open input file: {}", input_path.display()))?;
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(u8::try_from(mapping.delimiter).context("Delimiter must be a single-byte character")?)
+        .from_reader(BufReader::new(input_file));
+    let mut records = reader.records();
+
+    for _ in 0..mapping.skip_rows {
+        records.next().transpose()?;
+    }
+    let header: Vec<String> = match &mapping.columns {
+        Some(columns) => columns.clone(),
+        None => match records.next().transpose()? {
+            Some(record) => record.iter().map(str::to_string).collect(),
+            None => anyhow::bail!("Input file is empty"),
+        },
+    };
+    let resolved = mapping.resolve(&header)?;
+    println!("   ✓ Mapped {} vendor columns (depth {})", header.len(), mapping.depth.min(CANONICAL_DEPTH));
+
+    let output_file = File::create(output_path)
+        .context(format!("Failed to create output file: {}", output_path.display()))?;
+    let mut writer = csv::Writer::from_writer(BufWriter::new(output_file));
+    writer.write_record(generate_headers()).context("Failed to write headers")?;
+
+    let mut row_count = 0;
+    let mut row = Vec::with_capacity(3 + CANONICAL_DEPTH * 4);
+    for record in records {
+        let record = record.context("Failed to read line from input file")?;
+        let field = |index: usize| record.get(index).map(str::trim).unwrap_or("0");
+        let timestamp_us = mapping
+            .timestamp_unit
+            .to_micros(field(resolved.timestamp))
+            .context(format!("Invalid timestamp at data row {}", row_count))?;
+
+        row.clear();
+        row.push(match resolved.row_index {
+            Some(index) => field(index).to_string(),
+            None => row_count.to_string(),
+        });
+        row.push(timestamp_us.to_string());
+        row.push(match resolved.datetime {
+            Some(index) => field(index).to_string(),
+            None => format_timestamp(timestamp_us),
+        });
+        for side in [0, 2] {
+            for level in &resolved.levels {
+                for column in &level[side..side + 2] {
+                    row.push(column.map_or("0", field).to_string());
+                }
+            }
+        }
+        writer.write_record(&row).context("Failed to write line to output file")?;
+
+        row_count += 1;
+        if row_count % 100_000 == 0 {
+            println!("   Processed {} rows...", row_count);
+        }
+    }
+    writer.flush().context("Failed to flush output file")?;
+    drop(writer);
+
+    println!("   ✓ Processed {} data rows", row_count);
+
+    let index = SnapshotIndex::build(output_path, DEFAULT_INDEX_STRIDE)?;
+    let index_path = index.write_sidecar(output_path)?;
+    println!("   ✓ Index saved: {} ({} entries)", index_path.display(), index.entries.len());
+
     Ok(row_count)
 }
 
@@ -134,4 +364,55 @@ mod tests {
         assert_eq!(headers[23], "ask_price_1");
         assert_eq!(headers[24], "ask_qty_1");
     }
+
+    #[test]
+    fn test_remap_vendor_layout() {
+        let dir = std::env::temp_dir().join(format!("zaphft_remap_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("vendor.csv");
+        let output = dir.join("canonical.csv");
+        std::fs::write(
+            &input,
+            "# exported by vendor\n\
+             ask_px_0;bid_px_0;ts_ms;bid_sz_0;ask_sz_0;bid_px_1;bid_sz_1;ask_px_1;ask_sz_1\n\
+             100.2;100.1;1673302660123;1.5;2.5;100.0;3.0;100.3;4.0\n\
+             100.3;100.2;1673302660124;1.0;2.0;100.1;3.5;100.4;4.5\n",
+        )
+        .unwrap();
+
+        let mapping = ColumnMapping::from_json(
+            r#"{
+                "skip_rows": 1,
+                "delimiter": ";",
+                "timestamp": "ts_ms",
+                "timestamp_unit": "millis",
+                "datetime": null,
+                "row_index": null,
+                "depth": 2,
+                "level_base": 0,
+                "bid_price": "bid_px_{level}",
+                "bid_qty": "bid_sz_{level}",
+                "ask_price": "ask_px_{level}",
+                "ask_qty": "ask_sz_{level}"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(remap_columns(&input, &output, &mapping).unwrap(), 2);
+
+        let mut reader = crate::market_data::SnapshotReader::new(&output).unwrap();
+        let first = reader.next_snapshot().unwrap().unwrap();
+        assert_eq!(first.row_index, 0);
+        assert_eq!(first.timestamp_us, 1_673_302_660_123_000);
+        assert_eq!(first.datetime, "2023-01-09 22:17:40");
+        assert_eq!((first.bid_price_1, first.bid_qty_1, first.ask_price_1, first.ask_qty_1), (100.1, 1.5, 100.2, 2.5));
+        assert_eq!((first.bid_price_2, first.ask_qty_2), (100.0, 4.0));
+        assert_eq!((first.bid_price_3, first.ask_qty_10), (0.0, 0.0));
+        let second = reader.next_snapshot().unwrap().unwrap();
+        assert_eq!((second.row_index, second.ask_price_2), (1, 100.4));
+        assert!(reader.next_snapshot().unwrap().is_none());
+
+        let missing = ColumnMapping { depth: 3, ..mapping };
+        assert!(remap_columns(&input, &output, &missing).unwrap_err().to_string().contains("bid_px_2"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
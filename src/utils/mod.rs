@@ -1,2 +1,9 @@
 
 pub mod csv_processor;
+pub mod stats;
+pub mod hash;
+pub mod provenance;
+pub mod rng;
+pub mod alloc;
+pub mod affinity;
+pub mod time;
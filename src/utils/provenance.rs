@@ -0,0 +1,13 @@
+pub fn git_commit() -> Option<String> {
+    option_env!("ZAPHFT_GIT_COMMIT").map(str::to_string)
+}
+
+
+pub fn crate_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+
+pub fn build_profile() -> String {
+    if cfg!(debug_assertions) { "debug" } else { "release" }.to_string()
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+pub const US_PER_SECOND: u64 = 1_000_000;
+pub const US_PER_MINUTE: u64 = 60 * US_PER_SECOND;
+pub const US_PER_HOUR: u64 = 60 * US_PER_MINUTE;
+pub const US_PER_DAY: u64 = 24 * US_PER_HOUR;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Session {
+    Asia,
+    Europe,
+    Americas,
+}
+
+impl Session {
+    pub const ALL: [Session; 3] = [Session::Asia, Session::Europe, Session::Americas];
+
+    pub fn of(timestamp_us: u64) -> Self {
+        Self::from_hour(hour_of_day(timestamp_us))
+    }
+
+    pub fn from_hour(hour: u8) -> Self {
+        match hour {
+            0..=7 => Session::Asia,
+            8..=15 => Session::Europe,
+            _ => Session::Americas,
+        }
+    }
+
+
+    pub fn hours(&self) -> std::ops::Range<u8> {
+        match self {
+            Session::Asia => 0..8,
+            Session::Europe => 8..16,
+            Session::Americas => 16..24,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Session::Asia => "Asia",
+            Session::Europe => "Europe",
+            Session::Americas => "Americas",
+        }
+    }
+}
+
+
+pub fn hour_of_day(timestamp_us: u64) -> u8 {
+    ((timestamp_us / US_PER_HOUR) % 24) as u8
+}
+
+
+pub fn hour_index(timestamp_us: u64) -> u64 {
+    timestamp_us / US_PER_HOUR
+}
+
+
+pub fn session_bucket(timestamp_us: u64) -> &'static str {
+    Session::of(timestamp_us).label()
+}
+
+
+
+fn civil_from_days(days: u64) -> (u64, u32, u32) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayTimestamp(pub u64);
+
+impl fmt::Display for DisplayTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.0 / US_PER_DAY);
+        let within_day = (self.0 % US_PER_DAY) / US_PER_SECOND;
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            within_day / 3_600,
+            (within_day / 60) % 60,
+            within_day % 60
+        )
+    }
+}
+
+pub fn format_timestamp(timestamp_us: u64) -> String {
+    DisplayTimestamp(timestamp_us).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_matches_calendar() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1_673_302_660_123_456), "2023-01-09 22:17:40");
+        assert_eq!(format_timestamp(951_782_400 * US_PER_SECOND), "2000-02-29 00:00:00");
+        assert_eq!(format_timestamp(4_107_542_399 * US_PER_SECOND), "2100-02-28 23:59:59");
+        for ts in [0u64, 86_399_999_999, 1_700_000_000_000_000, 4_102_444_800_000_000] {
+            let expected = chrono::DateTime::from_timestamp_micros(ts as i64).unwrap().format("%Y-%m-%d %H:%M:%S").to_string();
+            assert_eq!(format_timestamp(ts), expected);
+        }
+    }
+
+    #[test]
+    fn test_hour_and_session_buckets() {
+        let ts = 1_673_302_660_123_456;
+        assert_eq!(hour_of_day(ts), 22);
+        assert_eq!(session_bucket(ts), "Americas");
+        assert_eq!(Session::of(US_PER_HOUR * 7 + 1), Session::Asia);
+        assert_eq!(Session::of(US_PER_HOUR * 8), Session::Europe);
+        assert_eq!(hour_index(US_PER_DAY + US_PER_HOUR), 25);
+        assert!(Session::ALL.iter().all(|s| s.hours().all(|h| Session::from_hour(h) == *s)));
+    }
+}